@@ -0,0 +1,32 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks the phases of the mutation testing pipeline on the bundled sample packages.
+//!
+//! Run with `cargo bench -p move-mutation-test`.
+
+use clap::Parser;
+use move_mutation_test::{
+    bench::{run_benchmarks, BenchOptions},
+    run_as_coverage_baseline_child,
+};
+
+// The same values as in the integration tests.
+const RED_ZONE: usize = 128 * 1024; // 128 KiB
+const STACK_SIZE: usize = 32 * RED_ZONE; // 4 MiB
+
+fn main() {
+    // The baseline tests with coverage are run by the benchmark started again as a child process.
+    if let Some(result) = run_as_coverage_baseline_child() {
+        result.expect("running the baseline tests with coverage failed");
+        return;
+    }
+
+    // The arguments passed by `cargo bench` are not the options of the tool.
+    let options = BenchOptions::parse_from(["bench", "--move-2", "--gas-limit", "2000"]);
+
+    stacker::maybe_grow(RED_ZONE, STACK_SIZE, || {
+        run_benchmarks(&options).expect("running the benchmarks failed");
+    });
+}
@@ -0,0 +1,264 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    cli::TestBuildConfig,
+    mutation_test::{run_tests_on_mutated_code, FailedTests},
+};
+use anyhow::Context;
+use fs_extra::dir::CopyOptions;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsStr,
+    fs, io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// The attribute of the tests expected to fail.
+const EXPECTED_FAILURE: &str = "expected_failure";
+
+/// The abort code of the probe annotations, which no test is expected to abort with.
+pub(crate) const PROBE_ABORT_CODE: u64 = u64::MAX;
+
+/// A loose `#[expected_failure]` annotation, which lets the test pass with any abort code.
+#[derive(Debug, PartialEq)]
+struct LooseExpectation {
+    /// The location of the `expected_failure` attribute in the source.
+    range: Range<usize>,
+    /// The name of the test (`module::function`).
+    test: String,
+}
+
+/// The tests whose loose `#[expected_failure]` annotations have been pinned to the abort codes
+/// observed on the original code (`--pin-abort-codes`).
+#[derive(Debug, Default)]
+pub(crate) struct PinnedTests {
+    /// The names of the tests (`module::function`).
+    tests: BTreeSet<String>,
+}
+
+impl PinnedTests {
+    /// Checks whether the tests failed only because the mutant changed the abort codes of the
+    /// pinned tests, which would have passed with the loose annotations.
+    pub(crate) fn explain(&self, failed: &FailedTests) -> bool {
+        !failed.tests.is_empty()
+            && failed.tests.iter().all(|test| {
+                self.tests.contains(short_name(test)) && failed.abort_codes.contains_key(test)
+            })
+    }
+}
+
+/// Pins the loose `#[expected_failure]` annotations of the package to the abort codes the tests
+/// abort with on the original code, so the mutants changing the abort code are detected.
+///
+/// The codes are found by running the tests with the annotations expecting an impossible abort
+/// code, as the runner reports the actual one. Tests failing for other reasons (e.g. arithmetic
+/// errors) keep the loose annotations.
+pub(crate) fn pin_abort_codes(
+    test_config: &TestBuildConfig,
+    package_path: &Path,
+    outdir: &Path,
+) -> anyhow::Result<PinnedTests> {
+    let mut files = vec![];
+    collect_move_files(package_path, &mut files)?;
+    let mut sources = vec![];
+    for path in files {
+        let source = fs::read_to_string(&path)?;
+        if !loose_expectations(&source).is_empty() {
+            sources.push((source, path));
+        }
+    }
+    if sources.is_empty() {
+        return Ok(PinnedTests::default());
+    }
+
+    let probe_dir = outdir.join("abort_code_probe");
+    let _ = fs::remove_dir_all(&probe_dir);
+    fs_extra::dir::copy(
+        package_path,
+        &probe_dir,
+        &CopyOptions::new().content_only(true),
+    )?;
+    for (source, path) in &sources {
+        let (probe, _) = pin(source, |_| Some(PROBE_ABORT_CODE));
+        fs::write(probe_dir.join(path.strip_prefix(package_path)?), probe)?;
+    }
+
+    let result = run_tests_on_mutated_code(test_config, &probe_dir);
+    let _ = fs::remove_dir_all(&probe_dir);
+    let abort_codes = match result {
+        Ok(_) => BTreeMap::new(),
+        Err(e) => e
+            .downcast::<FailedTests>()
+            .context("failed to run the tests to find the abort codes")?
+            .abort_codes
+            .into_iter()
+            .map(|(test, code)| (short_name(&test).to_owned(), code))
+            .collect(),
+    };
+
+    let mut pinned = PinnedTests::default();
+    for (source, path) in sources {
+        let (source, tests) = pin(&source, |test| abort_codes.get(test).copied());
+        if !tests.is_empty() {
+            fs::write(&path, source)?;
+            pinned.tests.extend(tests);
+        }
+    }
+    println!(
+        "Pinned the abort codes of {} tests with loose #[expected_failure] annotations",
+        pinned.tests.len()
+    );
+    Ok(pinned)
+}
+
+/// Collects the Move source files of the package (without the build directory).
+pub(crate) fn collect_move_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name() != Some(OsStr::new("build")) {
+                collect_move_files(&path, files)?;
+            }
+        } else if path.extension() == Some(OsStr::new("move")) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites the loose annotations of the tests with a known abort code to expect that code.
+/// Returns the new source and the names of the pinned tests.
+fn pin(source: &str, abort_code: impl Fn(&str) -> Option<u64>) -> (String, Vec<String>) {
+    let mut source = source.to_owned();
+    let mut tests = vec![];
+    for expectation in loose_expectations(&source).into_iter().rev() {
+        if let Some(code) = abort_code(&expectation.test) {
+            source.replace_range(
+                expectation.range,
+                &format!("{EXPECTED_FAILURE}(abort_code = {code})"),
+            );
+            tests.push(expectation.test);
+        }
+    }
+    tests.reverse();
+    (source, tests)
+}
+
+/// Finds the `expected_failure` attributes without arguments and the tests they annotate.
+fn loose_expectations(source: &str) -> Vec<LooseExpectation> {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    let mut expectations = vec![];
+    for (start, _) in source.match_indices(EXPECTED_FAILURE) {
+        let end = start + EXPECTED_FAILURE.len();
+        let (before, after) = (&source[..start], &source[end..]);
+        let is_word = !before.ends_with(is_ident) && !after.starts_with(is_ident);
+        let in_attribute = before
+            .rfind("#[")
+            .is_some_and(|open| !before[open..].contains(']'));
+        if !is_word || !in_attribute || after.trim_start().starts_with('(') {
+            continue;
+        }
+
+        let function = after
+            .find("fun ")
+            .map(|idx| identifier(&after[idx + "fun ".len()..]));
+        let module = enclosing_module(before);
+        if let (Some(module), Some(function)) = (module, function) {
+            expectations.push(LooseExpectation {
+                range: start..end,
+                test: format!("{module}::{function}"),
+            });
+        }
+    }
+    expectations
+}
+
+/// Returns the name of the last module declared in the source before the position.
+pub(crate) fn enclosing_module(before: &str) -> Option<&str> {
+    before.rfind("module ").map(|idx| {
+        let path = before[idx + "module ".len()..].trim_start();
+        let path = &path[..path
+            .find(|c: char| c.is_whitespace() || c == '{')
+            .unwrap_or(path.len())];
+        identifier(path.rsplit("::").next().unwrap_or(path))
+    })
+}
+
+/// Returns the identifier at the start of the text.
+pub(crate) fn identifier(text: &str) -> &str {
+    let text = text.trim_start();
+    let end = text
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+/// Returns the test name without the address (`module::function`).
+pub(crate) fn short_name(test: &str) -> &str {
+    match test.rmatch_indices("::").nth(1) {
+        Some((idx, _)) => &test[idx + 2..],
+        None => test,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "module 0x42::sum {
+    public fun sum(a: u64, b: u64): u64 { a + b }
+
+    #[test]
+    #[expected_failure]
+    fun test_abort() { abort 3 }
+
+    #[test, expected_failure(abort_code = 3)]
+    fun test_pinned() { abort 3 }
+
+    #[test, expected_failure]
+    fun test_overflow() { sum(18446744073709551615, 1); }
+}
+";
+
+    #[test]
+    fn loose_annotations_are_pinned() {
+        let tests = loose_expectations(SOURCE)
+            .into_iter()
+            .map(|expectation| expectation.test)
+            .collect::<Vec<_>>();
+        assert_eq!(tests, vec!["sum::test_abort", "sum::test_overflow"]);
+
+        let (pinned, tests) = pin(SOURCE, |test| (test == "sum::test_abort").then_some(3));
+        assert_eq!(tests, vec!["sum::test_abort"]);
+        assert!(pinned.contains("#[expected_failure(abort_code = 3)]\n    fun test_abort()"));
+        assert!(pinned.contains("#[test, expected_failure]\n    fun test_overflow()"));
+    }
+
+    #[test]
+    fn only_abort_code_changes_of_pinned_tests_are_explained() {
+        let pinned = PinnedTests {
+            tests: ["sum::test_abort".to_owned()].into(),
+        };
+        let failed = |tests: &[&str], abort_codes: &[&str]| FailedTests {
+            tests: tests.iter().map(|t| t.to_string()).collect(),
+            passed: vec![],
+            reason: mutator_common::report::KillReason::TestFailure,
+            abort_codes: abort_codes.iter().map(|t| (t.to_string(), 4)).collect(),
+        };
+
+        assert_eq!(short_name("0x42::sum::test_abort"), "sum::test_abort");
+        assert!(pinned.explain(&failed(&["0x42::sum::test_abort"], &[
+            "0x42::sum::test_abort"
+        ])));
+        assert!(!pinned.explain(&failed(&["0x42::sum::test_abort"], &[])));
+        assert!(!pinned.explain(&failed(
+            &["0x42::sum::test_abort", "0x42::sum::test_sum"],
+            &["0x42::sum::test_abort"]
+        )));
+        assert!(!pinned.explain(&failed(&[], &[])));
+    }
+}
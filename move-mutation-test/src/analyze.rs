@@ -0,0 +1,80 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `analyze` command: analyses of the test suite based on the report, and of the package.
+
+use crate::{
+    cli::{create_mutator_options, CLIOptions, TestBuildConfig},
+    mutator_build_config,
+};
+use clap::{Parser, Subcommand};
+use move_mutator::inventory::package_inventory;
+use mutator_common::analyze::AnalyzeCmd;
+use std::path::PathBuf;
+
+/// Analyze the test suite using the report, or the package under test.
+#[derive(Parser)]
+pub struct AnalyzeOptions {
+    /// Report location. The default file is "report.txt" under the same directory.
+    #[clap(global = true, long, default_value = "report.txt")]
+    pub path_to_report: PathBuf,
+
+    /// Analyze subcommands.
+    #[clap(subcommand)]
+    pub cmds: AnalyzeCommands,
+}
+
+/// Analyze subcommands.
+#[derive(Subcommand)]
+pub enum AnalyzeCommands {
+    /// Print the modules and functions of the package with their spans, spec presence, coverage
+    /// status, called functions and the number of potential mutation sites per operator as JSON.
+    /// No mutants are generated and the report isn't needed.
+    Inventory(InventoryOptions),
+
+    #[command(flatten)]
+    Report(AnalyzeCmd),
+}
+
+/// Options of the `analyze inventory` command.
+#[derive(Parser, Debug, Clone)]
+pub struct InventoryOptions {
+    /// The options of the mutation test tool. The module and function filters, the optional
+    /// operators and the mutator configuration file are respected.
+    #[clap(flatten)]
+    pub cli_options: CLIOptions,
+
+    /// The package options. With `--coverage`, the coverage status of the functions is included,
+    /// which requires the coverage map generated by `aptos move test --coverage`.
+    #[clap(flatten)]
+    pub test_build_config: TestBuildConfig,
+}
+
+impl AnalyzeOptions {
+    /// Execute the command.
+    ///
+    /// # Errors
+    /// Returns an error if the analysis fails.
+    pub fn execute(&self) -> anyhow::Result<()> {
+        match &self.cmds {
+            AnalyzeCommands::Inventory(options) => print_inventory(options),
+            AnalyzeCommands::Report(cmd) => cmd.execute(&self.path_to_report),
+        }
+    }
+}
+
+/// Prints the inventory of the package as JSON.
+fn print_inventory(options: &InventoryOptions) -> anyhow::Result<()> {
+    let test_config = &options.test_build_config;
+    let package_path = test_config.move_pkg.get_package_path()?;
+    let mutator_options = create_mutator_options(&options.cli_options, test_config);
+
+    let inventory = package_inventory(
+        mutator_options,
+        &mutator_build_config(test_config),
+        &package_path,
+    )?;
+    println!("{}", serde_json::to_string_pretty(&inventory)?);
+    Ok(())
+}
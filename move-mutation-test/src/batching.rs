@@ -0,0 +1,370 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_model::{
+    metadata::{CompilerVersion, LanguageVersion},
+    model::GlobalEnv,
+};
+use move_mutator::report::MutationReport;
+use move_package::{BuildConfig, ModelConfig};
+use mutator_common::tmp_package_dir::strip_path_prefix;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+/// The number of mutants of a module tested in a row when the mutants are shuffled. It matches the
+/// chunks of mutants tested in parallel, so interleaving the modules prepares no extra sandboxes.
+const SHUFFLED_SLICE_LEN: usize = 64;
+
+/// Mutants of a single module which are tested using the same prepared sandbox.
+pub(crate) struct ModuleBatch<'a> {
+    /// Full name (`address::name`) of the mutated module.
+    pub(crate) module: String,
+    /// The layer of the module in the dependency graph - modules only depend on modules in the
+    /// lower layers.
+    pub(crate) layer: usize,
+    /// Mutants of the module.
+    pub(crate) mutants: Vec<&'a MutationReport>,
+}
+
+/// The modules of the package with their dependencies, taken from the compiled model.
+#[derive(Debug, Default)]
+pub(crate) struct PackageModules {
+    /// The full names (`address::name`) of the modules by their source file (relative to the
+    /// package) and their name.
+    full_names: BTreeMap<(PathBuf, String), String>,
+    /// The full names of the modules used by each module.
+    dependencies: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl PackageModules {
+    /// Compiles the model of the package and collects its modules.
+    ///
+    /// The batches are only ordered by the dependencies, so if the model can't be built, the
+    /// modules are left without them and tested in alphabetical order.
+    pub(crate) fn load(config: &BuildConfig, package_path: &Path) -> Self {
+        match build_model(config, package_path).and_then(|env| Self::of(&env, package_path)) {
+            Ok(modules) => modules,
+            Err(err) => {
+                warn!("Failed to read the module dependencies of the package: {err}");
+                Self::default()
+            },
+        }
+    }
+
+    /// Collects the target modules of the model and the modules they use.
+    fn of(env: &GlobalEnv, package_path: &Path) -> anyhow::Result<Self> {
+        let package_path = package_path.canonicalize()?;
+        let mut modules = Self::default();
+        for module in env.get_modules().filter(|module| module.is_target()) {
+            let full_name = module.get_full_name_str();
+            let name = module.symbol_pool().string(module.get_name().name());
+            let source_path = Path::new(module.get_source_path()).canonicalize()?;
+            let file = source_path
+                .strip_prefix(&package_path)
+                .unwrap_or(&source_path)
+                .to_path_buf();
+            modules
+                .full_names
+                .insert((file, name.to_string()), full_name.clone());
+            let used = module
+                .get_used_modules(false)
+                .iter()
+                .copied()
+                .filter(|id| *id != module.get_id())
+                .map(|id| env.get_module(id).get_full_name_str())
+                .collect();
+            modules.dependencies.insert(full_name, used);
+        }
+        Ok(modules)
+    }
+
+    /// Returns the full name of the mutated module, or just its name if it isn't known.
+    fn full_name(&self, mutant: &MutationReport) -> String {
+        let module = mutant.get_module_name();
+        strip_path_prefix(mutant.original_file_path())
+            .ok()
+            .and_then(|file| self.full_names.get(&(file, module.to_owned())))
+            .cloned()
+            .unwrap_or_else(|| module.to_owned())
+    }
+}
+
+/// Builds the model of the package, the same way as the tests compile it.
+fn build_model(config: &BuildConfig, package_path: &Path) -> anyhow::Result<GlobalEnv> {
+    config
+        .clone()
+        .move_model_for_package(package_path, ModelConfig {
+            all_files_as_targets: false,
+            target_filter: None,
+            compiler_version: config
+                .compiler_config
+                .compiler_version
+                .unwrap_or(CompilerVersion::V2_0),
+            language_version: config
+                .compiler_config
+                .language_version
+                .unwrap_or(LanguageVersion::V1),
+        })
+}
+
+/// Groups mutants by the module and orders the batches by the package's module dependency graph.
+///
+/// Modules which other modules depend on come first, so their artifacts are prepared (and the
+/// build directory is warmed up) before the modules depending on them are tested. Modules are
+/// told apart by their addresses too, so same-named modules at different addresses get their own
+/// batches. Modules which are part of a dependency cycle are appended in alphabetical order as the
+/// last layer.
+///
+/// # Arguments
+///
+/// * `mutants` - mutants to batch.
+/// * `modules` - modules of the tested package.
+///
+/// # Returns
+///
+/// * `Vec<ModuleBatch>` - batches of mutants in the order they should be tested.
+pub(crate) fn batch_mutants_by_module<'a>(
+    mutants: &'a [MutationReport],
+    modules: &PackageModules,
+) -> Vec<ModuleBatch<'a>> {
+    let mut batches = BTreeMap::<String, Vec<&MutationReport>>::new();
+    for mutant in mutants {
+        batches
+            .entry(modules.full_name(mutant))
+            .or_default()
+            .push(mutant);
+    }
+
+    let graph = batches
+        .keys()
+        .map(|module| {
+            let deps = modules
+                .dependencies
+                .get(module)
+                .cloned()
+                .unwrap_or_default();
+            (module.clone(), deps)
+        })
+        .collect();
+
+    dependency_layers(&graph)
+        .into_iter()
+        .enumerate()
+        .flat_map(|(layer, modules)| modules.into_iter().map(move |module| (layer, module)))
+        .map(|(layer, module)| {
+            let mutants = batches.remove(&module).unwrap_or_default();
+            ModuleBatch {
+                module,
+                layer,
+                mutants,
+            }
+        })
+        .collect()
+}
+
+/// Shuffles the mutants using the given seed.
+///
+/// The mutants are shuffled within each batch, then the batches are split into slices which are
+/// interleaved, taking a slice from every module of the dependency layer in turn. So a run cut
+/// short samples the modules of the layer rather than testing only the first ones, while the
+/// dependencies are still prepared first. The same seed always produces the same order.
+pub(crate) fn shuffle_batches(batches: Vec<ModuleBatch<'_>>, seed: u64) -> Vec<ModuleBatch<'_>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut layers = BTreeMap::<usize, Vec<_>>::new();
+    for mut batch in batches {
+        batch.mutants.shuffle(&mut rng);
+        let slices = batch
+            .mutants
+            .chunks(SHUFFLED_SLICE_LEN)
+            .map(|mutants| ModuleBatch {
+                module: batch.module.clone(),
+                layer: batch.layer,
+                mutants: mutants.to_vec(),
+            })
+            .collect::<Vec<_>>();
+        layers
+            .entry(batch.layer)
+            .or_default()
+            .push(slices.into_iter());
+    }
+
+    let mut interleaved = vec![];
+    for mut slices in layers.into_values() {
+        loop {
+            let taken = interleaved.len();
+            interleaved.extend(slices.iter_mut().filter_map(Iterator::next));
+            if interleaved.len() == taken {
+                break;
+            }
+        }
+    }
+    interleaved
+}
+
+/// Splits the modules into layers, so that dependencies always come in lower layers than the
+/// modules depending on them. The modules of each layer are in alphabetical order.
+fn dependency_layers(graph: &BTreeMap<String, BTreeSet<String>>) -> Vec<Vec<String>> {
+    let mut layers = vec![];
+    let mut remaining = graph.clone();
+
+    while !remaining.is_empty() {
+        let ready = remaining
+            .iter()
+            .filter(|(_, deps)| deps.iter().all(|dep| !remaining.contains_key(dep)))
+            .map(|(module, _)| module.clone())
+            .collect::<Vec<_>>();
+
+        if ready.is_empty() {
+            // Dependency cycle - test the rest in alphabetical order.
+            layers.push(remaining.into_keys().collect());
+            break;
+        }
+
+        for module in &ready {
+            remaining.remove(module);
+        }
+        layers.push(ready);
+    }
+
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &[&str])]) -> BTreeMap<String, BTreeSet<String>> {
+        edges
+            .iter()
+            .map(|(module, deps)| {
+                (
+                    module.to_string(),
+                    deps.iter().map(|d| d.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dependencies_come_first() {
+        let g = graph(&[("A", &["B", "C"]), ("B", &["C"]), ("C", &[]), ("D", &[])]);
+        assert_eq!(dependency_layers(&g), vec![
+            vec!["C", "D"],
+            vec!["B"],
+            vec!["A"]
+        ]);
+    }
+
+    #[test]
+    fn cycles_are_appended_alphabetically() {
+        let g = graph(&[("A", &["B"]), ("B", &["A"]), ("C", &[])]);
+        assert_eq!(dependency_layers(&g), vec![vec!["C"], vec!["A", "B"]]);
+    }
+
+    fn mutants_of(module: &str, count: usize) -> Vec<MutationReport> {
+        (0..count)
+            .map(|i| {
+                let path = format!("mutants/{module}_{i}.move");
+                MutationReport::new(
+                    Path::new(&path),
+                    Path::new(&format!("{module}.move")),
+                    module,
+                    "sum",
+                    "",
+                    "",
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shuffling_is_reproducible() {
+        let mutants = mutants_of("Sum", 32);
+        let shuffled = |seed| {
+            let batches = vec![ModuleBatch {
+                module: "Sum".to_owned(),
+                layer: 0,
+                mutants: mutants.iter().collect(),
+            }];
+            let batches = shuffle_batches(batches, seed);
+            batches[0]
+                .mutants
+                .iter()
+                .map(|m| m.mutant_path().to_path_buf())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(shuffled(7), shuffled(7));
+        assert_ne!(shuffled(7), shuffled(8));
+        assert_eq!(shuffled(7).len(), mutants.len());
+    }
+
+    #[test]
+    fn shuffling_interleaves_the_modules_of_a_layer() {
+        let big = mutants_of("Big", 2 * SHUFFLED_SLICE_LEN + 2);
+        let small = mutants_of("Small", 10);
+        let top = mutants_of("Top", 10);
+        let batch = |module: &str, layer, mutants: &[MutationReport]| ModuleBatch {
+            module: module.to_owned(),
+            layer,
+            mutants: mutants.iter().collect(),
+        };
+        let batches = vec![
+            batch("Big", 0, &big),
+            batch("Small", 0, &small),
+            batch("Top", 1, &top),
+        ];
+
+        let shuffled = shuffle_batches(batches, 7);
+        let order = shuffled
+            .iter()
+            .map(|batch| (batch.module.as_str(), batch.mutants.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(order, [
+            ("Big", SHUFFLED_SLICE_LEN),
+            ("Small", 10),
+            ("Big", SHUFFLED_SLICE_LEN),
+            ("Big", 2),
+            ("Top", 10),
+        ]);
+    }
+
+    #[test]
+    fn same_named_modules_get_their_own_batches() {
+        let mutant = |file: &str| {
+            MutationReport::new(
+                Path::new("mutants/Coin_0.move"),
+                Path::new(file),
+                "Coin",
+                "mint",
+                "",
+                "",
+            )
+        };
+        let mutants = [
+            mutant("sources/coin.move"),
+            mutant("sources/other_coin.move"),
+        ];
+        let modules = PackageModules {
+            full_names: [
+                (("sources/coin.move", "Coin"), "0x1::Coin"),
+                (("sources/other_coin.move", "Coin"), "0x2::Coin"),
+            ]
+            .into_iter()
+            .map(|((file, name), full_name)| ((file.into(), name.to_owned()), full_name.to_owned()))
+            .collect(),
+            dependencies: graph(&[("0x1::Coin", &["0x2::Coin"]), ("0x2::Coin", &[])]),
+        };
+
+        let batches = batch_mutants_by_module(&mutants, &modules);
+        let order = batches
+            .iter()
+            .map(|batch| (batch.module.as_str(), batch.mutants.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(order, [("0x2::Coin", 1), ("0x1::Coin", 1)]);
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks of the mutation testing pipeline phases.
+
+use crate::{
+    abort_codes::PinnedTests,
+    cli::{create_mutator_options, CLIOptions, TestBuildConfig},
+    mutation_test::run_tests_on_original_code,
+    mutator_build_config, run_tests_on_mutant,
+    view_snapshots::ViewSnapshots,
+};
+use clap::Parser;
+use fs_extra::dir::CopyOptions;
+use move_mutator::{compiler::generate_ast, configuration::Configuration, report::Report};
+use mutator_common::{benchmark::Benchmark, tmp_package_dir::setup_outdir_and_package_path};
+use std::{fs, path::Path, time::Duration};
+
+/// The directory with the sample packages bundled with the tool.
+const SAMPLE_PACKAGES_DIR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../move-mutator/tests/move-assets"
+);
+
+/// Sample packages benchmarked when no package is given.
+const SAMPLE_PACKAGES: &[&str] = &["simple", "breakcontinue"];
+
+/// Options of the `bench` command.
+#[derive(Parser, Debug, Clone)]
+pub struct BenchOptions {
+    /// How many times the baseline tests, AST generation and mutant generation are measured.
+    #[clap(long, default_value_t = 3)]
+    pub iterations: usize,
+
+    /// The maximum number of mutants executed per package.
+    #[clap(long, default_value_t = 20)]
+    pub max_mutants: usize,
+
+    /// The options of the mutation test tool.
+    #[clap(flatten)]
+    pub cli_options: CLIOptions,
+
+    /// The configuration options for running the tests. If the package isn't set, the sample
+    /// packages bundled with the tool are benchmarked.
+    #[clap(flatten)]
+    pub test_build_config: TestBuildConfig,
+}
+
+/// Measured durations of a single phase of the pipeline.
+#[derive(Debug, Clone)]
+pub struct PhaseTimings {
+    /// Name of the phase.
+    pub phase: &'static str,
+    /// Durations of all measurements.
+    pub samples: Vec<Duration>,
+}
+
+impl PhaseTimings {
+    /// Returns the shortest, the median and the longest duration.
+    #[must_use]
+    pub fn summary(&self) -> Option<(Duration, Duration, Duration)> {
+        let mut samples = self.samples.clone();
+        samples.sort();
+        Some((
+            *samples.first()?,
+            samples[samples.len() / 2],
+            *samples.last()?,
+        ))
+    }
+}
+
+/// Benchmarks the phases of the pipeline and prints the results.
+///
+/// # Errors
+/// Returns an error if any phase of the pipeline fails.
+pub fn run_benchmarks(options: &BenchOptions) -> anyhow::Result<()> {
+    options.test_build_config.setup_test_env();
+
+    let packages = match &options.test_build_config.move_pkg.package_dir {
+        Some(package) => vec![package.clone()],
+        None => SAMPLE_PACKAGES
+            .iter()
+            .map(|package| Path::new(SAMPLE_PACKAGES_DIR).join(package))
+            .collect(),
+    };
+
+    for package in packages {
+        let timings = bench_package(options, &package)?;
+        print_timings(&package, &timings);
+    }
+    Ok(())
+}
+
+/// Benchmarks the phases of the pipeline on a single package.
+fn bench_package(options: &BenchOptions, package: &Path) -> anyhow::Result<Vec<PhaseTimings>> {
+    let mut test_config = options.test_build_config.clone();
+    test_config.move_pkg.package_dir = Some(package.to_path_buf());
+    let (outdir, package_path) = setup_outdir_and_package_path(package)?;
+    let build_config = mutator_build_config(&test_config);
+
+    let baseline = measure(options.iterations, || {
+        run_tests_on_original_code(&test_config, &package_path)
+    })?;
+
+    let mutator_options = create_mutator_options(&options.cli_options, &test_config);
+    let configuration = Configuration::load(mutator_options.clone(), Some(package_path.clone()))?;
+    let ast = measure(options.iterations, || {
+        generate_ast(&configuration, &build_config, &package_path)
+    })?;
+
+    let outdir_mutant = outdir.join("mutants");
+    let generate = |verify_mutants| {
+        let options = move_mutator::cli::CLIOptions {
+            out_mutant_dir: Some(outdir_mutant.clone()),
+            verify_mutants: Some(verify_mutants),
+            ..mutator_options.clone()
+        };
+        move_mutator::run_move_mutator(options, &build_config, &package_path)
+    };
+    let generation = measure(options.iterations, || generate(false))?;
+    let verification = measure(options.iterations, || generate(true))?;
+
+    // Mutants from the last (verified) generation are executed in a single sandbox, the same way
+    // the tool does it.
+    let report = Report::load_from_json_file(&outdir_mutant.join("report.json"))?;
+    let job_outdir = outdir.join("bench_job");
+    fs::create_dir_all(&job_outdir)?;
+    fs_extra::dir::copy(
+        &package_path,
+        &job_outdir,
+        &CopyOptions::new().content_only(true),
+    )?;
+    let kill_policy = options.cli_options.kill_policy();
+    let execution = report
+        .get_mutants()
+        .iter()
+        .take(options.max_mutants)
+        .map(|mutant| {
+            let (benchmark, _) = run_tests_on_mutant(
+                &test_config,
+                &kill_policy,
+                options.cli_options.runs_per_mutant(),
+                &PinnedTests::default(),
+                &ViewSnapshots::default(),
+                mutant,
+                &package_path,
+                &job_outdir,
+            );
+            benchmark.elapsed
+        })
+        .collect();
+
+    let _ = fs::remove_dir_all(outdir);
+
+    Ok(vec![
+        PhaseTimings {
+            phase: "baseline tests",
+            samples: baseline,
+        },
+        PhaseTimings {
+            phase: "AST generation",
+            samples: ast,
+        },
+        PhaseTimings {
+            phase: "mutant generation",
+            samples: generation,
+        },
+        PhaseTimings {
+            phase: "generation with verification",
+            samples: verification,
+        },
+        PhaseTimings {
+            phase: "mutant execution",
+            samples: execution,
+        },
+    ])
+}
+
+/// Runs the phase the given number of times (at least once) and returns the durations.
+fn measure<T>(
+    iterations: usize,
+    mut phase: impl FnMut() -> anyhow::Result<T>,
+) -> anyhow::Result<Vec<Duration>> {
+    (0..iterations.max(1))
+        .map(|_| {
+            let mut benchmark = Benchmark::new();
+            benchmark.start();
+            phase()?;
+            benchmark.stop();
+            Ok(benchmark.elapsed)
+        })
+        .collect()
+}
+
+/// Prints the benchmark results of the package.
+fn print_timings(package: &Path, timings: &[PhaseTimings]) {
+    println!("Benchmarks for {}:", package.display());
+    println!(
+        "{:<30} {:>8} {:>12} {:>12} {:>12}",
+        "phase", "samples", "min", "median", "max"
+    );
+    for timing in timings {
+        let Some((min, median, max)) = timing.summary() else {
+            println!("{:<30} {:>8}", timing.phase, 0);
+            continue;
+        };
+        let [min, median, max] = [min, median, max].map(|d| format!("{d:.1?}"));
+        println!(
+            "{:<30} {:>8} {min:>12} {median:>12} {max:>12}",
+            timing.phase,
+            timing.samples.len(),
+        );
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phase_summary() {
+        let timings = PhaseTimings {
+            phase: "test",
+            samples: [30, 10, 20].map(Duration::from_millis).to_vec(),
+        };
+        let (min, median, max) = timings.summary().unwrap();
+        assert_eq!(min, Duration::from_millis(10));
+        assert_eq!(median, Duration::from_millis(20));
+        assert_eq!(max, Duration::from_millis(30));
+
+        let empty = PhaseTimings {
+            phase: "empty",
+            samples: vec![],
+        };
+        assert!(empty.summary().is_none());
+    }
+}
@@ -2,13 +2,33 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use aptos::{common::types::MovePackageDir, move_tool::experiments_from_opt_level};
 use aptos_framework::extended_checks;
-use clap::Parser;
+use aptos_types::account_address::AccountAddress;
+use clap::{parser::ValueSource, CommandFactory, FromArgMatches, Parser};
+use move_command_line_common::address::NumericalAddress;
 use move_model::metadata::LanguageVersion;
-use move_mutator::cli::{FunctionFilter, ModuleFilter};
+use move_mutator::{
+    cli::{parse_operator, parse_optional_operator, ConfigFormat, FunctionFilter, ModuleFilter},
+    compiler::CompilerTarget,
+};
 use move_package::CompilerConfig;
-use std::path::PathBuf;
+use move_unit_test::UnitTestingConfig;
+#[cfg(feature = "webhook")]
+use mutator_common::notify::NotifyOn;
+#[cfg(feature = "upload")]
+use mutator_common::upload::UploadTarget;
+use mutator_common::{
+    checkpoint::CheckpointOptions,
+    crash_report::{self, CrashContext, REDACTED},
+    locale::Locale,
+    post_run::SummaryFormat,
+    report::{KillReason, ReportFormat},
+    results_store::StoreLocation,
+    score::ScoreOptions,
+};
+use std::{collections::BTreeMap, env, ffi::OsString, num::NonZeroUsize, path::PathBuf};
 
 /// Command line options for mutation test tool.
 #[derive(Parser, Default, Debug, Clone)]
@@ -31,10 +51,29 @@ pub struct CLIOptions {
     )]
     pub mutate_functions: FunctionFilter,
 
-    /// Save report to a JSON file.
+    /// Optional configuration file (TOML) for mutator tool. Run `config schema` to see the
+    /// available keys.
+    #[clap(long, value_parser, conflicts_with = "use_generated_mutants")]
+    pub mutator_conf: Option<PathBuf>,
+
+    /// Print the effective mutator options (defaults merged with the configuration file,
+    /// environment variables and the command line) in the given format and exit.
+    #[clap(long, value_enum, num_args = 0..=1, default_missing_value = "toml")]
+    pub print_config: Option<ConfigFormat>,
+
+    /// Save report to a file (JSON by default, see `--report-format`).
     #[clap(long, value_parser)]
     pub output: Option<PathBuf>,
 
+    /// The format of the report saved to the output file.
+    #[clap(long, value_enum, default_value_t)]
+    pub report_format: ReportFormat,
+
+    /// The locale used to format numbers in the summary printed on the screen (e.g. `en`,
+    /// `de-DE`). The saved report is not affected.
+    #[clap(long, default_value_t)]
+    pub locale: Locale,
+
     /// Use previously generated mutants.
     #[clap(long, value_parser)]
     pub use_generated_mutants: Option<PathBuf>,
@@ -42,21 +81,318 @@ pub struct CLIOptions {
     /// Remove averagely given percentage of mutants. See the doc for more details.
     #[clap(long, conflicts_with = "use_generated_mutants")]
     pub downsampling_ratio_percentage: Option<usize>,
+
+    /// Evaluate only the mutants whose IDs are listed in the file, one per line (`#` starts a
+    /// comment), e.g. to re-check the survivors of a previous run after adding tests. With
+    /// `--use-generated-mutants`, the listed mutants are picked from the generated ones, so
+    /// nothing is regenerated.
+    #[clap(long, value_parser, conflicts_with = "downsampling_ratio_percentage")]
+    pub only_mutants: Option<PathBuf>,
+
+    /// When downsampling, select the mutants tested by the latest run of the package recorded in
+    /// the `--results-store` first, so the score changes reflect the changes of the code and tests
+    /// rather than a different sample.
+    #[clap(
+        long,
+        requires = "results_store",
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub stable_sampling: bool,
+
+    /// Keep at most the given number of mutants per file. The kept mutants are spread across the
+    /// functions and operators of the file.
+    #[clap(long, conflicts_with = "use_generated_mutants")]
+    pub max_mutants_per_file: Option<usize>,
+
+    /// Optional mutation operators to enable (disabled by default), separated by commas.
+    #[clap(
+        long,
+        value_parser = parse_optional_operator,
+        value_delimiter = ',',
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub extra_operators: Vec<String>,
+
+    /// Mutation operators to disable, separated by commas, e.g. `assert_error_code_replacement`
+    /// to keep mutating the `assert!` conditions but not their error codes.
+    #[clap(
+        long,
+        value_parser = parse_operator,
+        value_delimiter = ',',
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub disable_operators: Vec<String>,
+
+    /// Generate skeletons of unit tests for the surviving mutants in the `suggested_tests`
+    /// directory.
+    #[clap(long)]
+    pub suggest_tests: bool,
+
+    /// Show the unit test coverage side by side with the mutation score for each module.
+    #[clap(long, requires = "apply_coverage")]
+    pub coverage_summary: bool,
+
+    /// Reasons for which a mutant counts as killed, separated by commas. Mutants killed for other
+    /// reasons are excluded from the mutation score. All reasons except `compile-failure` count
+    /// if not set. With `compile-failure`, the mutants which don't compile are scored as killed.
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub kill_on: Vec<KillReason>,
+
+    /// Options of the mutation score computation.
+    #[clap(flatten)]
+    pub score: ScoreOptions,
+
+    /// Only warn (instead of failing) when the module and function filters match no mutation sites.
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub allow_empty: Option<bool>,
+
+    /// Record the mutants which fail to compile in the report, together with the compiler
+    /// diagnostics (see `display-report explain`).
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub keep_stillborn: Option<bool>,
+
+    /// Verify the mutants also under the given language versions (optionally with the compiler
+    /// version, e.g. `2.0,2.1@2.0`) and report the mutants which compile under some of them but
+    /// not the others.
+    #[clap(long, value_delimiter = ',', conflicts_with = "use_generated_mutants")]
+    pub compiler_matrix: Vec<CompilerTarget>,
+
+    /// Test mutants on lines changed in the last given number of days first (based on
+    /// `git blame`) and flag them in the report.
+    #[clap(long, conflicts_with = "new_code_commits")]
+    pub new_code_days: Option<u64>,
+
+    /// Test mutants on lines changed in the last given number of commits first (based on
+    /// `git blame`) and flag them in the report.
+    #[clap(long)]
+    pub new_code_commits: Option<usize>,
+
+    /// Attribute the surviving mutants to owners using the given `CODEOWNERS` file (or a mapping
+    /// file with the same syntax).
+    #[clap(long, value_parser)]
+    pub codeowners: Option<PathBuf>,
+
+    /// A command run after the tool finishes, e.g. to post the summary of the new surviving
+    /// mutants to an issue tracker. The command is run without a shell and gets the summary on the
+    /// standard input. See the README for the provided environment variables.
+    #[clap(long)]
+    pub post_run_cmd: Option<String>,
+
+    /// The report of an earlier run (e.g. on the main branch). The mutants which survived it are
+    /// left out of the post-run summary, so only the new survivors are reported.
+    #[clap(long, value_parser, requires = "post_run_cmd")]
+    pub post_run_baseline: Option<PathBuf>,
+
+    /// The format of the post-run summary.
+    #[clap(long, value_enum, default_value_t, requires = "post_run_cmd")]
+    pub post_run_format: SummaryFormat,
+
+    /// The URL to POST the JSON summary to when the run finishes (or fails).
+    #[cfg(feature = "webhook")]
+    #[clap(long)]
+    pub notify_webhook: Option<String>,
+
+    /// When to notify the webhook.
+    #[cfg(feature = "webhook")]
+    #[clap(long, value_enum, default_value_t, requires = "notify_webhook")]
+    pub notify_on: NotifyOn,
+
+    /// Record the summary of the run in the results store and print the change of the mutation
+    /// score since the previous run: a `.jsonl` file, an SQLite database (`.db`, `.sqlite`, with
+    /// the `sqlite` feature) or a `postgres://` URL (with the `postgres` feature).
+    #[clap(long)]
+    pub results_store: Option<StoreLocation>,
+
+    /// Upload the report and the generated mutants to object storage after the run, e.g.
+    /// `s3://bucket/prefix` or `gs://bucket/prefix`. The credentials are read from the
+    /// environment, see the README.
+    #[cfg(feature = "upload")]
+    #[clap(long)]
+    pub upload_to: Option<UploadTarget>,
+
+    /// Print the time spent in each phase of the run (with the number of processed mutants)
+    /// at the end.
+    #[clap(long)]
+    pub timings: bool,
+
+    /// Sample the memory usage of the tool and its worker processes during the run and print the
+    /// peak memory with the timings at the end (Linux only).
+    #[clap(long)]
+    pub profile_memory: bool,
+
+    /// Print each surviving mutant as soon as it survives: the diff with the changed words
+    /// highlighted and the tests which passed on the mutant.
+    #[clap(short, long)]
+    pub verbose: bool,
+
+    /// Skip the baseline run (the tests on the original code), e.g. when it has already passed in a
+    /// prior CI step. If the baseline fails, all mutants are reported as killed.
+    #[clap(long)]
+    pub skip_baseline: bool,
+
+    /// Evaluate the mutants in a random order, interleaving the modules. That balances the load
+    /// across the parallel workers and avoids a systematic bias when the run is cut short.
+    #[clap(long)]
+    pub shuffle: bool,
+
+    /// The seed used by `--shuffle`, so the order can be reproduced. If not set, a random seed is
+    /// picked and printed.
+    #[clap(long, requires = "shuffle")]
+    pub shuffle_seed: Option<u64>,
+
+    /// Skip the remaining mutants of a function once its first given number of tested mutants
+    /// have all survived, as no test exercises the function effectively. The skipped mutants are
+    /// reported with the `skipped` status and count as uncovered in the mutation score.
+    #[clap(long)]
+    pub skip_uncovered_after: Option<NonZeroUsize>,
+
+    /// Experimental: also run the tests with mutated named address values in `Move.toml` (fresh
+    /// values and swaps), to find tests coupled to specific address literals. The results are
+    /// reported separately from the code mutants.
+    #[clap(long)]
+    pub mutate_manifest: bool,
+
+    /// Run the tests the given number of times for every mutant, for tests whose outcome isn't
+    /// deterministic (e.g. property tests). The mutant is killed if the majority of the runs kill
+    /// it, and the kill probability of the mutants with differing outcomes is reported.
+    #[clap(long)]
+    pub runs_per_mutant: Option<NonZeroUsize>,
+
+    /// Pin the `#[expected_failure]` annotations without the abort code to the codes the tests
+    /// abort with on the original code, so the mutants changing the abort code are detected.
+    /// Such kills have the `abort-code` kill reason, which can be left out of `--kill-on` to only
+    /// flag them.
+    #[clap(long, conflicts_with = "use_generated_mutants")]
+    pub pin_abort_codes: bool,
+
+    /// Record the outputs of the view functions called in the tests annotated with
+    /// `#[mutation::view_snapshot]` on the original code, so the mutants changing the outputs are
+    /// killed even if no test asserts on them. Such kills have the `view-output` kill reason.
+    #[clap(long, conflicts_with = "use_generated_mutants")]
+    pub view_snapshots: bool,
+
+    /// Options of the checkpoints, which allow resuming an interrupted run.
+    #[clap(flatten)]
+    pub checkpoint: CheckpointOptions,
+}
+
+impl CLIOptions {
+    /// Returns the kill policy - the reasons for which a mutant counts as killed.
+    #[must_use]
+    pub fn kill_policy(&self) -> Vec<KillReason> {
+        if self.kill_on.is_empty() {
+            KillReason::DEFAULT.to_vec()
+        } else {
+            let mut policy = self.kill_on.clone();
+            policy.sort();
+            policy.dedup();
+            policy
+        }
+    }
+
+    /// Returns the seed used for shuffling the mutants, if the shuffling is enabled.
+    #[must_use]
+    pub fn shuffle_seed(&self) -> Option<u64> {
+        self.shuffle
+            .then(|| self.shuffle_seed.unwrap_or_else(rand::random))
+    }
+
+    /// Returns the number of test runs for every mutant (a single one by default).
+    #[must_use]
+    pub fn runs_per_mutant(&self) -> NonZeroUsize {
+        self.runs_per_mutant.unwrap_or(NonZeroUsize::MIN)
+    }
+
+    /// Returns the window defining which lines are considered new code, if set.
+    #[must_use]
+    pub fn new_code_window(&self) -> Option<NewCodeWindow> {
+        match (self.new_code_days, self.new_code_commits) {
+            (Some(days), _) => Some(NewCodeWindow::Days(days)),
+            (None, Some(commits)) => Some(NewCodeWindow::Commits(commits)),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the context of the crash report, with the webhook URL and the values of the test
+    /// environment variables redacted.
+    #[must_use]
+    pub fn crash_context(&self, test_config: &TestBuildConfig) -> CrashContext {
+        let mut options = self.clone();
+        #[cfg(feature = "webhook")]
+        {
+            options.notify_webhook = options.notify_webhook.map(|_| REDACTED.to_owned());
+        }
+        if let Some(StoreLocation::Postgres(url)) = &mut options.results_store {
+            REDACTED.clone_into(url);
+        }
+        let mut test_config = test_config.clone();
+        for (_, value) in &mut test_config.test_env {
+            *value = REDACTED.to_owned();
+        }
+
+        CrashContext {
+            tool: "move-mutation-test".to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            output_dir: crash_report::report_dir(self.output.as_deref()),
+            package_dir: test_config.move_pkg.get_package_path().ok(),
+            options: format!("{options:#?}\n{test_config:#?}"),
+        }
+    }
+}
+
+/// Defines which lines of the source code are considered new code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewCodeWindow {
+    /// Lines authored in the last given number of days.
+    Days(u64),
+    /// Lines authored in the last given number of commits.
+    Commits(usize),
 }
 
 /// This function creates a mutator CLI options from the given mutation-test options.
+///
+/// The coverage and compiler warnings options are shared with the test configuration. The test
+/// runner reads them only from the command line, so they can only turn the options of the mutator
+/// on.
 #[must_use]
 pub fn create_mutator_options(
     options: &CLIOptions,
-    apply_coverage: bool,
+    test_config: &TestBuildConfig,
 ) -> move_mutator::cli::CLIOptions {
     move_mutator::cli::CLIOptions {
         mutate_functions: options.mutate_functions.clone(),
         mutate_modules: options.mutate_modules.clone(),
         downsampling_ratio_percentage: options.downsampling_ratio_percentage,
-        apply_coverage,
+        only_mutants: options.only_mutants.clone(),
+        max_mutants_per_file: options.max_mutants_per_file,
+        apply_coverage: test_config.apply_coverage.then_some(true),
+        ignore_compile_warnings: test_config.ignore_compile_warnings.then_some(true),
+        extra_operators: options.extra_operators.clone(),
+        disable_operators: options.disable_operators.clone(),
+        allow_empty: options.allow_empty,
+        // The mutants which don't compile are scored if the compile failures count as kills.
+        keep_stillborn: if options.kill_policy().contains(&KillReason::CompileFailure) {
+            Some(true)
+        } else {
+            options.keep_stillborn
+        },
+        compiler_matrix: options.compiler_matrix.clone(),
+        mutator_conf: options.mutator_conf.clone(),
+        use_generated_mutants: options.use_generated_mutants.clone(),
         // To run tests, compilation must succeed
-        verify_mutants: true,
+        verify_mutants: Some(true),
         ..Default::default()
     }
 }
@@ -87,12 +423,84 @@ pub struct TestBuildConfig {
     #[clap(long = "coverage", conflicts_with = "use_generated_mutants")]
     pub apply_coverage: bool,
 
+    /// The coverage map used if collecting the coverage fails while the tests pass, e.g. the map
+    /// saved by a previous run (see the `coverage_map` field of the report) or the one generated
+    /// by `aptos move test --coverage`. A warning is printed instead of aborting the run.
+    #[clap(long, value_parser, requires = "apply_coverage")]
+    pub coverage_fallback: Option<PathBuf>,
+
     /// The maximum gas limit for each test.
     ///
     /// Used mainly for disabling mutants with infinite loops.
     /// The default value is large enough for all normal tests in most projects.
-    #[clap(long, default_value_t = 1_000_000)]
+    ///
+    /// The tests run with zero native gas parameters, so only the executed bytecode instructions
+    /// are charged and the limit is an instruction bound (hence the `--instruction-limit` alias).
+    /// The unit test runner doesn't support a separate gas bound.
+    #[clap(long, visible_alias = "instruction-limit", default_value_t = 1_000_000)]
     pub gas_limit: u64,
+
+    /// Path to the `build` directory produced by a prior step (e.g. a CI job).
+    ///
+    /// The compiled artifacts are reused for the tested package and the dependencies are not
+    /// fetched again.
+    #[clap(long, value_parser)]
+    pub prebuilt_package: Option<PathBuf>,
+
+    /// Extra arguments passed to the Move unit test runner, e.g. `--test-args "--threads 4"`.
+    ///
+    /// The number of threads and the statistics given here take precedence over the values chosen
+    /// by the tool. The other options of the tool (e.g. `--filter`) take precedence over the
+    /// arguments given here.
+    #[clap(long, allow_hyphen_values = true)]
+    pub test_args: Option<String>,
+
+    /// Environment variables set for the test runs, e.g. `--test-env SEED=42`.
+    ///
+    /// Can be given multiple times.
+    #[clap(long, value_parser = parse_env_var)]
+    pub test_env: Vec<(String, String)>,
+
+    /// Clear the environment variables before running the tests, so the runs don't depend on the
+    /// environment of the tool. Only the variables needed by the tool itself and the ones given
+    /// with `--test-env` are kept.
+    #[clap(long)]
+    pub test_env_clear: bool,
+
+    /// The seed passed to the tests as the `test_seed` named address, so tests using randomness
+    /// can be seeded from it (e.g. `randomness::set_seed(bcs::to_bytes(&@test_seed))`) and their
+    /// outcome is reproducible.
+    ///
+    /// With `--runs-per-mutant`, every run of a mutant uses the next seed (the first run uses the
+    /// given one), and the seed of the run which decided the outcome is reported.
+    #[clap(long)]
+    pub test_seed: Option<u64>,
+
+    /// A JSON snapshot of the on-chain state the tests start with, so packages relying on
+    /// pre-existing resources (e.g. framework or oracle data) can be tested without setup code.
+    ///
+    /// The file lists the resources with their BCS-serialized values in hex:
+    /// `{"resources": [{"address": "0xcafe", "type": "0xcafe::oracle::Price", "value": "0x2a00"}]}`.
+    /// The resources are published in addition to the default feature flags.
+    #[clap(long, value_parser)]
+    pub genesis: Option<PathBuf>,
+}
+
+/// The named address which passes the `--test-seed` to the tests.
+const TEST_SEED_ADDRESS: &str = "test_seed";
+
+/// Environment variables kept by `--test-env-clear`, as the tool needs them to fetch and build the
+/// dependencies and to store the temporary files.
+const KEPT_ENV_VARS: &[&str] = &["PATH", "HOME", "TMPDIR", "MOVE_HOME", "RUST_LOG"];
+
+/// Parses the environment variable in the `KEY=VALUE` form.
+fn parse_env_var(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_owned(), value.to_owned())),
+        _ => Err(format!(
+            "invalid environment variable '{s}', expected KEY=VALUE"
+        )),
+    }
 }
 
 impl TestBuildConfig {
@@ -111,6 +519,182 @@ impl TestBuildConfig {
             experiments: experiments_from_opt_level(&self.move_pkg.optimize),
         }
     }
+
+    /// Returns the named addresses of the package with the `test_seed` address, if the seed is set.
+    pub fn named_addresses(&self) -> BTreeMap<String, AccountAddress> {
+        let mut addresses = self.move_pkg.named_addresses();
+        if let Some(seed) = self.test_seed {
+            addresses.insert(TEST_SEED_ADDRESS.to_owned(), seed_address(seed));
+        }
+        addresses
+    }
+
+    /// Returns the configuration of the given (zero-based) run of a mutant, with the seed
+    /// shifted by the run number.
+    #[must_use]
+    pub fn for_run(&self, run: usize) -> Self {
+        let mut config = self.clone();
+        config.test_seed = self
+            .test_seed
+            .map(|seed| seed.wrapping_add(u64::try_from(run).unwrap_or(u64::MAX)));
+        config
+    }
+
+    /// Sets up the environment variables of the test runs.
+    ///
+    /// The tests run within the tool's process, so the environment is shared by all test runs and
+    /// it must be set up before any of them starts.
+    pub fn setup_test_env(&self) {
+        if self.test_env_clear {
+            for (key, _) in env::vars_os() {
+                if !KEPT_ENV_VARS.iter().any(|kept| key == *kept) {
+                    env::remove_var(key);
+                }
+            }
+        }
+        for (key, value) in &self.test_env {
+            env::set_var(key, value);
+        }
+    }
+
+    /// Creates the [`UnitTestingConfig`] for a test run from the `--test-args` and the options
+    /// of the tool.
+    ///
+    /// The `num_threads` and `report_statistics` are used only if they are not given in
+    /// `--test-args`.
+    pub fn unit_testing_config(
+        &self,
+        num_threads: usize,
+        report_statistics: bool,
+    ) -> anyhow::Result<UnitTestingConfig> {
+        let args = match &self.test_args {
+            Some(args) => shlex::split(args)
+                .ok_or_else(|| anyhow!("invalid quoting in the test arguments: {args}"))?,
+            None => vec![],
+        };
+        let matches = UnitTestingConfig::command()
+            .no_binary_name(true)
+            .try_get_matches_from(args)
+            .map_err(|e| anyhow!("invalid test arguments: {e}"))?;
+        let mut config = UnitTestingConfig::from_arg_matches(&matches)?;
+
+        let is_set = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+        if !is_set("num_threads") {
+            config.num_threads = num_threads;
+        }
+        if !is_set("report_statistics") {
+            config.report_statistics = report_statistics;
+        }
+
+        if self.filter.is_some() {
+            config.filter.clone_from(&self.filter);
+        }
+        config.report_storage_on_error |= self.dump_state;
+        config.ignore_compile_warnings |= self.ignore_compile_warnings;
+        config
+            .named_address_values
+            .extend(
+                self.named_addresses()
+                    .iter()
+                    .map(|(name, account_address)| {
+                        (
+                            name.clone(),
+                            NumericalAddress::from_account_address(*account_address),
+                        )
+                    }),
+            );
+        Ok(config)
+    }
+
+    /// Returns the command line arguments (with the binary name) recreating the options used to
+    /// build and run the tests, so the tests can be run the same way in a child process.
+    ///
+    /// The package is not included, as the child process gets its own copy of the package.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<OsString> {
+        let mut args = vec![OsString::from("move-mutation-test")];
+        let mut push = |name: &str, value: Option<OsString>| {
+            args.push(format!("--{name}").into());
+            args.extend(value);
+        };
+
+        let move_pkg = &self.move_pkg;
+        if let Some(output_dir) = &move_pkg.output_dir {
+            push("output-dir", Some(output_dir.into()));
+        }
+        let named_addresses = move_pkg.named_addresses();
+        if !named_addresses.is_empty() {
+            let named_addresses = named_addresses
+                .iter()
+                .map(|(name, address)| format!("{name}={}", address.to_hex_literal()))
+                .collect::<Vec<_>>()
+                .join(",");
+            push("named-addresses", Some(named_addresses.into()));
+        }
+        // The versions are derived from `--move-2` if it's set.
+        if move_pkg.move_2 {
+            push("move-2", None);
+        } else {
+            if let Some(version) = move_pkg.bytecode_version {
+                push("bytecode-version", Some(version.to_string().into()));
+            }
+            if let Some(version) = move_pkg.compiler_version {
+                push("compiler-version", Some(version.to_string().into()));
+            }
+            if let Some(version) = move_pkg.language_version {
+                push("language-version", Some(version.to_string().into()));
+            }
+        }
+        if let Some(level) = &move_pkg.optimize {
+            // The optimization levels are parsed from their lowercase names.
+            push("optimize", Some(format!("{level:?}").to_lowercase().into()));
+        }
+        for (flag, name) in [
+            (move_pkg.dev, "dev"),
+            (
+                move_pkg.skip_fetch_latest_git_deps,
+                "skip-fetch-latest-git-deps",
+            ),
+            (move_pkg.skip_attribute_checks, "skip-attribute-checks"),
+            (move_pkg.check_test_code, "check-test-code"),
+            (self.dump_state, "dump"),
+            (self.ignore_compile_warnings, "ignore-compile-warnings"),
+            (self.test_env_clear, "test-env-clear"),
+        ] {
+            if flag {
+                push(name, None);
+            }
+        }
+
+        if let Some(filter) = &self.filter {
+            push("filter", Some(filter.into()));
+        }
+        push("gas-limit", Some(self.gas_limit.to_string().into()));
+        if let Some(prebuilt_package) = &self.prebuilt_package {
+            push("prebuilt-package", Some(prebuilt_package.into()));
+        }
+        for (key, value) in &self.test_env {
+            push("test-env", Some(format!("{key}={value}").into()));
+        }
+        if let Some(seed) = self.test_seed {
+            push("test-seed", Some(seed.to_string().into()));
+        }
+        if let Some(genesis) = &self.genesis {
+            push("genesis", Some(genesis.into()));
+        }
+        if let Some(test_args) = &self.test_args {
+            // Given with `=`, as the value may start with a hyphen.
+            args.push(format!("--test-args={test_args}").into());
+        }
+        args
+    }
+}
+
+/// Converts the seed to the value of the `test_seed` named address.
+fn seed_address(seed: u64) -> AccountAddress {
+    let mut bytes = [0; AccountAddress::LENGTH];
+    bytes[AccountAddress::LENGTH - 8..].copy_from_slice(&seed.to_be_bytes());
+    AccountAddress::new(bytes)
 }
 
 /// Get bytecode version.
@@ -144,8 +728,113 @@ mod tests {
             ..Default::default()
         };
 
-        let mutator_options = create_mutator_options(&options, false);
+        let test_config = TestBuildConfig::parse_from(["test", "--ignore-compile-warnings"]);
+
+        let mutator_options = create_mutator_options(&options, &test_config);
 
         assert_eq!(mutator_options.mutate_modules, options.mutate_modules);
+        assert_eq!(mutator_options.ignore_compile_warnings, Some(true));
+        // Not set, so the configuration file can set it.
+        assert_eq!(mutator_options.apply_coverage, None);
+        assert_eq!(mutator_options.keep_stillborn, None);
+
+        let options = crate::cli::CLIOptions {
+            kill_on: vec![KillReason::TestFailure, KillReason::CompileFailure],
+            ..Default::default()
+        };
+        assert_eq!(
+            create_mutator_options(&options, &test_config).keep_stillborn,
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_args_are_forwarded_to_the_test_runner() {
+        let config = TestBuildConfig::parse_from([
+            "test",
+            "--filter",
+            "sum",
+            "--test-args",
+            "--threads 3 --filter other",
+        ]);
+        let unit_config = config.unit_testing_config(1, false).unwrap();
+        assert_eq!(unit_config.num_threads, 3);
+        // The tool's own options take precedence.
+        assert_eq!(unit_config.filter.as_deref(), Some("sum"));
+
+        let config = TestBuildConfig::parse_from(["test"]);
+        let unit_config = config.unit_testing_config(1, true).unwrap();
+        assert_eq!(unit_config.num_threads, 1);
+        assert!(unit_config.report_statistics);
+
+        let config = TestBuildConfig::parse_from(["test", "--test-args", "--no-such-option"]);
+        assert!(config.unit_testing_config(1, false).is_err());
+    }
+
+    #[test]
+    fn test_build_config_is_recreated_from_args() {
+        let config = TestBuildConfig::parse_from([
+            "test",
+            "--package-dir",
+            "pkg",
+            "--named-addresses",
+            "alice=0x1234",
+            "--dev",
+            "--filter",
+            "sum",
+            "--gas-limit",
+            "5000",
+            "--test-args",
+            "--threads 3",
+            "--test-env",
+            "SEED=42",
+            "--test-seed",
+            "7",
+            "--coverage",
+        ]);
+        let recreated = TestBuildConfig::parse_from(config.to_args());
+
+        assert!(recreated.move_pkg.package_dir.is_none());
+        assert_eq!(
+            recreated.move_pkg.named_addresses(),
+            config.move_pkg.named_addresses()
+        );
+        assert!(recreated.move_pkg.dev);
+        assert_eq!(recreated.filter.as_deref(), Some("sum"));
+        assert_eq!(recreated.gas_limit, 5000);
+        assert_eq!(recreated.test_args.as_deref(), Some("--threads 3"));
+        assert_eq!(recreated.test_env, config.test_env);
+        assert_eq!(recreated.named_addresses(), config.named_addresses());
+        // The coverage is collected by the child process anyway.
+        assert!(!recreated.apply_coverage);
+    }
+
+    #[test]
+    fn parse_env_var_requires_key_and_value() {
+        assert_eq!(
+            parse_env_var("SEED=42=1"),
+            Ok(("SEED".to_owned(), "42=1".to_owned()))
+        );
+        assert_eq!(
+            parse_env_var("EMPTY="),
+            Ok(("EMPTY".to_owned(), String::new()))
+        );
+        assert!(parse_env_var("SEED").is_err());
+        assert!(parse_env_var("=42").is_err());
+    }
+
+    #[test]
+    fn test_seed_is_passed_as_named_address() {
+        let config = TestBuildConfig::parse_from(["test", "--test-seed", "42"]);
+        assert_eq!(
+            config.named_addresses().get(TEST_SEED_ADDRESS),
+            Some(&AccountAddress::from_hex_literal("0x2a").unwrap())
+        );
+        assert_eq!(config.for_run(0).test_seed, Some(42));
+        assert_eq!(config.for_run(2).test_seed, Some(44));
+
+        let config = TestBuildConfig::parse_from(["test"]);
+        assert!(!config.named_addresses().contains_key(TEST_SEED_ADDRESS));
+        assert_eq!(config.for_run(2).test_seed, None);
     }
 }
@@ -0,0 +1,714 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `daemon` command: a long-running service running the queued mutation test jobs.
+
+use crate::{
+    cli::{CLIOptions, TestBuildConfig},
+    run_mutation_test_saving_build,
+};
+use anyhow::{anyhow, bail, Context};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use mutator_common::package_defaults::{PackageDefaults, DEFAULTS_FILE};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, VecDeque},
+    env, fs,
+    hash::{Hash, Hasher},
+    io::{BufRead, BufReader, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{Arc, Condvar, Mutex},
+    thread,
+};
+use tempfile::TempDir;
+
+/// The file name of the default socket of the daemon.
+const SOCKET_NAME: &str = "move-mutation-test-daemon.sock";
+
+/// Returns the default socket of the daemon, in the runtime directory of the user (or in the home
+/// directory), which other users can't write to.
+fn default_socket() -> PathBuf {
+    env::var_os("XDG_RUNTIME_DIR")
+        .or_else(|| env::var_os("HOME"))
+        .map_or_else(env::temp_dir, PathBuf::from)
+        .join(SOCKET_NAME)
+}
+
+/// Run the tool as a daemon accepting mutation test jobs, or talk to a running daemon.
+#[derive(Subcommand, Debug)]
+pub enum DaemonCmd {
+    /// Start the daemon. The jobs are run one at a time in the order they are submitted.
+    Start {
+        /// The Unix socket to listen on. It's accessible only to the user running the daemon.
+        #[clap(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+
+        /// The directory the packages of the jobs must be in. Can be given multiple times.
+        #[clap(long = "allow-root", required = true)]
+        allow_roots: Vec<PathBuf>,
+    },
+
+    /// Submit a job with the options of the `run` command, e.g.
+    /// `daemon submit -- --package-dir pkg --output report.json`.
+    Submit {
+        /// The socket of the daemon.
+        #[clap(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+
+        /// The options of the `run` command.
+        #[clap(last = true)]
+        args: Vec<String>,
+    },
+
+    /// Print the status of the given job, or of all jobs.
+    Status {
+        /// The socket of the daemon.
+        #[clap(long, default_value_os_t = default_socket())]
+        socket: PathBuf,
+
+        /// The job ID.
+        id: Option<u64>,
+    },
+}
+
+impl DaemonCmd {
+    /// Execute the command.
+    ///
+    /// # Errors
+    /// Returns an error if the daemon can't be started or reached.
+    pub fn execute(&self) -> anyhow::Result<()> {
+        let (socket, request) = match self {
+            DaemonCmd::Start {
+                socket,
+                allow_roots,
+            } => return run_daemon(socket, allow_roots),
+            DaemonCmd::Submit { socket, args } => (socket, Request::Submit {
+                args: args.clone(),
+                cwd: env::current_dir()?,
+            }),
+            DaemonCmd::Status {
+                socket,
+                id: Some(id),
+            } => (socket, Request::Status { id: *id }),
+            DaemonCmd::Status { socket, id: None } => (socket, Request::List),
+        };
+
+        match send_request(socket, &request)? {
+            Response::Error { message } => Err(anyhow!(message)),
+            response => {
+                println!("{}", serde_json::to_string_pretty(&response)?);
+                Ok(())
+            },
+        }
+    }
+}
+
+/// A request sent to the daemon as a single line of JSON.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+enum Request {
+    /// Queue a job with the options of the `run` command. The relative paths in the options are
+    /// relative to `cwd`, the working directory of the client.
+    Submit { args: Vec<String>, cwd: PathBuf },
+    /// Get the status of the job.
+    Status { id: u64 },
+    /// Get the status of all jobs.
+    List,
+}
+
+/// The response of the daemon as a single line of JSON.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum Response {
+    /// The job has been queued.
+    Submitted { id: u64 },
+    /// The status of a job.
+    Job(JobStatus),
+    /// The status of all jobs.
+    Jobs { jobs: Vec<JobStatus> },
+    /// The request failed.
+    Error { message: String },
+}
+
+/// The state of a job.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+enum JobState {
+    /// Waiting in the queue.
+    Queued,
+    /// Being run.
+    Running,
+    /// The tool finished successfully.
+    Succeeded,
+    /// The tool failed.
+    Failed,
+}
+
+/// The status of a job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct JobStatus {
+    /// The job ID.
+    id: u64,
+    /// The options of the `run` command.
+    args: Vec<String>,
+    /// The working directory of the job, the one of the client which submitted it.
+    cwd: PathBuf,
+    /// The tested package.
+    package: PathBuf,
+    /// The state of the job.
+    state: JobState,
+    /// The error of the failed job (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The options of a job, parsed to validate them when the job is submitted.
+#[derive(Parser, Debug)]
+#[command(no_binary_name = true)]
+struct JobArgs {
+    #[clap(flatten)]
+    cli_options: CLIOptions,
+
+    #[clap(flatten)]
+    test_build_config: TestBuildConfig,
+}
+
+impl JobArgs {
+    /// Parses the options of the job with the defaults of the package applied, like the options
+    /// of the `run` command, and returns them with the package directory.
+    fn parse(args: &[String], cwd: &Path) -> anyhow::Result<(Self, PathBuf)> {
+        let job_args = Self::try_parse_from(args)?;
+        let package_path = match &job_args.test_build_config.move_pkg.package_dir {
+            Some(package_dir) => cwd.join(package_dir),
+            None => cwd.to_path_buf(),
+        }
+        .canonicalize()
+        .context("invalid package directory")?;
+
+        let job_args = match PackageDefaults::load(&package_path, "move-mutation-test")? {
+            Some(defaults) => {
+                let matches = defaults
+                    .apply(Self::command(), None)?
+                    .try_get_matches_from(args)?;
+                Self::from_arg_matches(&matches)?
+            },
+            None => job_args,
+        };
+        Ok((job_args, package_path))
+    }
+
+    /// Returns the files configuring the job, which are read again by every job, so they can be
+    /// edited while the daemon runs. They don't change the compiled package.
+    fn config_files(&self, cwd: &Path, package: &Path) -> Vec<PathBuf> {
+        let options = &self.cli_options;
+        [&options.mutator_conf, &options.score.suppressions]
+            .into_iter()
+            .flatten()
+            .map(|path| cwd.join(path))
+            .chain([package.join(DEFAULTS_FILE)])
+            .map(|path| path.canonicalize().unwrap_or(path))
+            .collect()
+    }
+
+    /// Rejects the options running commands, changing the environment of the daemon or reaching
+    /// the network, as the jobs run within the daemon.
+    fn check_allowed(&self) -> anyhow::Result<()> {
+        let options = &self.cli_options;
+        let test_config = &self.test_build_config;
+        let mut rejected = vec![];
+        if options.post_run_cmd.is_some() {
+            rejected.push("--post-run-cmd");
+        }
+        if options.results_store.is_some() {
+            rejected.push("--results-store");
+        }
+        #[cfg(feature = "webhook")]
+        if options.notify_webhook.is_some() {
+            rejected.push("--notify-webhook");
+        }
+        #[cfg(feature = "upload")]
+        if options.upload_to.is_some() {
+            rejected.push("--upload-to");
+        }
+        if !test_config.test_env.is_empty() {
+            rejected.push("--test-env");
+        }
+        if test_config.test_env_clear {
+            rejected.push("--test-env-clear");
+        }
+        if !rejected.is_empty() {
+            bail!(
+                "{} can't be used in the jobs of the daemon, run the tool directly instead",
+                rejected.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The jobs of the daemon.
+#[derive(Debug)]
+struct Jobs {
+    /// All submitted jobs, the job ID is the index plus one.
+    jobs: Vec<JobStatus>,
+    /// The indices of the queued jobs.
+    queue: VecDeque<usize>,
+    /// The directories the packages of the jobs must be in.
+    allowed_roots: Vec<PathBuf>,
+}
+
+impl Jobs {
+    /// Creates the empty job list accepting the packages in the given (canonical) directories.
+    fn new(allowed_roots: Vec<PathBuf>) -> Self {
+        Self {
+            jobs: vec![],
+            queue: VecDeque::new(),
+            allowed_roots,
+        }
+    }
+
+    /// Handles the request of a client.
+    fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::Submit { args, cwd } => match self.submit(args, cwd) {
+                Ok(id) => Response::Submitted { id },
+                Err(e) => Response::Error {
+                    message: format!("{e:#}"),
+                },
+            },
+            Request::Status { id } => match usize::try_from(id)
+                .ok()
+                .and_then(|id| self.jobs.get(id.checked_sub(1)?))
+            {
+                Some(job) => Response::Job(job.clone()),
+                None => Response::Error {
+                    message: format!("job {id} not found"),
+                },
+            },
+            Request::List => Response::Jobs {
+                jobs: self.jobs.clone(),
+            },
+        }
+    }
+
+    /// Validates the options of the job and queues it.
+    fn submit(&mut self, args: Vec<String>, cwd: PathBuf) -> anyhow::Result<u64> {
+        if !cwd.is_absolute() {
+            bail!("the working directory of the job must be absolute");
+        }
+        let (job_args, package) = JobArgs::parse(&args, &cwd)?;
+        if !self
+            .allowed_roots
+            .iter()
+            .any(|root| package.starts_with(root))
+        {
+            bail!(
+                "the package {} is not in the directories allowed by the daemon (--allow-root)",
+                package.display()
+            );
+        }
+        job_args.check_allowed()?;
+
+        let id = u64::try_from(self.jobs.len() + 1)?;
+        self.queue.push_back(self.jobs.len());
+        self.jobs.push(JobStatus {
+            id,
+            args,
+            cwd,
+            package,
+            state: JobState::Queued,
+            error: None,
+        });
+        Ok(id)
+    }
+}
+
+/// The compiled packages kept by the daemon between the jobs, so the later jobs of a package don't
+/// fetch and compile its dependencies again.
+struct BuildCache {
+    /// The directory with the saved builds, removed when the daemon stops.
+    dir: TempDir,
+    /// The saved build of every package, with the fingerprint of the package it was built from.
+    builds: BTreeMap<PathBuf, (u64, PathBuf)>,
+}
+
+impl BuildCache {
+    /// Returns the saved build of the package if the package hasn't changed since.
+    fn get(&self, package: &Path, fingerprint: u64) -> Option<&Path> {
+        self.builds
+            .get(package)
+            .filter(|(saved, _)| *saved == fingerprint)
+            .map(|(_, build_dir)| build_dir.as_path())
+    }
+
+    /// Keeps the build of the package, replacing the previous one.
+    fn insert(&mut self, package: PathBuf, fingerprint: u64, build_dir: PathBuf) {
+        if let Some((_, previous)) = self.builds.insert(package, (fingerprint, build_dir)) {
+            let _ = fs::remove_dir_all(previous);
+        }
+    }
+}
+
+/// Returns the fingerprint of the files of the package (except the `build` directory and the
+/// ignored files), which changes when any file is added, removed or modified.
+fn fingerprint(package: &Path, ignored: &[PathBuf]) -> anyhow::Result<u64> {
+    let mut hasher = DefaultHasher::new();
+    let mut dirs = vec![package.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = fs::read_dir(&dir)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(fs::DirEntry::file_name);
+        for entry in entries {
+            let path = entry.path();
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                if path != package.join("build") {
+                    dirs.push(path);
+                }
+                continue;
+            }
+            if ignored.contains(&path) {
+                continue;
+            }
+            path.strip_prefix(package)?.hash(&mut hasher);
+            metadata.len().hash(&mut hasher);
+            metadata.modified()?.hash(&mut hasher);
+        }
+    }
+    Ok(hasher.finish())
+}
+
+/// The jobs shared between the connections and the worker.
+type SharedJobs = Arc<(Mutex<Jobs>, Condvar)>;
+
+/// Runs the daemon: accepts the requests and runs the queued jobs in the background.
+fn run_daemon(socket: &Path, allow_roots: &[PathBuf]) -> anyhow::Result<()> {
+    let allowed_roots = allow_roots
+        .iter()
+        .map(|root| {
+            root.canonicalize()
+                .with_context(|| format!("invalid allowed directory {}", root.display()))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let cache = BuildCache {
+        dir: tempfile::tempdir()?,
+        builds: BTreeMap::new(),
+    };
+    let listener = bind(socket)?;
+    println!("Daemon listening on {}", socket.display());
+
+    let jobs = Arc::new((Mutex::new(Jobs::new(allowed_roots)), Condvar::new()));
+    let worker_jobs = Arc::clone(&jobs);
+    thread::spawn(move || run_jobs(&worker_jobs, cache));
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("failed to accept a connection: {e}");
+                continue;
+            },
+        };
+        let jobs = Arc::clone(&jobs);
+        thread::spawn(move || {
+            if let Err(e) = serve_client(stream, &jobs) {
+                warn!("failed to serve a client: {e:#}");
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Creates the socket of the daemon, accessible only to the user running it.
+///
+/// The socket is created in a private directory and its permissions are set before it's moved in
+/// place, so no other user can connect in between.
+fn bind(socket: &Path) -> anyhow::Result<UnixListener> {
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            bail!("a daemon is already listening on {}", socket.display());
+        }
+        // Left behind by a daemon which has stopped.
+        fs::remove_file(socket)?;
+    }
+    let parent = socket
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let staging = tempfile::tempdir_in(parent)?;
+    let staged = staging.path().join(SOCKET_NAME);
+    let listener = UnixListener::bind(&staged)
+        .with_context(|| format!("failed to listen on {}", socket.display()))?;
+    fs::set_permissions(&staged, fs::Permissions::from_mode(0o600))?;
+    fs::rename(&staged, socket)?;
+    Ok(listener)
+}
+
+/// Answers the requests of a single client connection.
+fn serve_client(stream: UnixStream, jobs: &SharedJobs) -> anyhow::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let response = match serde_json::from_str::<Request>(&line?) {
+            Ok(request) => {
+                let (jobs, queued) = &**jobs;
+                let mut jobs = jobs.lock().unwrap_or_else(|e| e.into_inner());
+                let response = jobs.handle(request);
+                queued.notify_one();
+                response
+            },
+            Err(e) => Response::Error {
+                message: format!("invalid request: {e}"),
+            },
+        };
+        writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+    }
+    Ok(())
+}
+
+/// Runs the queued jobs one by one.
+fn run_jobs(jobs: &SharedJobs, mut cache: BuildCache) {
+    let (jobs, queued) = &**jobs;
+    loop {
+        let job = {
+            let mut jobs = jobs.lock().unwrap_or_else(|e| e.into_inner());
+            let idx = loop {
+                match jobs.queue.pop_front() {
+                    Some(idx) => break idx,
+                    None => jobs = queued.wait(jobs).unwrap_or_else(|e| e.into_inner()),
+                }
+            };
+            jobs.jobs[idx].state = JobState::Running;
+            jobs.jobs[idx].clone()
+        };
+
+        println!("Running job {}", job.id);
+        let result = run_job(&job, &mut cache);
+        match &result {
+            Ok(()) => println!("Job {} succeeded", job.id),
+            Err(e) => println!("Job {} failed: {e:#}", job.id),
+        }
+
+        let mut jobs = jobs.lock().unwrap_or_else(|e| e.into_inner());
+        let status = &mut jobs.jobs[usize::try_from(job.id - 1).unwrap_or(usize::MAX)];
+        match result {
+            Ok(()) => status.state = JobState::Succeeded,
+            Err(e) => {
+                status.state = JobState::Failed;
+                status.error = Some(format!("{e:#}"));
+            },
+        }
+    }
+}
+
+/// Runs the job within the daemon, with the output written to the output of the daemon.
+///
+/// The package compiled by the first job of the package is reused by its later jobs (as with
+/// `--prebuilt-package`) until a file of the package changes. The configuration files of the job
+/// (the mutator configuration, the suppressions and the package defaults) are read again by every
+/// job and editing them doesn't discard the compiled package. The job runs in the working
+/// directory of the client, so the relative paths in its options are resolved the same way as if
+/// the client ran the tool itself. A panicking job doesn't stop the daemon.
+fn run_job(job: &JobStatus, cache: &mut BuildCache) -> anyhow::Result<()> {
+    let (mut job_args, package) = JobArgs::parse(&job.args, &job.cwd)?;
+    let fingerprint = fingerprint(&package, &job_args.config_files(&job.cwd, &package))?;
+    let save_build = match cache.get(&package, fingerprint) {
+        Some(build_dir) if job_args.test_build_config.prebuilt_package.is_none() => {
+            info!("reusing the package compiled by an earlier job");
+            job_args.test_build_config.prebuilt_package = Some(build_dir.to_path_buf());
+            None
+        },
+        _ => Some(cache.dir.path().join(format!("job_{}", job.id))),
+    };
+
+    env::set_current_dir(&job.cwd)?;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_mutation_test_saving_build(
+            &job_args.cli_options,
+            &job_args.test_build_config,
+            save_build.as_deref(),
+        )
+    }))
+    .unwrap_or_else(|_| Err(anyhow!("the job panicked")));
+
+    if let Some(build_dir) = save_build.filter(|build_dir| build_dir.is_dir()) {
+        cache.insert(package, fingerprint, build_dir);
+    }
+    result
+}
+
+/// Sends the request to the daemon and returns its response.
+fn send_request(socket: &Path, request: &Request) -> anyhow::Result<Response> {
+    let mut stream = UnixStream::connect(socket)
+        .with_context(|| format!("failed to connect to the daemon at {}", socket.display()))?;
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package() -> TempDir {
+        let package = tempfile::tempdir().unwrap();
+        fs::write(
+            package.path().join("Move.toml"),
+            "[package]\nname = \"pkg\"\n",
+        )
+        .unwrap();
+        package
+    }
+
+    fn submit(jobs: &mut Jobs, args: &[&str], cwd: &Path) -> Response {
+        jobs.handle(Request::Submit {
+            args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+            cwd: cwd.to_owned(),
+        })
+    }
+
+    #[test]
+    fn jobs_are_queued() {
+        let package = package();
+        let package_path = package.path().canonicalize().unwrap();
+        let mut jobs = Jobs::new(vec![package_path.clone()]);
+
+        // The package directory is relative to the working directory of the client.
+        assert_eq!(
+            submit(&mut jobs, &["--package-dir", "."], package.path()),
+            Response::Submitted { id: 1 }
+        );
+        assert_eq!(
+            submit(&mut jobs, &[], package.path()),
+            Response::Submitted { id: 2 }
+        );
+        assert_eq!(jobs.queue, [0, 1]);
+        assert!(matches!(
+            submit(&mut jobs, &[], Path::new("relative")),
+            Response::Error { .. }
+        ));
+
+        let Response::Job(job) = jobs.handle(Request::Status { id: 2 }) else {
+            panic!("the job exists");
+        };
+        assert_eq!(job.state, JobState::Queued);
+        assert_eq!(job.cwd, package.path());
+        assert_eq!(job.package, package_path);
+
+        assert!(matches!(
+            submit(&mut jobs, &["--no-such-option"], package.path()),
+            Response::Error { .. }
+        ));
+        assert!(matches!(
+            jobs.handle(Request::Status { id: 0 }),
+            Response::Error { .. }
+        ));
+    }
+
+    #[test]
+    fn jobs_are_limited_to_allowed_packages_and_options() {
+        let package = package();
+        let other = tempfile::tempdir().unwrap();
+        let mut jobs = Jobs::new(vec![other.path().canonicalize().unwrap()]);
+        let Response::Error { message } = submit(&mut jobs, &[], package.path()) else {
+            panic!("the package is not allowed");
+        };
+        assert!(message.contains("--allow-root"));
+
+        let mut jobs = Jobs::new(vec![package.path().canonicalize().unwrap()]);
+        for args in [
+            &["--post-run-cmd", "touch pwned"][..],
+            &["--test-env", "KEY=VALUE"],
+            &["--test-env-clear"],
+            &["--results-store", "postgres://host/db"],
+        ] {
+            let Response::Error { message } = submit(&mut jobs, args, package.path()) else {
+                panic!("{args:?} are rejected");
+            };
+            assert!(message.contains(args[0]));
+        }
+        assert!(jobs.jobs.is_empty());
+    }
+
+    #[test]
+    fn config_files_are_resolved_in_the_working_directory() {
+        let package = package();
+        let cwd = package.path().canonicalize().unwrap();
+        fs::write(cwd.join("suppressions.txt"), "").unwrap();
+        let args = ["--suppressions", "suppressions.txt"].map(str::to_owned);
+
+        let (job_args, package) = JobArgs::parse(&args, &cwd).unwrap();
+        assert_eq!(job_args.config_files(&cwd, &package), [
+            cwd.join("suppressions.txt"),
+            cwd.join(DEFAULTS_FILE)
+        ]);
+    }
+
+    #[test]
+    fn socket_is_private() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("daemon.sock");
+        let _listener = bind(&socket).unwrap();
+        let mode = fs::metadata(&socket).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(UnixStream::connect(&socket).is_ok());
+        assert!(bind(&socket).is_err());
+    }
+
+    #[test]
+    fn builds_are_reused_until_the_package_changes() {
+        let package = package();
+        let suppressions = package.path().join("suppressions.txt");
+        let ignored = [suppressions.clone()];
+        let fingerprint_before = fingerprint(package.path(), &ignored).unwrap();
+        let mut cache = BuildCache {
+            dir: tempfile::tempdir().unwrap(),
+            builds: BTreeMap::new(),
+        };
+        let build_dir = cache.dir.path().join("job_1");
+        fs::create_dir(&build_dir).unwrap();
+        cache.insert(
+            package.path().to_owned(),
+            fingerprint_before,
+            build_dir.clone(),
+        );
+
+        // The build directory and the configuration files of the package don't count.
+        fs::create_dir(package.path().join("build")).unwrap();
+        fs::write(&suppressions, "3f2a1b7c9d0e4f5a equivalent").unwrap();
+        assert_eq!(
+            fingerprint(package.path(), &ignored).unwrap(),
+            fingerprint_before
+        );
+        assert_eq!(
+            cache.get(package.path(), fingerprint_before),
+            Some(build_dir.as_path())
+        );
+
+        fs::create_dir(package.path().join("sources")).unwrap();
+        fs::write(
+            package.path().join("sources").join("m.move"),
+            "module 0x1::m {}",
+        )
+        .unwrap();
+        let fingerprint_after = fingerprint(package.path(), &ignored).unwrap();
+        assert_ne!(fingerprint_after, fingerprint_before);
+        assert_eq!(cache.get(package.path(), fingerprint_after), None);
+    }
+
+    #[test]
+    fn requests_are_single_json_lines() {
+        let request = serde_json::to_string(&Request::Status { id: 3 }).unwrap();
+        assert_eq!(request, r#"{"command":"status","id":3}"#);
+        assert_eq!(
+            serde_json::from_str::<Request>(r#"{"command":"list"}"#).unwrap(),
+            Request::List
+        );
+    }
+}
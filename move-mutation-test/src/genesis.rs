@@ -0,0 +1,120 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, Context};
+use aptos_types::on_chain_config::aptos_test_feature_flags_genesis;
+use move_core_types::{
+    account_address::AccountAddress,
+    effects::{ChangeSet, Op},
+    language_storage::StructTag,
+};
+use serde::Deserialize;
+use std::{fs, path::Path, str::FromStr};
+
+/// A snapshot of the on-chain state the unit tests start with (`--genesis`).
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenesisSnapshot {
+    /// The resources published before the tests run.
+    #[serde(default)]
+    resources: Vec<SnapshotResource>,
+}
+
+/// A resource of the snapshot.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SnapshotResource {
+    /// The account holding the resource, e.g. `0x1`.
+    address: String,
+    /// The type of the resource, e.g. `0x1::oracle::Price`.
+    #[serde(rename = "type")]
+    type_: String,
+    /// The BCS-serialized value of the resource as a hex string.
+    value: String,
+}
+
+/// Returns the genesis state of the unit test VM: the default feature flags, extended with the
+/// resources of the snapshot file, if given.
+///
+/// # Errors
+/// Returns an error if the snapshot can't be read or it's invalid.
+pub(crate) fn genesis_change_set(snapshot: Option<&Path>) -> anyhow::Result<ChangeSet> {
+    let mut change_set = aptos_test_feature_flags_genesis();
+    let Some(path) = snapshot else {
+        return Ok(change_set);
+    };
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read the genesis snapshot {}", path.display()))?;
+    add_snapshot(&mut change_set, &content)
+        .with_context(|| format!("invalid genesis snapshot {}", path.display()))?;
+    Ok(change_set)
+}
+
+/// Adds the resources of the snapshot (JSON) to the change set.
+fn add_snapshot(change_set: &mut ChangeSet, snapshot: &str) -> anyhow::Result<()> {
+    let snapshot: GenesisSnapshot = serde_json::from_str(snapshot)?;
+    for resource in snapshot.resources {
+        let address = AccountAddress::from_hex_literal(&resource.address)
+            .map_err(|e| anyhow!("invalid address '{}': {e}", resource.address))?;
+        let struct_tag = StructTag::from_str(&resource.type_)
+            .map_err(|e| anyhow!("invalid resource type '{}': {e}", resource.type_))?;
+        let value = decode_hex(&resource.value)
+            .with_context(|| format!("invalid value of {}", resource.type_))?;
+
+        change_set
+            .add_resource_op(address, struct_tag, Op::New(value.into()))
+            .map_err(|e| {
+                anyhow!(
+                    "failed to add {} at {}: {e:?}",
+                    resource.type_,
+                    resource.address
+                )
+            })?;
+    }
+    Ok(())
+}
+
+/// Decodes the hex string (with an optional `0x` prefix).
+fn decode_hex(hex: &str) -> anyhow::Result<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        bail!("'{hex}' isn't a hex string with whole bytes");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| Ok(u8::from_str_radix(&hex[i..i + 2], 16)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_resources_are_added_to_genesis() {
+        let snapshot = r#"{
+            "resources": [
+                { "address": "0xcafe", "type": "0xcafe::oracle::Price", "value": "0x2a00000000000000" }
+            ]
+        }"#;
+        let mut change_set = ChangeSet::new();
+        add_snapshot(&mut change_set, snapshot).unwrap();
+        assert_eq!(change_set.accounts().len(), 1);
+
+        // The same resource can't be published twice.
+        assert!(add_snapshot(&mut change_set, snapshot).is_err());
+
+        let invalid =
+            r#"{ "resources": [ { "address": "0x1", "type": "0x1::m::R", "value": "0x2" } ] }"#;
+        assert!(add_snapshot(&mut ChangeSet::new(), invalid).is_err());
+    }
+
+    #[test]
+    fn hex_is_decoded() {
+        assert_eq!(decode_hex("0x2a00ff").unwrap(), vec![0x2A, 0x00, 0xFF]);
+        assert_eq!(decode_hex("").unwrap(), Vec::<u8>::new());
+        assert!(decode_hex("0xzz").is_err());
+    }
+}
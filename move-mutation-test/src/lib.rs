@@ -2,25 +2,65 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod abort_codes;
+pub mod analyze;
+mod batching;
+pub mod bench;
 pub mod cli;
+#[cfg(unix)]
+pub mod daemon;
+mod genesis;
+mod manifest;
 mod mutation_test;
+mod new_code;
+mod skip_uncovered;
+mod suggested_tests;
+mod view_snapshots;
 
 extern crate pretty_env_logger;
 #[macro_use]
 extern crate log;
 
-use crate::mutation_test::{run_tests_on_mutated_code, run_tests_on_original_code};
+use crate::{
+    abort_codes::{pin_abort_codes, PinnedTests},
+    batching::{batch_mutants_by_module, shuffle_batches, PackageModules},
+    manifest::run_manifest_mutants,
+    mutation_test::{run_tests_on_mutated_code, run_tests_on_original_code, FailedTests},
+    new_code::{find_new_code_mutants, prioritize_new_code},
+    skip_uncovered::UncoveredFunctions,
+    view_snapshots::{record_view_snapshots, ViewSnapshots},
+};
 use cli::TestBuildConfig;
 use fs_extra::dir::CopyOptions;
+use move_mutator::{
+    configuration::Configuration, report::MutationReport, source_drift::remap_reformatted_mutants,
+};
 use move_package::BuildConfig;
+pub use mutation_test::run_as_coverage_baseline_child;
+#[cfg(feature = "webhook")]
+use mutator_common::notify::notify_webhook;
 use mutator_common::{
     benchmark::{Benchmark, Benchmarks},
-    report::{MiniReport, MutantStatus, Report},
-    tmp_package_dir::{setup_outdir_and_package_path, strip_path_prefix},
+    codeowners::CodeOwners,
+    crash_report,
+    diff_render::render_survivor,
+    memory::{self, MemoryProfiler},
+    mutant_ids::load_mutant_ids,
+    post_run::run_post_run_cmd,
+    report::{
+        Backend, KillReason, MiniReport, MutantRecord, MutantRuns, MutantStatus, Report,
+        StillbornRecord, UnassessedFunctionRecord,
+    },
+    results_store::{record_run, write_last_tested_mutants},
+    tmp_package_dir::{setup_outdir_and_package_path, strip_path_prefix, use_prebuilt_package},
 };
+#[cfg(feature = "upload")]
+use mutator_common::{report::ReportFormat, upload::Uploader};
 use rayon::prelude::*;
 use std::{
+    collections::{BTreeMap, BTreeSet},
     fs,
+    num::NonZeroUsize,
     path::{Path, PathBuf},
 };
 
@@ -31,6 +71,9 @@ use std::{
 /// results in a temporary directory. Then it runs tests on the mutated
 /// code and stores the results, using them to generate the report at the end.
 ///
+/// With the `--coverage` option, the baseline tests run in a child process started from the
+/// current executable, which must call [`run_as_coverage_baseline_child`] at the start of `main`.
+///
 /// # Arguments
 ///
 /// * `options` - A `cli::Options` representing the options for the mutation test tool.
@@ -47,54 +90,161 @@ pub fn run_mutation_test(
     options: &cli::CLIOptions,
     test_config: &TestBuildConfig,
 ) -> anyhow::Result<()> {
+    run_mutation_test_saving_build(options, test_config, None)
+}
+
+/// Runs the mutation testing like [`run_mutation_test`]. With `save_build`, the `build`
+/// directory of the package compiled by the baseline run is copied there, so the later runs of
+/// the package can reuse it with `--prebuilt-package`.
+pub(crate) fn run_mutation_test_saving_build(
+    options: &cli::CLIOptions,
+    test_config: &TestBuildConfig,
+    save_build: Option<&Path>,
+) -> anyhow::Result<()> {
+    if let Some(format) = options.print_config {
+        let configuration =
+            Configuration::load(cli::create_mutator_options(options, test_config), None)?;
+        println!("{}", configuration.project.to_config_string(format)?);
+        return Ok(());
+    }
+
+    let result = run(options, test_config, save_build);
+    if let (Some(store), Ok(report)) = (&options.results_store, &result) {
+        record_run(store, "move-mutation-test", report);
+    }
+    #[cfg(feature = "webhook")]
+    if let Some(url) = &options.notify_webhook {
+        notify_webhook(url, options.notify_on, "move-mutation-test", &result);
+    }
+    result.map(|_| ())
+}
+
+/// Runs the tool and returns the final report.
+fn run(
+    options: &cli::CLIOptions,
+    test_config: &TestBuildConfig,
+    save_build: Option<&Path>,
+) -> anyhow::Result<Report> {
     // We need to initialize logger using try_init() as it might be already initialized in some other tool
     // (e.g. move-mutator). If we use init() instead, we will get an abort.
     let _ = pretty_env_logger::try_init();
 
-    // Setup output dir and clone package path there.
-    let original_package_path = test_config.move_pkg.get_package_path()?.canonicalize()?;
-    let (outdir, package_path) = setup_outdir_and_package_path(&original_package_path)?;
-
-    info!("Running tool the following options: {options:?} and test config: {test_config:?}");
-
     // Always create and use benchmarks.
     // Benchmarks call only time getting functions, so it's safe to use them in any case and
     // they are not expensive to create (won't hit the performance).
     let mut benchmarks = Benchmarks::new();
     benchmarks.total_tool_duration.start();
+    let memory_profiler = options
+        .profile_memory
+        .then(|| MemoryProfiler::start(memory::SAMPLING_INTERVAL));
+    benchmarks.resolution.start();
+
+    // Reject invalid mutator options (including the ones from the configuration file) and test
+    // arguments before running anything.
+    let configuration =
+        Configuration::load(cli::create_mutator_options(options, test_config), None)?;
+    if options.stable_sampling
+        && configuration
+            .project
+            .downsampling_ratio_percentage
+            .is_none()
+    {
+        anyhow::bail!("'--stable-sampling' requires '--downsampling-ratio-percentage' to be set");
+    }
+    test_config.unit_testing_config(1, false)?;
+    let suppressions = options.score.load_suppressions()?;
+    #[cfg(feature = "upload")]
+    let uploader = options.upload_to.as_ref().map(Uploader::new).transpose()?;
+
+    // All test runs share the environment of the process.
+    test_config.setup_test_env();
+
+    // Setup output dir and clone package path there.
+    let original_package_path = test_config.move_pkg.get_package_path()?.canonicalize()?;
+    let (outdir, package_path) = setup_outdir_and_package_path(&original_package_path)?;
+    if let Some(build_dir) = &test_config.prebuilt_package {
+        use_prebuilt_package(build_dir, &package_path)?;
+    }
+    let checkpoint = options
+        .checkpoint
+        .open("move-mutation-test", &original_package_path)?;
+
+    benchmarks.resolution.stop();
+
+    info!("Running tool the following options: {options:?} and test config: {test_config:?}");
 
     // Run original tests to ensure the original tests are working:
-    benchmarks.executing_original_package.start();
-    run_tests_on_original_code(test_config, &package_path)?;
-    benchmarks.executing_original_package.stop();
+    if options.skip_baseline {
+        warn_skipped_baseline();
+    } else {
+        benchmarks.executing_original_package.start();
+        run_tests_on_original_code(test_config, &package_path)?;
+        benchmarks.executing_original_package.stop();
+        if let Some(save_build) = save_build {
+            save_package_build(&package_path, save_build);
+        }
+    }
+
+    // Pinned before generating the mutants, so the mutant files contain the pinned annotations.
+    let pinned_tests = if options.pin_abort_codes {
+        pin_abort_codes(test_config, &package_path, &outdir)?
+    } else {
+        PinnedTests::default()
+    };
+    // Recorded before generating the mutants too, so the mutant files contain the snapshot checks.
+    let view_snapshots = if options.view_snapshots {
+        record_view_snapshots(test_config, &package_path, &outdir)?
+    } else {
+        ViewSnapshots::default()
+    };
+
+    let mut build_config = mutator_build_config(test_config);
+    // The dependencies haven't been fetched by the baseline run.
+    build_config.skip_fetch_latest_git_deps = !options.skip_baseline;
 
     // Create mutants:
     let outdir_mutant = if let Some(mutant_path) = &options.use_generated_mutants {
         mutant_path.clone()
     } else {
         benchmarks.mutator.start();
-        let mutator_config = BuildConfig {
-            dev_mode: test_config.move_pkg.dev,
-            additional_named_addresses: test_config.move_pkg.named_addresses(),
-            full_model_generation: test_config.move_pkg.check_test_code,
-            // No need to fetch latest deps again.
-            skip_fetch_latest_git_deps: true,
-            compiler_config: test_config.compiler_config(),
-            ..Default::default()
-        };
         let outdir_mutant = run_mutator(
             options,
-            test_config.apply_coverage,
-            &mutator_config,
+            test_config,
+            &build_config,
             &package_path,
+            &original_package_path,
             &outdir,
         )?;
         benchmarks.mutator.stop();
         outdir_mutant
     };
 
-    let report =
+    let mut report =
         move_mutator::report::Report::load_from_json_file(&outdir_mutant.join("report.json"))?;
+    // The mutator generates only the listed mutants, the previously generated ones are filtered
+    // here.
+    if let (Some(only_mutants), Some(mutant_path)) =
+        (&options.only_mutants, &options.use_generated_mutants)
+    {
+        let missing = report.retain_listed_mutants(&load_mutant_ids(only_mutants)?);
+        if missing > 0 {
+            let msg = format!(
+                "{missing} of the mutants listed in {} aren't in {}",
+                only_mutants.display(),
+                mutant_path.display()
+            );
+            warn!("{msg}");
+            eprintln!("WARNING: {msg}");
+        }
+    }
+    // The previously generated mutants are checked against the current sources, so the reported
+    // positions stay accurate after the package is reformatted.
+    if options.use_generated_mutants.is_some() {
+        let remapped = remap_reformatted_mutants(&mut report, &package_path, &outdir)?;
+        if remapped > 0 {
+            println!("Re-mapped {remapped} mutants to the reformatted sources");
+        }
+    }
 
     // Run tests on mutants:
     benchmarks.executing_tests_on_mutants.start();
@@ -103,134 +253,665 @@ pub fn run_mutation_test(
     let mutants = report.get_mutants();
     info!("Running the tool on {} mutants", mutants.len());
 
+    let kill_policy = options.kill_policy();
+
     let mut mutation_test_benchmarks = Vec::<Benchmark>::with_capacity(mutants.len());
     let mut mini_reports = Vec::<MiniReport>::with_capacity(mutants.len());
-    //  Split mutants into chunks before applying rayon threads, as trying to process them all in
-    //  one go can lead to memory starvation if the number of mutants is too huge to handle.
-    const CHUNK_SIZE: usize = 64;
-    let mut chunk_iter = 0;
-    mutants.chunks(CHUNK_SIZE).for_each(|mutant_set| {
-        let (mut benchmarks, mut reports) = mutant_set
-            .into_par_iter()
-            .map(|elem| {
-                let mut benchmark = Benchmark::new();
-
-                let mutant_file = elem.mutant_path();
-                // In case the number of mutants is very low, a single thread might be used.
-                let rayon_tid = rayon::current_thread_index().unwrap_or(0);
-                info!(
-                    "job_{rayon_tid}: Running tests for mutant {}",
-                    mutant_file.display()
-                );
-
-                // Strip prefix to get the path relative to the package directory.
-                let original_file =
-                    strip_path_prefix(elem.original_file_path()).expect("invalid package path");
-
-                let job_outdir = outdir.join(format!("mutation_test_{rayon_tid}"));
-                let _ = fs::remove_dir_all(&job_outdir);
-
-                fs_extra::dir::copy(&package_path, &job_outdir, &cp_opts)
-                    .expect("copying directory failed");
+    let mut mutants_finished = 0;
+
+    // Mutants are tested module by module - dependencies first - so each sandbox is prepared
+    // (copied and with dependencies compiled) once and then reused by all mutants of the module.
+    let modules = PackageModules::load(&build_config, &package_path);
+    let mut batches = batch_mutants_by_module(mutants, &modules);
+
+    // Shuffling is applied before prioritizing new code, which is a stable sort, so new code
+    // mutants still come first.
+    if let Some(seed) = options.shuffle_seed() {
+        println!("Shuffling mutants with seed {seed}");
+        batches = shuffle_batches(batches, seed);
+    }
 
-                trace!(
-                    "Copying mutant file {} to the package directory {:?}",
-                    mutant_file.display(),
-                    job_outdir.join(&original_file)
-                );
-                // Should never fail, since files will always exists.
-                fs::copy(mutant_file, job_outdir.join(&original_file))
-                    .expect("copying file failed");
+    // Mutants touching new code are tested first, so results relevant for the latest changes
+    // are available as soon as possible.
+    let new_code = match options
+        .new_code_window()
+        .map(|window| find_new_code_mutants(mutants, &original_package_path, window))
+    {
+        Some(Ok(new_code)) => {
+            prioritize_new_code(&mut batches, &new_code);
+            new_code
+        },
+        Some(Err(e)) => {
+            let msg = format!("failed to find the new code ({e:#}), mutants are not prioritized");
+            warn!("{msg}");
+            eprintln!("WARNING: {msg}");
+            BTreeSet::new()
+        },
+        None => BTreeSet::new(),
+    };
 
-                benchmark.start();
-                let result = run_tests_on_mutated_code(test_config, &job_outdir);
-                benchmark.stop();
+    let uncovered_functions = options.skip_uncovered_after.map(UncoveredFunctions::new);
 
-                let mutant_status = if let Err(e) = result {
-                    trace!("Mutant killed! Unit test failed with error: {e}");
-                    MutantStatus::Killed
-                } else {
-                    info!("Mutant {} hasn't been killed!", mutant_file.display());
-                    MutantStatus::Alive
-                };
-
-                let diff = elem.get_diff().to_owned();
-
-                // Qualified name for the function.
-                let mut qname = elem.get_module_name().to_owned();
-                qname.push_str("::");
-                qname.push_str(elem.get_function_name());
-
-                (
-                    benchmark,
-                    MiniReport::new(original_file.to_path_buf(), qname, mutant_status, diff),
-                )
-            })
-            .collect::<Vec<(_, _)>>()
-            .into_iter()
-            .unzip();
-
-        chunk_iter += 1;
+    for batch in batches {
         info!(
-            "update: finished running tests for {} mutants",
-            chunk_iter * CHUNK_SIZE
+            "Running tests for {} mutants of the module {}",
+            batch.mutants.len(),
+            batch.module
         );
 
-        mutation_test_benchmarks.append(&mut benchmarks);
-        mini_reports.append(&mut reports);
-    });
+        //  Split mutants into chunks before applying rayon threads, as trying to process them all in
+        //  one go can lead to memory starvation if the number of mutants is too huge to handle.
+        const CHUNK_SIZE: usize = 64;
+        for mutant_set in batch.mutants.chunks(CHUNK_SIZE) {
+            // Every thread gets a single sandbox for its share of the chunk.
+            let sandbox_share = mutant_set.len().div_ceil(rayon::current_num_threads());
+            let (benchmarks, mut reports): (Vec<_>, Vec<_>) = mutant_set
+                .par_chunks(sandbox_share)
+                .flat_map_iter(|share| {
+                    // In case the number of mutants is very low, a single thread might be used.
+                    let rayon_tid = rayon::current_thread_index().unwrap_or(0);
+                    let job_outdir = outdir.join(format!("mutation_test_{rayon_tid}"));
+                    let _ = fs::remove_dir_all(&job_outdir);
+
+                    fs_extra::dir::copy(&package_path, &job_outdir, &cp_opts)
+                        .expect("copying directory failed");
+
+                    share
+                        .iter()
+                        .map(|elem| {
+                            let is_new_code = new_code.contains(elem.mutant_path());
+                            let qname = qualified_name(elem);
+                            if let Some(status) =
+                                elem.mutant_id().and_then(|id| suppressions.status(id))
+                            {
+                                info!(
+                                    "Mutant {} is {status} in the suppressions file",
+                                    elem.mutant_path().display()
+                                );
+                                let mini_report = mini_report(elem, qname, status);
+                                return (None, mini_report.with_new_code(is_new_code));
+                            }
+                            if let Some(mini_report) = checkpoint
+                                .as_ref()
+                                .zip(elem.mutant_id())
+                                .and_then(|(checkpoint, id)| checkpoint.resumed(id))
+                            {
+                                info!(
+                                    "Mutant {} has been evaluated before the interruption",
+                                    elem.mutant_path().display()
+                                );
+                                if let Some(uncovered) = &uncovered_functions {
+                                    uncovered.record(&qname, mini_report.mutant_status);
+                                }
+                                return (None, mini_report.with_new_code(is_new_code));
+                            }
+                            if let Some(uncovered) = &uncovered_functions {
+                                if uncovered.should_skip(&qname) {
+                                    info!(
+                                        "Mutant {} skipped, as the function {qname} has no \
+                                         effective coverage",
+                                        elem.mutant_path().display()
+                                    );
+                                    let mini_report =
+                                        mini_report(elem, qname, MutantStatus::Skipped);
+                                    return (None, mini_report.with_new_code(is_new_code));
+                                }
+                            }
+
+                            crash_report::set_current_mutant(Some(elem.mutant_path()));
+                            let (benchmark, mini_report) = run_tests_on_mutant(
+                                test_config,
+                                &kill_policy,
+                                options.runs_per_mutant(),
+                                &pinned_tests,
+                                &view_snapshots,
+                                elem,
+                                &package_path,
+                                &job_outdir,
+                            );
+                            crash_report::set_current_mutant(None);
+                            if let Some(checkpoint) = &checkpoint {
+                                checkpoint.record(&mini_report);
+                            }
+                            if options.verbose && mini_report.mutant_status == MutantStatus::Alive {
+                                // A single print keeps the output of parallel jobs together.
+                                print!(
+                                    "{}",
+                                    render_survivor(&mini_report, elem.original_file_path())
+                                );
+                            }
+                            if let Some(uncovered) = &uncovered_functions {
+                                uncovered.record(&qname, mini_report.mutant_status);
+                            }
+                            (Some(benchmark), mini_report.with_new_code(is_new_code))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<(_, _)>>()
+                .into_iter()
+                .unzip();
+
+            mutants_finished += mutant_set.len();
+            info!("update: finished running tests for {mutants_finished} mutants");
+
+            mutation_test_benchmarks.extend(benchmarks.into_iter().flatten());
+            mini_reports.append(&mut reports);
+        }
+    }
+
+    // The mutants which don't compile are scored only if the compile failures count as kills,
+    // otherwise they are just discarded.
+    let scored_stillborn = if kill_policy.contains(&KillReason::CompileFailure) {
+        report.get_stillborn().as_slice()
+    } else {
+        &[]
+    };
+    for elem in scored_stillborn {
+        mini_reports.push(stillborn_mini_report(elem, &kill_policy));
+    }
 
     benchmarks.executing_tests_on_mutants.stop();
+    benchmarks.reporting.start();
     benchmarks.mutant_results = mutation_test_benchmarks;
 
+    let covering_tests = if test_config.apply_coverage {
+        find_covering_tests(&build_config, &package_path, mutants)
+    } else {
+        BTreeMap::new()
+    };
+
     // Prepare a report.
-    let mut test_report = Report::new(original_package_path);
-    for MiniReport {
-        original_file,
-        qname,
-        mutant_status,
-        diff,
-    } in mini_reports
-    {
-        test_report.increment_mutants_tested(&original_file, &qname);
-        if let MutantStatus::Alive = mutant_status {
-            test_report.add_mutants_alive_diff(&original_file, &qname, &diff);
-        } else {
-            test_report.increment_mutants_killed(&original_file, &qname);
-            test_report.add_mutants_killed_diff(&original_file, &qname, &diff);
+    let mut test_report = Report::new(original_package_path.clone());
+    test_report.set_backend(Backend::UnitTests);
+    for mini_report in &mini_reports {
+        let MiniReport {
+            original_file,
+            qname,
+            mutant_status,
+            diff,
+            ..
+        } = mini_report;
+
+        match mutant_status {
+            MutantStatus::Alive => {
+                test_report.increment_mutants_tested(original_file, qname);
+                test_report.add_mutants_alive_diff(original_file, qname, diff);
+            },
+            MutantStatus::Killed => {
+                test_report.increment_mutants_tested(original_file, qname);
+                test_report.increment_mutants_killed(original_file, qname);
+                test_report.add_mutants_killed_diff(original_file, qname, diff);
+            },
+            // Excluded mutants and the ones which haven't been tested count according to the
+            // score policy.
+            MutantStatus::Excluded
+            | MutantStatus::Skipped
+            | MutantStatus::Suppressed
+            | MutantStatus::Equivalent => {},
+        }
+        let mut record = MutantRecord::from(mini_report);
+        if let Some(tests) = covering_tests.get(&record.id) {
+            record.covered_by.clone_from(tests);
         }
+        test_report.add_mutant_record(record);
+    }
+    // Stillborn mutants might be kept only to be scored.
+    if options.keep_stillborn == Some(true) {
+        for elem in report.get_stillborn() {
+            test_report.add_stillborn_record(stillborn_record(elem)?);
+        }
+    }
+    for function in report.get_unassessed_functions() {
+        test_report.add_unassessed_function_record(UnassessedFunctionRecord {
+            original_file: strip_path_prefix(&function.original_file)?,
+            qname: function.qname(),
+        });
+    }
+    test_report.set_discarded_mutants(
+        u32::try_from(report.invalid_mutants() - scored_stillborn.len())?,
+        u32::try_from(report.uncovered_mutants())?,
+    );
+    test_report.set_kill_policy(kill_policy);
+    test_report.set_score_policy(options.score.score_policy());
+    test_report.set_tests(
+        mini_reports
+            .iter()
+            .flat_map(|r| r.killed_by.iter().chain(&r.passed_tests))
+            .cloned(),
+    );
+
+    let coverage_map = move_mutator::coverage_map_path(&package_path);
+    if test_config.apply_coverage && coverage_map.exists() {
+        println!("Coverage map saved to: {}", coverage_map.display());
+        test_report.set_coverage_map(coverage_map);
     }
 
-    test_report.print_table();
+    if options.mutate_manifest {
+        for record in run_manifest_mutants(test_config, &package_path, &outdir)? {
+            test_report.add_manifest_mutant_record(record);
+        }
+    }
 
-    benchmarks.total_tool_duration.stop();
-    benchmarks.display();
+    if options.coverage_summary {
+        let module_coverage = move_mutator::module_coverage_summary(&build_config, &package_path)?;
+        test_report.set_module_coverage(module_coverage);
+    }
+
+    if let Some(codeowners) = &options.codeowners {
+        test_report.assign_owners(&CodeOwners::from_file(codeowners)?);
+    }
+
+    test_report.print_table(&options.locale);
+
+    if options.suggest_tests {
+        let suggested_tests_dir = std::env::current_dir()?.join(SUGGESTED_TESTS_DIR);
+        let generated =
+            generate_suggested_tests(&mini_reports, &original_package_path, &suggested_tests_dir)?;
+        if !generated.is_empty() {
+            println!(
+                "Suggested tests for surviving mutants saved to: {}",
+                suggested_tests_dir.display()
+            );
+        }
+    }
 
     if let Some(outfile) = &options.output {
         let out = std::env::current_dir()?.join(outfile);
-        test_report.save_to_json_file(&out)?;
+        test_report.save_to_file(&out, options.report_format)?;
         println!("Report saved to: {}", out.display());
     }
+    // The run has finished, so there is nothing to resume.
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.finish();
+    }
+
+    #[cfg(feature = "upload")]
+    if let Some(uploader) = &uploader {
+        upload_artifacts(uploader, options, &test_report, &outdir, &outdir_mutant)?;
+    }
 
+    benchmarks.reporting.stop();
+    benchmarks.total_tool_duration.stop();
+    benchmarks.memory = memory_profiler.map(MemoryProfiler::finish);
+    benchmarks.display();
+    if options.timings || options.profile_memory {
+        print!(
+            "{}",
+            benchmarks.timings_table(
+                report.get_mutants().len() + report.invalid_mutants(),
+                report.invalid_mutants()
+            )
+        );
+    }
+
+    if let Some(cmd) = &options.post_run_cmd {
+        run_post_run_cmd(
+            cmd,
+            &test_report,
+            options.post_run_baseline.as_deref(),
+            options.post_run_format,
+        )?;
+    }
+
+    Ok(test_report)
+}
+
+/// Uploads the report and the generated mutants (with their `report.json` manifest) to the
+/// object storage.
+#[cfg(feature = "upload")]
+fn upload_artifacts(
+    uploader: &Uploader,
+    options: &cli::CLIOptions,
+    test_report: &Report,
+    outdir: &Path,
+    outdir_mutant: &Path,
+) -> anyhow::Result<()> {
+    let report_file = match &options.output {
+        Some(outfile) => std::env::current_dir()?.join(outfile),
+        None => {
+            let report_file = outdir.join("mutation-report.json");
+            test_report.save_to_file(&report_file, ReportFormat::Json)?;
+            report_file
+        },
+    };
+    let report_name = report_file
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    uploader.upload_file(&report_file, &report_name)?;
+    let mutants = uploader.upload_dir(outdir_mutant, "mutants")?;
+    println!(
+        "Uploaded the report and {mutants} mutant files to {}",
+        uploader.target()
+    );
     Ok(())
 }
 
+/// Copies the `build` directory of the package compiled by the baseline run to the given directory.
+///
+/// Failing to copy it doesn't fail the run, only a warning is printed.
+fn save_package_build(package_path: &Path, save_build: &Path) {
+    let _ = fs::remove_dir_all(save_build);
+    let result = fs::create_dir_all(save_build)
+        .map_err(anyhow::Error::from)
+        .and_then(|()| {
+            let options = CopyOptions::new().content_only(true);
+            fs_extra::dir::copy(package_path.join("build"), save_build, &options)?;
+            Ok(())
+        });
+    if let Err(e) = result {
+        let msg = format!(
+            "failed to save the compiled package to {}: {e:#}",
+            save_build.display()
+        );
+        warn!("{msg}");
+        eprintln!("WARNING: {msg}");
+    }
+}
+
+/// Warns that the baseline run is skipped, so the results can't be trusted if the tests fail on
+/// the original code.
+fn warn_skipped_baseline() {
+    let msg = "the baseline test run is skipped (--skip-baseline), if the tests fail on the \
+               original code, all mutants will be reported as killed";
+    warn!("{msg}");
+    eprintln!("WARNING: {msg}");
+}
+
+/// Creates the build configuration used for generating mutants.
+fn mutator_build_config(test_config: &TestBuildConfig) -> BuildConfig {
+    BuildConfig {
+        dev_mode: test_config.move_pkg.dev,
+        additional_named_addresses: test_config.named_addresses(),
+        full_model_generation: test_config.move_pkg.check_test_code,
+        // No need to fetch latest deps again.
+        skip_fetch_latest_git_deps: true,
+        compiler_config: test_config.compiler_config(),
+        ..Default::default()
+    }
+}
+
+/// Runs tests on a single mutant using the prepared sandbox.
+///
+/// The mutant file is copied into the sandbox and the original file is restored afterwards, so
+/// the sandbox can be reused for the next mutant. With several runs, the majority of the runs
+/// decides whether the mutant is killed, and the test results of the first such run are
+/// reported.
+fn run_tests_on_mutant(
+    test_config: &TestBuildConfig,
+    kill_policy: &[KillReason],
+    runs: NonZeroUsize,
+    pinned_tests: &PinnedTests,
+    view_snapshots: &ViewSnapshots,
+    elem: &MutationReport,
+    package_path: &Path,
+    job_outdir: &Path,
+) -> (Benchmark, MiniReport) {
+    let mut benchmark = Benchmark::new();
+
+    let mutant_file = elem.mutant_path();
+    let rayon_tid = rayon::current_thread_index().unwrap_or(0);
+    info!(
+        "job_{rayon_tid}: Running tests for mutant {}",
+        mutant_file.display()
+    );
+
+    // Strip prefix to get the path relative to the package directory.
+    let original_file = strip_path_prefix(elem.original_file_path()).expect("invalid package path");
+
+    trace!(
+        "Copying mutant file {} to the package directory {:?}",
+        mutant_file.display(),
+        job_outdir.join(&original_file)
+    );
+    // Should never fail, since files will always exists.
+    fs::copy(mutant_file, job_outdir.join(&original_file)).expect("copying file failed");
+
+    benchmark.start();
+    let results = (0..runs.get())
+        .map(|run| {
+            let config = test_config.for_run(run);
+            (
+                config.test_seed,
+                run_tests_on_mutated_code(&config, job_outdir),
+            )
+        })
+        .collect::<Vec<_>>();
+    benchmark.stop();
+
+    // Restore the original file so the sandbox can be reused.
+    fs::copy(
+        package_path.join(&original_file),
+        job_outdir.join(&original_file),
+    )
+    .expect("restoring file failed");
+
+    let killed_runs = results.iter().filter(|(_, result)| result.is_err()).count();
+    let runs = (results.len() > 1).then(|| MutantRuns {
+        runs: u32::try_from(results.len()).unwrap_or(u32::MAX),
+        killed: u32::try_from(killed_runs).unwrap_or(u32::MAX),
+    });
+    let killed = runs.map_or(killed_runs > 0, |runs| runs.is_killed());
+    let (seed, result) = results
+        .into_iter()
+        .find(|(_, result)| result.is_err() == killed)
+        .expect("a run with the decisive outcome exists");
+
+    let mut killed_by = vec![];
+    let mut passed_tests = vec![];
+    let mut kill_reason = None;
+    let mutant_status = match result {
+        Err(e) => {
+            trace!("Mutant killed! Unit test failed with error: {e}");
+            let reason = if let Some(failed) = e.downcast_ref::<FailedTests>() {
+                killed_by.clone_from(&failed.tests);
+                passed_tests.clone_from(&failed.passed);
+                if failed.reason == KillReason::TestFailure && pinned_tests.explain(failed) {
+                    KillReason::AbortCode
+                } else if failed.reason == KillReason::TestFailure && view_snapshots.explain(failed)
+                {
+                    KillReason::ViewOutput
+                } else if failed.reason == KillReason::TestFailure
+                    && failed.only_abort_codes_changed()
+                {
+                    KillReason::SoftKill
+                } else {
+                    failed.reason
+                }
+            } else {
+                // Mutants are verified before testing, so the test code didn't compile.
+                KillReason::CompileFailure
+            };
+            kill_reason = Some(reason);
+
+            if kill_policy.contains(&reason) {
+                MutantStatus::Killed
+            } else {
+                info!(
+                    "Mutant {} excluded due to the kill policy ({reason})",
+                    mutant_file.display()
+                );
+                MutantStatus::Excluded
+            }
+        },
+        Ok(passed) => {
+            info!("Mutant {} hasn't been killed!", mutant_file.display());
+            passed_tests = passed;
+            MutantStatus::Alive
+        },
+    };
+
+    let mut mini_report = mini_report(elem, qualified_name(elem), mutant_status)
+        .with_killed_by(killed_by)
+        .with_passed_tests(passed_tests)
+        .with_duration(benchmark.elapsed);
+    if let Some(reason) = kill_reason {
+        mini_report = mini_report.with_kill_reason(reason);
+    }
+    if let Some(runs) = runs {
+        mini_report = mini_report.with_runs(runs);
+    }
+    if let Some(seed) = seed {
+        mini_report = mini_report.with_seed(seed);
+    }
+
+    (benchmark, mini_report)
+}
+
+/// Returns the qualified name of the mutated function.
+fn qualified_name(elem: &MutationReport) -> String {
+    format!("{}::{}", elem.get_module_name(), elem.get_function_name())
+}
+
+/// Finds the tests executing the mutated code of each mutant, using the coverage recorded by the
+/// baseline run. Returns the test names by the mutant identifiers.
+fn find_covering_tests(
+    build_config: &BuildConfig,
+    package_path: &Path,
+    mutants: &[MutationReport],
+) -> BTreeMap<String, Vec<String>> {
+    let test_coverage = match move_mutator::TestCoverage::load(build_config, package_path) {
+        Ok(Some(test_coverage)) => test_coverage,
+        Ok(None) => return BTreeMap::new(),
+        Err(e) => {
+            warn!("failed to load the coverage of the tests: {e}");
+            return BTreeMap::new();
+        },
+    };
+
+    mutants
+        .iter()
+        .filter_map(|elem| {
+            let ranges = elem
+                .get_mutations()
+                .iter()
+                .map(|m| (m.get_range().start(), m.get_range().end()))
+                .collect::<Vec<_>>();
+            let tests = test_coverage.covering_tests(&qualified_name(elem), &ranges);
+            Some((elem.mutant_id()?.to_owned(), tests))
+        })
+        .collect()
+}
+
+/// Creates the report of the mutant with the given status, without the test results.
+fn mini_report(elem: &MutationReport, qname: String, mutant_status: MutantStatus) -> MiniReport {
+    let original_file = strip_path_prefix(elem.original_file_path()).expect("invalid package path");
+    let operators = elem
+        .get_mutations()
+        .iter()
+        .map(|m| m.get_operator_name().to_owned())
+        .collect();
+
+    MiniReport::new(
+        original_file,
+        qname,
+        mutant_status,
+        elem.get_diff().to_owned(),
+    )
+    .with_mutant_info(elem.mutant_id().unwrap_or_default(), operators)
+    .with_labels(elem.get_labels().to_vec())
+}
+
+/// Creates the report of the mutant which failed to compile, scored according to the kill policy.
+fn stillborn_mini_report(elem: &MutationReport, kill_policy: &[KillReason]) -> MiniReport {
+    let status = if kill_policy.contains(&KillReason::CompileFailure) {
+        MutantStatus::Killed
+    } else {
+        MutantStatus::Excluded
+    };
+    mini_report(elem, qualified_name(elem), status).with_kill_reason(KillReason::CompileFailure)
+}
+
+/// Creates the record of the mutant which failed to compile.
+fn stillborn_record(elem: &MutationReport) -> anyhow::Result<StillbornRecord> {
+    Ok(StillbornRecord {
+        id: elem.mutant_id().unwrap_or_default().to_owned(),
+        original_file: strip_path_prefix(elem.original_file_path())?,
+        qname: qualified_name(elem),
+        operators: elem
+            .get_mutations()
+            .iter()
+            .map(|m| m.get_operator_name().to_owned())
+            .collect(),
+        diagnostics: elem.get_diagnostics().unwrap_or_default().to_owned(),
+        diff: elem.get_diff().to_owned(),
+    })
+}
+
 /// This function runs the Move Mutator tool.
 fn run_mutator(
     options: &cli::CLIOptions,
-    apply_coverage: bool,
+    test_config: &TestBuildConfig,
     config: &BuildConfig,
     package_path: &Path,
+    original_package_path: &Path,
     outdir: &Path,
 ) -> anyhow::Result<PathBuf> {
     debug!("Running the move mutator tool");
     let outdir_mutant = outdir.join("mutants");
     fs::create_dir_all(&outdir_mutant)?;
 
-    let mut mutator_conf = cli::create_mutator_options(options, apply_coverage);
+    let mut mutator_conf = cli::create_mutator_options(options, test_config);
     mutator_conf.out_mutant_dir = Some(outdir_mutant.clone());
+    if let (true, Some(store)) = (options.stable_sampling, &options.results_store) {
+        mutator_conf.stable_sampling = write_last_tested_mutants(
+            store,
+            "move-mutation-test",
+            original_package_path,
+            &outdir.join("stable_sampling.txt"),
+        )?;
+    }
 
     move_mutator::run_move_mutator(mutator_conf, config, package_path)?;
 
     Ok(outdir_mutant)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stillborn_mutant(dir: &Path) -> MutationReport {
+        let original = "module 0x42::sum {\n    fun sum(x: u64): u64 { x + 1 }\n}\n";
+        let mutated = original.replace("x + 1", "x + true");
+        let original_file = dir.join("original_package/sources/sum.move");
+        let mut elem = MutationReport::new(
+            &dir.join("stillborn/sum_mutant_0123456789abcdef.move"),
+            &original_file,
+            "sum",
+            "sum",
+            &mutated,
+            original,
+        );
+        elem.set_diagnostics(Some("error: invalid argument".to_owned()));
+        elem
+    }
+
+    fn score(kill_policy: &[KillReason]) -> (MutantStatus, u32, u32) {
+        let dir = tempfile::tempdir().unwrap();
+        let mini_report = stillborn_mini_report(&stillborn_mutant(dir.path()), kill_policy);
+        assert_eq!(mini_report.kill_reason, Some(KillReason::CompileFailure));
+        assert_eq!(mini_report.original_file, PathBuf::from("sources/sum.move"));
+
+        let mut report = Report::new(dir.path().to_path_buf());
+        if mini_report.mutant_status == MutantStatus::Killed {
+            report.increment_mutants_tested(&mini_report.original_file, &mini_report.qname);
+            report.increment_mutants_killed(&mini_report.original_file, &mini_report.qname);
+        }
+        report.add_mutant_record(MutantRecord::from(&mini_report));
+        (
+            mini_report.mutant_status,
+            report.mutants_tested(),
+            report.mutants_killed(),
+        )
+    }
+
+    #[test]
+    fn stillborn_mutants_are_scored_by_the_kill_policy() {
+        assert_eq!(
+            score(&[KillReason::TestFailure, KillReason::CompileFailure]),
+            (MutantStatus::Killed, 1, 1)
+        );
+        assert_eq!(score(&KillReason::DEFAULT), (MutantStatus::Excluded, 0, 0));
+    }
+}
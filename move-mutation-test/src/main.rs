@@ -4,17 +4,37 @@
 
 #![forbid(unsafe_code)]
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+#[cfg(unix)]
+use move_mutation_test::daemon::DaemonCmd;
 use move_mutation_test::{
+    analyze::AnalyzeOptions,
+    bench::{run_benchmarks, BenchOptions},
     cli::{CLIOptions, TestBuildConfig},
-    run_mutation_test,
+    run_as_coverage_baseline_child, run_mutation_test,
 };
-use mutator_common::display_report::DisplayReportOptions;
+use move_mutator::cli::{ConfigCmd, DescribeOperatorOptions};
+use mutator_common::{
+    cli_docs::{print_completions, render_manpage, Shell},
+    color::{set_color_choice, ColorChoice},
+    crash_report,
+    display_report::DisplayReportOptions,
+    env_vars::with_env_vars,
+    package_defaults::get_matches_with_package_defaults,
+};
+
+/// The prefix of environment variables which can be used to set the options of all commands.
+const ENV_VAR_PREFIX: &str = "MOVE_MUTATION_TEST_";
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 #[command(propagate_version = true)]
 struct Opts {
+    /// When to use colors in the output. The `NO_COLOR` environment variable disables the colors
+    /// in the `auto` mode.
+    #[clap(global = true, long, value_enum, default_value_t)]
+    color: ColorChoice,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -33,17 +53,79 @@ enum Commands {
     },
 
     /// Display the report in a more readable format.
+    #[clap(visible_alias = "report")]
     DisplayReport(DisplayReportOptions),
+
+    /// Analyze the test suite using the report, or the package under test.
+    Analyze(AnalyzeOptions),
+
+    /// Benchmark the phases of the mutation testing pipeline.
+    Bench(BenchOptions),
+
+    /// Run a daemon with a queue of mutation test jobs, or submit jobs to it.
+    #[cfg(unix)]
+    #[command(subcommand)]
+    Daemon(DaemonCmd),
+
+    /// Work with the mutator configuration file.
+    #[command(subcommand)]
+    Config(ConfigCmd),
+
+    /// Describe a mutation operator, with an example and the weakness of the tests it reveals.
+    DescribeOperator(DescribeOperatorOptions),
+
+    /// Generate the shell completion script.
+    Completions {
+        /// The shell to generate the script for.
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+
+    /// Generate the man page.
+    Manpage,
+}
+
+/// Creates the command with all options, including the ones set by environment variables.
+fn command() -> clap::Command {
+    with_env_vars(Opts::command(), ENV_VAR_PREFIX, Some("run"))
 }
 
 fn main() -> anyhow::Result<()> {
-    let opts = Opts::parse();
+    // The baseline tests with coverage are run by the tool started again as a child process.
+    if let Some(result) = run_as_coverage_baseline_child() {
+        return result;
+    }
+
+    let matches = get_matches_with_package_defaults(command(), Some("run"), "move-mutation-test")?;
+    let opts = Opts::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    set_color_choice(opts.color);
 
     match &opts.command {
         Commands::Run {
             cli_options,
             test_build_config,
-        } => run_mutation_test(cli_options, test_build_config),
+        } => {
+            crash_report::init_logger();
+            crash_report::install(cli_options.crash_context(test_build_config));
+            run_mutation_test(cli_options, test_build_config)
+        },
         Commands::DisplayReport(display_report) => display_report.execute(),
+        Commands::Bench(bench) => run_benchmarks(bench),
+        #[cfg(unix)]
+        Commands::Daemon(daemon) => {
+            crash_report::init_logger();
+            daemon.execute()
+        },
+        Commands::Config(config) => config.execute(),
+        Commands::DescribeOperator(describe) => describe.execute(),
+        Commands::Analyze(analyze) => analyze.execute(),
+        Commands::Completions { shell } => {
+            print_completions(*shell, &mut command());
+            Ok(())
+        },
+        Commands::Manpage => {
+            print!("{}", render_manpage(&command())?);
+            Ok(())
+        },
     }
 }
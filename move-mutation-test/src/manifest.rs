@@ -0,0 +1,193 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    cli::TestBuildConfig,
+    mutation_test::{run_tests_on_mutated_code, FailedTests},
+};
+use anyhow::Context;
+use fs_extra::dir::CopyOptions;
+use mutator_common::report::{ManifestMutantRecord, ManifestMutantStatus};
+use rayon::prelude::*;
+use std::{collections::BTreeSet, fs, path::Path};
+use toml::{Table, Value};
+
+/// The manifest sections with the named addresses which are mutated.
+const ADDRESS_SECTIONS: [&str; 2] = ["addresses", "dev-addresses"];
+
+/// The first value tried for the fresh addresses, which are not used by the package.
+const FRESH_ADDRESS_START: u64 = 0xCAFE;
+
+/// A mutated package manifest.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ManifestMutant {
+    /// The description of the mutated entries.
+    pub(crate) description: String,
+    /// The content of the mutated manifest.
+    pub(crate) manifest: String,
+}
+
+/// Generates the mutants of the manifest: every named address value is replaced with a fresh
+/// address, and the values of every two named addresses of the same section are swapped.
+///
+/// Unassigned addresses and the reserved framework addresses (`0x0` to `0xa`) are never mutated.
+pub(crate) fn manifest_mutants(manifest: &str) -> anyhow::Result<Vec<ManifestMutant>> {
+    let table = manifest
+        .parse::<Table>()
+        .context("failed to parse the package manifest")?;
+
+    let used = ADDRESS_SECTIONS
+        .iter()
+        .filter_map(|section| table.get(*section)?.as_table())
+        .flat_map(|addresses| addresses.values().filter_map(Value::as_str))
+        .collect::<BTreeSet<_>>();
+    let mut fresh_addresses = (FRESH_ADDRESS_START..)
+        .map(|address| format!("{address:#x}"))
+        .filter(|address| !used.contains(address.as_str()));
+
+    let mut mutants = vec![];
+    for section in ADDRESS_SECTIONS {
+        let Some(addresses) = table.get(section).and_then(Value::as_table) else {
+            continue;
+        };
+        // Sorted by the name, so the mutants don't depend on the order of the entries.
+        let mut mutable = addresses
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.as_str()?)))
+            .filter(|(_, value)| is_mutable_address(value))
+            .collect::<Vec<_>>();
+        mutable.sort_unstable();
+
+        let mutant = |changes: &[(&str, &str)]| {
+            let mut table = table.clone();
+            let addresses = table
+                .get_mut(section)
+                .and_then(Value::as_table_mut)
+                .expect("the section is a table");
+            for (name, value) in changes {
+                addresses.insert((*name).to_owned(), Value::String((*value).to_owned()));
+            }
+            toml::to_string(&table).context("failed to serialize the mutated manifest")
+        };
+
+        for &(name, value) in &mutable {
+            let fresh = fresh_addresses
+                .next()
+                .expect("the fresh addresses are infinite");
+            mutants.push(ManifestMutant {
+                description: format!("{section}: {name} = {value} -> {fresh}"),
+                manifest: mutant(&[(name, &fresh)])?,
+            });
+        }
+
+        for (i, &(a, a_value)) in mutable.iter().enumerate() {
+            for &(b, b_value) in &mutable[i + 1..] {
+                if a_value == b_value {
+                    continue;
+                }
+                mutants.push(ManifestMutant {
+                    description: format!("{section}: swap {a} ({a_value}) and {b} ({b_value})"),
+                    manifest: mutant(&[(a, b_value), (b, a_value)])?,
+                });
+            }
+        }
+    }
+    Ok(mutants)
+}
+
+/// Checks whether the address value can be mutated - it's assigned and not reserved for the
+/// framework.
+fn is_mutable_address(value: &str) -> bool {
+    let Some(digits) = value.strip_prefix("0x") else {
+        return false;
+    };
+    let digits = digits.trim_start_matches('0');
+    digits.len() > 1 || u8::from_str_radix(digits, 16).is_ok_and(|digit| digit > 0xA)
+}
+
+/// Runs the tests of the original code with every mutant of the manifest.
+///
+/// Unlike the code mutants, the tests are expected to pass: a failing test is coupled to the
+/// specific address value.
+pub(crate) fn run_manifest_mutants(
+    test_config: &TestBuildConfig,
+    package_path: &Path,
+    outdir: &Path,
+) -> anyhow::Result<Vec<ManifestMutantRecord>> {
+    let manifest_path = package_path.join("Move.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+    let mutants = manifest_mutants(&manifest)?;
+    info!("Running the tests with {} manifest mutants", mutants.len());
+
+    let cp_opts = CopyOptions::new().content_only(true);
+    mutants
+        .into_par_iter()
+        .enumerate()
+        .map(|(idx, mutant)| -> anyhow::Result<ManifestMutantRecord> {
+            let job_outdir = outdir.join(format!("manifest_mutant_{idx}"));
+            let _ = fs::remove_dir_all(&job_outdir);
+            fs_extra::dir::copy(package_path, &job_outdir, &cp_opts)?;
+            fs::write(job_outdir.join("Move.toml"), &mutant.manifest)?;
+
+            let result = run_tests_on_mutated_code(test_config, &job_outdir);
+            let _ = fs::remove_dir_all(&job_outdir);
+
+            let (status, failed_tests) = match result {
+                Ok(_) => (ManifestMutantStatus::Independent, vec![]),
+                Err(e) => match e.downcast::<FailedTests>() {
+                    Ok(failed) => (ManifestMutantStatus::Coupled, failed.tests),
+                    Err(e) => {
+                        info!("Manifest mutant '{}' is invalid: {e:#}", mutant.description);
+                        (ManifestMutantStatus::Invalid, vec![])
+                    },
+                },
+            };
+            Ok(ManifestMutantRecord {
+                description: mutant.description,
+                status,
+                failed_tests,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_are_replaced_and_swapped() {
+        let manifest = r#"
+            [package]
+            name = "pkg"
+
+            [addresses]
+            std = "0x1"
+            pkg = "0x42"
+            admin = "0xcafe"
+            user = "_"
+
+            [dev-addresses]
+            user = "0x43"
+        "#;
+        let descriptions = manifest_mutants(manifest)
+            .unwrap()
+            .into_iter()
+            .map(|mutant| mutant.description)
+            .collect::<Vec<_>>();
+        assert_eq!(descriptions, vec![
+            "addresses: admin = 0xcafe -> 0xcaff",
+            "addresses: pkg = 0x42 -> 0xcb00",
+            "addresses: swap admin (0xcafe) and pkg (0x42)",
+            "dev-addresses: user = 0x43 -> 0xcb01",
+        ]);
+
+        let swapped = &manifest_mutants(manifest).unwrap()[2].manifest;
+        let addresses = swapped.parse::<Table>().unwrap()["addresses"].clone();
+        assert_eq!(addresses["admin"].as_str(), Some("0x42"));
+        assert_eq!(addresses["pkg"].as_str(), Some("0xcafe"));
+        assert_eq!(addresses["std"].as_str(), Some("0x1"));
+    }
+}
@@ -2,20 +2,69 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::cli::TestBuildConfig;
-use anyhow::{anyhow, Error};
+use crate::{cli::TestBuildConfig, genesis::genesis_change_set};
+use anyhow::{anyhow, bail, Error};
 use aptos::move_tool::aptos_debug_natives::aptos_debug_natives;
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
-use aptos_types::on_chain_config::aptos_test_feature_flags_genesis;
+use clap::Parser;
 use move_cli::base::test::UnitTestResult;
-use move_command_line_common::address::NumericalAddress;
 use move_package::BuildConfig;
-use move_unit_test::UnitTestingConfig;
-use std::{fs, path::Path, thread};
+use mutator_common::{
+    color::{termcolor_choice, Stream},
+    crash_report,
+    report::KillReason,
+};
+use std::{collections::BTreeMap, env, fmt, fs, path::Path, process::Command, thread};
 use termcolor::WriteColor;
 
+/// The error returned when the test suite fails on the mutated code.
+/// It contains the names of the failed tests and the reason of the failure.
+#[derive(Debug)]
+pub(crate) struct FailedTests {
+    /// Names of the failed tests.
+    pub(crate) tests: Vec<String>,
+    /// Names of the tests which passed.
+    pub(crate) passed: Vec<String>,
+    /// The reason of the failure.
+    pub(crate) reason: KillReason,
+    /// The abort codes of the failed tests which aborted with a different code than expected.
+    pub(crate) abort_codes: BTreeMap<String, u64>,
+}
+
+impl fmt::Display for FailedTests {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Move unit test error ({}), failed tests: {}",
+            self.reason,
+            self.tests.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for FailedTests {}
+
+impl FailedTests {
+    /// Checks whether all the failed tests still aborted, just with a different code than
+    /// expected, so the mutant changed only the error and not the outcome (a soft kill).
+    pub(crate) fn only_abort_codes_changed(&self) -> bool {
+        !self.tests.is_empty()
+            && self
+                .tests
+                .iter()
+                .all(|test| self.abort_codes.contains_key(test))
+    }
+}
+
+/// The environment variable set for the child process which runs the baseline tests with
+/// coverage. It contains the path to the package.
+const COVERAGE_BASELINE_ENV_VAR: &str = "MUTATION_TEST_COVERAGE_BASELINE_PACKAGE";
+
 /// Runs tests on the original code and produces a nice informative output.
 ///
+/// If the coverage is requested, it's collected during this run, so the package doesn't need to
+/// be tested with `aptos move test --coverage` beforehand.
+///
 /// # Arguments
 ///
 /// * `cfg` - A `TestBuildConfig` representing the test configuration.
@@ -28,35 +77,166 @@ pub(crate) fn run_tests_on_original_code(
     cfg: &TestBuildConfig,
     package_path: &Path,
 ) -> anyhow::Result<()> {
-    let mut error_writer = termcolor::StandardStream::stderr(termcolor::ColorChoice::Auto);
+    let result = if cfg.apply_coverage {
+        run_baseline_in_child_process(cfg, package_path).or_else(|e| match &cfg.coverage_fallback {
+            Some(coverage_map) => use_coverage_fallback(cfg, package_path, coverage_map, e),
+            None => Err(e),
+        })
+    } else {
+        run_baseline(cfg, package_path, false)
+    };
+
+    if let Err(e) = result {
+        let msg = format!(
+            "Test suite is failing for the original code! Unit test failed with error: {e}"
+        );
+        error!("{msg}");
+        return Err(anyhow!(msg));
+    }
+
+    Ok(())
+}
+
+/// Runs the baseline tests with coverage if the process has been started as the child process by
+/// [`run_tests_on_original_code`]. Returns `None` otherwise.
+///
+/// The test options are parsed from the arguments of the process, which are created by
+/// [`TestBuildConfig::to_args`].
+pub fn run_as_coverage_baseline_child() -> Option<anyhow::Result<()>> {
+    let package_path = env::var_os(COVERAGE_BASELINE_ENV_VAR)?;
+    crash_report::init_logger();
+    Some(
+        TestBuildConfig::try_parse_from(env::args_os())
+            .map_err(|e| anyhow!("invalid options of the coverage baseline run: {e}"))
+            .and_then(|cfg| run_baseline(&cfg, Path::new(&package_path), true)),
+    )
+}
+
+/// Runs the baseline tests in a child process, collecting the coverage.
+///
+/// Collecting the coverage enables the MoveVM tracing for the rest of the process lifetime (see
+/// `run_tests`), which would slow down testing the mutants. The current executable is started
+/// again with the test options only, so it must call [`run_as_coverage_baseline_child`] first
+/// thing, before parsing its own arguments.
+fn run_baseline_in_child_process(cfg: &TestBuildConfig, package_path: &Path) -> anyhow::Result<()> {
+    info!("running the testsuite with coverage on the original package in a child process");
+    let status = Command::new(env::current_exe()?)
+        .args(cfg.to_args())
+        .env(COVERAGE_BASELINE_ENV_VAR, package_path)
+        .status()?;
+
+    if !status.success() {
+        bail!("Move unit test error");
+    }
+    Ok(())
+}
+
+/// Falls back to the coverage map recorded by an earlier run if the baseline run with coverage has
+/// failed (`--coverage-fallback`).
+///
+/// The baseline tests are run again without the coverage first, so the original error is returned
+/// if the tests themselves fail.
+fn use_coverage_fallback(
+    cfg: &TestBuildConfig,
+    package_path: &Path,
+    coverage_map: &Path,
+    error: Error,
+) -> anyhow::Result<()> {
+    info!("collecting the coverage failed ({error}), checking the tests without the coverage");
+    run_baseline(cfg, package_path, false).map_err(|_| error)?;
+
+    fs::copy(coverage_map, move_mutator::coverage_map_path(package_path)).map_err(|e| {
+        anyhow!(
+            "failed to use the fallback coverage map {}: {e}",
+            coverage_map.display()
+        )
+    })?;
 
+    let msg = format!(
+        "collecting the coverage failed, using the previously recorded coverage map {} \
+         (--coverage-fallback), mutants in code changed since then may be filtered out wrongly",
+        coverage_map.display()
+    );
+    warn!("{msg}");
+    eprintln!("WARNING: {msg}");
+    Ok(())
+}
+
+/// Runs tests on the original code, optionally computing the coverage.
+///
+/// With the coverage, the instructions executed by each test are recorded too (see
+/// [`move_mutator::record_test_coverage`]), so the tests run on a single thread.
+fn run_baseline(
+    cfg: &TestBuildConfig,
+    package_path: &Path,
+    compute_coverage: bool,
+) -> anyhow::Result<()> {
     // Show informative statistics to users.
     let report_statistics = true;
 
     // We need to check for the latest git deps only for the first time we run the test.
-    let skip_fetch_deps = false;
+    // When the package has been prebuilt, dependencies have already been fetched.
+    let skip_fetch_deps = cfg.prebuilt_package.is_some() || cfg.move_pkg.skip_fetch_latest_git_deps;
+
+    let into_result = |result: UnitTestResult| match result {
+        UnitTestResult::Success => Ok(()),
+        UnitTestResult::Failure => Err(Error::msg("Move unit test error")),
+    };
 
-    let num_threads = thread::available_parallelism()?.get();
-    info!("using {num_threads} number of threads to run the testsuite on the original package");
+    if !compute_coverage {
+        let num_threads = thread::available_parallelism()?.get();
+        info!("using {num_threads} number of threads to run the testsuite on the original package");
 
+        let mut error_writer = termcolor::StandardStream::stderr(termcolor_choice(Stream::Stderr));
+        return run_tests(
+            cfg,
+            package_path,
+            skip_fetch_deps,
+            report_statistics,
+            num_threads,
+            false,
+            &mut error_writer,
+        )
+        .and_then(into_result);
+    }
+
+    // The trace doesn't tell which test executed the instructions, the tests can be told apart
+    // only if they don't run in parallel.
+    let num_threads = 1;
+    info!("using a single thread to run the testsuite with coverage on the original package");
+
+    // The output is kept to find out which tests ran.
+    let stderr = termcolor::BufferWriter::stderr(termcolor_choice(Stream::Stderr));
+    let mut error_writer = stderr.buffer();
     let result = run_tests(
         cfg,
         package_path,
         skip_fetch_deps,
         report_statistics,
         num_threads,
+        true,
         &mut error_writer,
     );
+    stderr.print(&error_writer)?;
+    let result = result.and_then(into_result);
 
-    if let Err(e) = result {
-        let msg = format!(
-            "Test suite is failing for the original code! Unit test failed with error: {e}"
-        );
-        error!("{msg}");
-        return Err(anyhow!(msg));
+    let trace_path = move_mutator::trace_path(package_path);
+    if result.is_ok() {
+        let output = String::from_utf8_lossy(error_writer.as_slice());
+        let tests = parse_tests_with_status(&output, &["PASS", "FAIL", "TIMEOUT"]);
+        if let Err(e) = move_mutator::record_test_coverage(package_path, &tests) {
+            warn!("failed to record the coverage of the tests: {e}");
+        }
     }
 
-    Ok(())
+    // Disk space optimization: only the coverage map and the coverage of the tests are used, and
+    // since the tool copies the package directory to temp directories when running tests, let's
+    // keep the copied directory as small as possible.
+    if fs::remove_file(&trace_path).is_ok() {
+        info!("removing {}", trace_path.display());
+    }
+
+    result
 }
 
 /// Runs tests on the mutated code.
@@ -72,11 +252,12 @@ pub(crate) fn run_tests_on_original_code(
 ///
 /// # Returns
 ///
-/// * `anyhow::Result<()>` - The result of the test suite for the package.
+/// * `anyhow::Result<Vec<String>>` - The names of the passed tests. If any test fails, the error
+///   contains [`FailedTests`] with the names of the failed and passed tests.
 pub(crate) fn run_tests_on_mutated_code(
     cfg: &TestBuildConfig,
     package_path: &Path,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Vec<String>> {
     // Ignore statistics on mutants.
     let report_statistics = false;
 
@@ -84,7 +265,8 @@ pub(crate) fn run_tests_on_mutated_code(
     let skip_fetch_deps = true;
 
     // No need to print anything to the screen, due to many threads, it might be messy and slow.
-    let mut error_writer = std::io::sink();
+    // The output is kept only to find out which tests killed the mutant and which passed.
+    let mut error_writer = termcolor::NoColor::new(Vec::<u8>::new());
 
     // Do not calculate the coverage on mutants.
     let mut test_config = cfg.clone();
@@ -96,14 +278,125 @@ pub(crate) fn run_tests_on_mutated_code(
     // thread should be more than enough. Using more threads here slows the overall time.
     let num_threads = 1;
 
-    run_tests(
+    let result = run_tests(
         &test_config,
         package_path,
         skip_fetch_deps,
         report_statistics,
         num_threads,
+        false,
         &mut error_writer,
-    )
+    )?;
+
+    let output = String::from_utf8_lossy(error_writer.get_ref());
+    match result {
+        UnitTestResult::Success => Ok(parse_passed_tests(&output)),
+        UnitTestResult::Failure => Err(FailedTests {
+            tests: parse_failed_tests(&output),
+            passed: parse_passed_tests(&output),
+            reason: parse_kill_reason(&output),
+            abort_codes: parse_unexpected_abort_codes(&output),
+        }
+        .into()),
+    }
+}
+
+/// Extracts the names of the failed tests from the unit test output.
+fn parse_failed_tests(output: &str) -> Vec<String> {
+    parse_tests_with_status(output, &["FAIL", "TIMEOUT"])
+}
+
+/// Extracts the names of the passed tests from the unit test output.
+fn parse_passed_tests(output: &str) -> Vec<String> {
+    parse_tests_with_status(output, &["PASS"])
+}
+
+/// Extracts the names of the tests with one of the given statuses from the unit test output.
+fn parse_tests_with_status(output: &str, statuses: &[&str]) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = strip_ansi_codes(line);
+            let (status, name) = line.trim().strip_prefix('[')?.split_once(']')?;
+            statuses
+                .contains(&status.trim())
+                .then(|| name.trim().to_owned())
+        })
+        .collect()
+}
+
+/// Finds out why the test suite failed from the unit test output.
+fn parse_kill_reason(output: &str) -> KillReason {
+    if output
+        .lines()
+        .any(|line| strip_ansi_codes(line).contains("[ TIMEOUT ]"))
+    {
+        KillReason::Timeout
+    } else if output.contains("OUT_OF_GAS") {
+        KillReason::GasExhaustion
+    } else {
+        KillReason::TestFailure
+    }
+}
+
+/// Extracts the actual abort codes of the tests which didn't abort with the expected code from the
+/// failure details of the unit test output.
+///
+/// The failures are listed per module (`Failures in 0x42::sum:`) with a header for every test
+/// (`┌── test_sum ──────`), so the names are qualified the same way as in the test statuses.
+fn parse_unexpected_abort_codes(output: &str) -> BTreeMap<String, u64> {
+    const WRONG_ABORT: &str = "but instead it aborted with";
+
+    let mut abort_codes = BTreeMap::new();
+    let mut module = String::new();
+    let mut test = String::new();
+    for line in output.lines().map(strip_ansi_codes) {
+        if let Some(name) = line
+            .trim()
+            .strip_prefix("Failures in ")
+            .and_then(|name| name.strip_suffix(':'))
+        {
+            name.clone_into(&mut module);
+        } else if let Some(header) = line.trim().strip_prefix("┌── ") {
+            let function = header.split_whitespace().next().unwrap_or_default();
+            test = format!("{module}::{function}");
+        } else if let Some((_, actual)) = line.split_once(WRONG_ABORT) {
+            let code = actual
+                .split_whitespace()
+                .find_map(|word| parse_abort_code(word.trim_end_matches(['.', ','])));
+            if let Some(code) = code {
+                abort_codes.entry(test.clone()).or_insert(code);
+            }
+        }
+    }
+    abort_codes
+}
+
+/// Parses the abort code in the decimal or hexadecimal (`0x` prefixed) form.
+fn parse_abort_code(word: &str) -> Option<u64> {
+    match word.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => word.parse().ok(),
+    }
+}
+
+/// Removes the ANSI color codes from the line.
+fn strip_ansi_codes(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip the whole escape sequence, which ends with a letter.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
 }
 
 /// The `run_tests` function is responsible for running the tests for the provided package.
@@ -115,11 +408,12 @@ fn run_tests<W: WriteColor + Send>(
     skip_fetch_latest_git_deps: bool,
     report_statistics: bool,
     num_threads: usize,
+    compute_coverage: bool,
     mut error_writer: &mut W,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<UnitTestResult> {
     let config = BuildConfig {
         dev_mode: cfg.move_pkg.dev,
-        additional_named_addresses: cfg.move_pkg.named_addresses(),
+        additional_named_addresses: cfg.named_addresses(),
         test_mode: true,
         full_model_generation: cfg.move_pkg.check_test_code,
         install_dir: cfg.move_pkg.output_dir.clone(),
@@ -132,60 +426,104 @@ fn run_tests<W: WriteColor + Send>(
     let cost_table = None;
     // By using a reasonably large value, we ensure the original test suite will always pass,
     // while mutants with infinite loops will be killed quite quickly.
+    // Natives are free with the zero gas parameters, so only the instructions are bounded.
     let gas_limit = Some(cfg.gas_limit);
 
     let result = move_cli::base::test::run_move_unit_tests(
         package_path,
         config.clone(),
-        UnitTestingConfig {
-            filter: cfg.filter.clone(),
-            report_storage_on_error: cfg.dump_state,
-            ignore_compile_warnings: cfg.ignore_compile_warnings,
-            report_statistics,
-            num_threads,
-            named_address_values: cfg
-                .move_pkg
-                .named_addresses()
-                .iter()
-                .map(|(name, account_address)| {
-                    (
-                        name.clone(),
-                        NumericalAddress::from_account_address(*account_address),
-                    )
-                })
-                .collect(),
-            ..UnitTestingConfig::default()
-        },
+        cfg.unit_testing_config(num_threads, report_statistics)?,
         natives,
-        aptos_test_feature_flags_genesis(),
+        genesis_change_set(cfg.genesis.as_deref())?,
         gas_limit,
         cost_table,
-        // We cannot use `true` here since that would set a static variable TRACING_ENABLED deep
-        // within MoveVM to true, and that could cause a huge slowdown, even test failures in the
-        // later "mutation-test" phase.
-        // Until we can somehow reconfigure:
+        // Using `true` here sets a static variable TRACING_ENABLED deep within MoveVM to true,
+        // and that could cause a huge slowdown, even test failures in the later "mutation-test"
+        // phase. Until we can somehow reconfigure:
         // https://github.com/aptos-labs/aptos-core/blob/2bb2d43037a93d883729869d65c7c6c75b028fa1/third_party/move/move-vm/runtime/src/tracing.rs#L40
-        // we are forced to avoid computing coverage before running the tests.
+        // the coverage is computed only in a separate child process.
         // How it works: compute_coverage sets `MOVE_VM_TRACE` env variable that configures this
         // once_cell value above and then we can't change it back anymore.
-        false,
+        compute_coverage,
         &mut error_writer,
     )
     .map_err(|err| Error::msg(format!("failed to run unit tests: {err:#}")))?;
 
-    if cfg.apply_coverage {
-        // Disk space optimization:
-        let trace_path = package_path.join(".trace");
-        // Our tool doesn't use the .trace file at all, only the .coverage_map.mvcov file, and
-        // since the tool copy package directory to temp directories for when running tests,
-        // so let's keep copied directory as small as possible.
-        if fs::remove_file(&trace_path).is_ok() {
-            info!("removing {}", trace_path.display());
-        }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_failed_tests_finds_failed_and_timed_out_tests() {
+        let output = "Running Move unit tests\n\
+                      [ PASS    ] 0x42::sum::test_sum\n\
+                      [ FAIL    ] 0x42::sum::test_sum_overflow\n\
+                      \u{1b}[31m[ TIMEOUT ]\u{1b}[0m 0x42::sum::test_loop\n\
+                      Test result: FAILED. Total tests: 3; passed: 1; failed: 2";
+        assert_eq!(parse_failed_tests(output), vec![
+            "0x42::sum::test_sum_overflow",
+            "0x42::sum::test_loop"
+        ]);
+        assert_eq!(parse_passed_tests(output), vec!["0x42::sum::test_sum"]);
+        assert_eq!(parse_kill_reason(output), KillReason::Timeout);
     }
 
-    match result {
-        UnitTestResult::Success => Ok(()),
-        UnitTestResult::Failure => Err(Error::msg("Move unit test error")),
+    #[test]
+    fn parse_kill_reason_detects_gas_exhaustion() {
+        let output = "[ FAIL    ] 0x42::sum::test_sum\n\
+                      Test was not expected to error, but it gave a OUT_OF_GAS (code 4002) error";
+        assert_eq!(parse_kill_reason(output), KillReason::GasExhaustion);
+
+        let output = "[ FAIL    ] 0x42::sum::test_sum\n\
+                      Test was not expected to error, but it aborted with code 1";
+        assert_eq!(parse_kill_reason(output), KillReason::TestFailure);
+    }
+
+    #[test]
+    fn parse_unexpected_abort_codes_finds_actual_codes() {
+        let output = "[ FAIL    ] 0x42::sum::test_abort\n\
+                      [ FAIL    ] 0x42::sum::test_overflow\n\
+                      \n\
+                      Test failures:\n\
+                      \n\
+                      Failures in 0x42::sum:\n\
+                      \n\
+                      ┌── test_abort ──────\n\
+                      │ Test did not abort with expected code. Expected test to abort with \
+                      18446744073709551615 but instead it aborted with 3 here\n\
+                      └──────────────────\n\
+                      \n\
+                      ┌── test_overflow ──────\n\
+                      │ Test was not expected to error, but it gave an arithmetic error\n\
+                      └──────────────────\n";
+        assert_eq!(
+            parse_unexpected_abort_codes(output),
+            [("0x42::sum::test_abort".to_owned(), 3)].into()
+        );
+    }
+
+    #[test]
+    fn soft_kills_change_only_abort_codes() {
+        let failed = |tests: &[&str], abort_codes: &[&str]| FailedTests {
+            tests: tests.iter().map(|t| t.to_string()).collect(),
+            passed: vec![],
+            reason: KillReason::TestFailure,
+            abort_codes: abort_codes.iter().map(|t| (t.to_string(), 2)).collect(),
+        };
+
+        assert!(
+            failed(&["0x42::sum::test_abort"], &["0x42::sum::test_abort"])
+                .only_abort_codes_changed()
+        );
+        assert!(
+            !failed(&["0x42::sum::test_abort", "0x42::sum::test_sum"], &[
+                "0x42::sum::test_abort"
+            ])
+            .only_abort_codes_changed()
+        );
+        assert!(!failed(&[], &[]).only_abort_codes_changed());
     }
 }
@@ -0,0 +1,237 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{batching::ModuleBatch, cli::NewCodeWindow};
+use anyhow::{Context, Result};
+use move_mutator::report::MutationReport;
+use mutator_common::{source::changed_lines, tmp_package_dir::strip_path_prefix};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The commit hash `git blame` reports for lines which are not committed yet.
+const UNCOMMITTED_HASH: &str = "0000000000000000000000000000000000000000";
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A line of the source file as reported by `git blame`.
+#[derive(Debug, PartialEq)]
+struct BlameLine {
+    /// The line number (starting from 1).
+    line: usize,
+    /// The hash of the commit which authored the line.
+    commit: String,
+    /// The author time (seconds since the Unix epoch).
+    author_time: u64,
+}
+
+/// Finds the mutants which touch lines of new code.
+///
+/// The authorship of the lines is taken from `git blame` run in the original package directory.
+/// Lines which are not committed yet are always considered new.
+///
+/// # Arguments
+///
+/// * `mutants` - mutants to check.
+/// * `package_path` - path to the original package (inside a git repository).
+/// * `window` - defines which lines are considered new code.
+///
+/// # Errors
+///
+/// Returns an error if `git` fails, e.g. when the package is not inside a git repository.
+///
+/// # Returns
+///
+/// * `BTreeSet<PathBuf>` - paths of the mutant files touching new code.
+pub(crate) fn find_new_code_mutants(
+    mutants: &[MutationReport],
+    package_path: &Path,
+    window: NewCodeWindow,
+) -> Result<BTreeSet<PathBuf>> {
+    let is_new: Box<dyn Fn(&BlameLine) -> bool> = match window {
+        NewCodeWindow::Days(days) => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let since = now.saturating_sub(days.saturating_mul(SECONDS_PER_DAY));
+            Box::new(move |line| line.author_time >= since)
+        },
+        NewCodeWindow::Commits(commits) => {
+            let recent = git(package_path, &[
+                "rev-list",
+                &format!("--max-count={commits}"),
+                "HEAD",
+            ])?
+            .lines()
+            .map(str::to_owned)
+            .collect::<BTreeSet<_>>();
+            Box::new(move |line| recent.contains(&line.commit))
+        },
+    };
+
+    let mut new_lines = BTreeMap::<PathBuf, BTreeSet<usize>>::new();
+    let mut new_code_mutants = BTreeSet::new();
+    for mutant in mutants {
+        let original_file = strip_path_prefix(mutant.original_file_path())?;
+        if !new_lines.contains_key(&original_file) {
+            let blame = git(package_path, &[
+                "blame",
+                "--line-porcelain",
+                "--",
+                &original_file.to_string_lossy(),
+            ])?;
+            let lines = parse_blame(&blame)
+                .into_iter()
+                .filter(|l| l.commit == UNCOMMITTED_HASH || is_new(l))
+                .map(|l| l.line)
+                .collect();
+            new_lines.insert(original_file.clone(), lines);
+        }
+
+        if changed_lines(mutant.get_diff())?
+            .iter()
+            .any(|line| new_lines[&original_file].contains(line))
+        {
+            new_code_mutants.insert(mutant.mutant_path().clone());
+        }
+    }
+
+    info!("Found {} mutants touching new code", new_code_mutants.len());
+    Ok(new_code_mutants)
+}
+
+/// Reorders the batches, so mutants touching new code are tested first.
+///
+/// The batches are reordered only within their dependency layers, so the dependencies are still
+/// prepared first. The order of the batches (and of mutants inside the batches) is otherwise
+/// preserved.
+pub(crate) fn prioritize_new_code(batches: &mut [ModuleBatch], new_code: &BTreeSet<PathBuf>) {
+    for batch in batches.iter_mut() {
+        batch
+            .mutants
+            .sort_by_key(|m| !new_code.contains(m.mutant_path()));
+    }
+    batches.sort_by_key(|b| {
+        let is_new = b
+            .mutants
+            .first()
+            .is_some_and(|m| new_code.contains(m.mutant_path()));
+        (b.layer, !is_new)
+    });
+}
+
+/// Runs the `git` command in the given directory and returns its standard output.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Parses the output of `git blame --line-porcelain`.
+fn parse_blame(blame: &str) -> Vec<BlameLine> {
+    let mut lines = vec![];
+    let mut current: Option<BlameLine> = None;
+    for line in blame.lines() {
+        if line.starts_with('\t') {
+            // The content of the line ends the entry.
+            lines.extend(current.take());
+        } else if let Some(author_time) = line.strip_prefix("author-time ") {
+            if let Some(current) = current.as_mut() {
+                current.author_time = author_time.parse().unwrap_or_default();
+            }
+        } else if current.is_none() {
+            // The header: `<commit> <original line> <final line> [<lines in group>]`.
+            let mut fields = line.split_whitespace();
+            let commit = fields.next().unwrap_or_default().to_owned();
+            let line = fields
+                .nth(1)
+                .and_then(|l| l.parse().ok())
+                .unwrap_or_default();
+            current = Some(BlameLine {
+                line,
+                commit,
+                author_time: 0,
+            });
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_code_is_prioritized_within_the_dependency_layers() {
+        let mutant = |module: &str| {
+            MutationReport::new(
+                Path::new(&format!("mutants/{module}_0.move")),
+                Path::new(&format!("sources/{module}.move")),
+                module,
+                "f",
+                "",
+                "",
+            )
+        };
+        let mutants = [mutant("A"), mutant("B"), mutant("C"), mutant("D")];
+        let mut batches = mutants
+            .iter()
+            .zip([0, 0, 1, 1])
+            .map(|(m, layer)| ModuleBatch {
+                module: m.get_module_name().to_owned(),
+                layer,
+                mutants: vec![m],
+            })
+            .collect::<Vec<_>>();
+        let new_code = [&mutants[1], &mutants[3]]
+            .iter()
+            .map(|m| m.mutant_path().clone())
+            .collect();
+
+        prioritize_new_code(&mut batches, &new_code);
+        let order = batches
+            .iter()
+            .map(|b| b.module.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(order, ["B", "A", "D", "C"]);
+    }
+
+    #[test]
+    fn parses_blame_output() {
+        let commit = "8d91ac5e0c4f7b3a1d2e9f6b5c4a3d2e1f0a9b8c";
+        let blame = format!(
+            "{commit} 1 1 2\nauthor Alice\nauthor-time 1700000000\nfilename sources/Sum.move\n\tmodule 0x42::Sum {{\n\
+             {commit} 2 2\nauthor Alice\nauthor-time 1700000000\nfilename sources/Sum.move\n\t}}\n\
+             {UNCOMMITTED_HASH} 3 3 1\nauthor Not Committed Yet\nauthor-time 1800000000\nfilename sources/Sum.move\n\t\n"
+        );
+        assert_eq!(parse_blame(&blame), vec![
+            BlameLine {
+                line: 1,
+                commit: commit.to_owned(),
+                author_time: 1700000000,
+            },
+            BlameLine {
+                line: 2,
+                commit: commit.to_owned(),
+                author_time: 1700000000,
+            },
+            BlameLine {
+                line: 3,
+                commit: UNCOMMITTED_HASH.to_owned(),
+                author_time: 1800000000,
+            },
+        ]);
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use mutator_common::report::MutantStatus;
+use std::{collections::BTreeMap, num::NonZeroUsize, sync::Mutex};
+
+/// The results of the tested mutants of a single function.
+#[derive(Debug, Default)]
+struct FunctionResults {
+    /// The number of tested mutants which survived.
+    survived: usize,
+    /// Whether any tested mutant has been detected by the tests (killed or excluded).
+    detected: bool,
+}
+
+/// Tracks the tested mutants of every function to skip the remaining ones once the first mutants
+/// have all survived (`--skip-uncovered-after`), as the function is likely not exercised by any
+/// test.
+///
+/// Mutants are tested in parallel, so a few more mutants than the threshold may be tested before
+/// the function is skipped.
+#[derive(Debug)]
+pub(crate) struct UncoveredFunctions {
+    /// The number of surviving mutants after which the function is skipped.
+    threshold: NonZeroUsize,
+    /// The results per qualified function name.
+    functions: Mutex<BTreeMap<String, FunctionResults>>,
+}
+
+impl UncoveredFunctions {
+    /// Creates the tracker which skips a function after the given number of surviving mutants.
+    pub(crate) fn new(threshold: NonZeroUsize) -> Self {
+        Self {
+            threshold,
+            functions: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Checks whether the remaining mutants of the function should be skipped.
+    pub(crate) fn should_skip(&self, qname: &str) -> bool {
+        let functions = self.functions.lock().unwrap_or_else(|e| e.into_inner());
+        functions
+            .get(qname)
+            .is_some_and(|f| !f.detected && f.survived >= self.threshold.get())
+    }
+
+    /// Records the status of the tested mutant of the function.
+    pub(crate) fn record(&self, qname: &str, status: MutantStatus) {
+        let mut functions = self.functions.lock().unwrap_or_else(|e| e.into_inner());
+        let results = functions.entry(qname.to_owned()).or_default();
+        match status {
+            MutantStatus::Alive => results.survived += 1,
+            MutantStatus::Killed | MutantStatus::Excluded => results.detected = true,
+            MutantStatus::Skipped | MutantStatus::Suppressed | MutantStatus::Equivalent => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_is_skipped_after_surviving_mutants() {
+        let tracker = UncoveredFunctions::new(NonZeroUsize::new(2).unwrap());
+
+        tracker.record("Sum::sum", MutantStatus::Alive);
+        assert!(!tracker.should_skip("Sum::sum"));
+        tracker.record("Sum::sum", MutantStatus::Alive);
+        assert!(tracker.should_skip("Sum::sum"));
+
+        // A single detected mutant shows the function is exercised by the tests.
+        tracker.record("Sum::sub", MutantStatus::Alive);
+        tracker.record("Sum::sub", MutantStatus::Killed);
+        tracker.record("Sum::sub", MutantStatus::Alive);
+        assert!(!tracker.should_skip("Sum::sub"));
+
+        assert!(!tracker.should_skip("Sum::mul"));
+    }
+}
@@ -0,0 +1,399 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use mutator_common::{
+    report::{MiniReport, MutantStatus},
+    source::find_module_address,
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The directory name where the suggested tests are stored.
+pub const SUGGESTED_TESTS_DIR: &str = "suggested_tests";
+
+/// Generates skeletons of unit tests for the surviving mutants.
+///
+/// A single file with a `#[test_only]` module is generated for every module containing surviving
+/// mutants, named after the module and its address. Every test calls the mutated function and contains TODO placeholders for the arguments
+/// and assertions which should kill the mutant.
+///
+/// # Arguments
+///
+/// * `mini_reports` - results of the mutation testing.
+/// * `package_path` - path to the tested package.
+/// * `outdir` - directory where the suggested tests are stored.
+///
+/// # Returns
+///
+/// * `anyhow::Result<Vec<PathBuf>>` - paths to the generated files.
+pub(crate) fn generate_suggested_tests(
+    mini_reports: &[MiniReport],
+    package_path: &Path,
+    outdir: &Path,
+) -> anyhow::Result<Vec<PathBuf>> {
+    // Group survivors by the module.
+    let mut survivors = BTreeMap::<(&str, &Path), Vec<&MiniReport>>::new();
+    for report in mini_reports
+        .iter()
+        .filter(|r| r.mutant_status == MutantStatus::Alive)
+    {
+        let Some((module, _)) = report.qname.split_once("::") else {
+            continue;
+        };
+        survivors
+            .entry((module, report.original_file.as_path()))
+            .or_default()
+            .push(report);
+    }
+
+    if survivors.is_empty() {
+        return Ok(vec![]);
+    }
+
+    fs::create_dir_all(outdir)?;
+
+    let mut generated = vec![];
+    for ((module, original_file), reports) in survivors {
+        let source = fs::read_to_string(package_path.join(original_file))?;
+        let address = find_module_address(&source, module).unwrap_or("TODO_ADDRESS");
+
+        let mut content = String::new();
+        writeln!(
+            content,
+            "// Suggested tests for the surviving mutants in the `{module}` module."
+        )?;
+        writeln!(
+            content,
+            "// Replace the TODO placeholders so the tests kill the mutants."
+        )?;
+        writeln!(content, "#[test_only]")?;
+        writeln!(content, "module {address}::{module}_suggested_tests {{")?;
+        writeln!(content, "    use {address}::{module};")?;
+
+        for (index, report) in reports.into_iter().enumerate() {
+            let (_, function) = report.qname.split_once("::").unwrap_or_default();
+            let signature = find_function_signature(&source, function);
+            content.push('\n');
+            write_test_skeleton(
+                &mut content,
+                module,
+                function,
+                signature.as_ref(),
+                report,
+                index,
+            )?;
+        }
+        writeln!(content, "}}")?;
+
+        // Modules with the same name may be published at different addresses.
+        let path = outdir.join(format!("{address}_{module}_suggested_tests.move"));
+        fs::write(&path, content)?;
+        generated.push(path);
+    }
+
+    Ok(generated)
+}
+
+/// Writes a single test skeleton for the surviving mutant.
+///
+/// The test name ends with the mutant identifier prefix, or with the index of the mutant in the
+/// module if the identifier is unknown.
+fn write_test_skeleton(
+    content: &mut String,
+    module: &str,
+    function: &str,
+    signature: Option<&FunctionSignature>,
+    report: &MiniReport,
+    index: usize,
+) -> std::fmt::Result {
+    writeln!(content, "    // Surviving mutant {}:", report.mutant_id)?;
+    for line in report
+        .diff
+        .lines()
+        .filter(|l| l.starts_with(['-', '+']) && !l.starts_with("---") && !l.starts_with("+++"))
+    {
+        writeln!(content, "    // {line}")?;
+    }
+
+    let id_suffix = match report.mutant_id.get(..8).unwrap_or(&report.mutant_id) {
+        "" => index.to_string(),
+        prefix => prefix.to_owned(),
+    };
+    let signers = signature
+        .map(|s| {
+            s.params
+                .iter()
+                .filter(|(_, ty)| ty == "&signer")
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if signers.is_empty() {
+        writeln!(content, "    #[test]")?;
+        writeln!(content, "    fun test_{function}_{id_suffix}() {{")?;
+    } else {
+        let attrs = signers
+            .iter()
+            .map(|s| format!("{s} = @0xCAFE"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let params = signers
+            .iter()
+            .map(|s| format!("{s}: &signer"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(content, "    #[test({attrs})]")?;
+        writeln!(content, "    fun test_{function}_{id_suffix}({params}) {{")?;
+    }
+
+    let Some(signature) = signature else {
+        writeln!(
+            content,
+            "        // TODO: call `{module}::{function}` and assert the result."
+        )?;
+        return writeln!(content, "    }}");
+    };
+
+    if !signature.is_public {
+        writeln!(
+            content,
+            "        // TODO: `{function}` is not public - move this test into the `{module}` module."
+        )?;
+    }
+
+    let args = signature
+        .params
+        .iter()
+        .map(|(name, ty)| {
+            if ty == "&signer" {
+                name.clone()
+            } else {
+                format!("/* TODO {name}: {ty} */")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if signature.has_return_value {
+        writeln!(
+            content,
+            "        let result = {module}::{function}({args});"
+        )?;
+        writeln!(
+            content,
+            "        // TODO: assert the result, so the mutant is killed."
+        )?;
+        writeln!(
+            content,
+            "        assert!(result == /* TODO expected */, 0);"
+        )?;
+    } else {
+        writeln!(content, "        {module}::{function}({args});")?;
+        writeln!(
+            content,
+            "        // TODO: assert the state changed by the call, so the mutant is killed."
+        )?;
+    }
+    writeln!(content, "    }}")
+}
+
+/// A simplified function signature found in the source code.
+#[derive(Debug, PartialEq)]
+struct FunctionSignature {
+    /// Function parameters - pairs of the name and the type.
+    params: Vec<(String, String)>,
+    /// Whether the function is callable from other modules.
+    is_public: bool,
+    /// Whether the function returns a value.
+    has_return_value: bool,
+}
+
+/// Finds the signature of the function with the given name.
+fn find_function_signature(source: &str, function: &str) -> Option<FunctionSignature> {
+    let pattern = format!("fun {function}");
+    let (idx, _) = source.match_indices(&pattern).find(|(idx, _)| {
+        source[idx + pattern.len()..]
+            .trim_start()
+            .starts_with(['(', '<'])
+    })?;
+
+    let line_start = source[..idx].rfind('\n').map_or(0, |i| i + 1);
+    let is_public = source[line_start..idx].contains("public");
+
+    let rest = &source[idx + pattern.len()..];
+    let params_start = rest.find('(')?;
+    let params_len = find_closing_paren(&rest[params_start..])?;
+    let params = split_top_level(&rest[params_start + 1..params_start + params_len])
+        .into_iter()
+        .filter_map(|param| {
+            let (name, ty) = param.split_once(':')?;
+            Some((name.trim().to_owned(), ty.trim().to_owned()))
+        })
+        .collect();
+
+    let after_params = &rest[params_start + params_len + 1..];
+    let header = after_params.split('{').next().unwrap_or_default();
+    let has_return_value = header.trim_start().starts_with(':');
+
+    Some(FunctionSignature {
+        params,
+        is_public,
+        has_return_value,
+    })
+}
+
+/// Finds the index of the parenthesis closing the one at the beginning of the string.
+fn find_closing_paren(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Splits the string by commas which are not nested inside brackets.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (idx, c) in s.char_indices() {
+        match c {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(&s[start..idx]);
+                start = idx + 1;
+            },
+            _ => {},
+        }
+    }
+    result.push(&s[start..]);
+    result
+        .into_iter()
+        .filter(|p| !p.trim().is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"module TestAccount::Sum {
+    use std::signer;
+
+    public fun sum(x: u128, y: u128): u128 {
+        x + y
+    }
+
+    fun store(account: &signer, values: vector<Table<u8, u64>>) {
+        move_to(account, Values { values });
+    }
+}
+"#;
+
+    #[test]
+    fn finds_function_signature() {
+        assert_eq!(
+            find_function_signature(SOURCE, "sum"),
+            Some(FunctionSignature {
+                params: vec![
+                    ("x".to_owned(), "u128".to_owned()),
+                    ("y".to_owned(), "u128".to_owned())
+                ],
+                is_public: true,
+                has_return_value: true,
+            })
+        );
+        assert_eq!(
+            find_function_signature(SOURCE, "store"),
+            Some(FunctionSignature {
+                params: vec![
+                    ("account".to_owned(), "&signer".to_owned()),
+                    ("values".to_owned(), "vector<Table<u8, u64>>".to_owned())
+                ],
+                is_public: false,
+                has_return_value: false,
+            })
+        );
+        assert_eq!(find_function_signature(SOURCE, "su"), None);
+    }
+
+    #[test]
+    fn generates_tests_only_for_survivors() {
+        let package_path = tempfile::tempdir().unwrap().into_path();
+        fs::create_dir_all(package_path.join("sources")).unwrap();
+        fs::write(package_path.join("sources/Sum.move"), SOURCE).unwrap();
+        let outdir = package_path.join(SUGGESTED_TESTS_DIR);
+
+        let diff = diffy::create_patch("        x + y\n", "        x - y\n").to_string();
+        let reports = vec![
+            MiniReport::new(
+                PathBuf::from("sources/Sum.move"),
+                "Sum::sum".to_owned(),
+                MutantStatus::Alive,
+                diff.clone(),
+            )
+            .with_mutant_info("0123456789abcdef", vec![]),
+            MiniReport::new(
+                PathBuf::from("sources/Sum.move"),
+                "Sum::store".to_owned(),
+                MutantStatus::Killed,
+                diff,
+            )
+            .with_mutant_info("fedcba9876543210", vec![]),
+        ];
+
+        let generated = generate_suggested_tests(&reports, &package_path, &outdir).unwrap();
+        assert_eq!(generated, vec![
+            outdir.join("TestAccount_Sum_suggested_tests.move")
+        ]);
+
+        let content = fs::read_to_string(&generated[0]).unwrap();
+        assert!(content.contains("module TestAccount::Sum_suggested_tests {"));
+        assert!(content.contains("fun test_sum_01234567()"));
+        assert!(content.contains("// -        x + y"));
+        assert!(content.contains("let result = Sum::sum(/* TODO x: u128 */, /* TODO y: u128 */);"));
+        assert!(!content.contains("test_store"));
+
+        fs::remove_dir_all(package_path).unwrap();
+    }
+
+    #[test]
+    fn mutants_without_id_get_distinct_test_names() {
+        let package_path = tempfile::tempdir().unwrap().into_path();
+        fs::create_dir_all(package_path.join("sources")).unwrap();
+        fs::write(package_path.join("sources/Sum.move"), SOURCE).unwrap();
+        let outdir = package_path.join(SUGGESTED_TESTS_DIR);
+
+        let diff = diffy::create_patch("        x + y\n", "        x - y\n").to_string();
+        let survivor = MiniReport::new(
+            PathBuf::from("sources/Sum.move"),
+            "Sum::sum".to_owned(),
+            MutantStatus::Alive,
+            diff,
+        );
+        let reports = vec![survivor.clone(), survivor];
+
+        let generated = generate_suggested_tests(&reports, &package_path, &outdir).unwrap();
+        let content = fs::read_to_string(&generated[0]).unwrap();
+        assert!(content.contains("fun test_sum_0()"));
+        assert!(content.contains("fun test_sum_1()"));
+
+        fs::remove_dir_all(package_path).unwrap();
+    }
+}
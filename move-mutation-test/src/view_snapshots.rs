@@ -0,0 +1,446 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    abort_codes::{collect_move_files, enclosing_module, identifier, short_name, PROBE_ABORT_CODE},
+    cli::TestBuildConfig,
+    mutation_test::{run_tests_on_mutated_code, FailedTests},
+};
+use anyhow::Context;
+use fs_extra::dir::CopyOptions;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    ops::Range,
+    path::Path,
+};
+
+/// The attribute of the tests providing the state for the snapshots of the view functions.
+const VIEW_SNAPSHOT: &str = "mutation::view_snapshot";
+
+/// The attribute of the view functions.
+const VIEW: &str = "#[view]";
+
+/// The variable collecting the outputs of the view functions in the instrumented tests.
+const OUTPUTS: &str = "mutation_view_outputs";
+
+/// The variable with the digest of the outputs in the instrumented tests.
+const DIGEST: &str = "mutation_view_digest";
+
+/// A test annotated with `#[mutation::view_snapshot]`, which sets up the state and calls the view
+/// functions as separate statements.
+#[derive(Debug, PartialEq)]
+struct SnapshotTest {
+    /// The location of the `mutation::view_snapshot` attribute in the source.
+    attribute: Range<usize>,
+    /// The location of the test body, between the braces.
+    body: Range<usize>,
+    /// The locations of the view function calls, without the semicolons.
+    calls: Vec<Range<usize>>,
+    /// The name of the test (`module::function`).
+    test: String,
+}
+
+/// How the snapshot test checks the outputs of the view functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Snapshot {
+    /// The test aborts with the digest of the outputs, so the runner reports it.
+    Probe,
+    /// The test fails if the digest differs from the one recorded on the original code.
+    Recorded(u64),
+}
+
+/// The snapshot tests whose view function outputs have been recorded on the original code
+/// (`--view-snapshots`).
+#[derive(Debug, Default)]
+pub(crate) struct ViewSnapshots {
+    /// The names of the tests (`module::function`).
+    tests: BTreeSet<String>,
+}
+
+impl ViewSnapshots {
+    /// Checks whether only the snapshot tests failed, i.e. the mutant changed the outputs of the
+    /// view functions, which no other test noticed.
+    pub(crate) fn explain(&self, failed: &FailedTests) -> bool {
+        !failed.tests.is_empty()
+            && failed
+                .tests
+                .iter()
+                .all(|test| self.tests.contains(short_name(test)))
+    }
+}
+
+/// Records the outputs of the view functions called in the tests annotated with
+/// `#[mutation::view_snapshot]` on the original code, so the mutants changing the outputs are
+/// killed even if no test asserts on them.
+///
+/// Each call of a view function given as a separate statement of such test is replaced by
+/// collecting the BCS bytes of its output, and the test checks the digest of all outputs at the
+/// end. The digests are found by running the tests aborting with the digest, as the runner
+/// reports the actual abort code. The view functions don't abort in these tests, since they
+/// passed on the original code in the baseline run.
+pub(crate) fn record_view_snapshots(
+    test_config: &TestBuildConfig,
+    package_path: &Path,
+    outdir: &Path,
+) -> anyhow::Result<ViewSnapshots> {
+    let mut files = vec![];
+    collect_move_files(package_path, &mut files)?;
+    let mut sources = vec![];
+    for path in files {
+        sources.push((fs::read_to_string(&path)?, path));
+    }
+    let views = sources
+        .iter()
+        .flat_map(|(source, _)| view_functions(source))
+        .collect::<BTreeSet<_>>();
+    let mut tests = vec![];
+    sources.retain(|(source, _)| {
+        let found = snapshot_tests(source, &views);
+        let is_found = !found.is_empty();
+        tests.extend(found.into_iter().map(|test| test.test));
+        is_found
+    });
+    if tests.is_empty() {
+        println!("No tests annotated with #[{VIEW_SNAPSHOT}] call view functions");
+        return Ok(ViewSnapshots::default());
+    }
+
+    let probe_dir = outdir.join("view_snapshot_probe");
+    let _ = fs::remove_dir_all(&probe_dir);
+    fs_extra::dir::copy(
+        package_path,
+        &probe_dir,
+        &CopyOptions::new().content_only(true),
+    )?;
+    for (source, path) in &sources {
+        let (probe, _) = instrument(source, &views, |_| Some(Snapshot::Probe));
+        fs::write(probe_dir.join(path.strip_prefix(package_path)?), probe)?;
+    }
+
+    let result = run_tests_on_mutated_code(test_config, &probe_dir);
+    let _ = fs::remove_dir_all(&probe_dir);
+    let digests = match result {
+        Ok(_) => BTreeMap::new(),
+        Err(e) => e
+            .downcast::<FailedTests>()
+            .context("failed to run the tests to record the view function outputs")?
+            .abort_codes
+            .into_iter()
+            .map(|(test, digest)| (short_name(&test).to_owned(), digest))
+            .collect(),
+    };
+
+    let mut snapshots = ViewSnapshots::default();
+    for (source, path) in sources {
+        let (source, tests) = instrument(&source, &views, |test| {
+            digests.get(test).copied().map(Snapshot::Recorded)
+        });
+        if !tests.is_empty() {
+            fs::write(&path, source)?;
+            snapshots.tests.extend(tests);
+        }
+    }
+    let unrecorded = tests
+        .iter()
+        .filter(|test| !snapshots.tests.contains(*test))
+        .cloned()
+        .collect::<Vec<_>>();
+    if !unrecorded.is_empty() {
+        let msg = format!(
+            "the view function outputs of the tests {} couldn't be recorded",
+            unrecorded.join(", ")
+        );
+        warn!("{msg}");
+        eprintln!("WARNING: {msg}");
+    }
+    println!(
+        "Recorded the view function outputs of {} snapshot tests",
+        snapshots.tests.len()
+    );
+    Ok(snapshots)
+}
+
+/// Rewrites the snapshot tests to check the outputs of the view functions they call. Returns the
+/// new source and the names of the rewritten tests.
+fn instrument(
+    source: &str,
+    views: &BTreeSet<String>,
+    snapshot: impl Fn(&str) -> Option<Snapshot>,
+) -> (String, Vec<String>) {
+    let mut result = source.to_owned();
+    let mut tests = vec![];
+    for test in snapshot_tests(source, views).into_iter().rev() {
+        let Some(snapshot) = snapshot(&test.test) else {
+            continue;
+        };
+
+        let check = match snapshot {
+            Snapshot::Probe => format!("abort {DIGEST}"),
+            Snapshot::Recorded(code) => format!("assert!({DIGEST} == {code}, {DIGEST});"),
+        };
+        // The last statement of the body may be missing the semicolon.
+        let body = source[test.body.clone()].trim_end();
+        let separator = if body.is_empty() || body.ends_with(';') {
+            ""
+        } else {
+            ";"
+        };
+        result.insert_str(
+            test.body.start + body.len(),
+            &format!(
+                "{separator} let mutation_view_hash = std::hash::sha3_256({OUTPUTS}); \
+                 let {DIGEST} = 0u64; let mutation_view_byte = 0; \
+                 while (mutation_view_byte < 8) {{ \
+                 {DIGEST} = ({DIGEST} << 8) | \
+                 (*std::vector::borrow(&mutation_view_hash, mutation_view_byte) as u64); \
+                 mutation_view_byte = mutation_view_byte + 1; }}; {check}"
+            ),
+        );
+        for call in test.calls.iter().rev() {
+            result.replace_range(
+                call.clone(),
+                &format!(
+                    "std::vector::append(&mut {OUTPUTS}, std::bcs::to_bytes(&{}))",
+                    &source[call.clone()]
+                ),
+            );
+        }
+        result.insert_str(
+            test.body.start,
+            &format!(" let {OUTPUTS} = std::vector::empty<u8>();"),
+        );
+        if snapshot == Snapshot::Probe {
+            result.replace_range(
+                test.attribute,
+                &format!("expected_failure(abort_code = {PROBE_ABORT_CODE})"),
+            );
+        }
+        tests.push(test.test);
+    }
+    tests.reverse();
+    (result, tests)
+}
+
+/// Finds the names of the view functions declared in the source.
+fn view_functions(source: &str) -> Vec<String> {
+    source
+        .match_indices(VIEW)
+        .filter_map(|(start, _)| {
+            let after = &source[start + VIEW.len()..];
+            let fun = after.find("fun ")?;
+            Some(identifier(&after[fun + "fun ".len()..]).to_owned())
+        })
+        .collect()
+}
+
+/// Finds the snapshot tests of the source which call any of the view functions.
+fn snapshot_tests(source: &str, views: &BTreeSet<String>) -> Vec<SnapshotTest> {
+    let mut tests = vec![];
+    for (start, _) in source.match_indices(VIEW_SNAPSHOT) {
+        let end = start + VIEW_SNAPSHOT.len();
+        let (before, after) = (&source[..start], &source[end..]);
+        let in_attribute = before
+            .rfind("#[")
+            .is_some_and(|open| !before[open..].contains(']'));
+        let Some(fun) = after.find("fun ") else {
+            continue;
+        };
+        // Tests expected to fail can't check the outputs at the end.
+        let attributes_start = before.rfind(['}', ';']).map_or(0, |idx| idx + 1);
+        let attributes = &source[attributes_start..end + fun];
+        if !in_attribute || attributes.contains("expected_failure") {
+            continue;
+        }
+
+        let function = identifier(&after[fun + "fun ".len()..]);
+        let module = enclosing_module(before);
+        let open = after[fun..].find('{').map(|idx| end + fun + idx);
+        let close = open.and_then(|open| closing_delimiter(source, open));
+        let (Some(module), Some(open), Some(close)) = (module, open, close) else {
+            continue;
+        };
+
+        let body = open + 1..close;
+        let calls = statements(source, body.clone())
+            .into_iter()
+            .filter(|range| is_view_call(&source[range.clone()], views))
+            .collect::<Vec<_>>();
+        if !calls.is_empty() {
+            tests.push(SnapshotTest {
+                attribute: start..end,
+                body,
+                calls,
+                test: format!("{module}::{function}"),
+            });
+        }
+    }
+    tests
+}
+
+/// Splits the block into the statements terminated by semicolons, skipping the comments and the
+/// whitespace around them.
+fn statements(source: &str, block: Range<usize>) -> Vec<Range<usize>> {
+    let bytes = source.as_bytes();
+    let mut statements = vec![];
+    let mut depth = 0usize;
+    let mut start = block.start;
+    let mut idx = block.start;
+    while idx < block.end {
+        match bytes[idx] {
+            b'/' if bytes.get(idx + 1) == Some(&b'/') => {
+                idx = source[idx..block.end]
+                    .find('\n')
+                    .map_or(block.end, |newline| idx + newline);
+                if depth == 0 && source[start..idx].trim().starts_with("//") {
+                    start = idx;
+                }
+                continue;
+            },
+            b'(' | b'{' | b'[' => depth += 1,
+            b')' | b'}' | b']' => depth = depth.saturating_sub(1),
+            b';' if depth == 0 => {
+                let text = &source[start..idx];
+                let trimmed_start = start + (text.len() - text.trim_start().len());
+                statements.push(trimmed_start..start + text.trim_end().len());
+                start = idx + 1;
+            },
+            _ => {},
+        }
+        idx += 1;
+    }
+    statements
+}
+
+/// Checks if the statement is a whole call of one of the view functions, e.g.
+/// `vault::balance(@0xA11CE)`.
+fn is_view_call(statement: &str, views: &BTreeSet<String>) -> bool {
+    let path_end = statement
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != ':')
+        .unwrap_or(statement.len());
+    let name = statement[..path_end]
+        .rsplit("::")
+        .next()
+        .unwrap_or_default();
+    if !views.contains(name) {
+        return false;
+    }
+
+    // Skip the type arguments, if any.
+    let mut args = path_end;
+    if statement[args..].starts_with('<') {
+        match closing_delimiter(statement, args) {
+            Some(close) => args = close + 1,
+            None => return false,
+        }
+    }
+    statement[args..].starts_with('(')
+        && closing_delimiter(statement, args) == Some(statement.len() - 1)
+}
+
+/// Finds the delimiter closing the one at the position.
+fn closing_delimiter(text: &str, open: usize) -> Option<usize> {
+    let (open_char, close_char) = match text.as_bytes()[open] {
+        b'(' => (b'(', b')'),
+        b'{' => (b'{', b'}'),
+        b'<' => (b'<', b'>'),
+        _ => return None,
+    };
+    let mut depth = 0usize;
+    for (idx, c) in text.bytes().enumerate().skip(open) {
+        if c == open_char {
+            depth += 1;
+        } else if c == close_char {
+            depth -= 1;
+            if depth == 0 {
+                return Some(idx);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "module 0x42::vault_tests {
+    use 0x42::vault;
+
+    #[test(account = @0xA11CE)]
+    #[mutation::view_snapshot]
+    fun views(account: &signer) {
+        vault::deposit(account, 10);
+        // The balance after the deposit.
+        vault::balance(@0xA11CE);
+        vault::balance(@0xA11CE) + 1;
+        vault::is_frozen<u64>(@0xA11CE);
+        vault::deposit(account, 1)
+    }
+
+    #[test, expected_failure, mutation::view_snapshot]
+    fun aborting_views() { vault::balance(@0x0); }
+}
+";
+
+    fn views() -> BTreeSet<String> {
+        view_functions(
+            "module 0x42::vault {
+    #[view]
+    public fun balance(owner: address): u64 { 0 }
+
+    public fun deposit(account: &signer, amount: u64) {}
+
+    #[view]
+    public fun is_frozen<T>(owner: address): bool { false }
+}",
+        )
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn view_calls_are_found() {
+        assert_eq!(
+            views(),
+            ["balance".to_owned(), "is_frozen".to_owned()].into()
+        );
+
+        let tests = snapshot_tests(SOURCE, &views());
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].test, "vault_tests::views");
+        let calls = tests[0]
+            .calls
+            .iter()
+            .map(|call| &SOURCE[call.clone()])
+            .collect::<Vec<_>>();
+        assert_eq!(calls, vec![
+            "vault::balance(@0xA11CE)",
+            "vault::is_frozen<u64>(@0xA11CE)"
+        ]);
+    }
+
+    #[test]
+    fn snapshot_tests_are_instrumented() {
+        let (probe, tests) = instrument(SOURCE, &views(), |_| Some(Snapshot::Probe));
+        assert_eq!(tests, vec!["vault_tests::views"]);
+        assert!(probe.contains(&format!(
+            "#[expected_failure(abort_code = {PROBE_ABORT_CODE})]\n    fun views("
+        )));
+        assert!(probe.contains(
+            "std::vector::append(&mut mutation_view_outputs, \
+             std::bcs::to_bytes(&vault::balance(@0xA11CE)));"
+        ));
+        assert!(probe.contains("vault::deposit(account, 1); let mutation_view_hash"));
+        assert!(probe.contains("abort mutation_view_digest\n    }"));
+
+        let (recorded, _) = instrument(SOURCE, &views(), |_| Some(Snapshot::Recorded(42)));
+        assert!(recorded.contains("#[mutation::view_snapshot]\n    fun views("));
+        assert!(recorded.contains("assert!(mutation_view_digest == 42, mutation_view_digest);"));
+
+        let (unchanged, tests) = instrument(SOURCE, &views(), |_| None);
+        assert!(tests.is_empty());
+        assert_eq!(unchanged, SOURCE);
+    }
+}
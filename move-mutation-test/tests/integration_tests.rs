@@ -36,7 +36,14 @@ fn test_run_mutation_test(path: &Path, expected_report: String) -> datatest_stab
         // TODO(rqnsom): maybe we could set it to true, but it would require `aptos` command in
         // the `build.rs` - using `process::Command` slowed down the execution a lot
         apply_coverage: false,
+        coverage_fallback: None,
         gas_limit: 2000,
+        prebuilt_package: None,
+        test_args: None,
+        test_env: vec![],
+        test_env_clear: false,
+        test_seed: None,
+        genesis: None,
     };
 
     let report_file = PathBuf::from("report.txt");
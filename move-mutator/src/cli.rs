@@ -2,11 +2,22 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use clap::Parser;
+use crate::{
+    compiler::CompilerTarget,
+    configuration::FileConfiguration,
+    labels::LabelRule,
+    operators::{operator_info, OPERATOR_INFOS},
+    signature::OperatorGate,
+};
+use anyhow::anyhow;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::{path::PathBuf, str::FromStr};
 
 pub const DEFAULT_OUTPUT_DIR: &str = "mutants_output";
 
+/// The optional operators mutating the specifications, which only the prover can kill.
+pub const SPEC_OPERATORS: &[&str] = &[crate::operators::loop_invariant::OPERATOR_NAME];
+
 /// Command line options for mutator
 #[derive(Parser, Debug, Clone)]
 pub struct CLIOptions {
@@ -26,9 +37,40 @@ pub struct CLIOptions {
     #[clap(long, value_parser)]
     pub out_mutant_dir: Option<PathBuf>,
 
+    /// Allow the output directory to be placed inside the package sources (or to contain the
+    /// package). Mutants placed in the package sources are compiled as sources in later runs.
+    #[clap(long)]
+    pub allow_output_in_package: bool,
+
     /// Indicates if mutants should be verified and made sure mutants can compile.
-    #[clap(long, default_value = "false", conflicts_with = "move_sources")]
-    pub verify_mutants: bool,
+    ///
+    /// The boolean options take an optional value (e.g. `--verify-mutants=false`), so they can
+    /// turn off the option set in the configuration file.
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        conflicts_with = "move_sources"
+    )]
+    pub verify_mutants: Option<bool>,
+
+    /// Keep the mutants which fail the verification in the report, together with the compiler
+    /// diagnostics, to debug operators producing invalid code. They are never tested.
+    /// Has effect only when the mutants are verified.
+    #[clap(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    pub keep_stillborn: Option<bool>,
+
+    /// Verify the mutants also under the given language versions (optionally with the compiler
+    /// version, e.g. `2.0,2.1@2.0`) and report the mutants which compile under some of them but
+    /// not the others. Has effect only when the mutants are verified.
+    #[clap(long, value_delimiter = ',')]
+    pub compiler_matrix: Vec<CompilerTarget>,
+
+    /// Don't report the compiler warnings of the mutated package. Warnings never fail the
+    /// generation or the verification of the mutants.
+    #[clap(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    pub ignore_compile_warnings: Option<bool>,
 
     /// Indicates if the output files should be overwritten.
     #[clap(long, default_value = "false")]
@@ -38,9 +80,94 @@ pub struct CLIOptions {
     #[clap(long)]
     pub downsampling_ratio_percentage: Option<usize>,
 
+    /// A file listing the IDs of the mutants tested by a previous run, one per line (`#` starts a
+    /// comment). When downsampling, these mutants are selected first, so the score changes
+    /// reflect the changes of the code and tests rather than a different sample.
+    #[clap(long, value_parser)]
+    pub stable_sampling: Option<PathBuf>,
+
+    /// A file listing the IDs of the mutants to generate, one per line (`#` starts a comment),
+    /// e.g. the survivors of a previous run. The other mutants are neither verified nor saved.
+    #[clap(long, value_parser)]
+    pub only_mutants: Option<PathBuf>,
+
+    /// Keep at most the given number of mutants per file. The kept mutants are spread across the
+    /// functions and operators of the file.
+    #[clap(long)]
+    pub max_mutants_per_file: Option<usize>,
+
     /// Use the unit test coverage report to generate mutants for source code with unit test coverage.
-    #[clap(long = "coverage", conflicts_with = "move_sources")]
-    pub apply_coverage: bool,
+    #[clap(
+        long = "coverage",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        conflicts_with = "move_sources"
+    )]
+    pub apply_coverage: Option<bool>,
+
+    /// Optional mutation operators to enable (disabled by default), separated by commas.
+    /// Available: `struct_pack_field_swap`, `destructuring_binding_swap`, `loop_invariant_weakening`,
+    /// `constant_replacement`, `off_by_one_replacement`.
+    #[clap(long, value_parser = parse_optional_operator, value_delimiter = ',')]
+    pub extra_operators: Vec<String>,
+
+    /// Mutation operators to disable, separated by commas, e.g. `assert_error_code_replacement`
+    /// to keep mutating the `assert!` conditions but not their error codes.
+    #[clap(long, value_parser = parse_operator, value_delimiter = ',')]
+    pub disable_operators: Vec<String>,
+
+    /// Only warn (instead of failing) when the module and function filters match no mutation sites.
+    #[clap(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    pub allow_empty: Option<bool>,
+
+    /// Optional configuration file (TOML) with the mutator options. Options set on the command
+    /// line take precedence. Run `config schema` to see the available keys.
+    #[clap(long, value_parser)]
+    pub mutator_conf: Option<PathBuf>,
+
+    /// Print the effective options (defaults merged with the configuration file, environment
+    /// variables and the command line) in the given format and exit.
+    #[clap(long, value_enum, num_args = 0..=1, default_missing_value = "toml")]
+    pub print_config: Option<ConfigFormat>,
+
+    /// Rules attaching labels to the mutants. They can be set only in the configuration file.
+    #[clap(skip)]
+    pub labels: Vec<LabelRule>,
+
+    /// Gates limiting the operators to the functions with the matching signatures. They can be set
+    /// only in the configuration file.
+    #[clap(skip)]
+    pub operator_gates: Vec<OperatorGate>,
+
+    /// The mutants generated by a previous run, which the tools test instead of running the
+    /// mutator. It's set only by the tools, so the conflicting options are checked in one place.
+    #[clap(skip)]
+    pub use_generated_mutants: Option<PathBuf>,
+}
+
+/// Format of the printed configuration.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl CLIOptions {
+    /// Serializes the options in the given format, as the configuration file with the same effect.
+    ///
+    /// Only the options which can be set in the configuration file are included, so the printed
+    /// TOML can be used as the `--mutator-conf` file.
+    ///
+    /// # Errors
+    /// Returns an error if the options can't be serialized.
+    pub fn to_config_string(&self, format: ConfigFormat) -> anyhow::Result<String> {
+        let configuration = FileConfiguration::from(self);
+        Ok(match format {
+            ConfigFormat::Toml => toml::to_string(&configuration)?,
+            ConfigFormat::Json => serde_json::to_string_pretty(&configuration)?,
+        })
+    }
 }
 
 /// Checker for conflicts with CLI arguments.
@@ -78,11 +205,125 @@ impl Default for CLIOptions {
             mutate_modules: ModuleFilter::All,
             mutate_functions: FunctionFilter::All,
             out_mutant_dir: Some(PathBuf::from(DEFAULT_OUTPUT_DIR)),
-            verify_mutants: false,
+            allow_output_in_package: false,
+            verify_mutants: None,
+            ignore_compile_warnings: None,
+            keep_stillborn: None,
+            compiler_matrix: vec![],
             no_overwrite: false,
-            apply_coverage: false,
+            apply_coverage: None,
             downsampling_ratio_percentage: None,
+            stable_sampling: None,
+            only_mutants: None,
+            max_mutants_per_file: None,
+            extra_operators: vec![],
+            disable_operators: vec![],
+            allow_empty: None,
+            mutator_conf: None,
+            print_config: None,
+            labels: vec![],
+            operator_gates: vec![],
+            use_generated_mutants: None,
+        }
+    }
+}
+
+/// Commands for working with the mutator configuration file.
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCmd {
+    /// Print the JSON schema of the mutator configuration file.
+    Schema,
+}
+
+impl ConfigCmd {
+    /// Executes the command.
+    ///
+    /// # Errors
+    /// Returns an error if the output can't be serialized.
+    pub fn execute(&self) -> anyhow::Result<()> {
+        match self {
+            ConfigCmd::Schema => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&FileConfiguration::json_schema())?
+                );
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Options of the command describing the mutation operators.
+#[derive(Args, Debug, Clone)]
+pub struct DescribeOperatorOptions {
+    /// The name of the operator. All operators are listed if not given.
+    pub name: Option<String>,
+
+    /// Print the description in JSON format.
+    #[clap(long)]
+    pub json: bool,
+}
+
+impl DescribeOperatorOptions {
+    /// Executes the command.
+    ///
+    /// # Errors
+    /// Returns an error if there is no operator with such name.
+    pub fn execute(&self) -> anyhow::Result<()> {
+        let Some(name) = &self.name else {
+            if self.json {
+                println!("{}", serde_json::to_string_pretty(OPERATOR_INFOS)?);
+            } else {
+                for info in OPERATOR_INFOS {
+                    println!("{:<28} {}", info.name, info.description);
+                }
+            }
+            return Ok(());
+        };
+
+        let info = operator_info(name).ok_or_else(|| {
+            let names = OPERATOR_INFOS.iter().map(|info| info.name);
+            anyhow!(
+                "unknown operator '{name}', available operators: {}",
+                names.collect::<Vec<_>>().join(", ")
+            )
+        })?;
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(info)?);
+        } else {
+            println!("{info}");
         }
+        Ok(())
+    }
+}
+
+/// Parses the name of the optional mutation operator.
+///
+/// # Errors
+/// Returns an error if there is no optional operator with such name.
+pub fn parse_optional_operator(s: &str) -> Result<String, String> {
+    if crate::operators::OPTIONAL_OPERATORS.contains(&s) {
+        Ok(s.to_owned())
+    } else {
+        Err(format!(
+            "unknown optional operator '{s}', available operators: {}",
+            crate::operators::OPTIONAL_OPERATORS.join(", ")
+        ))
+    }
+}
+
+/// Parses the name of the mutation operator.
+///
+/// # Errors
+/// Returns an error if there is no operator with such name.
+pub fn parse_operator(s: &str) -> Result<String, String> {
+    if crate::operators::OPERATORS.contains(&s) {
+        Ok(s.to_owned())
+    } else {
+        Err(format!(
+            "unknown operator '{s}', available operators: {}",
+            crate::operators::OPERATORS.join(", ")
+        ))
     }
 }
 
@@ -10,7 +10,10 @@ use itertools::Itertools;
 use move_command_line_common::{address::NumericalAddress, parser::NumberFormat};
 use move_compiler::{attr_derivation, shared::Flags};
 use move_compiler_v2::run_checker;
-use move_model::model::GlobalEnv;
+use move_model::{
+    metadata::{CompilerVersion, LanguageVersion},
+    model::GlobalEnv,
+};
 use move_package::{
     compilation::compiled_package::{make_source_and_deps_for_compiler, CompiledPackage},
     resolution::resolution_graph::ResolvedTable,
@@ -18,7 +21,8 @@ use move_package::{
     BuildConfig,
 };
 use move_symbol_pool::Symbol;
-use std::{collections::BTreeMap, path::Path};
+use mutator_common::color::{termcolor_choice, Stream};
+use std::{collections::BTreeMap, fmt, path::Path, str::FromStr};
 
 /// Generate the AST from the Move sources.
 ///
@@ -77,11 +81,18 @@ pub fn generate_ast(
     let env = run_checker(options)?;
 
     if env.has_errors() {
-        let mut error_writer = termcolor::StandardStream::stderr(termcolor::ColorChoice::Auto);
+        let mut error_writer = termcolor::StandardStream::stderr(termcolor_choice(Stream::Stderr));
         env.report_diag(&mut error_writer, Severity::Warning);
         anyhow::bail!("AST generation failed");
     }
 
+    // Warnings don't stop the generation, but the unit tests fail on them unless they are ignored,
+    // so they are shown the same way.
+    if env.has_warnings() && mutator_config.project.ignore_compile_warnings != Some(true) {
+        let mut error_writer = termcolor::StandardStream::stderr(termcolor_choice(Stream::Stderr));
+        env.report_diag(&mut error_writer, Severity::Warning);
+    }
+
     trace!("Sources parsed successfully, AST generated");
 
     Ok(env)
@@ -333,6 +344,110 @@ pub fn verify_mutant(
     Ok(())
 }
 
+/// A language version (optionally with the compiler version) under which the mutants are verified
+/// by the `--compiler-matrix` option, written as `LANGUAGE[@COMPILER]`, e.g. `2.1@2.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompilerTarget {
+    pub language_version: LanguageVersion,
+    pub compiler_version: Option<CompilerVersion>,
+}
+
+impl CompilerTarget {
+    /// Returns the build configuration with the versions of the target.
+    #[must_use]
+    pub fn apply(&self, config: &BuildConfig) -> BuildConfig {
+        let mut config = config.clone();
+        config.compiler_config.language_version = Some(self.language_version);
+        if let Some(compiler_version) = self.compiler_version {
+            config.compiler_config.compiler_version = Some(compiler_version);
+        }
+        config
+    }
+}
+
+impl FromStr for CompilerTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (language, compiler) = match s.split_once('@') {
+            Some((language, compiler)) => (language, Some(compiler)),
+            None => (s, None),
+        };
+        let language_version = language
+            .parse()
+            .map_err(|e| format!("invalid language version in '{s}': {e}"))?;
+        let compiler_version = compiler
+            .map(|compiler| {
+                compiler
+                    .parse()
+                    .map_err(|e| format!("invalid compiler version in '{s}': {e}"))
+            })
+            .transpose()?;
+        Ok(Self {
+            language_version,
+            compiler_version,
+        })
+    }
+}
+
+impl fmt::Display for CompilerTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.language_version)?;
+        if let Some(compiler_version) = self.compiler_version {
+            write!(f, "@{compiler_version}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Verifies the mutant under each target of the compiler matrix.
+///
+/// `base_compiles` tells whether the mutant compiles under the versions of the base configuration,
+/// which take part in the comparison like the targets of the matrix.
+///
+/// Returns the versions under which the mutant doesn't compile, if it compiles under at least one
+/// other of them - such mutants depend on the compiler or language version. Mutants compiling
+/// under all versions or none of them give an empty result.
+#[must_use]
+pub fn version_dependent_targets(
+    config: &BuildConfig,
+    base_compiles: bool,
+    matrix: &[CompilerTarget],
+    mutated_source: &str,
+    original_file: &Path,
+) -> Vec<String> {
+    let (viable, stillborn): (Vec<_>, Vec<_>) = matrix.iter().partition(|target| {
+        verify_mutant(&target.apply(config), mutated_source, original_file)
+            .inspect_err(|e| debug!("Mutant doesn't compile under {target}: {e:#}"))
+            .is_ok()
+    });
+    let all_same = if base_compiles {
+        stillborn.is_empty()
+    } else {
+        viable.is_empty()
+    };
+    if all_same {
+        return vec![];
+    }
+
+    let base = (!base_compiles).then(|| base_target(config));
+    base.into_iter()
+        .chain(stillborn.into_iter().map(ToString::to_string))
+        .collect()
+}
+
+/// Names the versions of the base configuration like a target of the compiler matrix.
+fn base_target(config: &BuildConfig) -> String {
+    match config.compiler_config.language_version {
+        Some(language_version) => CompilerTarget {
+            language_version,
+            compiler_version: config.compiler_config.compiler_version,
+        }
+        .to_string(),
+        None => "default".to_owned(),
+    }
+}
+
 pub(crate) fn compile_package(
     build_config: BuildConfig,
     package_path: &Path,
@@ -341,17 +456,20 @@ pub(crate) fn compile_package(
     let external_checks = vec![];
 
     // Compile the package.
-    let (compiled_package, _env) = build_config.compile_package_no_exit(
-        package_path,
-        external_checks,
-        &mut compilation_msg,
-    )?;
-
-    info!(
-        "Compilation status: {}",
-        String::from_utf8(compilation_msg)
-            .unwrap_or("Internal error: can't convert compilation error to UTF8".to_string())
-    );
+    let result =
+        build_config.compile_package_no_exit(package_path, external_checks, &mut compilation_msg);
+
+    let diagnostics = String::from_utf8(compilation_msg)
+        .unwrap_or("Internal error: can't convert compilation error to UTF8".to_string());
+    info!("Compilation status: {diagnostics}");
+
+    // The diagnostics are attached to the error, so they explain why the compilation failed.
+    let (compiled_package, _env) = match result {
+        Err(e) if !diagnostics.trim().is_empty() => {
+            return Err(e.context(diagnostics.trim_end().to_owned()));
+        },
+        result => result?,
+    };
 
     Ok(compiled_package)
 }
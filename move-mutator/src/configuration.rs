@@ -2,8 +2,23 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{cli::CLIOptions, coverage::Coverage};
-use std::path::PathBuf;
+use crate::{
+    cli::{parse_operator, parse_optional_operator, CLIOptions, FunctionFilter, ModuleFilter},
+    coverage::Coverage,
+    labels::{LabelRule, Labeler, LABEL_RULE_KEYS},
+    mutate::suggest_similar,
+    operators::{OPERATORS, OPTIONAL_OPERATORS},
+    signature::{OperatorGate, OperatorGates, OPERATOR_GATE_BOOL_KEYS, OPERATOR_GATE_KEYS},
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// Directories of the Move package which are compiled together with the package.
+const PACKAGE_SOURCE_DIRS: &[&str] = &["sources", "tests", "scripts", "examples"];
 
 /// Mutator configuration for the Move project.
 #[derive(Debug, Default)]
@@ -27,4 +42,915 @@ impl Configuration {
             coverage: Coverage::default(),
         }
     }
+
+    /// Creates a new configuration using command line options merged with the configuration file
+    /// (if set) and validates it.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration file is invalid or the options conflict.
+    pub fn load(mut project: CLIOptions, project_path: Option<PathBuf>) -> anyhow::Result<Self> {
+        if let Some(path) = project.mutator_conf.clone() {
+            FileConfiguration::from_file(&path)?.apply_to(&mut project);
+        }
+
+        let configuration = Self::new(project, project_path);
+        configuration.validate()?;
+        Ok(configuration)
+    }
+
+    /// Checks the options for conflicts and invalid values.
+    ///
+    /// The options may come from the command line, environment variables or the configuration
+    /// file, so the same rules as for the command line are checked here once everything is merged.
+    ///
+    /// # Errors
+    /// Returns an error listing all problems found.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let project = &self.project;
+        let mut errors = vec![];
+
+        for (a, b) in CONFLICTING_OPTIONS {
+            if is_option_set(project, a) && is_option_set(project, b) {
+                errors.push(format!(
+                    "options '--{a}' and '--{b}' cannot be used together"
+                ));
+            }
+        }
+
+        if let Some(percentage) = project.downsampling_ratio_percentage {
+            if percentage > 100 {
+                errors.push(format!(
+                    "'--downsampling-ratio-percentage' must be in range 0..=100, found {percentage}"
+                ));
+            }
+        }
+
+        if project.stable_sampling.is_some() && project.downsampling_ratio_percentage.is_none() {
+            errors.push(
+                "'--stable-sampling' requires '--downsampling-ratio-percentage' to be set"
+                    .to_owned(),
+            );
+        }
+
+        if !project.compiler_matrix.is_empty() && project.verify_mutants != Some(true) {
+            errors.push("'--compiler-matrix' requires '--verify-mutants' to be set".to_owned());
+        }
+
+        if project.max_mutants_per_file == Some(0) {
+            errors.push("'--max-mutants-per-file' must be greater than 0".to_owned());
+        }
+
+        errors.extend(
+            project
+                .extra_operators
+                .iter()
+                .filter_map(|op| parse_optional_operator(op).err()),
+        );
+        errors.extend(
+            project
+                .disable_operators
+                .iter()
+                .filter_map(|op| parse_operator(op).err()),
+        );
+
+        if let Err(e) = Labeler::new(&project.labels) {
+            errors.push(format!("{e:#}"));
+        }
+
+        if let Err(e) = OperatorGates::new(&project.operator_gates) {
+            errors.push(format!("{e:#}"));
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!(errors.join("\n"));
+        }
+        Ok(())
+    }
+
+    /// Checks that the output directory doesn't overlap with the mutated package.
+    ///
+    /// Mutants placed inside the package sources are picked up as sources in later runs, and the
+    /// output directory is removed at the start of the run, so it must not contain the package.
+    /// The check can be disabled with the `--allow-output-in-package` option.
+    ///
+    /// # Errors
+    /// Returns an error if the output directory is inside the package sources or contains the
+    /// package.
+    pub fn check_output_dir(&self, output_dir: &Path) -> anyhow::Result<()> {
+        let Some(project_path) = &self.project_path else {
+            return Ok(());
+        };
+        // Single files are mutated without any package.
+        if !self.project.move_sources.is_empty() || self.project.allow_output_in_package {
+            return Ok(());
+        }
+
+        let output_dir = absolute_path(output_dir)?;
+        let project_path = absolute_path(project_path)?;
+
+        if project_path.starts_with(&output_dir) {
+            anyhow::bail!(
+                "the output directory {} contains the mutated package {} and would be removed, \
+                 choose another '--out-mutant-dir' (or use '--allow-output-in-package')",
+                output_dir.display(),
+                project_path.display()
+            );
+        }
+
+        if let Some(dir) = PACKAGE_SOURCE_DIRS
+            .iter()
+            .map(|dir| project_path.join(dir))
+            .find(|dir| output_dir.starts_with(dir))
+        {
+            anyhow::bail!(
+                "the output directory {} is inside the package directory {}, so the mutants would \
+                 be compiled as package sources in later runs, choose another '--out-mutant-dir' \
+                 (or use '--allow-output-in-package')",
+                output_dir.display(),
+                dir.display()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks if the mutation operator is enabled.
+    /// Default operators are enabled unless disabled, optional ones only when explicitly requested.
+    pub(crate) fn is_operator_enabled(&self, name: &str) -> bool {
+        !self.project.disable_operators.iter().any(|op| op == name)
+            && (!OPTIONAL_OPERATORS.contains(&name)
+                || self.project.extra_operators.iter().any(|op| op == name))
+    }
+}
+
+/// Returns the absolute path with symlinks resolved, even if the path (or some of its parents)
+/// doesn't exist yet.
+fn absolute_path(path: &Path) -> anyhow::Result<PathBuf> {
+    let path = std::env::current_dir()?.join(path);
+
+    // The path may not exist, so the `.` and `..` components are resolved lexically.
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {},
+            Component::ParentDir => {
+                normalized.pop();
+            },
+            component => normalized.push(component),
+        }
+    }
+
+    let existing = normalized
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or(&normalized);
+    let rest = normalized.strip_prefix(existing)?;
+    Ok(existing.canonicalize()?.join(rest))
+}
+
+/// Checks if the option with the given name is set (boolean flags must be `true`).
+fn is_option_set(options: &CLIOptions, name: &str) -> bool {
+    match name {
+        "move-sources" => !options.move_sources.is_empty(),
+        "verify-mutants" => options.verify_mutants == Some(true),
+        "coverage" => options.apply_coverage == Some(true),
+        "only-mutants" => options.only_mutants.is_some(),
+        "downsampling-ratio-percentage" => options.downsampling_ratio_percentage.is_some(),
+        "use-generated-mutants" => options.use_generated_mutants.is_some(),
+        _ => unreachable!("unknown conflicting option {name}"),
+    }
+}
+
+impl From<&CLIOptions> for FileConfiguration {
+    /// Collects the options which can be set in the configuration file. The boolean options are
+    /// always included, the other ones only if they are set.
+    fn from(options: &CLIOptions) -> Self {
+        Self {
+            move_sources: non_empty(&options.move_sources),
+            mutate_modules: match &options.mutate_modules {
+                ModuleFilter::All => None,
+                ModuleFilter::Selected(modules) => Some(modules.clone()),
+            },
+            mutate_functions: match &options.mutate_functions {
+                FunctionFilter::All => None,
+                FunctionFilter::Selected(functions) => Some(functions.clone()),
+            },
+            verify_mutants: Some(options.verify_mutants.unwrap_or_default()),
+            ignore_compile_warnings: Some(options.ignore_compile_warnings.unwrap_or_default()),
+            keep_stillborn: Some(options.keep_stillborn.unwrap_or_default()),
+            downsampling_ratio_percentage: options.downsampling_ratio_percentage,
+            max_mutants_per_file: options.max_mutants_per_file,
+            coverage: Some(options.apply_coverage.unwrap_or_default()),
+            extra_operators: non_empty(&options.extra_operators),
+            disable_operators: non_empty(&options.disable_operators),
+            allow_empty: Some(options.allow_empty.unwrap_or_default()),
+            labels: non_empty(&options.labels),
+            operator_gates: non_empty(&options.operator_gates),
+        }
+    }
+}
+
+/// Returns the items, or `None` if there are no items.
+fn non_empty<T: Clone>(items: &[T]) -> Option<Vec<T>> {
+    (!items.is_empty()).then(|| items.to_vec())
+}
+
+/// Type of the value in the configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    Bool,
+    Integer { min: i64, max: i64 },
+    StringList,
+    LabelRules,
+    OperatorGates,
+}
+
+impl ValueKind {
+    /// Describes the expected value for the error messages.
+    fn describe(&self) -> String {
+        match self {
+            ValueKind::Bool => "a boolean".to_owned(),
+            ValueKind::Integer { min, max } => format!("an integer in range {min}..={max}"),
+            ValueKind::StringList => "an array of strings".to_owned(),
+            ValueKind::LabelRules => format!(
+                "an array of tables with the string keys {}",
+                LABEL_RULE_KEYS.join(", ")
+            ),
+            ValueKind::OperatorGates => format!(
+                "an array of tables with the string `operator` key and the keys {} (booleans {})",
+                OPERATOR_GATE_KEYS[1..].join(", "),
+                OPERATOR_GATE_BOOL_KEYS.join(", ")
+            ),
+        }
+    }
+
+    /// Checks if the value matches the kind.
+    fn matches(&self, value: &toml::Value) -> bool {
+        match (self, value) {
+            (ValueKind::Bool, toml::Value::Boolean(_)) => true,
+            (ValueKind::Integer { min, max }, toml::Value::Integer(i)) => (*min..=*max).contains(i),
+            (ValueKind::StringList, toml::Value::Array(items)) => {
+                items.iter().all(toml::Value::is_str)
+            },
+            (ValueKind::LabelRules, toml::Value::Array(items)) => items.iter().all(|item| {
+                item.as_table().is_some_and(|rule| {
+                    rule.get("label").is_some_and(toml::Value::is_str)
+                        && rule.iter().all(|(key, value)| {
+                            LABEL_RULE_KEYS.contains(&key.as_str()) && value.is_str()
+                        })
+                })
+            }),
+            (ValueKind::OperatorGates, toml::Value::Array(items)) => items.iter().all(|item| {
+                item.as_table().is_some_and(|gate| {
+                    gate.get("operator").is_some_and(toml::Value::is_str)
+                        && gate.iter().all(|(key, value)| {
+                            OPERATOR_GATE_KEYS.contains(&key.as_str())
+                                && if OPERATOR_GATE_BOOL_KEYS.contains(&key.as_str()) {
+                                    value.is_bool()
+                                } else {
+                                    value.is_str()
+                                }
+                        })
+                })
+            }),
+            _ => false,
+        }
+    }
+
+    /// Returns the JSON schema of the value.
+    fn json_schema(&self) -> serde_json::Value {
+        match self {
+            ValueKind::Bool => serde_json::json!({ "type": "boolean" }),
+            ValueKind::Integer { min, max } => {
+                serde_json::json!({ "type": "integer", "minimum": min, "maximum": max })
+            },
+            ValueKind::StringList => {
+                serde_json::json!({ "type": "array", "items": { "type": "string" } })
+            },
+            ValueKind::LabelRules => {
+                let properties = LABEL_RULE_KEYS
+                    .iter()
+                    .map(|key| (key.to_string(), serde_json::json!({ "type": "string" })))
+                    .collect::<serde_json::Map<_, _>>();
+                serde_json::json!({
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": properties,
+                        "required": ["label"],
+                        "additionalProperties": false,
+                    }
+                })
+            },
+            ValueKind::OperatorGates => {
+                let properties = OPERATOR_GATE_KEYS
+                    .iter()
+                    .map(|key| {
+                        let kind = if OPERATOR_GATE_BOOL_KEYS.contains(key) {
+                            "boolean"
+                        } else {
+                            "string"
+                        };
+                        (key.to_string(), serde_json::json!({ "type": kind }))
+                    })
+                    .collect::<serde_json::Map<_, _>>();
+                serde_json::json!({
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": properties,
+                        "required": ["operator"],
+                        "additionalProperties": false,
+                    }
+                })
+            },
+        }
+    }
+}
+
+/// A key of the configuration file.
+struct ConfigKey {
+    name: &'static str,
+    kind: ValueKind,
+    description: &'static str,
+}
+
+/// All keys which can be set in the configuration file. They are named after the CLI options.
+const CONFIG_KEYS: &[ConfigKey] = &[
+    ConfigKey {
+        name: "move-sources",
+        kind: ValueKind::StringList,
+        description: "The paths to the Move sources.",
+    },
+    ConfigKey {
+        name: "mutate-modules",
+        kind: ValueKind::StringList,
+        description: "Module names to be mutated.",
+    },
+    ConfigKey {
+        name: "mutate-functions",
+        kind: ValueKind::StringList,
+        description: "Function names to be mutated.",
+    },
+    ConfigKey {
+        name: "verify-mutants",
+        kind: ValueKind::Bool,
+        description: "Verify that the mutants compile.",
+    },
+    ConfigKey {
+        name: "keep-stillborn",
+        kind: ValueKind::Bool,
+        description: "Keep the mutants which fail the verification in the report, with the \
+                      compiler diagnostics.",
+    },
+    ConfigKey {
+        name: "ignore-compile-warnings",
+        kind: ValueKind::Bool,
+        description: "Don't report the compiler warnings of the mutated package.",
+    },
+    ConfigKey {
+        name: "downsampling-ratio-percentage",
+        kind: ValueKind::Integer { min: 0, max: 100 },
+        description: "Remove averagely given percentage of mutants.",
+    },
+    ConfigKey {
+        name: "max-mutants-per-file",
+        kind: ValueKind::Integer {
+            min: 1,
+            max: i64::MAX,
+        },
+        description: "Keep at most the given number of mutants per file, spread across the \
+                      functions and operators.",
+    },
+    ConfigKey {
+        name: "coverage",
+        kind: ValueKind::Bool,
+        description: "Generate mutants only for source code covered by unit tests.",
+    },
+    ConfigKey {
+        name: "extra-operators",
+        kind: ValueKind::StringList,
+        description: "Optional mutation operators to enable.",
+    },
+    ConfigKey {
+        name: "disable-operators",
+        kind: ValueKind::StringList,
+        description: "Mutation operators to disable.",
+    },
+    ConfigKey {
+        name: "allow-empty",
+        kind: ValueKind::Bool,
+        description: "Only warn when the filters match no mutation sites.",
+    },
+    ConfigKey {
+        name: "labels",
+        kind: ValueKind::LabelRules,
+        description: "Rules attaching the label to the mutants matching all given glob patterns \
+                      of the file path (relative to the package), operator and function.",
+    },
+    ConfigKey {
+        name: "operator-gates",
+        kind: ValueKind::OperatorGates,
+        description: "Gates applying the operators matching the `operator` glob pattern only to \
+                      the functions matching the given patterns of the function name, \
+                      visibility, parameter and return types, and the `entry` and `view` flags.",
+    },
+];
+
+/// Pairs of options which can't be set together, no matter if set on the command line or in the
+/// configuration file.
+const CONFLICTING_OPTIONS: &[(&str, &str)] = &[
+    ("move-sources", "verify-mutants"),
+    ("move-sources", "coverage"),
+    ("only-mutants", "downsampling-ratio-percentage"),
+    ("use-generated-mutants", "coverage"),
+];
+
+/// Mutator options loaded from the configuration file (TOML).
+///
+/// Options which are not set in the file are `None`. Options set on the command line take
+/// precedence over the ones from the file. The effective options are printed in the same format,
+/// so the output of `--print-config` can be used as the configuration file.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FileConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub move_sources: Option<Vec<PathBuf>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mutate_modules: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mutate_functions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify_mutants: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_compile_warnings: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_stillborn: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downsampling_ratio_percentage: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_mutants_per_file: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_operators: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_operators: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_empty: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<LabelRule>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator_gates: Option<Vec<OperatorGate>>,
+}
+
+impl FileConfiguration {
+    /// Loads and validates the configuration file.
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read or it's not a valid configuration.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("cannot read configuration file {}", path.display()))?;
+        Self::from_toml(&content)
+            .with_context(|| format!("invalid configuration file {}", path.display()))
+    }
+
+    /// Parses and validates the configuration.
+    ///
+    /// All problems (unknown keys, wrong types and conflicting keys) are reported at once.
+    ///
+    /// # Errors
+    /// Returns an error if the content is not a valid configuration.
+    pub fn from_toml(content: &str) -> anyhow::Result<Self> {
+        let table = content.parse::<toml::Table>()?;
+        let mut errors = vec![];
+
+        for (name, value) in &table {
+            let Some(key) = CONFIG_KEYS.iter().find(|key| key.name == name) else {
+                let keys = CONFIG_KEYS.iter().map(|key| key.name);
+                errors.push(match suggest_similar(name, keys) {
+                    Some(similar) => format!("unknown key `{name}`, did you mean `{similar}`?"),
+                    None => format!("unknown key `{name}`"),
+                });
+                continue;
+            };
+
+            if !key.kind.matches(value) {
+                errors.push(format!(
+                    "key `{name}` must be {}, found {} `{value}`",
+                    key.kind.describe(),
+                    value.type_str()
+                ));
+            }
+        }
+
+        if let Some(toml::Value::Array(operators)) = table.get("extra-operators") {
+            errors.extend(
+                operators
+                    .iter()
+                    .filter_map(toml::Value::as_str)
+                    .filter_map(|op| parse_optional_operator(op).err())
+                    .map(|err| format!("key `extra-operators`: {err}")),
+            );
+        }
+        if let Some(toml::Value::Array(operators)) = table.get("disable-operators") {
+            errors.extend(
+                operators
+                    .iter()
+                    .filter_map(toml::Value::as_str)
+                    .filter_map(|op| parse_operator(op).err())
+                    .map(|err| format!("key `disable-operators`: {err}")),
+            );
+        }
+
+        // Boolean flags set to false are the same as not set.
+        let is_set = |name| {
+            table
+                .get(name)
+                .is_some_and(|value| value.as_bool() != Some(false))
+        };
+        for (a, b) in CONFLICTING_OPTIONS {
+            if is_set(*a) && is_set(*b) {
+                errors.push(format!("keys `{a}` and `{b}` cannot be used together"));
+            }
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!(errors.join("\n"));
+        }
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Applies the options from the file which are not set on the command line.
+    pub fn apply_to(&self, options: &mut CLIOptions) {
+        if options.move_sources.is_empty() {
+            options.move_sources = self.move_sources.clone().unwrap_or_default();
+        }
+        if let (ModuleFilter::All, Some(modules)) = (&options.mutate_modules, &self.mutate_modules)
+        {
+            options.mutate_modules = ModuleFilter::Selected(modules.clone());
+        }
+        if let (FunctionFilter::All, Some(functions)) =
+            (&options.mutate_functions, &self.mutate_functions)
+        {
+            options.mutate_functions = FunctionFilter::Selected(functions.clone());
+        }
+        options.verify_mutants = options.verify_mutants.or(self.verify_mutants);
+        options.ignore_compile_warnings = options
+            .ignore_compile_warnings
+            .or(self.ignore_compile_warnings);
+        options.keep_stillborn = options.keep_stillborn.or(self.keep_stillborn);
+        options.downsampling_ratio_percentage = options
+            .downsampling_ratio_percentage
+            .or(self.downsampling_ratio_percentage);
+        options.max_mutants_per_file = options.max_mutants_per_file.or(self.max_mutants_per_file);
+        options.apply_coverage = options.apply_coverage.or(self.coverage);
+        for op in self.extra_operators.iter().flatten() {
+            if !options.extra_operators.contains(op) {
+                options.extra_operators.push(op.clone());
+            }
+        }
+        for op in self.disable_operators.iter().flatten() {
+            if !options.disable_operators.contains(op) {
+                options.disable_operators.push(op.clone());
+            }
+        }
+        options.allow_empty = options.allow_empty.or(self.allow_empty);
+        options.labels.extend(self.labels.iter().flatten().cloned());
+        options
+            .operator_gates
+            .extend(self.operator_gates.iter().flatten().cloned());
+    }
+
+    /// Returns the JSON schema of the configuration file.
+    #[must_use]
+    pub fn json_schema() -> serde_json::Value {
+        let properties = CONFIG_KEYS
+            .iter()
+            .map(|key| {
+                let mut schema = key.kind.json_schema();
+                schema["description"] = key.description.into();
+                if key.name == "extra-operators" {
+                    schema["items"]["enum"] = OPTIONAL_OPERATORS.into();
+                }
+                if key.name == "disable-operators" {
+                    schema["items"]["enum"] = OPERATORS.into();
+                }
+                (key.name.to_owned(), schema)
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Move mutator configuration",
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cli::ConfigFormat, compiler::CompilerTarget};
+
+    #[test]
+    fn file_configuration_is_parsed_and_applied() {
+        let conf = FileConfiguration::from_toml(
+            r#"
+            mutate-modules = ["Sum"]
+            downsampling-ratio-percentage = 50
+            extra-operators = ["struct_pack_field_swap"]
+            verify-mutants = true
+            "#,
+        )
+        .unwrap();
+
+        let mut options = CLIOptions {
+            mutate_functions: FunctionFilter::Selected(vec!["sum".to_owned()]),
+            downsampling_ratio_percentage: Some(10),
+            ..Default::default()
+        };
+        conf.apply_to(&mut options);
+        assert_eq!(
+            options.mutate_modules,
+            ModuleFilter::Selected(vec!["Sum".to_owned()])
+        );
+        // The command line takes precedence.
+        assert_eq!(
+            options.mutate_functions,
+            FunctionFilter::Selected(vec!["sum".to_owned()])
+        );
+        assert_eq!(options.downsampling_ratio_percentage, Some(10));
+        assert_eq!(options.extra_operators, vec!["struct_pack_field_swap"]);
+        assert_eq!(options.verify_mutants, Some(true));
+
+        // The boolean options set to false on the command line (or with the environment
+        // variables) take precedence too.
+        let mut options = CLIOptions {
+            verify_mutants: Some(false),
+            ..Default::default()
+        };
+        conf.apply_to(&mut options);
+        assert_eq!(options.verify_mutants, Some(false));
+    }
+
+    #[test]
+    fn file_configuration_errors_are_precise() {
+        let err = FileConfiguration::from_toml(
+            r#"
+            mutate-module = ["Sum"]
+            allow-empty = "yes"
+            downsampling-ratio-percentage = 150
+            extra-operators = ["no_such_operator"]
+            move-sources = ["Sum.move"]
+            verify-mutants = true
+            "#,
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("unknown key `mutate-module`, did you mean `mutate-modules`?"));
+        assert!(err.contains("key `allow-empty` must be a boolean, found string"));
+        assert!(err.contains("key `downsampling-ratio-percentage` must be an integer in range"));
+        assert!(err.contains("unknown optional operator 'no_such_operator'"));
+        assert!(err.contains("keys `move-sources` and `verify-mutants` cannot be used together"));
+        assert!(!err.contains("`move-sources` and `coverage`"));
+    }
+
+    #[test]
+    fn label_rules_are_parsed_and_validated() {
+        let conf = FileConfiguration::from_toml(
+            r#"
+            [[labels]]
+            label = "critical-path"
+            path = "sources/vault/*.move"
+
+            [[labels]]
+            label = "arithmetic"
+            operator = "binary_*"
+            "#,
+        )
+        .unwrap();
+        let mut options = CLIOptions::default();
+        conf.apply_to(&mut options);
+        assert_eq!(options.labels.len(), 2);
+        assert_eq!(
+            options.labels[0].path.as_deref(),
+            Some("sources/vault/*.move")
+        );
+        assert!(options
+            .to_config_string(ConfigFormat::Toml)
+            .unwrap()
+            .contains("[[labels]]"));
+
+        let err = FileConfiguration::from_toml("[[labels]]\npath = \"sources/*.move\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("key `labels` must be an array of tables"));
+
+        options.labels[0].path = Some("sources/[a.move".to_owned());
+        let err = Configuration::new(options, None).validate().unwrap_err();
+        assert!(err.to_string().contains("of label `critical-path`"));
+    }
+
+    #[test]
+    fn operator_gates_are_parsed_and_validated() {
+        let conf = FileConfiguration::from_toml(
+            r#"
+            [[operator-gates]]
+            operator = "unary_operator_replacement"
+            returns = "bool"
+
+            [[operator-gates]]
+            operator = "literal_*"
+            entry = true
+            "#,
+        )
+        .unwrap();
+        let mut options = CLIOptions::default();
+        conf.apply_to(&mut options);
+        assert_eq!(options.operator_gates.len(), 2);
+        assert_eq!(options.operator_gates[1].entry, Some(true));
+        assert!(options
+            .to_config_string(ConfigFormat::Toml)
+            .unwrap()
+            .contains("[[operator-gates]]"));
+
+        let err = FileConfiguration::from_toml(
+            "[[operator-gates]]\noperator = \"literal_*\"\nentry = \"yes\"\n",
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("key `operator-gates` must be an array of tables"));
+
+        options.operator_gates[0].returns = Some("[bool".to_owned());
+        let err = Configuration::new(options, None).validate().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("of the gate of operator `unary_operator_replacement`"));
+    }
+
+    #[test]
+    fn conflicts_are_detected_across_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let conf_path = dir.path().join("mutator.toml");
+        fs::write(&conf_path, "move-sources = [\"Sum.move\"]\n").unwrap();
+
+        let options = CLIOptions {
+            mutator_conf: Some(conf_path.clone()),
+            ..Default::default()
+        };
+        assert!(Configuration::load(options, None).is_ok());
+
+        // Neither the file nor the command line conflicts on its own.
+        let options = CLIOptions {
+            mutator_conf: Some(conf_path),
+            apply_coverage: Some(true),
+            downsampling_ratio_percentage: Some(101),
+            ..Default::default()
+        };
+        let err = Configuration::load(options, None).unwrap_err().to_string();
+        assert!(err.contains("options '--move-sources' and '--coverage' cannot be used together"));
+        assert!(err.contains("'--downsampling-ratio-percentage' must be in range 0..=100"));
+
+        // The coverage from the file can't be applied to the previously generated mutants.
+        fs::write(dir.path().join("coverage.toml"), "coverage = true\n").unwrap();
+        let options = CLIOptions {
+            mutator_conf: Some(dir.path().join("coverage.toml")),
+            use_generated_mutants: Some(PathBuf::from("mutants")),
+            ..Default::default()
+        };
+        let err = Configuration::load(options, None).unwrap_err().to_string();
+        assert!(err.contains(
+            "options '--use-generated-mutants' and '--coverage' cannot be used together"
+        ));
+    }
+
+    #[test]
+    fn compiler_matrix_requires_verification() {
+        let target = "2.1@2.0".parse::<CompilerTarget>().unwrap();
+        assert_eq!(target.to_string(), "2.1@2.0");
+        assert!(target.compiler_version.is_some());
+        assert!("2.0"
+            .parse::<CompilerTarget>()
+            .unwrap()
+            .compiler_version
+            .is_none());
+        assert!("9.9".parse::<CompilerTarget>().is_err());
+
+        let mut options = CLIOptions {
+            compiler_matrix: vec![target],
+            ..Default::default()
+        };
+        let err = Configuration::new(options.clone(), None)
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("'--compiler-matrix' requires"));
+        options.verify_mutants = Some(true);
+        assert!(Configuration::new(options, None).validate().is_ok());
+    }
+
+    #[test]
+    fn operators_can_be_disabled() {
+        let mut options = CLIOptions {
+            extra_operators: vec!["struct_pack_field_swap".to_owned()],
+            ..Default::default()
+        };
+        FileConfiguration::from_toml(
+            "disable-operators = [\"assert_error_code_replacement\", \"struct_pack_field_swap\"]\n",
+        )
+        .unwrap()
+        .apply_to(&mut options);
+
+        let conf = Configuration::new(options, None);
+        assert!(conf.is_operator_enabled("assert_condition_replacement"));
+        assert!(!conf.is_operator_enabled("assert_error_code_replacement"));
+        // Disabling takes precedence over enabling an optional operator.
+        assert!(!conf.is_operator_enabled("struct_pack_field_swap"));
+
+        let err = FileConfiguration::from_toml("disable-operators = [\"no_such_operator\"]\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("key `disable-operators`: unknown operator 'no_such_operator'"));
+    }
+
+    #[test]
+    fn effective_configuration_is_printed() {
+        let mut options = CLIOptions::default();
+        FileConfiguration::from_toml("mutate-modules = [\"Sum\"]\ncoverage = true\n")
+            .unwrap()
+            .apply_to(&mut options);
+
+        let toml = options.to_config_string(ConfigFormat::Toml).unwrap();
+        assert!(toml.contains("mutate-modules = [\"Sum\"]"));
+        assert!(!toml.contains("mutate-functions"));
+        assert!(toml.contains("coverage = true"));
+        assert!(!toml.contains("print-config"));
+
+        let json = options.to_config_string(ConfigFormat::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["mutate-modules"], serde_json::json!(["Sum"]));
+        assert!(json.get("out-mutant-dir").is_none());
+    }
+
+    #[test]
+    fn printed_configuration_is_a_valid_configuration_file() {
+        let mut options = CLIOptions {
+            mutate_functions: "sum,sub".parse().unwrap(),
+            out_mutant_dir: Some(PathBuf::from("mutants")),
+            allow_output_in_package: true,
+            no_overwrite: true,
+            verify_mutants: Some(true),
+            keep_stillborn: Some(true),
+            ignore_compile_warnings: Some(true),
+            downsampling_ratio_percentage: Some(30),
+            extra_operators: vec!["struct_pack_field_swap".to_owned()],
+            ..Default::default()
+        };
+        FileConfiguration::from_toml(
+            r#"
+            mutate-modules = ["Sum"]
+            disable-operators = ["assert_error_code_replacement"]
+
+            [[labels]]
+            label = "math"
+            path = "sources/*.move"
+
+            [[operator-gates]]
+            operator = "literal_*"
+            entry = true
+            "#,
+        )
+        .unwrap()
+        .apply_to(&mut options);
+
+        let toml = options.to_config_string(ConfigFormat::Toml).unwrap();
+        let conf = FileConfiguration::from_toml(&toml).unwrap();
+        assert_eq!(conf, FileConfiguration::from(&options));
+        assert_eq!(
+            conf.mutate_functions,
+            Some(vec!["sum".to_owned(), "sub".to_owned()])
+        );
+
+        // Applying the printed file to the defaults gives the same effective options.
+        let mut applied = CLIOptions::default();
+        conf.apply_to(&mut applied);
+        assert_eq!(FileConfiguration::from(&applied), conf);
+
+        let json = options.to_config_string(ConfigFormat::Json).unwrap();
+        let conf_from_json = serde_json::from_str::<FileConfiguration>(&json).unwrap();
+        assert_eq!(conf_from_json, conf);
+    }
+
+    #[test]
+    fn json_schema_lists_all_keys() {
+        let schema = FileConfiguration::json_schema();
+        for key in CONFIG_KEYS {
+            assert!(schema["properties"][key.name].is_object());
+        }
+        assert_eq!(schema["additionalProperties"], false);
+    }
 }
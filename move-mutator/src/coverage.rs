@@ -6,16 +6,21 @@ use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
 use move_coverage::{
     coverage_map::CoverageMap,
     source_coverage::{merge_spans, FunctionSourceCoverage, SourceCoverageBuilder},
+    summary::summarize_inst_cov,
 };
 use move_model::model::Loc;
 use move_package::BuildConfig;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     fs,
+    io::{BufRead, BufReader},
     path::{Path, PathBuf},
 };
 
 const COVERAGE_MAP_NAME: &str = ".coverage_map.mvcov";
+const TRACE_NAME: &str = ".trace";
+const TEST_COVERAGE_NAME: &str = ".test_coverage.json";
 
 /// Contains all uncovered spans in the project.
 #[derive(Debug, Default)]
@@ -34,15 +39,7 @@ impl Coverage {
     ) -> anyhow::Result<()> {
         info!("computing coverage");
 
-        let coverage_file = package_path.join(COVERAGE_MAP_NAME);
-        if !coverage_file.exists() {
-            bail!(
-                "Coverage map not found, please run `aptos move test --coverage` for the package"
-            );
-        }
-
-        let coverage_map = CoverageMap::from_binary_file(coverage_file)
-            .map_err(|e| Error::msg(format!("failed to retrieve the coverage map: {e}")))?;
+        let coverage_map = load_coverage_map(package_path)?;
 
         let mut coverage_config = build_config.clone();
         coverage_config.test_mode = false;
@@ -112,6 +109,11 @@ impl Coverage {
         Ok(())
     }
 
+    /// Checks if the whole function is covered by the unit tests.
+    pub(crate) fn is_fully_covered(&self, associated_fn_name: &str) -> bool {
+        !self.all_uncovered_spans.contains_key(associated_fn_name)
+    }
+
     /// Check if the location is covered by the unit test.
     pub(crate) fn check_location(&self, associated_fn_name: String, loc: &Loc) -> bool {
         let span = loc.span();
@@ -146,6 +148,232 @@ impl Coverage {
     }
 }
 
+/// Computes the coverage summary for all modules in the package - the same one as the
+/// `aptos move coverage summary` command shows.
+///
+/// Returns a map from the module name to the percentage of the covered instructions.
+pub fn module_coverage_summary(
+    build_config: &BuildConfig,
+    package_path: &Path,
+) -> anyhow::Result<BTreeMap<String, f64>> {
+    let coverage_map = load_coverage_map(package_path)?.to_unified_exec_map();
+
+    let mut coverage_config = build_config.clone();
+    coverage_config.test_mode = false;
+    let package = compile_package(coverage_config, package_path)?;
+
+    let mut summary = BTreeMap::new();
+    for unit in package.root_modules() {
+        if let CompiledUnit::Module(NamedCompiledModule { module, .. }) = &unit.unit {
+            let module_summary = summarize_inst_cov(module, &coverage_map);
+            let (total, covered) = module_summary
+                .function_summaries
+                .values()
+                .filter(|f| !f.fn_is_native)
+                .fold((0, 0), |(total, covered), f| {
+                    (total + f.total, covered + f.covered)
+                });
+
+            #[allow(clippy::cast_precision_loss)]
+            let percentage = if total == 0 {
+                100.0
+            } else {
+                covered as f64 / total as f64 * 100.0
+            };
+            summary.insert(module.self_name().to_string(), percentage);
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Returns the path to the coverage map of the package, which is generated by the
+/// `aptos move test --coverage` command.
+#[must_use]
+pub fn coverage_map_path(package_path: &Path) -> PathBuf {
+    package_path.join(COVERAGE_MAP_NAME)
+}
+
+/// Loads the coverage map generated by the `aptos move test --coverage` command.
+fn load_coverage_map(package_path: &Path) -> anyhow::Result<CoverageMap> {
+    let coverage_file = coverage_map_path(package_path);
+    if !coverage_file.exists() {
+        bail!("Coverage map not found, please run `aptos move test --coverage` for the package");
+    }
+
+    CoverageMap::from_binary_file(coverage_file)
+        .map_err(|e| Error::msg(format!("failed to retrieve the coverage map: {e}")))
+}
+
+/// The bytecode offsets executed by each test, per function (e.g. `42::sum::add`, the address
+/// without the `0x` prefix and the leading zeros).
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TestTraces(BTreeMap<String, BTreeMap<String, BTreeSet<u16>>>);
+
+/// Returns the path to the MoveVM trace of the tests run with coverage.
+#[must_use]
+pub fn trace_path(package_path: &Path) -> PathBuf {
+    package_path.join(TRACE_NAME)
+}
+
+/// Splits the MoveVM trace of the tests run with coverage by the tests and saves the instructions
+/// executed by each of them next to the coverage map.
+///
+/// The trace doesn't mark the tests, so a test starts where the trace enters its test function.
+/// The tests must run on a single thread, otherwise their instructions are interleaved.
+///
+/// # Arguments
+///
+/// * `package_path` - The package with the trace.
+/// * `tests` - The names of the tests which ran (e.g. `0x42::sum::test_sum`).
+pub fn record_test_coverage(package_path: &Path, tests: &[String]) -> anyhow::Result<()> {
+    let tests = tests
+        .iter()
+        .map(|test| (canonical_function(test), test))
+        .collect::<BTreeMap<_, _>>();
+
+    let trace = fs::File::open(trace_path(package_path))?;
+    let mut traces = TestTraces::default();
+    let mut current_test = None;
+    let mut previous_function = String::new();
+    for line in BufReader::new(trace).lines() {
+        let line = line?;
+        let mut fields = line.split(',');
+        let (Some(function), Some(pc)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let Ok(pc) = pc.parse::<u16>() else {
+            continue;
+        };
+        // Scripts (`script::main`) are never tested.
+        if function.split("::").count() != 3 {
+            continue;
+        }
+
+        let function = canonical_function(function);
+        if pc == 0 && function != previous_function {
+            if let Some(test) = tests.get(&function) {
+                current_test = Some(*test);
+            }
+        }
+        if let Some(test) = current_test {
+            traces
+                .0
+                .entry(test.clone())
+                .or_default()
+                .entry(function.clone())
+                .or_default()
+                .insert(pc);
+        }
+        previous_function = function;
+    }
+
+    fs::write(
+        package_path.join(TEST_COVERAGE_NAME),
+        serde_json::to_vec(&traces)?,
+    )?;
+    Ok(())
+}
+
+/// Returns the function name with the address in the canonical form - without the `0x` prefix
+/// and the leading zeros, as the trace uses the full addresses.
+fn canonical_function(function: &str) -> String {
+    match function.split_once("::") {
+        Some((address, rest)) => {
+            let address = address.trim_start_matches("0x").trim_start_matches('0');
+            let address = if address.is_empty() { "0" } else { address };
+            format!("{}::{rest}", address.to_lowercase())
+        },
+        None => function.to_owned(),
+    }
+}
+
+/// The source spans executed by each test, used to find the tests covering the mutants.
+#[derive(Debug, Default)]
+pub struct TestCoverage {
+    /// The executed spans per test and qualified function name (e.g. `sum::add`).
+    executed: BTreeMap<String, BTreeMap<String, Vec<Span>>>,
+}
+
+impl TestCoverage {
+    /// Loads the instructions executed by each test (see [`record_test_coverage`]) and maps them
+    /// to the sources of the package.
+    ///
+    /// Returns `None` if the tests haven't been run with coverage.
+    pub fn load(build_config: &BuildConfig, package_path: &Path) -> anyhow::Result<Option<Self>> {
+        let path = package_path.join(TEST_COVERAGE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let traces: TestTraces = serde_json::from_slice(&fs::read(path)?)?;
+
+        let mut coverage_config = build_config.clone();
+        coverage_config.test_mode = false;
+        let package = compile_package(coverage_config, package_path)?;
+
+        // The source spans of the instructions of every function of the package.
+        let mut functions = BTreeMap::new();
+        for unit in package.root_modules() {
+            if let CompiledUnit::Module(NamedCompiledModule {
+                module, source_map, ..
+            }) = &unit.unit
+            {
+                let address = canonical_function(&format!("{}::", module.address()));
+                for (idx, def) in module.function_defs().iter().enumerate() {
+                    let name = module.identifier_at(module.function_handle_at(def.function).name);
+                    let Some(function_map) = u16::try_from(idx)
+                        .ok()
+                        .and_then(|idx| source_map.function_map.get(&idx))
+                    else {
+                        continue;
+                    };
+                    functions.insert(
+                        format!("{address}{}::{name}", module.self_name()),
+                        (format!("{}::{name}", module.self_name()), function_map),
+                    );
+                }
+            }
+        }
+
+        let mut executed = BTreeMap::new();
+        for (test, test_traces) in traces.0 {
+            let mut spans = BTreeMap::<String, Vec<Span>>::new();
+            for (function, offsets) in test_traces {
+                let Some((qname, function_map)) = functions.get(&function) else {
+                    continue;
+                };
+                spans.entry(qname.clone()).or_default().extend(
+                    offsets
+                        .into_iter()
+                        .filter_map(|offset| function_map.get_code_location(offset))
+                        .map(|loc| Span::new(loc.start(), loc.end())),
+                );
+            }
+            executed.insert(test, spans);
+        }
+        Ok(Some(Self { executed }))
+    }
+
+    /// Returns the names of the tests which execute any of the given byte ranges of the function.
+    #[must_use]
+    pub fn covering_tests(&self, qname: &str, ranges: &[(usize, usize)]) -> Vec<String> {
+        self.executed
+            .iter()
+            .filter(|(_, functions)| {
+                functions.get(qname).is_some_and(|spans| {
+                    spans.iter().any(|span| {
+                        ranges.iter().any(|&(start, end)| {
+                            span.start().to_usize() < end.max(start + 1)
+                                && start < span.end().to_usize().max(span.start().to_usize() + 1)
+                        })
+                    })
+                })
+            })
+            .map(|(test, _)| test.clone())
+            .collect()
+    }
+}
+
 #[derive(Debug)]
 struct UncoveredSpans(Vec<Span>);
 
@@ -0,0 +1,241 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    cli::CLIOptions,
+    compiler::generate_ast,
+    configuration::Configuration,
+    mutant::Mutant,
+    mutate::{is_mutated_module, traverse_module},
+};
+use codespan::{ByteIndex, Span};
+use move_model::model::{FunctionEnv, GlobalEnv};
+use move_package::BuildConfig;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// The version of the inventory schema.
+pub const INVENTORY_SCHEMA_VERSION: u32 = 1;
+
+/// The modules, functions and potential mutation sites of the package, used for planning the
+/// mutation testing and by external prioritization tools.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Inventory {
+    /// The version of the inventory schema.
+    pub schema_version: u32,
+    /// The mutated modules of the package.
+    pub modules: Vec<ModuleInventory>,
+}
+
+/// A module of the package.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ModuleInventory {
+    /// The name of the module.
+    pub name: String,
+    /// The source file of the module.
+    pub file: PathBuf,
+    /// The location of the module in the source file.
+    pub span: SourceSpan,
+    /// The functions of the module.
+    pub functions: Vec<FunctionInventory>,
+}
+
+/// A function of the module.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct FunctionInventory {
+    /// The name of the function.
+    pub name: String,
+    /// The location of the function in the source file.
+    pub span: SourceSpan,
+    /// Whether the function has specification conditions.
+    pub has_spec: bool,
+    /// The unit test coverage of the function, if the coverage is applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<FunctionCoverage>,
+    /// The functions called by the function (`module::function`).
+    pub calls: Vec<String>,
+    /// The number of potential mutation sites per mutation operator. A site can produce several
+    /// mutants.
+    pub mutation_sites: BTreeMap<String, usize>,
+}
+
+/// The location in the source file.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct SourceSpan {
+    /// The byte offset of the start.
+    pub start: u32,
+    /// The byte offset of the end.
+    pub end: u32,
+    /// The line of the start (starting from 1).
+    pub start_line: usize,
+    /// The line of the end (starting from 1).
+    pub end_line: usize,
+}
+
+/// The unit test coverage of a function.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FunctionCoverage {
+    /// All the code of the function is covered.
+    Covered,
+    /// Some code of the function isn't covered.
+    Partial,
+    /// None of the mutation sites of the function is covered.
+    Uncovered,
+}
+
+impl SourceSpan {
+    /// Creates the span within the source file.
+    fn new(span: Span, source: &str) -> Self {
+        let line = |offset: ByteIndex| {
+            let offset = offset.to_usize().min(source.len());
+            source.as_bytes()[..offset]
+                .iter()
+                .filter(|&&b| b == b'\n')
+                .count()
+                + 1
+        };
+        Self {
+            start: span.start().0,
+            end: span.end().0,
+            start_line: line(span.start()),
+            end_line: line(span.end()),
+        }
+    }
+}
+
+/// Collects the inventory of the package without generating any mutants.
+///
+/// The mutation sites are found the same way as for the mutant generation, so the module and
+/// function filters and the `mutation::skip` attributes apply. The coverage status is included
+/// if the coverage is applied.
+///
+/// # Errors
+/// Returns an error if the options are invalid or the package can't be compiled.
+pub fn package_inventory(
+    options: CLIOptions,
+    config: &BuildConfig,
+    package_path: &Path,
+) -> anyhow::Result<Inventory> {
+    let package_path = package_path.canonicalize()?;
+    let mut configuration = Configuration::load(options, Some(package_path.clone()))?;
+    let env = generate_ast(&configuration, config, &package_path)?;
+
+    if configuration.project.apply_coverage == Some(true) {
+        let mut config = config.clone();
+        config.skip_fetch_latest_git_deps = true;
+        config.compiler_config.skip_attribute_checks = true;
+        configuration
+            .coverage
+            .compute_coverage(&config, &package_path)?;
+    }
+
+    collect_inventory(&env, &configuration)
+}
+
+/// Collects the inventory of the modules selected by the configuration.
+fn collect_inventory(env: &GlobalEnv, conf: &Configuration) -> anyhow::Result<Inventory> {
+    let mut inventory = Inventory {
+        schema_version: INVENTORY_SCHEMA_VERSION,
+        modules: vec![],
+    };
+    for module in env.get_modules() {
+        if !is_mutated_module(&module, conf)? {
+            continue;
+        }
+        let source = env.get_file_source(module.get_loc().file_id());
+
+        let mut sites = BTreeMap::<String, Vec<Mutant>>::new();
+        for mutant in traverse_module(&module, conf)? {
+            sites
+                .entry(mutant.get_function_name().unwrap_or_default())
+                .or_default()
+                .push(mutant);
+        }
+
+        let functions = module
+            .get_functions()
+            .map(|function| {
+                let mutants = sites.remove(&function.get_name_str()).unwrap_or_default();
+                function_inventory(&function, &mutants, conf, source)
+            })
+            .collect();
+
+        inventory.modules.push(ModuleInventory {
+            name: module.get_name().display(env).to_string(),
+            file: PathBuf::from(module.get_source_path()),
+            span: SourceSpan::new(module.get_loc().span(), source),
+            functions,
+        });
+    }
+    Ok(inventory)
+}
+
+/// Collects the inventory of a single function with its mutation sites.
+fn function_inventory(
+    function: &FunctionEnv<'_>,
+    mutants: &[Mutant],
+    conf: &Configuration,
+    source: &str,
+) -> FunctionInventory {
+    let env = function.module_env.env;
+
+    let mut mutation_sites = BTreeMap::new();
+    for mutant in mutants {
+        *mutation_sites
+            .entry(mutant.get_operator_name())
+            .or_default() += 1;
+    }
+
+    let coverage = (conf.project.apply_coverage == Some(true)).then(|| {
+        if conf
+            .coverage
+            .is_fully_covered(&function.get_full_name_str())
+        {
+            FunctionCoverage::Covered
+        } else if !mutants.is_empty() && !mutants.iter().any(Mutant::is_covered) {
+            FunctionCoverage::Uncovered
+        } else {
+            FunctionCoverage::Partial
+        }
+    });
+
+    let calls = function
+        .get_called_functions()
+        .into_iter()
+        .flatten()
+        .map(|callee| env.get_function(*callee).get_full_name_str())
+        .collect();
+
+    FunctionInventory {
+        name: function.get_name_str(),
+        span: SourceSpan::new(function.get_loc().span(), source),
+        has_spec: !function.get_spec().conditions.is_empty(),
+        coverage,
+        calls,
+        mutation_sites,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_span_has_lines() {
+        let source = "module 0x1::m {\n    fun f() {\n    }\n}\n";
+        let start = source.find("fun").unwrap() as u32;
+        let end = source.rfind("    }").unwrap() as u32 + 5;
+
+        assert_eq!(SourceSpan::new(Span::new(start, end), source), SourceSpan {
+            start,
+            end,
+            start_line: 2,
+            end_line: 3,
+        });
+    }
+}
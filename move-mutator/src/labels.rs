@@ -0,0 +1,161 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context;
+use globset::{GlobBuilder, GlobMatcher};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A rule attaching the label to the mutants matching all the given patterns.
+///
+/// Rules are set in the configuration file, e.g.:
+/// ```toml
+/// [[labels]]
+/// label = "critical-path"
+/// path = "sources/vault/*.move"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LabelRule {
+    /// The label attached to the matching mutants.
+    pub label: String,
+    /// Glob pattern of the mutated file path, relative to the package directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Glob pattern of the mutation operator name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    /// Glob pattern of the function name (`function` or `module::function`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+}
+
+/// Keys of a single label rule.
+pub(crate) const LABEL_RULE_KEYS: &[&str] = &["label", "path", "operator", "function"];
+
+/// The compiled label rule.
+#[derive(Debug)]
+struct Matcher {
+    label: String,
+    path: Option<GlobMatcher>,
+    operator: Option<GlobMatcher>,
+    function: Option<GlobMatcher>,
+}
+
+/// Attaches the labels to the mutants according to the label rules.
+#[derive(Debug, Default)]
+pub(crate) struct Labeler {
+    matchers: Vec<Matcher>,
+}
+
+impl Labeler {
+    /// Compiles the label rules.
+    ///
+    /// # Errors
+    /// Returns an error if any of the patterns is invalid.
+    pub(crate) fn new(rules: &[LabelRule]) -> anyhow::Result<Self> {
+        let matchers = rules
+            .iter()
+            .map(|rule| {
+                let compile = |pattern: &Option<String>, literal_separator| {
+                    pattern
+                        .as_deref()
+                        .map(|pattern| {
+                            GlobBuilder::new(pattern)
+                                .literal_separator(literal_separator)
+                                .build()
+                                .map(|glob| glob.compile_matcher())
+                                .with_context(|| {
+                                    format!("invalid pattern `{pattern}` of label `{}`", rule.label)
+                                })
+                        })
+                        .transpose()
+                };
+                Ok(Matcher {
+                    label: rule.label.clone(),
+                    path: compile(&rule.path, true)?,
+                    operator: compile(&rule.operator, false)?,
+                    function: compile(&rule.function, false)?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { matchers })
+    }
+
+    /// Returns the labels of the mutant, without duplicates and in the order of the rules.
+    pub(crate) fn labels_for(
+        &self,
+        path: &Path,
+        operator: &str,
+        module: &str,
+        function: &str,
+    ) -> Vec<String> {
+        let qname = format!("{module}::{function}");
+        let mut labels = Vec::<String>::new();
+        for matcher in &self.matchers {
+            let matches = matcher.path.as_ref().map_or(true, |m| m.is_match(path))
+                && matcher
+                    .operator
+                    .as_ref()
+                    .map_or(true, |m| m.is_match(operator))
+                && matcher
+                    .function
+                    .as_ref()
+                    .map_or(true, |m| m.is_match(function) || m.is_match(&qname));
+            if matches && !labels.contains(&matcher.label) {
+                labels.push(matcher.label.clone());
+            }
+        }
+        labels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(label: &str, path: Option<&str>, operator: Option<&str>) -> LabelRule {
+        LabelRule {
+            label: label.to_owned(),
+            path: path.map(str::to_owned),
+            operator: operator.map(str::to_owned),
+            function: None,
+        }
+    }
+
+    #[test]
+    fn labels_are_attached_to_matching_mutants() {
+        let mut rules = vec![
+            rule("critical-path", Some("sources/vault/*.move"), None),
+            rule("arithmetic", None, Some("binary_*")),
+            rule("critical-path", None, Some("literal_replacement")),
+        ];
+        rules.push(LabelRule {
+            function: Some("Vault::with*".to_owned()),
+            ..rule("withdrawals", None, None)
+        });
+        let labeler = Labeler::new(&rules).unwrap();
+
+        let vault = Path::new("sources/vault/Vault.move");
+        assert_eq!(
+            labeler.labels_for(vault, "binary_operator_replacement", "Vault", "withdraw"),
+            vec!["critical-path", "arithmetic", "withdrawals"]
+        );
+        assert_eq!(
+            labeler.labels_for(vault, "literal_replacement", "Vault", "deposit"),
+            vec!["critical-path"]
+        );
+        // The path separator is not matched by `*`.
+        let nested = Path::new("sources/vault/nested/Vault.move");
+        assert!(labeler
+            .labels_for(nested, "unary_operator_replacement", "Vault", "deposit")
+            .is_empty());
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        let err = Labeler::new(&[rule("broken", Some("sources/[a.move"), None)]).unwrap_err();
+        assert!(err.to_string().contains("label `broken`"));
+    }
+}
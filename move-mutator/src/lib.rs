@@ -13,26 +13,44 @@ mod mutate;
 
 pub mod configuration;
 pub(crate) mod coverage;
+pub mod inventory;
+pub mod labels;
 mod mutant;
 mod operator;
 mod operators;
 mod output;
 pub mod report;
+mod selection;
+pub mod signature;
+pub mod source_drift;
+pub mod testing;
 
+pub use crate::coverage::{
+    coverage_map_path, module_coverage_summary, record_test_coverage, trace_path, TestCoverage,
+};
 use crate::{
-    compiler::{generate_ast, verify_mutant},
+    compiler::{generate_ast, verify_mutant, version_dependent_targets},
     configuration::Configuration,
-    report::{MutationReport, Report},
+    labels::Labeler,
+    mutant::Mutant,
+    report::{GenerationSummary, MutationReport, Report},
+    selection::Site,
 };
+use anyhow::Context;
 use move_package::BuildConfig;
-use mutator_common::tmp_package_dir::setup_outdir_and_package_path;
-use rand::{seq::SliceRandom, thread_rng};
+use mutator_common::{mutant_ids::load_mutant_ids, tmp_package_dir::setup_outdir_and_package_path};
+use rand::thread_rng;
 use rayon::prelude::*;
 use std::{
+    collections::{BTreeMap, BTreeSet},
     fs,
     path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
+/// The subdirectory of the output directory with the mutants which failed the verification.
+const STILLBORN_DIR: &str = "stillborn";
+
 /// Runs the Move mutator tool.
 /// Entry point for the Move mutator tool both for the CLI and the Rust API.
 ///
@@ -65,17 +83,25 @@ pub fn run_move_mutator(
         "Executed move-mutator with the following options: {options:?} \n config: {config:?} \n package path: {package_path:?}"
     );
 
-    // Setup output dir and clone package path there.
     let original_package_path = package_path.canonicalize()?;
-    let (_, package_path) = if options.move_sources.is_empty() {
+    let mut mutator_configuration =
+        Configuration::load(options, Some(original_package_path.to_owned()))?;
+
+    if let Some(format) = mutator_configuration.project.print_config {
+        println!(
+            "{}",
+            mutator_configuration.project.to_config_string(format)?
+        );
+        return Ok(());
+    }
+
+    // Setup output dir and clone package path there.
+    let (_, package_path) = if mutator_configuration.project.move_sources.is_empty() {
         setup_outdir_and_package_path(&original_package_path)?
     } else {
         (PathBuf::new(), package_path.to_owned())
     };
 
-    let mut mutator_configuration =
-        Configuration::new(options, Some(original_package_path.to_owned()));
-
     trace!("Mutator configuration: {mutator_configuration:?}");
 
     let package_path = mutator_configuration
@@ -83,6 +109,7 @@ pub fn run_move_mutator(
         .clone()
         .unwrap_or(package_path.to_owned());
     let env = generate_ast(&mutator_configuration, config, &package_path)?;
+    let labeler = Labeler::new(&mutator_configuration.project.labels)?;
 
     info!("Generated AST");
 
@@ -91,7 +118,7 @@ pub fn run_move_mutator(
     config.skip_fetch_latest_git_deps = true;
     config.compiler_config.skip_attribute_checks = true;
 
-    if mutator_configuration.project.apply_coverage {
+    if mutator_configuration.project.apply_coverage == Some(true) {
         // This implies additional compilation inside.
         mutator_configuration
             .coverage
@@ -99,37 +126,145 @@ pub fn run_move_mutator(
     }
 
     let mutants = mutate::mutate(&env, &mutator_configuration)?;
+    mutate::check_filters_match(&env, &mutator_configuration, &mutants)?;
+    let unassessed_functions =
+        mutate::unassessed_functions(&env, &mutator_configuration, &mutants)?;
+    let mut summary = GenerationSummary {
+        sites: mutants.len(),
+        unassessed_functions: unassessed_functions.len(),
+        ..Default::default()
+    };
+
+    // The sources and canonical paths are looked up once per file, not once per mutant.
+    let sources = mutants
+        .iter()
+        .map(Mutant::get_file_id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|file_id| {
+            let path = Path::new(env.get_file(file_id)).canonicalize()?;
+            Ok((file_id, (env.get_file_source(file_id), path)))
+        })
+        .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+
+    let (mutants, uncovered): (Vec<_>, Vec<_>) = mutants.into_iter().partition(Mutant::is_covered);
     let output_dir = output::setup_output_dir(&mutator_configuration)?;
 
+    // Mutants in uncovered code are not generated, they are only counted for the statistics.
+    let uncovered_mutants = uncovered
+        .iter()
+        .map(|mutant| mutant.apply(sources[&mutant.get_file_id()].0).len())
+        .sum();
+    summary.uncovered = uncovered_mutants;
+
     // Generate mutants and extract all info needed for rayon threads below.
+    // The mutation sites hold the model expressions, which aren't thread-safe, so the operators
+    // are applied sequentially. The rest of the per-mutant work (hashing the enclosing function
+    // for the stable identifier) doesn't need the model and runs in parallel.
     let mut transformed_mutants: Vec<_> = mutants
-        .into_iter()
+        .iter()
         .flat_map(|mutant| {
             let file_id = mutant.get_file_id();
-            let original_source = env.get_file_source(file_id);
-            let filename = env.get_file(file_id);
-            let path = Path::new(filename)
-                .canonicalize()
-                .expect("canonicalizing failed");
             let fn_name = mutant.get_function_name().unwrap_or_default();
             let mod_name = mutant.get_module_name().unwrap_or("script".to_owned());
+            let function_span = mutant.get_function_span();
 
             mutant
-                .apply(original_source)
+                .apply(sources[&file_id].0)
                 .into_iter()
-                .map(|mutant_info| {
+                .map(move |mutant_info| {
                     (
                         mutant_info,
+                        file_id,
                         fn_name.clone(),
                         mod_name.clone(),
-                        path.clone(),
-                        original_source,
+                        function_span,
                     )
                 })
-                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(mutant_info, file_id, fn_name, mod_name, function_span)| {
+            let (original_source, path) = &sources[&file_id];
+            let qname = format!("{mod_name}::{fn_name}");
+            let mutant_id = mutant_info.stable_id(original_source, &qname, function_span);
+            (
+                mutant_info,
+                mutant_id,
+                fn_name,
+                mod_name,
+                path.clone(),
+                *original_source,
+            )
         })
         .collect();
 
+    // Mutants are ordered by their position in the sources (not by the traversal order of the
+    // AST), so identical sources and configuration always produce the same mutants in the same
+    // order. Sharding, caching and baselines rely on that.
+    transformed_mutants.sort_by(|(a, .., a_path, _), (b, .., b_path, _)| {
+        a_path
+            .cmp(b_path)
+            .then_with(|| a.mutation.get_range().cmp(&b.mutation.get_range()))
+            .then_with(|| {
+                a.mutation
+                    .get_operator_name()
+                    .cmp(b.mutation.get_operator_name())
+            })
+            .then_with(|| a.mutated_source.cmp(&b.mutated_source))
+    });
+
+    // Different operators can produce the same mutated source, which is tested only once, as the
+    // first of them in the order above.
+    let mut generated = BTreeSet::new();
+    transformed_mutants.retain(|(info, .., path, _)| {
+        generated.insert((path.clone(), info.mutated_source.clone()))
+    });
+
+    summary.generated = transformed_mutants.len() + uncovered_mutants;
+
+    // Only the listed mutants are kept, e.g. to re-check the survivors of a previous run.
+    if let Some(path) = &mutator_configuration.project.only_mutants {
+        let listed = load_mutant_ids(path)?;
+        let total_mutants = transformed_mutants.len();
+        let mut found = BTreeSet::new();
+        transformed_mutants.retain(|(_, mutant_id, ..)| {
+            let mutant_id = format!("{mutant_id:x}");
+            let is_listed = listed.contains(&mutant_id);
+            if is_listed {
+                found.insert(mutant_id);
+            }
+            is_listed
+        });
+        let missing = listed.len() - found.len();
+        if missing > 0 {
+            let msg = format!(
+                "{missing} of the mutants listed in {} weren't generated, the mutated code has \
+                 probably changed",
+                path.display()
+            );
+            warn!("{msg}");
+            eprintln!("WARNING: {msg}");
+        }
+        summary.unlisted = Some(total_mutants - transformed_mutants.len());
+    }
+
+    // Large files would take up most of the budget, so only a diverse subset of their mutants is
+    // kept.
+    if let Some(max) = mutator_configuration.project.max_mutants_per_file {
+        let total_mutants = transformed_mutants.len();
+        transformed_mutants = selection::cap_per_file(
+            transformed_mutants,
+            max,
+            |(info, _, function, module, path, _)| Site {
+                file: path.clone(),
+                function: format!("{module}::{function}"),
+                operator: info.mutation.get_operator_name().to_owned(),
+            },
+        );
+        summary.capped = total_mutants - transformed_mutants.len();
+    }
+
     // If the downsample ratio is set, we need to downsample the mutants.
     if let Some(percentage) = mutator_configuration.project.downsampling_ratio_percentage {
         let total_mutants = transformed_mutants.len();
@@ -141,17 +276,29 @@ pub fn run_move_mutator(
             "Invalid downsampling ratio"
         );
 
-        // Delete randomly elements from the vector.
-        let mut rng = thread_rng();
-        transformed_mutants = transformed_mutants
-            .choose_multiple(&mut rng, no_of_mutants_to_keep)
-            .cloned()
-            .collect();
+        // Delete randomly elements from the vector, keeping the mutants tested by the previous
+        // run if requested.
+        let previous_mutants = match &mutator_configuration.project.stable_sampling {
+            Some(path) => load_mutant_ids(path)
+                .context("cannot load the mutants tested by the previous run")?,
+            None => BTreeSet::new(),
+        };
+        transformed_mutants = selection::downsample(
+            transformed_mutants,
+            no_of_mutants_to_keep,
+            &previous_mutants,
+            |(_, mutant_id, ..)| mutant_id.to_string(),
+            &mut thread_rng(),
+        );
+        summary.downsampled = total_mutants - transformed_mutants.len();
     }
 
+    let invalid_mutants = AtomicUsize::new(0);
+    let version_dependent = AtomicUsize::new(0);
+    let compiler_matrix = &mutator_configuration.project.compiler_matrix;
     let mutation_reports: Vec<MutationReport> = transformed_mutants
         .into_par_iter()
-        .map(|(mutated_info, function, module, path, original_source)| {
+        .map(|(mutated_info, mutant_id, function, module, path, original_source)| {
             // An informative description for the mutant.
             let mutant = format!("{module}::{function}: {:?}", mutated_info.mutation);
 
@@ -159,18 +306,50 @@ pub fn run_move_mutator(
             let rayon_tid = rayon::current_thread_index().unwrap_or(0);
             info!("job_{rayon_tid}: Checking mutant {mutant}");
 
-            if mutator_configuration.project.verify_mutants {
+            let mut diagnostics = None;
+            let mut stillborn_under = vec![];
+            if mutator_configuration.project.verify_mutants == Some(true) {
                 let res = verify_mutant(&config, &mutated_info.mutated_source, &path);
 
-                // In case the mutant is not a valid Move file, skip the mutant (do not save it).
+                // Even the mutants rejected under the base versions are verified under all targets
+                // of the matrix.
+                stillborn_under = version_dependent_targets(
+                    &config,
+                    res.is_ok(),
+                    compiler_matrix,
+                    &mutated_info.mutated_source,
+                    &path,
+                );
+                if !stillborn_under.is_empty() {
+                    warn!(
+                        "job_{rayon_tid}: Mutant {mutant} doesn't compile under {}, but compiles under other versions",
+                        stillborn_under.join(", ")
+                    );
+                    version_dependent.fetch_add(1, Ordering::Relaxed);
+                }
+
+                // In case the mutant is not a valid Move file, skip the mutant (do not save it),
+                // unless stillborn mutants are kept for debugging or the mutant depends on the
+                // compiler version.
                 if let Err(e) = res {
                     info!("job_{rayon_tid}: Mutant {mutant} is invalid and will not be generated: {e:?}");
-                    return None;
+                    invalid_mutants.fetch_add(1, Ordering::Relaxed);
+                    if mutator_configuration.project.keep_stillborn != Some(true)
+                        && stillborn_under.is_empty()
+                    {
+                        return None;
+                    }
+                    diagnostics = Some(format!("{e:#}"));
                 }
             }
 
-            let mutant_id = mutated_info.unique_id();
-            let Ok(mutant_path) = output::setup_mutant_path(&output_dir, &path, mutant_id) else {
+            // Stillborn mutants are kept apart, so they are never picked up as valid mutants.
+            let mutant_dir = if diagnostics.is_some() {
+                output_dir.join(STILLBORN_DIR)
+            } else {
+                output_dir.clone()
+            };
+            let Ok(mutant_path) = output::setup_mutant_path(&mutant_dir, &path, mutant_id) else {
                 // If we cannot set up the mutant path, we skip the mutant.
                 trace!("Cannot set up mutant path for {path:?}");
                 return None;
@@ -193,16 +372,41 @@ pub fn run_move_mutator(
                 original_source,
             );
 
+            let relative_path = path.strip_prefix(&package_path).unwrap_or(&path);
+            entry.set_labels(labeler.labels_for(
+                relative_path,
+                mutated_info.mutation.get_operator_name(),
+                &module,
+                &function,
+            ));
             entry.add_modification(mutated_info.mutation);
+            entry.set_diagnostics(diagnostics);
+            entry.set_stillborn_under(stillborn_under);
             Some(entry)
         })
         .flatten()
         .collect();
 
+    summary.stillborn = invalid_mutants.into_inner();
+    if !compiler_matrix.is_empty() {
+        summary.version_dependent = Some(version_dependent.into_inner());
+    }
+    summary.kept = mutation_reports
+        .iter()
+        .filter(|entry| entry.get_diagnostics().is_none())
+        .count();
+    print!("{summary}");
+
     let mut report: Report = Report::new();
     for entry in mutation_reports {
-        report.add_entry(entry);
+        if entry.get_diagnostics().is_some() {
+            report.add_stillborn(entry);
+        } else {
+            report.add_entry(entry);
+        }
     }
+    report.set_discarded_mutants(summary.stillborn, uncovered_mutants);
+    report.set_unassessed_functions(unassessed_functions);
 
     trace!("Saving reports to: {output_dir:?}");
     report.save_to_json_file(output_dir.join(Path::new("report.json")).as_path())?;
@@ -4,33 +4,63 @@
 
 #![forbid(unsafe_code)]
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use move_mutator::{
-    cli::{CLIOptions, PackagePathCheck},
+    cli::{CLIOptions, PackagePathCheck, DEFAULT_OUTPUT_DIR},
     run_move_mutator,
 };
-use move_package::BuildConfig;
+use mutator_common::{
+    color::{set_color_choice, ColorChoice},
+    crash_report::{self, CrashContext},
+    env_vars::with_env_vars,
+    package::PackageArgs,
+    package_defaults::get_matches_with_package_defaults,
+};
 use std::path::PathBuf;
 
+/// Prefix of the environment variables that set the options.
+const ENV_VAR_PREFIX: &str = "MOVE_MUTATOR_";
+
 #[derive(Default, Parser, Debug, Clone)]
 pub struct Opts {
-    /// The path to the target Move package.
-    #[clap(long, value_parser)]
-    pub package_dir: Option<PathBuf>,
+    /// The package and build options.
+    #[clap(flatten)]
+    pub package: PackageArgs,
 
     /// Command line options for mutator.
     #[clap(flatten)]
     pub cli_options: CLIOptions,
 
-    /// The build configuration for the Move package.
-    #[clap(flatten)]
-    pub build_config: BuildConfig,
+    /// When to use colors in the output. The `NO_COLOR` environment variable disables the colors
+    /// in the `auto` mode.
+    #[clap(long, value_enum, default_value_t)]
+    pub color: ColorChoice,
 }
 
 fn main() -> anyhow::Result<()> {
-    let opts = Opts::parse();
+    let command = with_env_vars(Opts::command(), ENV_VAR_PREFIX, None);
+    let matches = get_matches_with_package_defaults(command, None, "move-mutator")?;
+    let opts = Opts::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    set_color_choice(opts.color);
+
+    let package_path = opts.cli_options.resolve(opts.package.package_dir.clone())?;
 
-    let package_path = opts.cli_options.resolve(opts.package_dir)?;
+    crash_report::init_logger();
+    crash_report::install(CrashContext {
+        tool: "move-mutator".to_owned(),
+        version: env!("CARGO_PKG_VERSION").to_owned(),
+        output_dir: opts
+            .cli_options
+            .out_mutant_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_OUTPUT_DIR)),
+        package_dir: Some(package_path.clone()),
+        options: format!("{:#?}", opts.cli_options),
+    });
 
-    run_move_mutator(opts.cli_options, &opts.build_config, &package_path)
+    run_move_mutator(
+        opts.cli_options,
+        &opts.package.build_config(),
+        &package_path,
+    )
 }
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::operator::{MutantInfo, MutationOp, MutationOperator};
-use codespan::FileId;
+use codespan::{FileId, Span};
 use std::fmt;
 
 /// A mutant is a piece of code that has been mutated by the mutation operator.
@@ -13,6 +13,8 @@ pub struct Mutant {
     operator: MutationOp,
     module_name: Option<String>,
     function_name: Option<String>,
+    function_span: Option<Span>,
+    is_covered: bool,
 }
 
 impl Mutant {
@@ -24,6 +26,8 @@ impl Mutant {
             operator,
             module_name: None,
             function_name: None,
+            function_span: None,
+            is_covered: true,
         }
     }
 
@@ -39,6 +43,11 @@ impl Mutant {
         self.operator.apply(source)
     }
 
+    /// Returns the name of the mutation operator.
+    pub fn get_operator_name(&self) -> String {
+        self.operator.name()
+    }
+
     /// Returns the module name that this mutant is in.
     pub fn get_module_name(&self) -> Option<String> {
         self.module_name.clone()
@@ -58,6 +67,26 @@ impl Mutant {
     pub fn set_function_name(&mut self, function_name: String) {
         self.function_name = Some(function_name);
     }
+
+    /// Returns the location of the function that this mutant is in.
+    pub fn get_function_span(&self) -> Option<Span> {
+        self.function_span
+    }
+
+    /// Sets the location of the function that this mutant is in.
+    pub fn set_function_span(&mut self, function_span: Span) {
+        self.function_span = Some(function_span);
+    }
+
+    /// Returns true if the mutated code is covered by unit tests (or the coverage is unknown).
+    pub fn is_covered(&self) -> bool {
+        self.is_covered
+    }
+
+    /// Marks the mutant as not covered by unit tests.
+    pub fn set_uncovered(&mut self) {
+        self.is_covered = false;
+    }
 }
 
 impl fmt::Display for Mutant {
@@ -6,42 +6,134 @@ use crate::{
     cli,
     configuration::Configuration,
     mutant::Mutant,
-    operator::MutationOp,
+    operator::{MutationOp, MutationOperator},
     operators::{
-        binary::Binary, binary_swap::BinarySwap, break_continue::BreakContinue,
-        delete_stmt::DeleteStmt, ifelse::IfElse, literal::Literal, unary::Unary, ExpLoc,
+        abort_code::AbortCode,
+        assert_abort_deletion::{self, is_deletable_abort, AssertAbortDeletion},
+        assert_condition::AssertCondition,
+        assert_error_code::{assert_error_code, AssertErrorCode},
+        binary::Binary,
+        binary_swap::BinarySwap,
+        bitwise::Bitwise,
+        boundary::{boundary_replacement, Boundary},
+        break_continue::BreakContinue,
+        conditional_negation::ConditionalNegation,
+        constant::{self, Constant},
+        delete_stmt::{self, DeleteStmt},
+        destructuring_swap::{self, DestructuringSwap},
+        exists_check::{self, ExistsCheck},
+        ifelse::IfElse,
+        literal::{self, Literal},
+        loop_invariant::{self, LoopInvariant},
+        match_arm::{ArmBody, MatchArm},
+        negation_removal::{self, NegationRemoval},
+        off_by_one::{self, OffByOne},
+        operator_info,
+        shift::Shift,
+        struct_pack_swap::{self, StructPackSwap, SwapItem},
+        unary::Unary,
+        variant_swap::VariantSwap,
+        variant_test::VariantTest,
+        ExpLoc,
     },
+    report::UnassessedFunction,
+    signature::{FunctionSignature, OperatorGates},
 };
 use move_model::{
-    ast::{Attribute, Exp, ExpData, Operation},
-    model::{FunctionEnv, GlobalEnv, ModuleEnv},
-    symbol::SymbolPool,
+    ast::{Attribute, Exp, ExpData, Operation, Pattern},
+    model::{FunId, FunctionEnv, GlobalEnv, ModuleEnv, ModuleId, StructId},
+    symbol::{Symbol, SymbolPool},
+    ty::Type,
 };
 use move_package::source_package::layout::SourcePackageLayout;
-use std::path::Path;
+use std::{collections::BTreeSet, path::Path};
+
+/// Functions with fewer expressions in the body are trivial (e.g. empty functions or plain field
+/// getters), so they are not reported when no operator applies to them.
+const MIN_NON_TRIVIAL_EXPRESSIONS: usize = 3;
 
 /// Traverses the AST, identifies places where mutation operators can be applied
 /// and returns a list of mutants.
+///
+/// The progress is printed for every mutated module, as traversing big packages takes a while.
 pub fn mutate(env: &GlobalEnv, conf: &Configuration) -> anyhow::Result<Vec<Mutant>> {
     trace!("Starting mutation process");
-    let mutants = env
+    let modules = env
         .get_modules()
-        .map(|module| traverse_module_with_check(&module, conf))
-        .collect::<Result<Vec<_>, _>>()?
-        .concat();
+        .filter_map(|module| match is_mutated_module(&module, conf) {
+            Ok(true) => Some(Ok(module)),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut mutants = vec![];
+    for (idx, module) in modules.iter().enumerate() {
+        let module_mutants = traverse_module(module, conf)?;
+        println!(
+            "[{}/{}] Found {} mutation sites in module {}",
+            idx + 1,
+            modules.len(),
+            module_mutants.len(),
+            module.get_name().display(env)
+        );
+        mutants.extend(module_mutants);
+    }
 
     trace!("Found {} possible mutations", mutants.len());
 
     Ok(mutants)
 }
 
-/// Traverses a single module and returns a list of mutants - helper function which filter out modules
-/// that are not included in the configuration.
+/// Checks if the module should be mutated - helper function which filters out modules that are
+/// not included in the configuration.
 #[inline]
-fn traverse_module_with_check(
+pub(crate) fn is_mutated_module(
     module: &ModuleEnv<'_>,
     conf: &Configuration,
-) -> anyhow::Result<Vec<Mutant>> {
+) -> anyhow::Result<bool> {
+    let module_name = module.env.symbol_pool().string(module.get_name().name());
+
+    if !is_project_module(module, conf)? {
+        return Ok(false);
+    }
+
+    // Now we need to check if the module is included in the configuration.
+    if let cli::ModuleFilter::Selected(mods) = &conf.project.mutate_modules {
+        if !mods.contains(&module_name) {
+            trace!("Skipping module {module_name}");
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Checks if the function should be mutated - it isn't a test, it isn't attributed with the
+/// `mutation::skip` attribute and it's selected by the function filter (if any).
+fn is_mutated_function(function: &FunctionEnv<'_>, conf: &Configuration) -> bool {
+    let function_name = function.get_name_str();
+
+    let attrs = function.get_attributes();
+    let sym_pool = function.module_env.symbol_pool();
+    if let Some(attr) = contains_attribute(attrs, sym_pool, &["test", "mutation::skip"]) {
+        trace!("Skipping function {function_name} attributed with the {attr} attribute");
+        return false;
+    }
+
+    // Mutate only the specified functions, if any. Otherwise, mutate all functions.
+    if let cli::FunctionFilter::Selected(funcs) = &conf.project.mutate_functions {
+        if !funcs.is_empty() && !funcs.contains(&function_name) {
+            trace!("Skipping function {function_name}");
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Checks if the module comes from the project sources (and not from the dependencies).
+fn is_project_module(module: &ModuleEnv<'_>, conf: &Configuration) -> anyhow::Result<bool> {
     let module_name = module.env.symbol_pool().string(module.get_name().name());
 
     // We need to check if module comes from our source tree or from the deps, as we don't want to traverse
@@ -56,7 +148,7 @@ fn traverse_module_with_check(
             .contains(&filename_path.to_path_buf())
     {
         trace!("Skipping module {module_name} as it does not come from source project");
-        return Ok(vec![]);
+        return Ok(false);
     }
 
     if conf.project.move_sources.is_empty() {
@@ -69,26 +161,204 @@ fn traverse_module_with_check(
                     test_root.to_string_lossy(),
                     project_path.to_string_lossy()
                 );
-                return Ok(vec![]);
+                return Ok(false);
             }
         }
     }
 
-    // Now we need to check if the module is included in the configuration.
-    if let cli::ModuleFilter::Selected(mods) = &conf.project.mutate_modules {
-        if !mods.contains(&module_name) {
-            trace!("Skipping module {module_name}");
-            return Ok(vec![]);
+    Ok(true)
+}
+
+/// Checks that the module and function filters match at least one mutation site.
+///
+/// Otherwise, the run would silently report a perfect score on zero mutants. The error lists the
+/// known modules and functions, so the user can fix the filters. With the `--allow-empty` option,
+/// only a warning is shown.
+///
+/// Filter values which don't match any module or function are reported with the most similar
+/// known name (e.g. "did you mean `coin_store`?") in any case.
+pub(crate) fn check_filters_match(
+    env: &GlobalEnv,
+    conf: &Configuration,
+    mutants: &[Mutant],
+) -> anyhow::Result<()> {
+    let selected_modules = match &conf.project.mutate_modules {
+        cli::ModuleFilter::Selected(modules) => modules.as_slice(),
+        cli::ModuleFilter::All => &[],
+    };
+    let selected_functions = match &conf.project.mutate_functions {
+        cli::FunctionFilter::Selected(functions) => functions.as_slice(),
+        cli::FunctionFilter::All => &[],
+    };
+    if selected_modules.is_empty() && selected_functions.is_empty() {
+        return Ok(());
+    }
+
+    let mut known_modules = vec![];
+    let mut known_functions = vec![];
+    for module in env.get_modules() {
+        if !is_project_module(&module, conf)? {
+            continue;
+        }
+
+        let module_name = module.get_name().display(module.env).to_string();
+        for function in module.get_functions() {
+            let attrs = function.get_attributes();
+            if contains_attribute(attrs, module.symbol_pool(), &["test"]).is_none() {
+                known_functions.push((module_name.clone(), function.get_name_str()));
+            }
+        }
+        known_modules.push(module_name);
+    }
+
+    let mut unmatched = vec![];
+    for module in selected_modules {
+        if !known_modules.contains(module) {
+            let candidates = known_modules.iter().map(String::as_str);
+            unmatched.push(unmatched_filter_msg("module", module, candidates));
+        }
+    }
+    for function in selected_functions {
+        if !known_functions.iter().any(|(_, name)| name == function) {
+            let candidates = known_functions.iter().map(|(_, name)| name.as_str());
+            unmatched.push(unmatched_filter_msg("function", function, candidates));
+        }
+    }
+
+    if !mutants.is_empty() {
+        for msg in unmatched {
+            eprintln!("WARNING: {msg}");
+        }
+        return Ok(());
+    }
+
+    let known_functions = known_functions
+        .iter()
+        .map(|(module, function)| format!("{module}::{function}"))
+        .collect::<Vec<_>>();
+    let mut msg = "the module and function filters match no mutation sites\n".to_owned();
+    for unmatched_msg in unmatched {
+        msg.push_str(&unmatched_msg);
+        msg.push('\n');
+    }
+    msg.push_str(&format!(
+        "known modules: {}\nknown functions: {}",
+        known_modules.join(", "),
+        known_functions.join(", ")
+    ));
+
+    if conf.project.allow_empty == Some(true) {
+        eprintln!("WARNING: {msg}");
+        Ok(())
+    } else {
+        anyhow::bail!("{msg}")
+    }
+}
+
+/// Finds the non-trivial functions without any mutation site.
+///
+/// No operator applies to such functions, so they would silently contribute nothing to the
+/// mutation score. They are reported as unassessed instead, so users know the score doesn't cover
+/// them. The same modules and functions as for the mutant generation are checked.
+pub(crate) fn unassessed_functions(
+    env: &GlobalEnv,
+    conf: &Configuration,
+    mutants: &[Mutant],
+) -> anyhow::Result<Vec<UnassessedFunction>> {
+    let assessed = mutants
+        .iter()
+        .map(|m| {
+            (
+                m.get_module_name().unwrap_or_default(),
+                m.get_function_name().unwrap_or_default(),
+            )
+        })
+        .collect::<BTreeSet<_>>();
+
+    let mut unassessed = vec![];
+    for module in env.get_modules() {
+        let attrs = module.get_attributes();
+        if !is_mutated_module(&module, conf)?
+            || contains_attribute(attrs, module.symbol_pool(), &["mutation::skip"]).is_some()
+        {
+            continue;
+        }
+
+        let module_name = module.get_name().display(env).to_string();
+        let original_file = Path::new(module.get_source_path()).canonicalize()?;
+        for function in module.get_functions() {
+            let function_name = function.get_name_str();
+            if !is_mutated_function(&function, conf)
+                || assessed.contains(&(module_name.clone(), function_name.clone()))
+            {
+                continue;
+            }
+            // Native functions have no body to mutate.
+            let Some(body) = function.get_def() else {
+                continue;
+            };
+
+            let mut expressions = 0;
+            body.visit_pre_post(&mut |asc, _| {
+                if !asc {
+                    expressions += 1;
+                }
+                true
+            });
+            if expressions >= MIN_NON_TRIVIAL_EXPRESSIONS {
+                unassessed.push(UnassessedFunction {
+                    original_file: original_file.clone(),
+                    module_name: module_name.clone(),
+                    function_name,
+                });
+            }
         }
     }
 
-    traverse_module(module, conf)
+    Ok(unassessed)
+}
+
+/// Creates a message about the filter value which doesn't match any known name, suggesting the
+/// most similar known name, if there is any.
+fn unmatched_filter_msg<'a>(
+    kind: &str,
+    value: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> String {
+    match suggest_similar(value, candidates) {
+        Some(similar) => format!("no {kind} named `{value}` found, did you mean `{similar}`?"),
+        None => format!("no {kind} named `{value}` found"),
+    }
+}
+
+/// Finds the known name most similar to the given (probably misspelled) one.
+pub(crate) fn suggest_similar<'a>(
+    name: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> Option<&'a str> {
+    // The same threshold is used by clap for suggesting misspelled arguments.
+    const SIMILARITY_THRESHOLD: f64 = 0.7;
+
+    let name = name.to_lowercase();
+    candidates
+        .map(|candidate| {
+            (
+                strsim::jaro_winkler(&name, &candidate.to_lowercase()),
+                candidate,
+            )
+        })
+        .filter(|(similarity, _)| *similarity > SIMILARITY_THRESHOLD)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate)
 }
 
 /// Traverses a single module and returns a list of mutants.
 /// Checks all the functions and constants defined in the module.
 #[allow(clippy::unnecessary_to_owned)]
-fn traverse_module(module: &ModuleEnv<'_>, conf: &Configuration) -> anyhow::Result<Vec<Mutant>> {
+pub(crate) fn traverse_module(
+    module: &ModuleEnv<'_>,
+    conf: &Configuration,
+) -> anyhow::Result<Vec<Mutant>> {
     let module_name = module.get_name().display(module.env);
 
     let attrs = module.get_attributes();
@@ -98,10 +368,13 @@ fn traverse_module(module: &ModuleEnv<'_>, conf: &Configuration) -> anyhow::Resu
         return Ok(vec![]);
     }
 
+    let gates = OperatorGates::new(&conf.project.operator_gates)?;
+    let abort_codes = module_abort_codes(module);
+
     trace!("Traversing module {}", &module_name);
     let mut mutants = module
         .get_functions()
-        .map(|func| traverse_function(&func, conf))
+        .map(|func| traverse_function(&func, conf, &gates, &abort_codes))
         .collect::<Result<Vec<_>, _>>()?
         .concat();
 
@@ -117,43 +390,91 @@ fn traverse_module(module: &ModuleEnv<'_>, conf: &Configuration) -> anyhow::Resu
     Ok(mutants)
 }
 
+/// Returns the error codes of the aborts and the asserts in the non-test functions of the module,
+/// in the source order. The default code of `assert!(cond)` has no place in the source, so it's
+/// left out.
+fn module_abort_codes(module: &ModuleEnv<'_>) -> Vec<ExpLoc> {
+    let env = module.env;
+    let mut codes = vec![];
+    for function in module.get_functions() {
+        let attrs = function.get_attributes();
+        if contains_attribute(attrs, module.symbol_pool(), &["test", "test_only"]).is_some() {
+            continue;
+        }
+        let Some(exp) = function.get_def() else {
+            continue;
+        };
+        let mut default_codes = BTreeSet::new();
+        exp.visit_pre_post(&mut |asc, exp_data| {
+            if asc {
+                return true;
+            }
+            match exp_data {
+                ExpData::IfElse(_, cond, if_exp, else_exp) => {
+                    if let Some(code) = assert_error_code(if_exp, else_exp) {
+                        let cond_loc = env.get_node_loc(condition_without_specs(cond).node_id());
+                        if env.get_node_loc(code.node_id()).span().start() < cond_loc.span().end() {
+                            default_codes.insert(code.node_id());
+                        }
+                    }
+                },
+                ExpData::Call(_, Operation::Abort, args) => {
+                    if let Some(code) = args
+                        .first()
+                        .filter(|code| !default_codes.contains(&code.node_id()))
+                    {
+                        codes.push(ExpLoc {
+                            exp: code.clone(),
+                            loc: env.get_node_loc(code.node_id()),
+                        });
+                    }
+                },
+                _ => {},
+            }
+            true
+        });
+    }
+    codes.sort_by_key(|code| code.loc.span().start());
+    codes
+}
+
 /// Traverses a single function and returns a list of mutants.
 /// Checks the body of the function by traversing its definition.
 fn traverse_function(
     function: &FunctionEnv<'_>,
     conf: &Configuration,
+    gates: &OperatorGates,
+    abort_codes: &[ExpLoc],
 ) -> anyhow::Result<Vec<Mutant>> {
     let function_name = &function.get_name_str();
-
-    let attrs = function.get_attributes();
-    let sym_pool = function.module_env.symbol_pool();
-    if let Some(attr) = contains_attribute(attrs, sym_pool, &["test", "mutation::skip"]) {
-        trace!("Skipping function {function_name} attributed with the {attr} attribute");
-        return Ok(vec![]);
-    }
-
-    let mut included_funcs = vec![];
-
-    // Check if any function is included in the general project configuration.
-    if let cli::FunctionFilter::Selected(funcs) = &conf.project.mutate_functions {
-        included_funcs = included_funcs.into_iter().chain(funcs.iter()).collect();
-    }
-
-    // Mutate only the specified functions, if any. Otherwise, mutate all functions.
-    if !included_funcs.is_empty() && !included_funcs.contains(&function_name) {
-        trace!("Skipping function {function_name}");
+    if !is_mutated_function(function, conf) {
         return Ok(vec![]);
     }
 
     trace!("Traversing function {function_name}");
     let mut result = Vec::<Mutant>::new();
     let mut is_inside_spec = false;
+    // The error codes of the asserts, which only the assert error code operator mutates.
+    let mut assert_codes = BTreeSet::new();
+    // The operands of `&&` and `||`, whose negations are removed by the negation removal operator.
+    let mut sub_conditions = BTreeSet::new();
+    // The operands of the comparisons and the vector indices, whose literals are moved by one by
+    // the off-by-one operator.
+    let mut fence_posts = BTreeSet::new();
     if let Some(exp) = function.get_def() {
         exp.visit_pre_post(&mut |asc, exp_data| {
             // Collect the spec blocks locations.
-            if let ExpData::SpecBlock(_, _) = exp_data {
+            if let ExpData::SpecBlock(node_id, _) = exp_data {
                 // Mark that we are inside of the spec block when going desc - and remove that when going asc.
                 is_inside_spec = !asc;
+
+                // The loop invariants are the only part of the spec blocks being mutated.
+                if !asc && conf.is_operator_enabled(loop_invariant::OPERATOR_NAME) {
+                    let loc = function.module_env.env.get_node_loc(*node_id);
+                    result.push(Mutant::new(MutationOp::new(Box::new(LoopInvariant::new(
+                        loc,
+                    )))));
+                }
             }
 
             // Parse only during the descend phase and when we are not inside the spec block.
@@ -164,27 +485,116 @@ fn traverse_function(
             let fn_loc = function.module_env.env.get_node_loc(exp_data.node_id());
             let fn_name = function.get_full_name_str();
             trace!("checking coverage {fn_loc:?} for {fn_name}");
+            match exp_data {
+                ExpData::Call(
+                    _,
+                    Operation::Eq
+                    | Operation::Neq
+                    | Operation::Lt
+                    | Operation::Le
+                    | Operation::Gt
+                    | Operation::Ge,
+                    args,
+                ) => fence_posts.extend(args.iter().map(|arg| arg.node_id())),
+                ExpData::Call(_, Operation::MoveFunction(mid, fid), args) => fence_posts.extend(
+                    vector_indices(function.module_env.env, *mid, *fid, args)
+                        .iter()
+                        .map(|arg| arg.node_id()),
+                ),
+                _ => {},
+            }
+            let is_fence_post = fence_posts.contains(&exp_data.node_id());
+            let mut mutants =
+                parse_expression_and_find_mutants(function, exp_data, is_fence_post, conf);
+            // The error codes are replaced with the other codes of the module.
+            if let ExpData::Call(_, Operation::Abort, args) = exp_data {
+                let code = args.first().and_then(|code| {
+                    abort_codes
+                        .iter()
+                        .find(|module_code| module_code.exp.node_id() == code.node_id())
+                });
+                if let Some(code) = code {
+                    mutants.push(Mutant::new(MutationOp::new(Box::new(AbortCode::new(
+                        code.clone(),
+                        abort_codes.to_vec(),
+                    )))));
+                }
+            }
+            if let ExpData::IfElse(_, _, if_exp, else_exp) = exp_data {
+                if let Some(code) = assert_error_code(if_exp, else_exp) {
+                    assert_codes.insert(code.node_id());
+                }
+            }
+            // The asserts are deleted as a whole, not just their aborts.
+            if let ExpData::Call(_, Operation::Abort, args) = exp_data {
+                if args
+                    .first()
+                    .is_some_and(|code| assert_codes.contains(&code.node_id()))
+                {
+                    mutants
+                        .retain(|m| m.get_operator_name() != assert_abort_deletion::OPERATOR_NAME);
+                }
+            }
+            if assert_codes.contains(&exp_data.node_id()) {
+                mutants.retain(|m| {
+                    !matches!(
+                        m.get_operator_name().as_str(),
+                        literal::OPERATOR_NAME | constant::OPERATOR_NAME
+                    )
+                });
+            }
+            if let ExpData::Call(_, Operation::And | Operation::Or, args) = exp_data {
+                sub_conditions.extend(args.iter().map(|arg| arg.node_id()));
+            }
+            if !sub_conditions.contains(&exp_data.node_id()) {
+                mutants.retain(|m| m.get_operator_name() != negation_removal::OPERATOR_NAME);
+            }
+            // Uncovered mutants are kept only to count them in the mutation score statistics.
             if !conf.coverage.check_location(fn_name, &fn_loc) {
-                return true;
+                mutants.iter_mut().for_each(Mutant::set_uncovered);
             }
 
-            result.extend(parse_expression_and_find_mutants(function, exp_data));
+            result.extend(mutants);
             true
         });
     };
 
-    result
-        .iter_mut()
-        .for_each(|m| m.set_function_name(function_name.clone()));
+    // Operators apply only to the functions allowed by both the operator and the operator gates,
+    // unless they are disabled.
+    let signature = FunctionSignature::of(function);
+    let module_env = &function.module_env;
+    let module_name = module_env.get_name().display(module_env.env).to_string();
+    result.retain(|m| {
+        let operator = m.get_operator_name();
+        let applicable = conf.is_operator_enabled(&operator)
+            && operator_info(&operator).map_or(true, |info| info.applies_to.matches(&signature))
+            && gates.allows(&operator, &signature, &module_name, function_name);
+        if !applicable {
+            trace!("Operator {operator} doesn't apply to function {function_name}");
+        }
+        applicable
+    });
+
+    let function_span = function.get_loc().span();
+    result.iter_mut().for_each(|m| {
+        m.set_function_name(function_name.clone());
+        m.set_function_span(function_span);
+    });
 
     Ok(result)
 }
 
 /// This function does the actual parsing of the expression and checks if any of the mutation operators
-/// can be applied to it.
+/// can be applied to it. The fence posts are the operands of the comparisons and the vector indices.
 /// When Move language is extended with new expressions, this function needs to be updated to support them.
 #[allow(clippy::too_many_lines)]
-fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData) -> Vec<Mutant> {
+fn parse_expression_and_find_mutants(
+    function: &FunctionEnv<'_>,
+    exp: &ExpData,
+    is_fence_post: bool,
+    conf: &Configuration,
+) -> Vec<Mutant> {
+    let env = function.module_env.env;
     let convert_exps_to_explocs = |exps: &[Exp]| -> Vec<ExpLoc> {
         exps.iter()
             .map(|e| ExpLoc {
@@ -198,10 +608,22 @@ fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData)
     match exp {
         ExpData::Call(node_id, op, exps) => match op {
             Operation::MoveTo | Operation::Abort => {
-                vec![Mutant::new(MutationOp::new(Box::new(DeleteStmt::new(
+                let mut mutants = vec![Mutant::new(MutationOp::new(Box::new(DeleteStmt::new(
                     exp.clone().into_exp(),
                     function.module_env.env.get_node_loc(*node_id),
-                ))))]
+                ))))];
+                // The aborts are deleted by the assert and abort deletion only if the statement
+                // deletion doesn't do that already. The aborts of the asserts are dropped in
+                // `traverse_function`, as the asserts are deleted as a whole.
+                if matches!(op, Operation::Abort)
+                    && !conf.is_operator_enabled(delete_stmt::OPERATOR_NAME)
+                    && is_deletable_abort(&env.get_node_type(*node_id))
+                {
+                    mutants.push(Mutant::new(MutationOp::new(Box::new(
+                        AssertAbortDeletion::new(env.get_node_loc(*node_id)),
+                    ))));
+                }
+                mutants
             },
             Operation::Add
             | Operation::Sub
@@ -222,11 +644,24 @@ fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData)
             | Operation::Shr
             | Operation::Xor => {
                 let exps_loc = convert_exps_to_explocs(exps);
-                let mut result = vec![Mutant::new(MutationOp::new(Box::new(Binary::new(
-                    op.clone(),
-                    function.module_env.env.get_node_loc(*node_id),
-                    exps_loc.clone(),
-                ))))];
+                let loc = function.module_env.env.get_node_loc(*node_id);
+                let replacement: Box<dyn MutationOperator> = match op {
+                    Operation::BitAnd | Operation::BitOr | Operation::Xor => {
+                        Box::new(Bitwise::new(op.clone(), loc, exps_loc.clone()))
+                    },
+                    Operation::Shl | Operation::Shr => {
+                        Box::new(Shift::new(op.clone(), loc, exps_loc.clone()))
+                    },
+                    _ => Box::new(Binary::new(op.clone(), loc, exps_loc.clone())),
+                };
+                let mut result = vec![Mutant::new(MutationOp::new(replacement))];
+                if boundary_replacement(op).is_some() {
+                    result.push(Mutant::new(MutationOp::new(Box::new(Boundary::new(
+                        op.clone(),
+                        function.module_env.env.get_node_loc(*node_id),
+                        exps_loc.clone(),
+                    )))));
+                }
 
                 result.push(Mutant::new(MutationOp::new(Box::new(BinarySwap::new(
                     op.clone(),
@@ -238,18 +673,58 @@ fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData)
             },
             Operation::Not => {
                 let exps_loc = convert_exps_to_explocs(exps);
-                vec![Mutant::new(MutationOp::new(Box::new(Unary::new(
-                    op.clone(),
-                    function.module_env.env.get_node_loc(*node_id),
-                    exps_loc,
-                ))))]
+                let loc = function.module_env.env.get_node_loc(*node_id);
+                vec![
+                    Mutant::new(MutationOp::new(Box::new(Unary::new(
+                        op.clone(),
+                        loc.clone(),
+                        exps_loc,
+                    )))),
+                    // Kept only for the sub-conditions, see `traverse_function`.
+                    Mutant::new(MutationOp::new(Box::new(NegationRemoval::new(loc)))),
+                ]
+            },
+            Operation::Pack(mid, sid, variant) => {
+                let mut mutants = vec![];
+                if let Some(variant) = variant {
+                    let alternatives = same_payload_variants(env, *mid, *sid, *variant);
+                    if !alternatives.is_empty() {
+                        mutants.push(Mutant::new(MutationOp::new(Box::new(VariantSwap::new(
+                            env.get_node_loc(*node_id),
+                            variant.display(env.symbol_pool()).to_string(),
+                            alternatives,
+                        )))));
+                    }
+                }
+
+                if conf.is_operator_enabled(struct_pack_swap::OPERATOR_NAME) {
+                    let fields = exps
+                        .iter()
+                        .map(|e| SwapItem {
+                            loc: env.get_node_loc(e.node_id()),
+                            ty: env.get_node_type(e.node_id()),
+                        })
+                        .collect();
+                    mutants.push(Mutant::new(MutationOp::new(Box::new(StructPackSwap::new(
+                        env.get_node_loc(*node_id),
+                        fields,
+                    )))));
+                }
+                mutants
             },
+            // Variant tests (`x is V`) are boolean expressions which no other operator mutates.
+            Operation::TestVariants(..) => vec![Mutant::new(MutationOp::new(Box::new(
+                VariantTest::new(env.get_node_loc(*node_id)),
+            )))],
             _ => vec![],
         },
-        ExpData::IfElse(_, cond, if_exp, else_exp) => {
+        ExpData::IfElse(node_id, cond, if_exp, else_exp) => {
+            // The spec blocks with the loop invariants stay in place, so the mutants don't move the
+            // invariants out of the loop header.
+            let cond_exp = condition_without_specs(cond);
             let cond_loc = ExpLoc {
-                exp: cond.clone(),
-                loc: function.module_env.env.get_node_loc(cond.node_id()),
+                exp: cond_exp.clone(),
+                loc: function.module_env.env.get_node_loc(cond_exp.node_id()),
             };
             let if_exp_loc = ExpLoc {
                 exp: if_exp.clone(),
@@ -259,24 +734,140 @@ fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData)
                 exp: else_exp.clone(),
                 loc: function.module_env.env.get_node_loc(else_exp.node_id()),
             };
-            vec![Mutant::new(MutationOp::new(Box::new(IfElse::new(
-                cond_loc,
-                if_exp_loc,
-                else_exp_loc,
-            ))))]
+            let is_exists_check = ExistsCheck::is_exists_check(cond, if_exp, else_exp);
+            // The conditions and the error codes of the asserts are mutated by their own
+            // operators, so they can be toggled and reported separately.
+            let mut mutants = if let Some(code) = assert_error_code(if_exp, else_exp) {
+                let code_loc = ExpLoc {
+                    exp: code.clone(),
+                    loc: function.module_env.env.get_node_loc(code.node_id()),
+                };
+                // The default error code of `assert!(cond)` has no place in the source.
+                let has_code = code_loc.loc.span().start() >= cond_loc.loc.span().end();
+                // Deleting the assert (or removing the existence check) is the same as replacing
+                // the condition with `true`.
+                let mut condition = AssertCondition::new(cond_loc);
+                if conf.is_operator_enabled(assert_abort_deletion::OPERATOR_NAME)
+                    || (is_exists_check && conf.is_operator_enabled(exists_check::OPERATOR_NAME))
+                {
+                    condition = condition.without_true();
+                }
+                let mut mutants = vec![Mutant::new(MutationOp::new(Box::new(condition)))];
+                if has_code {
+                    mutants.push(Mutant::new(MutationOp::new(Box::new(
+                        AssertErrorCode::new(code_loc),
+                    ))));
+                }
+                mutants
+            } else {
+                vec![
+                    Mutant::new(MutationOp::new(Box::new(IfElse::new(
+                        cond_loc.clone(),
+                        if_exp_loc,
+                        else_exp_loc,
+                    )))),
+                    Mutant::new(MutationOp::new(Box::new(ConditionalNegation::new(
+                        cond_loc,
+                    )))),
+                ]
+            };
+
+            // Existence checks like `assert!(exists<T>(addr), E)` can be removed as a whole.
+            if is_exists_check {
+                mutants.push(Mutant::new(MutationOp::new(Box::new(ExistsCheck::new(
+                    cond.clone(),
+                    function.module_env.env.get_node_loc(*node_id),
+                )))));
+            }
+            // The other asserts are deleted as a whole by their own operator.
+            if assert_error_code(if_exp, else_exp).is_some()
+                && !(is_exists_check && conf.is_operator_enabled(exists_check::OPERATOR_NAME))
+            {
+                mutants.push(Mutant::new(MutationOp::new(Box::new(
+                    AssertAbortDeletion::new(function.module_env.env.get_node_loc(*node_id)),
+                ))));
+            }
+
+            mutants
         },
         ExpData::Value(node_id, value) => {
-            let mutants = vec![Mutant::new(MutationOp::new(Box::new(Literal::new(
-                value.clone(),
-                function.module_env.env.get_node_type(*node_id),
-                function.module_env.env.get_node_loc(*node_id),
-            ))))];
+            let ty = function.module_env.env.get_node_type(*node_id);
+            let loc = function.module_env.env.get_node_loc(*node_id);
+            let mut literal = Literal::new(value.clone(), ty.clone(), loc.clone());
+            let mut mutants = vec![];
+            if conf.is_operator_enabled(constant::OPERATOR_NAME) {
+                literal = literal.without_extremes();
+                mutants.push(Mutant::new(MutationOp::new(Box::new(Constant::new(
+                    value.clone(),
+                    ty.clone(),
+                    loc.clone(),
+                )))));
+            }
+            if is_fence_post && conf.is_operator_enabled(off_by_one::OPERATOR_NAME) {
+                literal = literal.without_neighbours();
+                mutants.push(Mutant::new(MutationOp::new(Box::new(OffByOne::new(
+                    value.clone(),
+                    ty,
+                    loc,
+                )))));
+            }
+            mutants.push(Mutant::new(MutationOp::new(Box::new(literal))));
             mutants
         },
+        ExpData::Match(node_id, _, arms) => {
+            let arms = arms
+                .iter()
+                .map(|arm| {
+                    let bindings = arm
+                        .pattern
+                        .vars()
+                        .into_iter()
+                        .map(|(_, sym)| sym)
+                        .collect::<BTreeSet<_>>();
+                    let ty = env.get_node_type(arm.body.node_id());
+                    ArmBody {
+                        is_unit: matches!(&ty, Type::Tuple(tys) if tys.is_empty()),
+                        uses_bindings: !arm.body.free_vars().is_disjoint(&bindings),
+                        item: SwapItem {
+                            loc: env.get_node_loc(arm.body.node_id()),
+                            ty,
+                        },
+                    }
+                })
+                .collect();
+            vec![Mutant::new(MutationOp::new(Box::new(MatchArm::new(
+                env.get_node_loc(*node_id),
+                arms,
+            ))))]
+        },
         ExpData::LoopCont(node_id, ..) => vec![Mutant::new(MutationOp::new(Box::new(
             BreakContinue::new(function.module_env.env.get_node_loc(*node_id)),
         )))],
 
+        ExpData::Block(_, pattern, Some(_), _) | ExpData::Assign(_, pattern, _)
+            if conf.is_operator_enabled(destructuring_swap::OPERATOR_NAME) =>
+        {
+            let bindings = match pattern {
+                Pattern::Tuple(_, pats) | Pattern::Struct(_, _, _, pats) => pats
+                    .iter()
+                    .filter(|pat| matches!(pat, Pattern::Var(..)))
+                    .map(|pat| SwapItem {
+                        loc: env.get_node_loc(pat.node_id()),
+                        ty: env.get_node_type(pat.node_id()),
+                    })
+                    .collect::<Vec<_>>(),
+                _ => vec![],
+            };
+            if bindings.len() < 2 {
+                return vec![];
+            }
+
+            vec![Mutant::new(MutationOp::new(Box::new(
+                DestructuringSwap::new(env.get_node_loc(pattern.node_id()), bindings),
+            )))]
+        },
+        // Bodies of lambdas and receiver-style calls (which are plain function calls in the model)
+        // are visited by the traversal, so there is nothing to mutate on their own.
         ExpData::Return(..)
         | ExpData::Mutate(..)
         | ExpData::Assign(..)
@@ -289,13 +880,75 @@ fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData)
         | ExpData::SpecBlock(..)
         | ExpData::Sequence(..)
         | ExpData::Quant(..)
-        | ExpData::Match(..)
         | ExpData::Invalid(..) => vec![],
     }
 }
 
+/// Returns the condition without the leading spec blocks.
+///
+/// The loop invariants are written in the condition of the `while` loop
+/// (`while ({ spec { invariant i <= n; }; i < n })`), so only the last expression of such a
+/// sequence is the actual condition.
+fn condition_without_specs(cond: &Exp) -> &Exp {
+    match cond.as_ref() {
+        ExpData::Sequence(_, exps) => match exps.split_last() {
+            Some((last, init))
+                if !init.is_empty()
+                    && init
+                        .iter()
+                        .all(|e| matches!(e.as_ref(), ExpData::SpecBlock(..))) =>
+            {
+                last
+            },
+            _ => cond,
+        },
+        _ => cond,
+    }
+}
+
+/// Returns the index arguments of the calls to the `vector` functions taking indices. The index
+/// notation (`v[i]`) is translated to such calls too.
+fn vector_indices<'a>(env: &GlobalEnv, mid: ModuleId, fid: FunId, args: &'a [Exp]) -> &'a [Exp] {
+    let callee = env.get_function(mid.qualified(fid));
+    let module_name = callee.module_env.get_name().name();
+    if module_name.display(env.symbol_pool()).to_string() != "vector" {
+        return &[];
+    }
+    let indices = match callee.get_name_str().as_str() {
+        "borrow" | "borrow_mut" | "remove" | "swap_remove" => args.get(1..2),
+        "swap" => args.get(1..3),
+        "insert" => args.get(2..3),
+        _ => None,
+    };
+    indices.unwrap_or_default()
+}
+
+/// Returns the names of the other variants of the enum which have the same payload (field names
+/// and types) as the given variant, so they can replace it in the pack expression.
+fn same_payload_variants(
+    env: &GlobalEnv,
+    mid: ModuleId,
+    sid: StructId,
+    variant: Symbol,
+) -> Vec<String> {
+    let struct_env = env.get_struct(mid.qualified(sid));
+    let payload = |variant: Symbol| {
+        struct_env
+            .get_fields_of_variant(variant)
+            .map(|field| (field.get_name(), field.get_type()))
+            .collect::<Vec<_>>()
+    };
+    let expected = payload(variant);
+
+    struct_env
+        .get_variants()
+        .filter(|other| *other != variant && payload(*other) == expected)
+        .map(|other| other.display(env.symbol_pool()).to_string())
+        .collect()
+}
+
 /// Returns the first contained attribute if any.
-fn contains_attribute<'a>(
+pub(crate) fn contains_attribute<'a>(
     attributes: &[Attribute],
     symbol_pool: &SymbolPool,
     target: &[&'a str],
@@ -309,3 +962,22 @@ fn contains_attribute<'a>(
         .find(|&target_attr| attrs.iter().any(|attr| attr.as_str() == *target_attr))
         .copied()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_similar_names() {
+        let candidates = ["coin_store", "balance", "transfer"];
+        assert_eq!(
+            suggest_similar("coin_stor", candidates.into_iter()),
+            Some("coin_store")
+        );
+        assert_eq!(
+            suggest_similar("Transfer", candidates.into_iter()),
+            Some("transfer")
+        );
+        assert_eq!(suggest_similar("xyz", candidates.into_iter()), None);
+    }
+}
@@ -2,9 +2,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::report::Mutation;
-use ahash::RandomState;
-use codespan::FileId;
+use crate::{report::Mutation, signature::Applicability};
+use codespan::{FileId, Span};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::{
     fmt,
     fmt::{Debug, Display},
@@ -29,10 +30,108 @@ impl MutantInfo {
         }
     }
 
-    /// Calculates the unique hash of the mutant.
-    pub fn unique_id(&self) -> u64 {
-        let fixed_randomness = RandomState::with_seeds(1, 2, 3, 4);
-        fixed_randomness.hash_one(self)
+    /// Calculates the identifier of the mutant, which is stable across unrelated edits.
+    ///
+    /// The identifier depends only on the enclosing function (its qualified name and its source
+    /// code) and the mutation, with the position counted from the start of the function.
+    /// Whitespaces are ignored everywhere, so neither edits elsewhere in the file nor reformatting
+    /// change the identifier. If the enclosing function is unknown, the mutated code itself is
+    /// used in its place, so the identifier doesn't depend on the rest of the file either.
+    ///
+    /// The identifier is derived from a SHA-256 digest, so it is the same for every build of the
+    /// tool and can be compared with identifiers stored by earlier runs.
+    pub fn stable_id(&self, source: &str, qname: &str, function_span: Option<Span>) -> u64 {
+        let range = self.mutation.get_range();
+        let (fn_start, fn_end) = function_span.map_or((range.start(), range.end()), |span| {
+            (span.start().to_usize(), span.end().to_usize())
+        });
+        let strip = |s: &str| s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+        let position = source
+            .get(fn_start..range.start())
+            .map_or(0, |prefix| strip(prefix).len());
+
+        let mut hasher = Sha256::new();
+        for field in [
+            qname,
+            strip(source.get(fn_start..fn_end).unwrap_or_default()).as_str(),
+            position.to_string().as_str(),
+            self.mutation.get_operator_name(),
+            strip(self.mutation.get_original_value()).as_str(),
+            strip(self.mutation.get_new_value()).as_str(),
+        ] {
+            // Length prefixes keep the fields apart, so text moved between them changes the digest.
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        let digest = hasher.finalize();
+        u64::from_be_bytes(digest[..8].try_into().expect("SHA-256 digest has 32 bytes"))
+    }
+}
+
+/// The documentation of a mutation operator, shown by the `describe-operator` command.
+///
+/// Every operator provides it next to its implementation, so the documentation can't go missing
+/// when an operator is added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OperatorInfo {
+    /// The name of the operator.
+    pub name: &'static str,
+    /// The kind of code the operator mutates.
+    pub category: OperatorCategory,
+    /// The functions the operator applies to.
+    pub applies_to: Applicability,
+    /// What the operator changes.
+    pub description: &'static str,
+    /// An example of the original code.
+    pub before: &'static str,
+    /// The example code mutated by the operator.
+    pub after: &'static str,
+    /// Which weakness of the tests a surviving mutant of the operator reveals.
+    pub rationale: &'static str,
+}
+
+impl Display for OperatorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({})", self.name, self.category)?;
+        writeln!(f)?;
+        writeln!(f, "{}", self.description)?;
+        if self.applies_to != Applicability::AnyFunction {
+            writeln!(f, "Applies to {} only.", self.applies_to)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Example:")?;
+        writeln!(f, "  - {}", self.before)?;
+        writeln!(f, "  + {}", self.after)?;
+        writeln!(f)?;
+        write!(f, "Why: {}", self.rationale)
+    }
+}
+
+/// The kind of code a mutation operator mutates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OperatorCategory {
+    /// Operators and literals in expressions.
+    Expression,
+    /// Conditions, loops and statements.
+    ControlFlow,
+    /// Structs and enums.
+    Data,
+    /// Global storage operations.
+    Storage,
+    /// Specification conditions.
+    Specification,
+}
+
+impl Display for OperatorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OperatorCategory::Expression => "expression",
+            OperatorCategory::ControlFlow => "control flow",
+            OperatorCategory::Data => "data",
+            OperatorCategory::Storage => "storage",
+            OperatorCategory::Specification => "specification",
+        })
     }
 }
 
@@ -119,7 +218,7 @@ impl Display for MutationOp {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::operators::binary::Binary;
+    use crate::{operators::binary::Binary, report::Range};
     use codespan::Files;
     use move_model::{ast::Operation, model::Loc};
 
@@ -131,4 +230,65 @@ mod tests {
         let operator = MutationOp::new(Box::new(Binary::new(Operation::Add, loc, vec![])));
         assert_eq!(operator.get_file_id(), fid);
     }
+
+    fn mutant_info(source: &str, old: &str, new: &str) -> MutantInfo {
+        let start = source.find(old).unwrap();
+        let mutation = Mutation::new(
+            Range::new(start, start + old.len()),
+            "binary_operator_replacement".to_owned(),
+            old.to_owned(),
+            new.to_owned(),
+        );
+        MutantInfo::new(source.replacen(old, new, 1), mutation)
+    }
+
+    fn function_span(source: &str) -> Option<Span> {
+        let start = source.find("fun").unwrap();
+        Some(Span::new(start as u32, source.len() as u32))
+    }
+
+    #[test]
+    fn test_stable_id() {
+        let source = "fun sum(x: u64, y: u64): u64 { x + y }";
+        let id = mutant_info(source, "+", "-").stable_id(source, "M::sum", function_span(source));
+
+        // Edits outside the function and reformatting don't change the identifier.
+        let edited = "const C: u64 = 1;\n\nfun sum(x: u64, y: u64): u64 {\n    x + y\n}";
+        assert_eq!(
+            mutant_info(edited, "+", "-").stable_id(edited, "M::sum", function_span(edited)),
+            id
+        );
+
+        // Different mutation, function or function body do.
+        assert_ne!(
+            mutant_info(source, "+", "*").stable_id(source, "M::sum", function_span(source)),
+            id
+        );
+        assert_ne!(
+            mutant_info(source, "+", "-").stable_id(source, "N::sum", function_span(source)),
+            id
+        );
+        let changed = "fun sum(x: u64, y: u64): u64 { y + x }";
+        assert_ne!(
+            mutant_info(changed, "+", "-").stable_id(changed, "M::sum", function_span(changed)),
+            id
+        );
+    }
+
+    #[test]
+    fn test_stable_id_without_function_span() {
+        let source = "const C: u64 = 1 + 2;";
+        let id = mutant_info(source, "+", "-").stable_id(source, "M::C", None);
+
+        // Without the enclosing function, edits elsewhere in the file still keep the identifier.
+        let edited = "const B: u64 = 7;\nconst C: u64 = 1 + 2;\nfun f() {}";
+        assert_eq!(
+            mutant_info(edited, "+", "-").stable_id(edited, "M::C", None),
+            id
+        );
+        assert_ne!(
+            mutant_info(source, "+", "*").stable_id(source, "M::C", None),
+            id
+        );
+    }
 }
@@ -0,0 +1,171 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::ExpLoc,
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::ast::{ExpData, Value};
+use num::{BigInt, Zero};
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "abort_code_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces the error code of an `abort` or an `assert!` with `0` and with the next other error code used in the module.",
+    before: "assert!(amount > 0, E_ZERO_AMOUNT);",
+    after: "assert!(amount > 0, E_FROZEN);",
+    rationale: "Tests using `#[expected_failure]` without the `abort_code` pass whichever check aborts, so a surviving mutant means the tests don't tell the errors of the module apart.",
+};
+
+/// Returns the value of the code, if it's a number (a literal or a named constant).
+fn number(code: &ExpLoc) -> Option<&BigInt> {
+    match code.exp.as_ref() {
+        ExpData::Value(_, Value::Number(num)) => Some(num),
+        _ => None,
+    }
+}
+
+/// The abort code mutation operator.
+#[derive(Debug, Clone)]
+pub struct AbortCode {
+    code: ExpLoc,
+    /// The error codes used in the module, in the source order.
+    module_codes: Vec<ExpLoc>,
+}
+
+impl AbortCode {
+    /// Creates a new instance of the abort code mutation operator.
+    #[must_use]
+    pub fn new(code: ExpLoc, module_codes: Vec<ExpLoc>) -> Self {
+        Self { code, module_codes }
+    }
+
+    /// Returns the source of the first error code following this one in the module (wrapping
+    /// around), which differs from this one and from zero.
+    ///
+    /// Only the codes without variables are used, as they are valid anywhere in the module.
+    fn next_code<'a>(&self, source: &'a str, cur_op: &str) -> Option<&'a str> {
+        let position = self
+            .module_codes
+            .iter()
+            .position(|code| code.loc == self.code.loc)
+            .map_or(0, |idx| idx + 1);
+        let current = number(&self.code);
+        self.module_codes
+            .iter()
+            .cycle()
+            .skip(position)
+            .take(self.module_codes.len())
+            .filter(|code| {
+                code.loc.file_id() == self.code.loc.file_id() && code.exp.free_vars().is_empty()
+            })
+            .filter(|code| number(code).map_or(true, |num| !num.is_zero() && Some(num) != current))
+            .map(|code| {
+                &source[code.loc.span().start().to_usize()..code.loc.span().end().to_usize()]
+            })
+            .find(|text| *text != cur_op)
+    }
+}
+
+impl MutationOperator for AbortCode {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let start = self.code.loc.span().start().to_usize();
+        let end = self.code.loc.span().end().to_usize();
+        let cur_op = &source[start..end];
+
+        let mut replacements = vec![];
+        if !number(&self.code).is_some_and(Zero::is_zero) {
+            replacements.push("0".to_owned());
+        }
+        if let Some(next) = self.next_code(source, cur_op) {
+            replacements.push(next.to_owned());
+        }
+
+        replacements
+            .into_iter()
+            .map(|new_op| {
+                let mut mutated_source = source.to_string();
+                mutated_source.replace_range(start..end, &new_op);
+                MutantInfo::new(
+                    mutated_source,
+                    Mutation::new(
+                        Range::new(start, end),
+                        OPERATOR_NAME.to_string(),
+                        cur_op.to_owned(),
+                        new_op,
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.code.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for AbortCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AbortCodeOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.code.loc.file_id(),
+            self.code.loc.span().start().to_usize(),
+            self.code.loc.span().end().to_usize()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::model::{Loc, NodeId};
+
+    const SOURCE: &str = "abort 7; abort E_ONE; abort E_SEVEN; abort 0;";
+
+    fn apply(code_idx: usize) -> Vec<String> {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let code = |id: usize, value: u64, start: u32, end: u32| {
+            let exp = ExpData::Value(NodeId::new(id), Value::Number(BigInt::from(value)));
+            ExpLoc::new(
+                exp.into_exp(),
+                Loc::new(fid, codespan::Span::new(start, end)),
+            )
+        };
+        let codes = vec![
+            code(1, 7, 6, 7),
+            code(2, 1, 15, 20),
+            code(3, 7, 28, 35),
+            code(4, 0, 43, 44),
+        ];
+        AbortCode::new(codes[code_idx].clone(), codes)
+            .apply(SOURCE)
+            .into_iter()
+            .map(|m| m.mutation.get_new_value().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn codes_are_replaced_with_zero_and_the_next_code() {
+        assert_eq!(apply(0), ["0", "E_ONE"]);
+        assert_eq!(apply(1), ["0", "E_SEVEN"]);
+        // The zero and the codes with the same value are skipped, wrapping around.
+        assert_eq!(apply(2), ["0", "E_ONE"]);
+        assert_eq!(apply(3), ["7"]);
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::MOVE_EMPTY_STMT,
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::{model::Loc, ty::Type};
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "assert_abort_deletion";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::ControlFlow,
+    applies_to: Applicability::AnyFunction,
+    description: "Deletes a whole `assert!` statement or an `abort` expression in a statement position, replacing it with an empty statement.",
+    before: "assert!(amount <= balance, E_INSUFFICIENT_BALANCE);",
+    after: "{};",
+    rationale: "A surviving mutant points directly at an invariant the tests never check: no test expects the failure, so the code behaves the same without the check.",
+};
+
+/// Checks whether an `abort` of the given type can be replaced with an empty statement.
+///
+/// An `abort` can take any type, so only the ones used as statements (of the unit type, or left
+/// without an inferred type) are deleted. Replacing e.g. the value of a returning branch with `{}`
+/// wouldn't compile.
+pub(crate) fn is_deletable_abort(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(tys) if tys.is_empty()) || matches!(ty, Type::Var(_))
+}
+
+/// The assert and abort deletion mutation operator.
+///
+/// The asserts are deleted as a whole, including the error code. The abort inside the assert
+/// isn't deleted separately, as that would be the same mutant. The existence checks are left to
+/// the exists check removal operator.
+#[derive(Debug, Clone)]
+pub struct AssertAbortDeletion {
+    loc: Loc,
+}
+
+impl AssertAbortDeletion {
+    /// Creates a new instance of the assert and abort deletion mutation operator.
+    #[must_use]
+    pub fn new(loc: Loc) -> Self {
+        Self { loc }
+    }
+}
+
+impl MutationOperator for AssertAbortDeletion {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let (start, end) = (
+            self.loc.span().start().to_usize(),
+            self.loc.span().end().to_usize(),
+        );
+        let cur_op = &source[start..end];
+
+        let mut mutated_source = source.to_string();
+        mutated_source.replace_range(start..end, MOVE_EMPTY_STMT);
+        vec![MutantInfo::new(
+            mutated_source,
+            Mutation::new(
+                Range::new(start, end),
+                OPERATOR_NAME.to_string(),
+                cur_op.to_string(),
+                MOVE_EMPTY_STMT.to_string(),
+            ),
+        )]
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for AssertAbortDeletion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AssertAbortDeletionOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::ty::PrimitiveType;
+
+    #[test]
+    fn asserts_and_aborts_are_deleted() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let source = "if (x > 10) abort 42; assert!(x > 1, 1);";
+        let deleted = |start: usize, end: usize| {
+            let loc = Loc::new(fid, codespan::Span::new(start as u32, end as u32));
+            AssertAbortDeletion::new(loc)
+                .apply(source)
+                .into_iter()
+                .map(|m| m.mutated_source)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(deleted(12, 20), ["if (x > 10) {}; assert!(x > 1, 1);"]);
+        assert_eq!(deleted(22, 39), ["if (x > 10) abort 42; {};"]);
+    }
+
+    #[test]
+    fn only_statement_aborts_are_deletable() {
+        assert!(is_deletable_abort(&Type::Tuple(vec![])));
+        assert!(is_deletable_abort(&Type::Var(0)));
+        assert!(!is_deletable_abort(&Type::Primitive(PrimitiveType::U64)));
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{ExpLoc, MOVE_FALSE, MOVE_TRUE},
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "assert_condition_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::ControlFlow,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces the condition of an `assert!` with `true`, `false` or its negation. The error code is mutated separately by `assert_error_code_replacement`, and `true` is left to `assert_abort_deletion` when it's enabled.",
+    before: "assert!(amount <= balance, E_INSUFFICIENT_BALANCE);",
+    after: "assert!(false, E_INSUFFICIENT_BALANCE);",
+    rationale: "A surviving mutant means the check never fails in the tests, or the tests pass even when it always fails.",
+};
+
+/// The assert condition mutation operator.
+///
+/// The conditions of the other `if` expressions are mutated by the if/else replacement and the
+/// conditional negation operators.
+#[derive(Debug, Clone)]
+pub struct AssertCondition {
+    cond: ExpLoc,
+    /// Whether the condition is replaced with `true`.
+    always_true: bool,
+}
+
+impl AssertCondition {
+    /// Creates a new instance of the assert condition mutation operator.
+    #[must_use]
+    pub fn new(cond: ExpLoc) -> Self {
+        Self {
+            cond,
+            always_true: true,
+        }
+    }
+
+    /// Leaves out the `true` replacement, which is the same as deleting the whole assert.
+    #[must_use]
+    pub fn without_true(mut self) -> Self {
+        self.always_true = false;
+        self
+    }
+}
+
+impl MutationOperator for AssertCondition {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let start = self.cond.loc.span().start().to_usize();
+        let end = self.cond.loc.span().end().to_usize();
+        let cur_op = &source[start..end];
+
+        let mut ops = vec![];
+        if self.always_true {
+            ops.push(MOVE_TRUE.to_owned());
+        }
+        ops.push(MOVE_FALSE.to_owned());
+        ops.push(format!("!({cur_op})"));
+
+        ops.into_iter()
+            .map(|op| {
+                let mut mutated_source = source.to_string();
+                mutated_source.replace_range(start..end, &op);
+                MutantInfo::new(
+                    mutated_source,
+                    Mutation::new(
+                        Range::new(start, end),
+                        OPERATOR_NAME.to_string(),
+                        cur_op.to_owned(),
+                        op,
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.cond.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for AssertCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AssertConditionOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.cond.loc.file_id(),
+            self.cond.loc.span().start().to_usize(),
+            self.cond.loc.span().end().to_usize()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::{
+        ast::{ExpData, Value},
+        model::{Loc, NodeId},
+    };
+
+    #[test]
+    fn assert_conditions_are_replaced() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(8, 13));
+        let cond = ExpLoc::new(
+            ExpData::Value(NodeId::new(1), Value::Bool(true)).into_exp(),
+            loc,
+        );
+        let operator = AssertCondition::new(cond.clone());
+        let result = operator
+            .apply("assert!(a < b, 1);")
+            .into_iter()
+            .map(|m| m.mutated_source)
+            .collect::<Vec<_>>();
+        assert_eq!(result, [
+            "assert!(true, 1);",
+            "assert!(false, 1);",
+            "assert!(!(a < b), 1);"
+        ]);
+
+        let result = AssertCondition::new(cond)
+            .without_true()
+            .apply("assert!(a < b, 1);")
+            .into_iter()
+            .map(|m| m.mutated_source)
+            .collect::<Vec<_>>();
+        assert_eq!(result, ["assert!(false, 1);", "assert!(!(a < b), 1);"]);
+    }
+}
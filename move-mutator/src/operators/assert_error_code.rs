@@ -0,0 +1,141 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{exists_check::is_unit, ExpLoc},
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::ast::{Exp, ExpData, Operation, Value};
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "assert_error_code_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Changes the error code of an `assert!` by one. The condition is mutated separately by `assert_condition_replacement`.",
+    before: "assert!(amount <= balance, E_INSUFFICIENT_BALANCE);",
+    after: "assert!(amount <= balance, (E_INSUFFICIENT_BALANCE + 1));",
+    rationale: "Tests expecting just any abort (`#[expected_failure]` without the `abort_code`) pass even when the check fails with a wrong error code, which the callers and off-chain clients rely on.",
+};
+
+/// Returns the error code of the `assert!(cond, code)` expression, which is `if (cond) () else
+/// abort code` in the model.
+pub(crate) fn assert_error_code<'a>(if_exp: &ExpData, else_exp: &'a ExpData) -> Option<&'a Exp> {
+    if !is_unit(if_exp) {
+        return None;
+    }
+    abort_code(else_exp)
+}
+
+/// Returns the code of the abort expression (a single-statement sequence is unwrapped).
+fn abort_code(exp: &ExpData) -> Option<&Exp> {
+    match exp {
+        ExpData::Call(_, Operation::Abort, args) => args.first(),
+        ExpData::Sequence(_, exps) if exps.len() == 1 => abort_code(&exps[0]),
+        _ => None,
+    }
+}
+
+/// The assert error code mutation operator.
+#[derive(Debug, Clone)]
+pub struct AssertErrorCode {
+    code: ExpLoc,
+}
+
+impl AssertErrorCode {
+    /// Creates a new instance of the assert error code mutation operator.
+    #[must_use]
+    pub fn new(code: ExpLoc) -> Self {
+        Self { code }
+    }
+}
+
+impl MutationOperator for AssertErrorCode {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let start = self.code.loc.span().start().to_usize();
+        let end = self.code.loc.span().end().to_usize();
+        let cur_op = &source[start..end];
+
+        // Literal codes are changed directly, named constants and other expressions are wrapped.
+        let is_literal = cur_op.starts_with(|c: char| c.is_ascii_digit());
+        let new_op = match self.code.exp.as_ref() {
+            ExpData::Value(_, Value::Number(num)) if is_literal => (num + 1).to_string(),
+            _ => format!("({cur_op} + 1)"),
+        };
+
+        let mut mutated_source = source.to_string();
+        mutated_source.replace_range(start..end, &new_op);
+        vec![MutantInfo::new(
+            mutated_source,
+            Mutation::new(
+                Range::new(start, end),
+                OPERATOR_NAME.to_string(),
+                cur_op.to_owned(),
+                new_op,
+            ),
+        )]
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.code.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for AssertErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AssertErrorCodeOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.code.loc.file_id(),
+            self.code.loc.span().start().to_usize(),
+            self.code.loc.span().end().to_usize()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::model::{Loc, NodeId};
+    use num::BigInt;
+
+    fn apply(source: &str, start: u32, end: u32) -> String {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(start, end));
+        let code = ExpData::Value(NodeId::new(1), Value::Number(BigInt::from(7)));
+        let operator = AssertErrorCode::new(ExpLoc::new(code.into_exp(), loc));
+        operator.apply(source).remove(0).mutated_source
+    }
+
+    #[test]
+    fn error_codes_are_changed() {
+        assert_eq!(apply("assert!(a, 7);", 11, 12), "assert!(a, 8);");
+        assert_eq!(
+            apply("assert!(a, E_SEVEN);", 11, 18),
+            "assert!(a, (E_SEVEN + 1));"
+        );
+    }
+
+    #[test]
+    fn only_asserts_have_error_codes() {
+        let code = || ExpData::Value(NodeId::new(1), Value::Number(BigInt::from(7))).into_exp();
+        let unit = ExpData::Call(NodeId::new(2), Operation::Tuple, vec![]);
+        let abort = ExpData::Call(NodeId::new(3), Operation::Abort, vec![code()]);
+        assert!(assert_error_code(&unit, &abort).is_some());
+        // if (cond) abort code
+        assert!(assert_error_code(&abort, &unit).is_none());
+    }
+}
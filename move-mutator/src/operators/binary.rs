@@ -3,9 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
-    operators::ExpLoc,
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{boundary::boundary_replacement, ExpLoc},
     report::{self, Mutation},
+    signature::Applicability,
 };
 use codespan::FileId;
 use move_model::{
@@ -17,6 +18,17 @@ use std::fmt;
 
 pub const OPERATOR_NAME: &str = "binary_operator_replacement";
 
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces a binary operator with another one of the same group: arithmetic (`+ - * / %`), logical (`|| &&`) or comparison (`== != < > <= >=`).",
+    before: "if (balance >= amount)",
+    after: "if (balance > amount)",
+    rationale: "Tests which don't check the boundary values or the results of calculations precisely let such mutants survive.",
+};
+
 /// The binary mutation operator.
 #[derive(Debug, Clone)]
 pub struct Binary {
@@ -45,8 +57,6 @@ impl MutationOperator for Binary {
             return vec![];
         }
 
-        // We need to extract operator position, but we must use the positions of expressions to avoid
-        // extracting the operator of a different binary expression.
         let left = &self.exps[0].loc;
         let right = &self.exps[1].loc;
 
@@ -56,31 +66,16 @@ impl MutationOperator for Binary {
             return vec![];
         }
 
-        let start = left.span().end().to_usize();
-        // Adjust start to omit whitespaces before the operator
-        let start = source[start..]
-            .find(|c: char| !c.is_whitespace())
-            .map_or(start, |i| start + i);
-        let end = right.span().start().to_usize();
-        // Adjust end to omit whitespaces after the operator
-        let end = source[..end]
-            .rfind(|c: char| !c.is_whitespace())
-            .map_or(end, |i| i + 1);
+        let (start, end) = operator_range(source, left, right);
         let cur_op = &source[start..end];
 
         // Group of exchangeable binary operators - we only want to replace the operator with a different one
-        // within the same group.
+        // within the same group. The bitwise and shift operators are mutated by their own operators.
         use Operation::*;
         let ops: Vec<Operation> = match self.operation {
             Add | Sub | Mul | Div | Mod => {
                 vec![Add, Sub, Mul, Div, Mod]
             },
-            BitOr | BitAnd | Xor => {
-                vec![BitOr, BitAnd, Xor]
-            },
-            Shl | Shr => {
-                vec![Shl, Shr]
-            },
             Or | And => {
                 vec![Or, And]
             },
@@ -95,8 +90,10 @@ impl MutationOperator for Binary {
         let is_left_exp_zero = contains_value_zero(self.exps[0].exp.as_ref());
         let is_right_exp_zero = contains_value_zero(self.exps[1].exp.as_ref());
 
+        // The strictness of the comparisons is mutated by the boundary condition operator.
+        let boundary = boundary_replacement(&self.operation);
         ops.into_iter()
-            .filter(|v| self.operation != *v)
+            .filter(|v| self.operation != *v && boundary.as_ref() != Some(v))
             .filter(|v| match self.operation {
                 // All below mutants would lead to the same code logic and would become
                 // false-positive results.
@@ -155,7 +152,24 @@ impl MutationOperator for Binary {
     }
 }
 
-fn check_compound_assignment(op: &Operation, target_operation: &str) -> bool {
+/// Returns the range of the binary operator between the operands in the source.
+///
+/// The operator is found using the positions of the operands to avoid extracting the operator of
+/// a different binary expression. The whitespaces around the operator are omitted.
+pub(crate) fn operator_range(source: &str, left: &Loc, right: &Loc) -> (usize, usize) {
+    let start = left.span().end().to_usize();
+    let start = source[start..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(start, |i| start + i);
+    let end = right.span().start().to_usize();
+    let end = source[..end]
+        .rfind(|c: char| !c.is_whitespace())
+        .map_or(end, |i| i + 1);
+    (start, end)
+}
+
+/// Checks if the operator in the source is a compound assignment (e.g. `+=`) of the operation.
+pub(crate) fn check_compound_assignment(op: &Operation, target_operation: &str) -> bool {
     use Operation::*;
     match *op {
         Add => target_operation.starts_with("+="),
@@ -172,7 +186,8 @@ fn check_compound_assignment(op: &Operation, target_operation: &str) -> bool {
     }
 }
 
-fn contains_value_zero(exp: &ExpData) -> bool {
+/// Checks if the expression is the literal zero.
+pub(crate) fn contains_value_zero(exp: &ExpData) -> bool {
     if let ExpData::Value(_id, Value::Number(num)) = exp {
         return num.is_zero();
     }
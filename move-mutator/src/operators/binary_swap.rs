@@ -3,9 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
-    operators::ExpLoc,
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{binary::operator_range, ExpLoc},
     report::{Mutation, Range},
+    signature::Applicability,
 };
 use codespan::FileId;
 use move_model::{
@@ -16,6 +17,17 @@ use std::fmt;
 
 pub const OPERATOR_NAME: &str = "binary_operator_swap";
 
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Swaps the operands of a non-commutative binary operator.",
+    before: "let fee = amount - discount;",
+    after: "let fee = discount - amount;",
+    rationale: "A surviving mutant means the order of the operands is never checked, e.g. the tests only use equal values.",
+};
+
 /// The binary swap mutation operator.
 #[derive(Debug, Clone)]
 pub struct BinarySwap {
@@ -88,20 +100,9 @@ impl MutationOperator for BinarySwap {
             return vec![];
         }
 
-        // We need to extract operator position, but we must use the positions of expressions to avoid
-        // extracting the operator of a different binary expression.
         let left = &self.exps[0].loc;
         let right = &self.exps[1].loc;
-        let start = left.span().end().to_usize();
-        // Adjust start to omit whitespaces before the operator
-        let start = source[start..]
-            .find(|c: char| !c.is_whitespace())
-            .map_or(start, |i| start + i);
-        let end = right.span().start().to_usize();
-        // Adjust end to omit whitespaces after the operator
-        let end = source[..end]
-            .rfind(|c: char| !c.is_whitespace())
-            .map_or(end, |i| i + 1);
+        let (start, end) = operator_range(source, left, right);
         let binop_str = &source[start..end];
 
         let start = left.span().start().to_usize();
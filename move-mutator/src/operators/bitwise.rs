@@ -0,0 +1,190 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{
+        binary::{check_compound_assignment, contains_value_zero, operator_range},
+        ExpLoc,
+    },
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::{ast::Operation, model::Loc};
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "bitwise_operator_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces a bitwise operator (`| & ^`) with another bitwise operator or with its arithmetic counterpart: `|` with `+`, `&` with `*` and `^` with `+` or `-`.",
+    before: "let flags = flags | FLAG_FROZEN;",
+    after: "let flags = flags + FLAG_FROZEN;",
+    rationale: "Tests which use only flags with disjoint bits or single-bit values can't tell the bitwise operators from each other or from the arithmetic ones, e.g. setting an already set flag is never tested.",
+};
+
+/// The bitwise mutation operator.
+#[derive(Debug, Clone)]
+pub struct Bitwise {
+    operation: Operation,
+    loc: Loc,
+    exps: Vec<ExpLoc>,
+}
+
+impl Bitwise {
+    /// Creates a new instance of the bitwise mutation operator.
+    #[must_use]
+    pub fn new(operation: Operation, loc: Loc, exps: Vec<ExpLoc>) -> Self {
+        Self {
+            operation,
+            loc,
+            exps,
+        }
+    }
+
+    /// Returns the replacements of the operation.
+    ///
+    /// The arithmetic counterparts give the same results as the bitwise operators for some
+    /// operands: `|` and `^` behave like `+` for operands without common bits, `^` behaves like
+    /// `-` when clearing bits which are set and `&` behaves like `*` for single-bit values.
+    fn replacements(&self) -> Vec<Operation> {
+        use Operation::*;
+        let is_left_zero = contains_value_zero(self.exps[0].exp.as_ref());
+        let is_right_zero = contains_value_zero(self.exps[1].exp.as_ref());
+
+        let (bitwise, arithmetic) = match self.operation {
+            BitOr => (vec![BitAnd, Xor], vec![Add]),
+            BitAnd => (vec![BitOr, Xor], vec![Mul]),
+            Xor => (vec![BitOr, BitAnd], vec![Add, Sub]),
+            _ => (vec![], vec![]),
+        };
+
+        // The arithmetic counterparts are equivalent for the zero operand, e.g. `x | 0` and
+        // `x + 0` or `x & 0` and `x * 0`.
+        let arithmetic = arithmetic.into_iter().filter(|op| match op {
+            Add | Mul => !is_left_zero && !is_right_zero,
+            Sub => !is_right_zero,
+            _ => true,
+        });
+        bitwise.into_iter().chain(arithmetic).collect()
+    }
+}
+
+impl MutationOperator for Bitwise {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        if self.exps.len() != 2 {
+            warn!(
+                "BitwiseOperator: Expected exactly two expressions, got {}",
+                self.exps.len()
+            );
+            return vec![];
+        }
+
+        let left = &self.exps[0].loc;
+        let right = &self.exps[1].loc;
+        if left == right {
+            return vec![];
+        }
+
+        let (start, end) = operator_range(source, left, right);
+        let cur_op = &source[start..end];
+        let is_compound_assignment = check_compound_assignment(&self.operation, cur_op);
+
+        self.replacements()
+            .into_iter()
+            .map(|op| {
+                let mut new_op = op.to_string_if_binop().expect("binop not found").to_owned();
+                if is_compound_assignment {
+                    new_op += "=";
+                }
+
+                let mut mutated_source = source.to_string();
+                mutated_source.replace_range(start..end, &new_op);
+                MutantInfo::new(
+                    mutated_source,
+                    Mutation::new(
+                        Range::new(start, end),
+                        OPERATOR_NAME.to_string(),
+                        cur_op.to_owned(),
+                        new_op,
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for Bitwise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "BitwiseOperator({:?}, location: file id: {:?}, index start: {}, index stop: {})",
+            self.operation,
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::{
+        ast::{ExpData, Value},
+        model::NodeId,
+    };
+    use num::BigInt;
+
+    fn apply(operation: Operation, source: &str, right: u64) -> Vec<String> {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 5));
+        let left_loc = Loc::new(fid, codespan::Span::new(0, 1));
+        let right_loc = Loc::new(fid, codespan::Span::new(4, 5));
+        let left = ExpData::Value(NodeId::new(1), Value::Number(BigInt::from(3)));
+        let right = ExpData::Value(NodeId::new(2), Value::Number(BigInt::from(right)));
+        let operator = Bitwise::new(operation, loc, vec![
+            ExpLoc::new(left.into_exp(), left_loc),
+            ExpLoc::new(right.into_exp(), right_loc),
+        ]);
+        operator
+            .apply(source)
+            .into_iter()
+            .map(|m| m.mutated_source)
+            .collect()
+    }
+
+    #[test]
+    fn bitwise_operators_are_replaced() {
+        assert_eq!(apply(Operation::BitOr, "3 | 5", 5), [
+            "3 & 5", "3 ^ 5", "3 + 5"
+        ]);
+        assert_eq!(apply(Operation::BitAnd, "3 & 5", 5), [
+            "3 | 5", "3 ^ 5", "3 * 5"
+        ]);
+        assert_eq!(apply(Operation::Xor, "3 ^ 5", 5), [
+            "3 | 5", "3 & 5", "3 + 5", "3 - 5"
+        ]);
+    }
+
+    #[test]
+    fn equivalent_arithmetic_replacements_are_skipped() {
+        assert_eq!(apply(Operation::BitOr, "3 | 0", 0), ["3 & 0", "3 ^ 0"]);
+        assert_eq!(apply(Operation::Xor, "3 ^ 0", 0), ["3 | 0", "3 & 0"]);
+    }
+}
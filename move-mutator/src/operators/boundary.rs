@@ -0,0 +1,160 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{binary::operator_range, ExpLoc},
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::{ast::Operation, model::Loc};
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "boundary_condition_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces a strict comparison with the non-strict one and vice versa: `<` with `<=`, `>` with `>=` and back.",
+    before: "assert!(amount <= balance, E_INSUFFICIENT_BALANCE);",
+    after: "assert!(amount < balance, E_INSUFFICIENT_BALANCE);",
+    rationale: "Off-by-one errors at the boundaries are the most common comparison bugs, and they survive when the tests never use the boundary value itself, e.g. withdrawing exactly the whole balance.",
+};
+
+/// Returns the comparison with the opposite strictness, if the operation is a relational
+/// comparison.
+pub(crate) fn boundary_replacement(operation: &Operation) -> Option<Operation> {
+    match operation {
+        Operation::Lt => Some(Operation::Le),
+        Operation::Le => Some(Operation::Lt),
+        Operation::Gt => Some(Operation::Ge),
+        Operation::Ge => Some(Operation::Gt),
+        _ => None,
+    }
+}
+
+/// The boundary condition mutation operator.
+#[derive(Debug, Clone)]
+pub struct Boundary {
+    operation: Operation,
+    loc: Loc,
+    exps: Vec<ExpLoc>,
+}
+
+impl Boundary {
+    /// Creates a new instance of the boundary condition mutation operator.
+    #[must_use]
+    pub fn new(operation: Operation, loc: Loc, exps: Vec<ExpLoc>) -> Self {
+        Self {
+            operation,
+            loc,
+            exps,
+        }
+    }
+}
+
+impl MutationOperator for Boundary {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        if self.exps.len() != 2 {
+            warn!(
+                "BoundaryOperator: Expected exactly two expressions, got {}",
+                self.exps.len()
+            );
+            return vec![];
+        }
+        let Some(replacement) = boundary_replacement(&self.operation) else {
+            return vec![];
+        };
+
+        let left = &self.exps[0].loc;
+        let right = &self.exps[1].loc;
+        if left == right {
+            return vec![];
+        }
+
+        let (start, end) = operator_range(source, left, right);
+        let cur_op = &source[start..end];
+        let new_op = replacement
+            .to_string_if_binop()
+            .expect("binop not found")
+            .to_owned();
+
+        let mut mutated_source = source.to_string();
+        mutated_source.replace_range(start..end, &new_op);
+        vec![MutantInfo::new(
+            mutated_source,
+            Mutation::new(
+                Range::new(start, end),
+                OPERATOR_NAME.to_string(),
+                cur_op.to_owned(),
+                new_op,
+            ),
+        )]
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for Boundary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "BoundaryOperator({:?}, location: file id: {:?}, index start: {}, index stop: {})",
+            self.operation,
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::{
+        ast::{ExpData, Value},
+        model::NodeId,
+    };
+    use num::BigInt;
+
+    fn apply(operation: Operation, source: &str) -> Vec<String> {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, source.len() as u32));
+        let left_loc = Loc::new(fid, codespan::Span::new(0, 1));
+        let right_loc = Loc::new(
+            fid,
+            codespan::Span::new(source.len() as u32 - 1, source.len() as u32),
+        );
+        let value = |id| ExpData::Value(NodeId::new(id), Value::Number(BigInt::from(id)));
+        let operator = Boundary::new(operation, loc, vec![
+            ExpLoc::new(value(1).into_exp(), left_loc),
+            ExpLoc::new(value(2).into_exp(), right_loc),
+        ]);
+        operator
+            .apply(source)
+            .into_iter()
+            .map(|m| m.mutated_source)
+            .collect()
+    }
+
+    #[test]
+    fn boundaries_are_replaced() {
+        assert_eq!(apply(Operation::Lt, "1 < 2"), ["1 <= 2"]);
+        assert_eq!(apply(Operation::Le, "1 <= 2"), ["1 < 2"]);
+        assert_eq!(apply(Operation::Gt, "1 > 2"), ["1 >= 2"]);
+        assert_eq!(apply(Operation::Ge, "1 >= 2"), ["1 > 2"]);
+        assert!(apply(Operation::Eq, "1 == 2").is_empty());
+    }
+}
@@ -3,9 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
     operators::{MOVE_BREAK, MOVE_CONTINUE, MOVE_EMPTY_STMT},
     report::{Mutation, Range},
+    signature::Applicability,
 };
 use codespan::FileId;
 use move_model::model::Loc;
@@ -13,6 +14,19 @@ use std::{fmt, fmt::Debug};
 
 pub const OPERATOR_NAME: &str = "break_continue_replacement";
 
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::ControlFlow,
+    applies_to: Applicability::AnyFunction,
+    description:
+        "Replaces `break` with `continue` and the other way round, or deletes the statement.",
+    before: "if (found) break;",
+    after: "if (found) continue;",
+    rationale:
+        "Tests which don't check when a loop stops miss the early exits and skipped iterations.",
+};
+
 /// Break and continue mutation operator.
 /// Replaces break and continue statements with each other or deletes them.
 #[derive(Debug, Clone)]
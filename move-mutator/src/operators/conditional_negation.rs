@@ -0,0 +1,106 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::ExpLoc,
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "conditional_negation";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::ControlFlow,
+    applies_to: Applicability::AnyFunction,
+    description: "Negates the condition of an `if` expression or a `while` loop.",
+    before: "while (i < len) { ... }",
+    after: "while (!(i < len)) { ... }",
+    rationale: "Swapping the branches is caught only if the tests exercise both of them, so a surviving mutant means the tests take only one branch or can't tell the branches apart.",
+};
+
+/// The conditional negation mutation operator.
+///
+/// The `while` loops are `if` expressions inside a loop in the model, so the operator applies to
+/// the loop conditions as well.
+#[derive(Debug, Clone)]
+pub struct ConditionalNegation {
+    cond: ExpLoc,
+}
+
+impl ConditionalNegation {
+    /// Creates a new instance of the conditional negation mutation operator.
+    #[must_use]
+    pub fn new(cond: ExpLoc) -> Self {
+        Self { cond }
+    }
+}
+
+impl MutationOperator for ConditionalNegation {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let start = self.cond.loc.span().start().to_usize();
+        let end = self.cond.loc.span().end().to_usize();
+        let cur_op = &source[start..end];
+        let new_op = format!("!({cur_op})");
+
+        let mut mutated_source = source.to_string();
+        mutated_source.replace_range(start..end, &new_op);
+        vec![MutantInfo::new(
+            mutated_source,
+            Mutation::new(
+                Range::new(start, end),
+                OPERATOR_NAME.to_string(),
+                cur_op.to_owned(),
+                new_op,
+            ),
+        )]
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.cond.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for ConditionalNegation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ConditionalNegationOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.cond.loc.file_id(),
+            self.cond.loc.span().start().to_usize(),
+            self.cond.loc.span().end().to_usize()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::{
+        ast::{ExpData, Value},
+        model::{Loc, NodeId},
+    };
+
+    #[test]
+    fn conditions_are_negated() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(7, 12));
+        let expr = ExpData::Value(NodeId::new(1), Value::Bool(true));
+        let operator = ConditionalNegation::new(ExpLoc::new(expr.into_exp(), loc));
+        let result = operator.apply("while (i < n) { }");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].mutated_source, "while (!(i < n)) { }");
+        assert_eq!(operator.get_file_id(), fid);
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{MOVE_MAX_INFERRED_NUM, MOVE_MAX_U256},
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::{
+    ast::Value,
+    model::Loc,
+    ty::{PrimitiveType, Type},
+};
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "constant_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces an integer literal with `0`, `1` and the maximum value of its type. When enabled, the literal replacement keeps only the neighbouring values.",
+    before: "let fee = amount * fee_bps / 10000;",
+    after: "let fee = amount * fee_bps / 18446744073709551615;",
+    rationale: "Tests rarely exercise the extreme values, so a surviving mutant points to a missing overflow, underflow or edge case test.",
+};
+
+/// Returns the decimal representation of the maximum value of the integer type.
+pub(crate) fn max_value(ty: &Type) -> Option<String> {
+    let max = match ty {
+        Type::Primitive(PrimitiveType::U8) => u8::MAX.to_string(),
+        Type::Primitive(PrimitiveType::U16) => u16::MAX.to_string(),
+        Type::Primitive(PrimitiveType::U32) => u32::MAX.to_string(),
+        Type::Primitive(PrimitiveType::U64) => u64::MAX.to_string(),
+        Type::Primitive(PrimitiveType::U128) => u128::MAX.to_string(),
+        Type::Primitive(PrimitiveType::U256) => MOVE_MAX_U256.trim_end_matches("u256").to_owned(),
+        Type::Primitive(PrimitiveType::Num) => MOVE_MAX_INFERRED_NUM.to_owned(),
+        _ => return None,
+    };
+    Some(max)
+}
+
+/// The constant replacement mutation operator.
+#[derive(Debug, Clone)]
+pub struct Constant {
+    value: Value,
+    optype: Type,
+    loc: Loc,
+}
+
+impl Constant {
+    /// Creates a new instance of the constant replacement mutation operator.
+    #[must_use]
+    pub fn new(value: Value, optype: Type, loc: Loc) -> Self {
+        Self { value, optype, loc }
+    }
+}
+
+impl MutationOperator for Constant {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let (Value::Number(num), Some(max)) = (&self.value, max_value(&self.optype)) else {
+            return vec![];
+        };
+        let start = self.loc.span().start().to_usize();
+        let end = self.loc.span().end().to_usize();
+        let cur_op = &source[start..end];
+
+        // Like in the literal replacement, the `u256` values keep the suffix, as the type of
+        // such large values can't be inferred otherwise.
+        let suffix = if self.optype == Type::Primitive(PrimitiveType::U256) {
+            "u256"
+        } else {
+            ""
+        };
+        let current = num.to_string();
+        ["0".to_owned(), "1".to_owned(), max]
+            .into_iter()
+            .filter(|value| *value != current)
+            .map(|value| {
+                let op = format!("{value}{suffix}");
+                let mut mutated_source = source.to_string();
+                mutated_source.replace_range(start..end, &op);
+                MutantInfo::new(
+                    mutated_source,
+                    Mutation::new(
+                        Range::new(start, end),
+                        OPERATOR_NAME.to_string(),
+                        cur_op.to_owned(),
+                        op,
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ConstantOperator({:?}, location: file id: {:?}, index start: {}, index stop: {})",
+            self.value,
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+
+    fn apply(value: u64, ty: PrimitiveType, source: &str) -> Vec<String> {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, source.len() as u32));
+        Constant::new(Value::Number(value.into()), Type::Primitive(ty), loc)
+            .apply(source)
+            .into_iter()
+            .map(|m| m.mutated_source)
+            .collect()
+    }
+
+    #[test]
+    fn integers_are_replaced_with_extremes() {
+        assert_eq!(apply(30, PrimitiveType::U8, "30"), ["0", "1", "255"]);
+        assert_eq!(apply(1, PrimitiveType::U64, "1"), [
+            "0",
+            "18446744073709551615"
+        ]);
+        // The current value is compared numerically, not textually.
+        assert_eq!(apply(0, PrimitiveType::U16, "0x0"), ["1", "65535"]);
+        assert_eq!(apply(7, PrimitiveType::U256, "7u256"), [
+            "0u256".to_owned(),
+            "1u256".to_owned(),
+            MOVE_MAX_U256.to_owned()
+        ]);
+        assert!(apply(1, PrimitiveType::Bool, "true").is_empty());
+    }
+}
@@ -3,9 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
     operators::MOVE_EMPTY_STMT,
     report::{Mutation, Range},
+    signature::Applicability,
 };
 use codespan::FileId;
 use move_model::{ast::Exp, model::Loc};
@@ -13,6 +14,17 @@ use std::{fmt, fmt::Debug};
 
 pub const OPERATOR_NAME: &str = "delete_statement";
 
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::ControlFlow,
+    applies_to: Applicability::AnyFunction,
+    description: "Deletes a statement whose removal keeps the code compiling, e.g. a function call or an assignment.",
+    before: "coin::deposit(recipient, coins);",
+    after: "{};",
+    rationale: "A surviving mutant means the effect of the statement is never checked by the tests.",
+};
+
 /// Statement delete operator.
 /// Deletes statements which can be potentially deleted, still allowing the code to compile
 /// properly.
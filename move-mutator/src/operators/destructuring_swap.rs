@@ -0,0 +1,142 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::struct_pack_swap::{swap_same_typed_items, SwapItem},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::model::Loc;
+use std::{fmt, fmt::Debug};
+
+pub const OPERATOR_NAME: &str = "destructuring_binding_swap";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Data,
+    applies_to: Applicability::AnyFunction,
+    description: "Swaps two bindings of the same type in a destructuring pattern.",
+    before: "let Point { x, y } = p;",
+    after: "let Point { x: y, y: x } = p;",
+    rationale: "Tests using equal field values don't notice the fields being mixed up.",
+};
+
+/// Destructuring binding swap operator.
+/// Swaps two bindings with the same type in the destructuring pattern, e.g.
+/// `let (a, b) = f();` is mutated to `let (b, a) = f();` and `let Point { x, y } = p;` is mutated
+/// to `let Point { x: y, y: x } = p;`.
+#[derive(Debug, Clone)]
+pub struct DestructuringSwap {
+    loc: Loc,
+    bindings: Vec<SwapItem>,
+}
+
+impl DestructuringSwap {
+    /// Creates a new instance of the destructuring binding swap operator.
+    #[must_use]
+    pub fn new(loc: Loc, bindings: Vec<SwapItem>) -> Self {
+        Self { loc, bindings }
+    }
+}
+
+impl MutationOperator for DestructuringSwap {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        swap_same_typed_items(source, &self.bindings, OPERATOR_NAME)
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for DestructuringSwap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DestructuringSwapOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::ty::{PrimitiveType, Type};
+
+    fn binding(fid: FileId, start: u32, end: u32) -> SwapItem {
+        SwapItem {
+            loc: Loc::new(fid, codespan::Span::new(start, end)),
+            ty: Type::Primitive(PrimitiveType::U64),
+        }
+    }
+
+    #[test]
+    fn test_apply_tuple_destructuring_swap() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(4, 10));
+        let source = "let (a, b) = f();";
+
+        let operator = DestructuringSwap::new(loc, vec![binding(fid, 5, 6), binding(fid, 8, 9)]);
+        let expected = ["let (b, a) = f();"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_apply_struct_destructuring_swap() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(4, 18));
+        let source = "let Point { x, y } = p;";
+
+        let operator =
+            DestructuringSwap::new(loc, vec![binding(fid, 12, 13), binding(fid, 15, 16)]);
+        let expected = ["let Point { x: y, y: x } = p;"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_apply_struct_destructuring_swap_mixed_shorthand() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(4, 21));
+        let source = "let Point { x, y: b } = p;";
+
+        let operator =
+            DestructuringSwap::new(loc, vec![binding(fid, 12, 13), binding(fid, 18, 19)]);
+        let expected = ["let Point { x: b, y: x } = p;"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_get_file_id() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 0));
+        let operator = DestructuringSwap::new(loc, vec![]);
+        assert_eq!(operator.get_file_id(), fid);
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::MOVE_EMPTY_STMT,
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::{
+    ast::{Exp, ExpData, Operation},
+    model::Loc,
+};
+use std::{fmt, fmt::Debug};
+
+pub const OPERATOR_NAME: &str = "exists_check_removal";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Storage,
+    applies_to: Applicability::AnyFunction,
+    description: "Removes a resource existence check, such as `assert!(exists<T>(addr), E)`.",
+    before: "assert!(exists<Vault>(addr), E_NO_VAULT);",
+    after: "{};",
+    rationale: "A surviving mutant means no test covers the path with a missing (or an already existing) resource.",
+};
+
+/// Resource existence check removal operator.
+/// Removes whole checks like `assert!(exists<T>(addr), E)` or `if (!exists<T>(addr)) abort E`,
+/// which allows to verify that paths with uninitialized (or already initialized) resources are
+/// covered by tests and specifications.
+///
+/// The `exists` operation doesn't need to be listed in the `acquires` annotation, so removing
+/// such a check keeps the `acquires` list of the function valid and the mutant compiles.
+#[derive(Debug, Clone)]
+pub struct ExistsCheck {
+    check: Exp,
+    loc: Loc,
+}
+
+impl ExistsCheck {
+    /// Creates a new instance of the exists check removal operator.
+    #[must_use]
+    pub fn new(check: Exp, loc: Loc) -> Self {
+        Self { check, loc }
+    }
+
+    /// Checks whether the `if-else` expression is an existence check, so:
+    /// - the condition is `exists<T>(addr)` or `!exists<T>(addr)`,
+    /// - one of the branches aborts and the other one does nothing.
+    ///
+    /// That covers both `assert!(exists<T>(addr), E)` and `if (!exists<T>(addr)) abort E` forms.
+    pub fn is_exists_check(cond: &ExpData, if_exp: &ExpData, else_exp: &ExpData) -> bool {
+        let is_exists = |e: &ExpData| matches!(e, ExpData::Call(_, Operation::Exists(_), _));
+        let is_cond_exists_check = match cond {
+            ExpData::Call(_, Operation::Not, args) => args.len() == 1 && is_exists(&args[0]),
+            e => is_exists(e),
+        };
+
+        is_cond_exists_check
+            && ((is_abort(if_exp) && is_unit(else_exp)) || (is_unit(if_exp) && is_abort(else_exp)))
+    }
+}
+
+/// Checks if the expression aborts (a single-statement sequence is unwrapped).
+fn is_abort(exp: &ExpData) -> bool {
+    match exp {
+        ExpData::Call(_, Operation::Abort, _) => true,
+        ExpData::Sequence(_, exps) if exps.len() == 1 => is_abort(&exps[0]),
+        _ => false,
+    }
+}
+
+/// Checks if the expression is a unit expression `()`.
+pub(crate) fn is_unit(exp: &ExpData) -> bool {
+    match exp {
+        ExpData::Call(_, Operation::Tuple, args) => args.is_empty(),
+        ExpData::Sequence(_, exps) => exps.is_empty(),
+        _ => false,
+    }
+}
+
+impl MutationOperator for ExistsCheck {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let (start, end) = (
+            self.loc.span().start().to_usize(),
+            self.loc.span().end().to_usize(),
+        );
+        let cur_op = &source[start..end];
+
+        // The check is replaced with an empty statement, so the code still compiles.
+        let ops: Vec<&str> = vec![MOVE_EMPTY_STMT];
+
+        ops.into_iter()
+            .map(|op| {
+                let mut mutated_source = source.to_string();
+                mutated_source.replace_range(start..end, op);
+                MutantInfo::new(
+                    mutated_source,
+                    Mutation::new(
+                        Range::new(start, end),
+                        OPERATOR_NAME.to_string(),
+                        cur_op.to_string(),
+                        op.to_string(),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for ExistsCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ExistsCheckOperator({:?}, location: file id: {:?}, index start: {}, index stop: {})",
+            self.check,
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::{ast::Value, model::NodeId};
+
+    fn exists_call() -> Exp {
+        let addr = ExpData::Value(NodeId::new(1), Value::Bool(true)).into_exp();
+        ExpData::Call(NodeId::new(2), Operation::Exists(None), vec![addr]).into_exp()
+    }
+
+    fn abort_call() -> Exp {
+        let code = ExpData::Value(NodeId::new(3), Value::Number(1.into())).into_exp();
+        ExpData::Call(NodeId::new(4), Operation::Abort, vec![code]).into_exp()
+    }
+
+    fn unit() -> Exp {
+        ExpData::Call(NodeId::new(5), Operation::Tuple, vec![]).into_exp()
+    }
+
+    #[test]
+    fn test_apply_exists_check() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 24));
+
+        let operator = ExistsCheck::new(exists_call(), loc);
+        let source = "assert!(exists<T>(a), 1);";
+        let expected = ["{};"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_is_exists_check() {
+        let exists = exists_call();
+        let not_exists =
+            ExpData::Call(NodeId::new(6), Operation::Not, vec![exists.clone()]).into_exp();
+
+        // assert!(exists<T>(addr), E)
+        assert!(ExistsCheck::is_exists_check(
+            &exists,
+            &unit(),
+            &abort_call()
+        ));
+        // if (!exists<T>(addr)) abort E
+        assert!(ExistsCheck::is_exists_check(
+            &not_exists,
+            &abort_call(),
+            &unit()
+        ));
+        // Neither of the branches aborts.
+        assert!(!ExistsCheck::is_exists_check(&exists, &unit(), &unit()));
+        // Condition is not an existence check.
+        assert!(!ExistsCheck::is_exists_check(
+            &abort_call(),
+            &unit(),
+            &abort_call()
+        ));
+    }
+
+    #[test]
+    fn test_get_file_id() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 0));
+        let operator = ExistsCheck::new(exists_call(), loc);
+        assert_eq!(operator.get_file_id(), fid);
+    }
+}
@@ -3,15 +3,27 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
     operators::ExpLoc,
     report::{Mutation, Range},
+    signature::Applicability,
 };
 use codespan::FileId;
 use std::{fmt, fmt::Debug};
 
 pub const OPERATOR_NAME: &str = "if_else_replacement";
 
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::ControlFlow,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces the condition of an `if` expression with `true` or `false`.",
+    before: "if (amount > limit) abort E_LIMIT;",
+    after: "if (false) abort E_LIMIT;",
+    rationale: "A surviving mutant means one of the branches is never tested, or both branches behave the same in the tests.",
+};
+
 /// `IfElse` mutation operator.
 /// Replaces conditional expressions in if/else statements with literals.
 /// Currently only condition field is used.
@@ -41,12 +53,9 @@ impl MutationOperator for IfElse {
         let end = self.cond.loc.span().end().to_usize();
         let cur_op = &source[start..end];
 
-        // Change if/else expression to true/false.
-        let ops: Vec<String> = vec![
-            "true".to_owned(),
-            "false".to_owned(),
-            format!("!({})", cur_op),
-        ];
+        // Change if/else expression to true/false. The negation is left to the conditional
+        // negation operator.
+        let ops: Vec<String> = vec!["true".to_owned(), "false".to_owned()];
 
         ops.into_iter()
             .map(|op| {
@@ -104,7 +113,7 @@ mod tests {
         let exp = ExpLoc::new(expr.into_exp(), loc);
         let operator = IfElse::new(exp.clone(), exp.clone(), exp);
         let source = "if (a) { }";
-        let expected = ["if (true) { }", "if (false) { }", "if (!(a)) { }"];
+        let expected = ["if (true) { }", "if (false) { }"];
         let result = operator.apply(source);
         assert_eq!(result.len(), expected.len());
         for (i, r) in result.iter().enumerate() {
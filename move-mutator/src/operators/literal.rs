@@ -3,12 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
     operators::{
         MOVE_ADDR_MAX, MOVE_ADDR_ZERO, MOVE_FALSE, MOVE_MAX_INFERRED_NUM, MOVE_MAX_U256, MOVE_TRUE,
         MOVE_ZERO_U256,
     },
     report::{Mutation, Range},
+    signature::Applicability,
 };
 use codespan::FileId;
 use move_model::{
@@ -21,6 +22,17 @@ use std::{fmt, fmt::Debug};
 
 pub const OPERATOR_NAME: &str = "literal_replacement";
 
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces a literal with another value of the same type: integers with the minimum, the maximum and the neighbouring values, booleans with the opposite value and addresses with `0x0` and the maximum address.",
+    before: "let fee_bps = 30;",
+    after: "let fee_bps = 31;",
+    rationale: "A surviving mutant means the exact value of the constant is never checked by the tests.",
+};
+
 /// Literal replacement mutation operator.
 /// Replaces literal statements with other ones but withing the same type.
 #[derive(Debug, Clone)]
@@ -28,6 +40,10 @@ pub struct Literal {
     operation: Value,
     optype: Type,
     loc: Loc,
+    /// Whether the integers are replaced with the minimum and the maximum values too.
+    extremes: bool,
+    /// Whether the integers are replaced with the neighbouring values too.
+    neighbours: bool,
 }
 
 impl Literal {
@@ -38,8 +54,26 @@ impl Literal {
             operation,
             optype,
             loc,
+            extremes: true,
+            neighbours: true,
         }
     }
+
+    /// Keeps only the neighbouring values of the integers, as the extreme values are produced by
+    /// the constant replacement operator.
+    #[must_use]
+    pub fn without_extremes(mut self) -> Self {
+        self.extremes = false;
+        self
+    }
+
+    /// Keeps only the minimum and the maximum values of the integers, as the neighbouring values
+    /// are produced by the off-by-one operator.
+    #[must_use]
+    pub fn without_neighbours(mut self) -> Self {
+        self.neighbours = false;
+        self
+    }
 }
 
 impl MutationOperator for Literal {
@@ -139,10 +173,32 @@ impl MutationOperator for Literal {
             },
             _ => vec![],
         };
+        // The minimum and the maximum come first for all the integer types.
+        let is_integer = matches!(
+            self.optype,
+            Type::Primitive(
+                PrimitiveType::U8
+                    | PrimitiveType::U16
+                    | PrimitiveType::U32
+                    | PrimitiveType::U64
+                    | PrimitiveType::U128
+                    | PrimitiveType::U256
+                    | PrimitiveType::Num
+            )
+        );
+        let is_kept = |idx: usize| {
+            !is_integer
+                || if idx < 2 {
+                    self.extremes
+                } else {
+                    self.neighbours
+                }
+        };
 
         ops.into_iter()
-            .filter(|v| cur_op != *v)
-            .map(|op| {
+            .enumerate()
+            .filter(|(idx, v)| is_kept(*idx) && cur_op != *v)
+            .map(|(_, op)| {
                 let mut mutated_source = source.to_string();
                 mutated_source.replace_range(start..end, op.as_str());
                 MutantInfo::new(
@@ -210,6 +266,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn extremes_can_be_left_out() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 2));
+
+        let operator = Literal::new(
+            Value::Number(51.into()),
+            Type::Primitive(PrimitiveType::U8),
+            loc.clone(),
+        )
+        .without_extremes();
+        let result = operator.apply("51");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].mutated_source, "52");
+        assert_eq!(result[1].mutated_source, "50");
+
+        let operator = Literal::new(Value::Bool(true), Type::Primitive(PrimitiveType::Bool), loc)
+            .without_extremes();
+        assert_eq!(operator.apply("true")[0].mutated_source, "false");
+    }
+
+    #[test]
+    fn neighbours_can_be_left_out() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 2));
+
+        let operator = Literal::new(
+            Value::Number(51.into()),
+            Type::Primitive(PrimitiveType::U8),
+            loc.clone(),
+        )
+        .without_neighbours();
+        let result = operator.apply("51");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].mutated_source, "0");
+        assert_eq!(result[1].mutated_source, "255");
+
+        let operator = Literal::new(
+            Value::Number(51.into()),
+            Type::Primitive(PrimitiveType::U8),
+            loc,
+        )
+        .without_extremes()
+        .without_neighbours();
+        assert!(operator.apply("51").is_empty());
+    }
+
     #[test]
     fn test_apply_u16() {
         let mut files = Files::new();
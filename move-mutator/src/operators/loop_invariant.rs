@@ -0,0 +1,215 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::MOVE_TRUE,
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::model::Loc;
+use std::{fmt, fmt::Debug};
+
+pub const OPERATOR_NAME: &str = "loop_invariant_weakening";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Specification,
+    applies_to: Applicability::AnyFunction,
+    description: "Weakens a loop invariant to `true` or drops one of its conjuncts.",
+    before: "spec { invariant i <= n && sum == i * k; };",
+    after: "spec { invariant i <= n; };",
+    rationale: "A surviving mutant means the prover doesn't need the invariant (or its part) to verify the function specification.",
+};
+
+/// Loop invariant weakening operator.
+/// Replaces every `invariant` of a loop spec block with `true` and, for conjunctions, drops each
+/// of the top-level conjuncts in turn.
+///
+/// The operator is meant for the specification testing, where the prover should notice that the
+/// weakened invariant no longer suffices to prove the function specification. It is optional, as
+/// the unit tests ignore the specifications and such mutants would always survive.
+#[derive(Debug, Clone)]
+pub struct LoopInvariant {
+    loc: Loc,
+}
+
+impl LoopInvariant {
+    /// Creates a new instance of the loop invariant weakening operator for the spec block.
+    #[must_use]
+    pub fn new(loc: Loc) -> Self {
+        Self { loc }
+    }
+}
+
+/// Finds the expressions of the `invariant` conditions in the spec block source.
+/// Returns their ranges relative to the block.
+fn invariant_conditions(block: &str) -> Vec<(usize, usize)> {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    let mut conditions = vec![];
+    for (idx, keyword) in block.match_indices("invariant") {
+        let end = idx + keyword.len();
+        if block[..idx].ends_with(is_ident) || block[end..].starts_with(is_ident) {
+            continue;
+        }
+
+        // Skip the optional properties, e.g. `invariant [global] cond;`.
+        let mut start = end + (block[end..].len() - block[end..].trim_start().len());
+        if block[start..].starts_with('[') {
+            let Some(close) = block[start..].find(']') else {
+                continue;
+            };
+            start += close + 1;
+            start += block[start..].len() - block[start..].trim_start().len();
+        }
+
+        let Some(len) = top_level_end(&block[start..]) else {
+            continue;
+        };
+        let cond = block[start..start + len].trim_end();
+        if !cond.is_empty() {
+            conditions.push((start, start + cond.len()));
+        }
+    }
+    conditions
+}
+
+/// Returns the length of the expression ending with `;` or the closing brace of the block.
+fn top_level_end(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (idx, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            '}' if depth == 0 => return Some(idx),
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => return Some(idx),
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Splits the condition into its top-level conjuncts (`a && b && c`).
+fn conjuncts(cond: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    let bytes = cond.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b'&' if depth == 0 && bytes.get(idx + 1) == Some(&b'&') => {
+                parts.push(cond[start..idx].trim());
+                idx += 2;
+                start = idx;
+                continue;
+            },
+            _ => {},
+        }
+        idx += 1;
+    }
+    parts.push(cond[start..].trim());
+    parts
+}
+
+impl MutationOperator for LoopInvariant {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let block_start = self.loc.span().start().to_usize();
+        let block_end = self.loc.span().end().to_usize();
+        let block = &source[block_start..block_end];
+
+        let mut result = vec![];
+        for (start, end) in invariant_conditions(block) {
+            let (start, end) = (block_start + start, block_start + end);
+            let cur_op = &source[start..end];
+
+            let mut ops = vec![MOVE_TRUE.to_owned()];
+            let parts = conjuncts(cur_op);
+            if parts.len() > 1 {
+                ops.extend((0..parts.len()).map(|dropped| {
+                    parts
+                        .iter()
+                        .enumerate()
+                        .filter(|(idx, _)| *idx != dropped)
+                        .map(|(_, part)| *part)
+                        .collect::<Vec<_>>()
+                        .join(" && ")
+                }));
+            }
+
+            result.extend(ops.into_iter().map(|op| {
+                let mut mutated_source = source.to_string();
+                mutated_source.replace_range(start..end, &op);
+                MutantInfo::new(
+                    mutated_source,
+                    Mutation::new(
+                        Range::new(start, end),
+                        OPERATOR_NAME.to_string(),
+                        cur_op.to_string(),
+                        op,
+                    ),
+                )
+            }));
+        }
+        result
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for LoopInvariant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LoopInvariantOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+
+    #[test]
+    fn test_apply_loop_invariant() {
+        let source = "spec { invariant i <= n && sum == i * k; invariant [global] f(a, b); }";
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, source.len() as u32));
+
+        let operator = LoopInvariant::new(loc);
+        let expected = [
+            "spec { invariant true; invariant [global] f(a, b); }",
+            "spec { invariant sum == i * k; invariant [global] f(a, b); }",
+            "spec { invariant i <= n; invariant [global] f(a, b); }",
+            "spec { invariant i <= n && sum == i * k; invariant [global] true; }",
+        ];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_no_invariants() {
+        assert!(invariant_conditions("spec { assert invariants_ok; }").is_empty());
+        assert_eq!(conjuncts("(a && b) || c"), vec!["(a && b) || c"]);
+    }
+}
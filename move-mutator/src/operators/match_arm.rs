@@ -0,0 +1,203 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{
+        struct_pack_swap::{swap_same_typed_items, SwapItem},
+        MOVE_EMPTY_STMT,
+    },
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::model::Loc;
+use std::{fmt, fmt::Debug};
+
+pub const OPERATOR_NAME: &str = "match_arm_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::ControlFlow,
+    applies_to: Applicability::AnyFunction,
+    description: "Deletes the body of a `match` arm of the unit type, or swaps the bodies of the neighbouring arms.",
+    before: "match (o) { Buy => 1, Sell => 2 }",
+    after: "match (o) { Buy => 2, Sell => 1 }",
+    rationale: "A surviving mutant means the tests don't tell the arms apart.",
+};
+
+/// A body of a single `match` arm.
+#[derive(Debug, Clone)]
+pub struct ArmBody {
+    /// The body, its location and type.
+    pub item: SwapItem,
+    /// Whether the body is of the unit type, so it can be deleted.
+    pub is_unit: bool,
+    /// Whether the body uses variables bound by the arm pattern, so it can't be moved to
+    /// another arm.
+    pub uses_bindings: bool,
+}
+
+/// Match arm replacement operator.
+/// Mutates arms of the `match` expression:
+/// - bodies of the unit type are deleted (replaced with an empty block),
+/// - bodies of the neighbouring arms are swapped, e.g.
+///   `match (o) { Buy => 1, Sell => 2 }` is mutated to `match (o) { Buy => 2, Sell => 1 }`.
+///
+/// Bodies using the variables bound by the arm pattern are never swapped, as the variables
+/// don't exist in the other arm.
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    loc: Loc,
+    arms: Vec<ArmBody>,
+}
+
+impl MatchArm {
+    /// Creates a new instance of the match arm replacement operator.
+    #[must_use]
+    pub fn new(loc: Loc, arms: Vec<ArmBody>) -> Self {
+        Self { loc, arms }
+    }
+}
+
+impl MutationOperator for MatchArm {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let mut result = vec![];
+
+        for arm in self.arms.iter().filter(|arm| arm.is_unit) {
+            let (start, end) = (
+                arm.item.loc.span().start().to_usize(),
+                arm.item.loc.span().end().to_usize(),
+            );
+            let cur_op = &source[start..end];
+            if cur_op.trim() == MOVE_EMPTY_STMT {
+                continue;
+            }
+
+            let mut mutated_source = source.to_string();
+            mutated_source.replace_range(start..end, MOVE_EMPTY_STMT);
+            result.push(MutantInfo::new(
+                mutated_source,
+                Mutation::new(
+                    Range::new(start, end),
+                    OPERATOR_NAME.to_string(),
+                    cur_op.to_string(),
+                    MOVE_EMPTY_STMT.to_string(),
+                ),
+            ));
+        }
+
+        let movable = self
+            .arms
+            .iter()
+            .filter(|arm| !arm.uses_bindings)
+            .map(|arm| arm.item.clone())
+            .collect::<Vec<_>>();
+        result.extend(
+            swap_same_typed_items(source, &movable, OPERATOR_NAME)
+                .into_iter()
+                // Swapping identical bodies creates an equivalent mutant.
+                .filter(|mutant| mutant.mutated_source != source),
+        );
+
+        result
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MatchArmOperator(arms: {}, location: file id: {:?}, index start: {}, index stop: {})",
+            self.arms.len(),
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::ty::{PrimitiveType, Type};
+
+    fn arm(fid: FileId, start: u32, end: u32, is_unit: bool, uses_bindings: bool) -> ArmBody {
+        let ty = if is_unit {
+            Type::Tuple(vec![])
+        } else {
+            Type::Primitive(PrimitiveType::U64)
+        };
+        ArmBody {
+            item: SwapItem {
+                loc: Loc::new(fid, codespan::Span::new(start, end)),
+                ty,
+            },
+            is_unit,
+            uses_bindings,
+        }
+    }
+
+    #[test]
+    fn test_apply_match_arm_swap() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let source = "match (o) { A => 1, B { x } => x, C => 3, D => 3 }";
+        let loc = Loc::new(fid, codespan::Span::new(0, 51));
+        let arms = vec![
+            arm(fid, 17, 18, false, false),
+            arm(fid, 31, 32, false, true),
+            arm(fid, 39, 40, false, false),
+            arm(fid, 47, 48, false, false),
+        ];
+
+        let operator = MatchArm::new(loc, arms);
+        // The arm using the binding is skipped and swapping identical bodies is pointless.
+        let expected = ["match (o) { A => 3, B { x } => x, C => 1, D => 3 }"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_apply_match_arm_deletion() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let source = "match (o) { A => f(), B => {} }";
+        let loc = Loc::new(fid, codespan::Span::new(0, 31));
+        let arms = vec![arm(fid, 17, 20, true, false), arm(fid, 27, 29, true, false)];
+
+        let operator = MatchArm::new(loc, arms);
+        let expected = [
+            "match (o) { A => {}, B => {} }",
+            "match (o) { A => {}, B => f() }",
+        ];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_get_file_id() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 0));
+        let operator = MatchArm::new(loc, vec![]);
+        assert_eq!(operator.get_file_id(), fid);
+    }
+}
@@ -2,16 +2,106 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::operator::OperatorInfo;
 use move_model::{ast::Exp, model::Loc};
 
+pub(crate) mod abort_code;
+pub(crate) mod assert_abort_deletion;
+pub(crate) mod assert_condition;
+pub(crate) mod assert_error_code;
 pub(crate) mod binary;
 pub(crate) mod binary_swap;
-
+pub(crate) mod bitwise;
+pub(crate) mod boundary;
 pub(crate) mod break_continue;
+pub(crate) mod conditional_negation;
+pub(crate) mod constant;
 pub(crate) mod delete_stmt;
+pub(crate) mod destructuring_swap;
+pub(crate) mod exists_check;
 pub(crate) mod ifelse;
 pub(crate) mod literal;
+pub(crate) mod loop_invariant;
+pub(crate) mod match_arm;
+pub(crate) mod negation_removal;
+pub(crate) mod off_by_one;
+pub(crate) mod shift;
+pub(crate) mod struct_pack_swap;
 pub(crate) mod unary;
+pub(crate) mod variant_swap;
+pub(crate) mod variant_test;
+
+/// All the mutation operators.
+pub(crate) const OPERATORS: &[&str] = &[
+    abort_code::OPERATOR_NAME,
+    assert_abort_deletion::OPERATOR_NAME,
+    assert_condition::OPERATOR_NAME,
+    assert_error_code::OPERATOR_NAME,
+    binary::OPERATOR_NAME,
+    binary_swap::OPERATOR_NAME,
+    bitwise::OPERATOR_NAME,
+    boundary::OPERATOR_NAME,
+    break_continue::OPERATOR_NAME,
+    conditional_negation::OPERATOR_NAME,
+    constant::OPERATOR_NAME,
+    delete_stmt::OPERATOR_NAME,
+    destructuring_swap::OPERATOR_NAME,
+    exists_check::OPERATOR_NAME,
+    ifelse::OPERATOR_NAME,
+    literal::OPERATOR_NAME,
+    loop_invariant::OPERATOR_NAME,
+    match_arm::OPERATOR_NAME,
+    negation_removal::OPERATOR_NAME,
+    off_by_one::OPERATOR_NAME,
+    shift::OPERATOR_NAME,
+    struct_pack_swap::OPERATOR_NAME,
+    unary::OPERATOR_NAME,
+    variant_swap::OPERATOR_NAME,
+    variant_test::OPERATOR_NAME,
+];
+
+/// The documentation of all the mutation operators.
+pub(crate) const OPERATOR_INFOS: &[OperatorInfo] = &[
+    abort_code::INFO,
+    assert_abort_deletion::INFO,
+    assert_condition::INFO,
+    assert_error_code::INFO,
+    binary::INFO,
+    binary_swap::INFO,
+    bitwise::INFO,
+    boundary::INFO,
+    break_continue::INFO,
+    conditional_negation::INFO,
+    constant::INFO,
+    delete_stmt::INFO,
+    destructuring_swap::INFO,
+    exists_check::INFO,
+    ifelse::INFO,
+    literal::INFO,
+    loop_invariant::INFO,
+    match_arm::INFO,
+    negation_removal::INFO,
+    off_by_one::INFO,
+    shift::INFO,
+    struct_pack_swap::INFO,
+    unary::INFO,
+    variant_swap::INFO,
+    variant_test::INFO,
+];
+
+/// Returns the documentation of the operator.
+pub(crate) fn operator_info(name: &str) -> Option<&'static OperatorInfo> {
+    OPERATOR_INFOS.iter().find(|info| info.name == name)
+}
+
+/// Operators which are disabled by default and need to be explicitly enabled.
+pub(crate) const OPTIONAL_OPERATORS: &[&str] = &[
+    struct_pack_swap::OPERATOR_NAME,
+    destructuring_swap::OPERATOR_NAME,
+    loop_invariant::OPERATOR_NAME,
+    constant::OPERATOR_NAME,
+    off_by_one::OPERATOR_NAME,
+];
 
 // Section with Move constants.
 pub(crate) const MOVE_EMPTY_STMT: &str = "{}";
@@ -42,3 +132,20 @@ impl ExpLoc {
         Self { exp, loc }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_operator_is_documented() {
+        let documented = OPERATOR_INFOS
+            .iter()
+            .map(|info| info.name)
+            .collect::<Vec<_>>();
+        assert_eq!(documented, OPERATORS);
+        for info in OPERATOR_INFOS {
+            assert_ne!(info.before, info.after, "{}", info.name);
+        }
+    }
+}
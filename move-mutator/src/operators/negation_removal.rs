@@ -0,0 +1,115 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::model::Loc;
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "unary_negation_removal";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Deletes the negation `!` of an operand of `&&` or `||`, keeping the rest of the condition.",
+    before: "if (is_admin || !is_frozen) transfer();",
+    after: "if (is_admin || is_frozen) transfer();",
+    rationale: "Flipping the whole condition only shows that a branch is never taken. A surviving mutant means a single sub-condition is never tested with both values, while the other sub-conditions decide the outcome.",
+};
+
+/// The unary negation removal mutation operator.
+///
+/// Only the negations of the sub-conditions are removed. The whole conditions are negated by the
+/// conditional negation operator.
+#[derive(Debug, Clone)]
+pub struct NegationRemoval {
+    loc: Loc,
+}
+
+impl NegationRemoval {
+    /// Creates a new instance of the negation removal mutation operator for the negation
+    /// expression at the given location.
+    #[must_use]
+    pub fn new(loc: Loc) -> Self {
+        Self { loc }
+    }
+}
+
+impl MutationOperator for NegationRemoval {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let start = self.loc.span().start().to_usize();
+        let end = self.loc.span().end().to_usize();
+        // The negation starts with the operator, possibly after some whitespaces.
+        let Some(start) = source[start..end]
+            .find(|c: char| !c.is_whitespace())
+            .map(|i| start + i)
+            .filter(|&i| source[i..].starts_with('!'))
+        else {
+            warn!("NegationRemovalOperator: `!` not found at {start}..{end}");
+            return vec![];
+        };
+
+        let mut mutated_source = source.to_string();
+        mutated_source.replace_range(start..start + 1, "");
+        vec![MutantInfo::new(
+            mutated_source,
+            Mutation::new(
+                Range::new(start, start + 1),
+                OPERATOR_NAME.to_string(),
+                "!".to_owned(),
+                String::new(),
+            ),
+        )]
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for NegationRemoval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "NegationRemovalOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+
+    fn apply(source: &str, start: u32, end: u32) -> Vec<String> {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(start, end));
+        NegationRemoval::new(loc)
+            .apply(source)
+            .into_iter()
+            .map(|m| m.mutated_source)
+            .collect()
+    }
+
+    #[test]
+    fn negations_are_removed() {
+        assert_eq!(apply("a || !b", 5, 7), ["a || b"]);
+        assert_eq!(apply("a && !(b || c)", 5, 14), ["a && (b || c)"]);
+        assert!(apply("a && b", 5, 6).is_empty());
+    }
+}
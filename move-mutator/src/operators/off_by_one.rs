@@ -0,0 +1,137 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::constant::max_value,
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::{
+    ast::Value,
+    model::Loc,
+    ty::{PrimitiveType, Type},
+};
+use num::BigInt;
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "off_by_one_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces an integer literal used in a comparison or as a vector index with `literal + 1` and `literal - 1`. When enabled, the literal replacement doesn't produce these values for such literals.",
+    before: "if (vector::length(&members) < 10) {",
+    after: "if (vector::length(&members) < 11) {",
+    rationale: "Limits and indices are often tested only far from the fence post, so a surviving mutant means no test hits the exact limit or the first and the last element. Together with the boundary condition replacement it pinpoints the missing fence-post test.",
+};
+
+/// The off-by-one mutation operator.
+#[derive(Debug, Clone)]
+pub struct OffByOne {
+    value: Value,
+    optype: Type,
+    loc: Loc,
+}
+
+impl OffByOne {
+    /// Creates a new instance of the off-by-one mutation operator.
+    #[must_use]
+    pub fn new(value: Value, optype: Type, loc: Loc) -> Self {
+        Self { value, optype, loc }
+    }
+}
+
+impl MutationOperator for OffByOne {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let (Value::Number(num), Some(max)) = (&self.value, max_value(&self.optype)) else {
+            return vec![];
+        };
+        let max = max.parse::<BigInt>().expect("invalid maximum value");
+        let start = self.loc.span().start().to_usize();
+        let end = self.loc.span().end().to_usize();
+        let cur_op = &source[start..end];
+
+        let suffix = if self.optype == Type::Primitive(PrimitiveType::U256) {
+            "u256"
+        } else {
+            ""
+        };
+        // The values out of the range of the type wouldn't compile.
+        let mut values = vec![];
+        if *num < max {
+            values.push(num + 1);
+        }
+        if *num > BigInt::from(0) {
+            values.push(num - 1);
+        }
+        values
+            .into_iter()
+            .map(|value| {
+                let op = format!("{value}{suffix}");
+                let mut mutated_source = source.to_string();
+                mutated_source.replace_range(start..end, &op);
+                MutantInfo::new(
+                    mutated_source,
+                    Mutation::new(
+                        Range::new(start, end),
+                        OPERATOR_NAME.to_string(),
+                        cur_op.to_owned(),
+                        op,
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for OffByOne {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "OffByOneOperator({:?}, location: file id: {:?}, index start: {}, index stop: {})",
+            self.value,
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+
+    fn apply(value: u64, ty: PrimitiveType, source: &str) -> Vec<String> {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, source.len() as u32));
+        OffByOne::new(Value::Number(value.into()), Type::Primitive(ty), loc)
+            .apply(source)
+            .into_iter()
+            .map(|m| m.mutated_source)
+            .collect()
+    }
+
+    #[test]
+    fn literals_are_moved_by_one() {
+        assert_eq!(apply(10, PrimitiveType::U64, "10"), ["11", "9"]);
+        assert_eq!(apply(0, PrimitiveType::U64, "0"), ["1"]);
+        assert_eq!(apply(255, PrimitiveType::U8, "255"), ["254"]);
+        assert_eq!(apply(7, PrimitiveType::U256, "7u256"), ["8u256", "6u256"]);
+        assert!(apply(1, PrimitiveType::Bool, "true").is_empty());
+    }
+}
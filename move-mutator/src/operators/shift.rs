@@ -0,0 +1,196 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    operators::{
+        binary::{check_compound_assignment, contains_value_zero, operator_range},
+        ExpLoc,
+    },
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::{
+    ast::{ExpData, Operation, Value},
+    model::Loc,
+};
+use std::fmt;
+
+pub const OPERATOR_NAME: &str = "shift_operator_replacement";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces a shift operator (`<< >>`) with the opposite one and changes the shift amount by one in both directions.",
+    before: "let byte = ((value >> 8) as u8);",
+    after: "let byte = ((value >> 9) as u8);",
+    rationale: "Shifts in serialization and fixed-point math are often tested only with values for which an off-by-one shift amount gives the same result, e.g. zero or values with the low bits cleared.",
+};
+
+/// The shift mutation operator.
+#[derive(Debug, Clone)]
+pub struct Shift {
+    operation: Operation,
+    loc: Loc,
+    exps: Vec<ExpLoc>,
+}
+
+impl Shift {
+    /// Creates a new instance of the shift mutation operator.
+    #[must_use]
+    pub fn new(operation: Operation, loc: Loc, exps: Vec<ExpLoc>) -> Self {
+        Self {
+            operation,
+            loc,
+            exps,
+        }
+    }
+
+    /// Returns the shift amounts changed by one in both directions.
+    ///
+    /// Literal amounts are changed directly, other expressions are wrapped in parentheses. The
+    /// amount isn't decreased below zero, which would just abort.
+    fn amount_replacements(&self, amount: &str) -> Vec<String> {
+        let is_literal = amount.starts_with(|c: char| c.is_ascii_digit());
+        match self.exps[1].exp.as_ref() {
+            ExpData::Value(_, Value::Number(num)) if is_literal => {
+                let mut replacements = vec![(num + 1).to_string()];
+                if !contains_value_zero(self.exps[1].exp.as_ref()) {
+                    replacements.push((num - 1).to_string());
+                }
+                replacements
+            },
+            _ => vec![format!("({amount} + 1)"), format!("({amount} - 1)")],
+        }
+    }
+}
+
+impl MutationOperator for Shift {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        if self.exps.len() != 2 {
+            warn!(
+                "ShiftOperator: Expected exactly two expressions, got {}",
+                self.exps.len()
+            );
+            return vec![];
+        }
+
+        let left = &self.exps[0].loc;
+        let right = &self.exps[1].loc;
+        if left == right {
+            return vec![];
+        }
+
+        let (start, end) = operator_range(source, left, right);
+        let cur_op = &source[start..end];
+        let mut new_op = match self.operation {
+            Operation::Shl => ">>",
+            Operation::Shr => "<<",
+            _ => return vec![],
+        }
+        .to_owned();
+        if check_compound_assignment(&self.operation, cur_op) {
+            new_op += "=";
+        }
+
+        let mutant = |start: usize, end: usize, replacement: String| {
+            let mut mutated_source = source.to_string();
+            mutated_source.replace_range(start..end, &replacement);
+            MutantInfo::new(
+                mutated_source,
+                Mutation::new(
+                    Range::new(start, end),
+                    OPERATOR_NAME.to_string(),
+                    source[start..end].to_owned(),
+                    replacement,
+                ),
+            )
+        };
+
+        let amount_start = right.span().start().to_usize();
+        let amount_end = right.span().end().to_usize();
+        let amount = &source[amount_start..amount_end];
+        let mut mutants = vec![mutant(start, end, new_op)];
+        mutants.extend(
+            self.amount_replacements(amount)
+                .into_iter()
+                .map(|replacement| mutant(amount_start, amount_end, replacement)),
+        );
+        mutants
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for Shift {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ShiftOperator({:?}, location: file id: {:?}, index start: {}, index stop: {})",
+            self.operation,
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::model::NodeId;
+    use num::BigInt;
+
+    fn apply(operation: Operation, source: &str, right: ExpData) -> Vec<String> {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, source.len() as u32));
+        let left_loc = Loc::new(fid, codespan::Span::new(0, 1));
+        let right_loc = Loc::new(fid, codespan::Span::new(5, source.len() as u32));
+        let left = ExpData::Value(NodeId::new(1), Value::Number(BigInt::from(3)));
+        let operator = Shift::new(operation, loc, vec![
+            ExpLoc::new(left.into_exp(), left_loc),
+            ExpLoc::new(right.into_exp(), right_loc),
+        ]);
+        operator
+            .apply(source)
+            .into_iter()
+            .map(|m| m.mutated_source)
+            .collect()
+    }
+
+    fn number(value: u64) -> ExpData {
+        ExpData::Value(NodeId::new(2), Value::Number(BigInt::from(value)))
+    }
+
+    #[test]
+    fn shifts_are_replaced() {
+        assert_eq!(apply(Operation::Shl, "x << 8", number(8)), [
+            "x >> 8", "x << 9", "x << 7"
+        ]);
+        assert_eq!(apply(Operation::Shr, "x >> 0", number(0)), [
+            "x << 0", "x >> 1"
+        ]);
+    }
+
+    #[test]
+    fn non_literal_amounts_are_wrapped() {
+        let amount = ExpData::Temporary(NodeId::new(2), 0);
+        assert_eq!(apply(Operation::Shl, "x << n", amount), [
+            "x >> n",
+            "x << (n + 1)",
+            "x << (n - 1)"
+        ]);
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::{model::Loc, ty::Type};
+use std::{fmt, fmt::Debug};
+
+pub const OPERATOR_NAME: &str = "struct_pack_field_swap";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Data,
+    applies_to: Applicability::AnyFunction,
+    description: "Swaps the values of two fields of the same type in a struct pack expression.",
+    before: "Range { start: a, end: b }",
+    after: "Range { start: b, end: a }",
+    rationale: "Catches tests of constructor-like functions which don't check the values of the individual fields.",
+};
+
+/// A single item (a field value or a binding) which can be swapped with another item of the same type.
+#[derive(Debug, Clone)]
+pub struct SwapItem {
+    /// Location of the item in the source code.
+    pub loc: Loc,
+    /// Type of the item.
+    pub ty: Type,
+}
+
+/// Struct pack field swap operator.
+/// Swaps values of two fields with the same type in the struct pack expression, e.g.
+/// `Range { start: a, end: b }` is mutated to `Range { start: b, end: a }`.
+///
+/// That catches bugs in constructor-like functions where the values could be mixed up.
+#[derive(Debug, Clone)]
+pub struct StructPackSwap {
+    loc: Loc,
+    fields: Vec<SwapItem>,
+}
+
+impl StructPackSwap {
+    /// Creates a new instance of the struct pack field swap operator.
+    #[must_use]
+    pub fn new(loc: Loc, fields: Vec<SwapItem>) -> Self {
+        Self { loc, fields }
+    }
+}
+
+impl MutationOperator for StructPackSwap {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        swap_same_typed_items(source, &self.fields, OPERATOR_NAME)
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for StructPackSwap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "StructPackSwapOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+/// Swaps the source code of the items with the same type.
+///
+/// Each item is swapped only with the next item of the same type, so the number of mutants
+/// grows linearly with the number of items.
+///
+/// Items written using the field init shorthand syntax (e.g. `Point { x, y }`) are expanded
+/// to the full syntax (e.g. `Point { x: y, y: x }`), so the mutant is not equivalent to the
+/// original code. If only one of the items is a shorthand, only that one is expanded and just
+/// the value of the other one is replaced (e.g. `Point { x, y: b }` to `Point { x: b, y: x }`).
+pub(crate) fn swap_same_typed_items(
+    source: &str,
+    items: &[SwapItem],
+    operator_name: &str,
+) -> Vec<MutantInfo> {
+    let mut items = items
+        .iter()
+        .map(|item| {
+            (
+                item.loc.span().start().to_usize(),
+                item.loc.span().end().to_usize(),
+                &item.ty,
+            )
+        })
+        .collect::<Vec<_>>();
+    items.sort_by_key(|(start, ..)| *start);
+
+    // Items must not overlap, otherwise we can't swap them in the source code.
+    if items.windows(2).any(|w| w[0].1 > w[1].0) {
+        return vec![];
+    }
+
+    let Some(&(first_start, ..)) = items.first() else {
+        return vec![];
+    };
+    let is_named = find_opening_delimiter(source, first_start) == Some('{');
+    let is_shorthand =
+        |start: usize| is_named && source[..start].trim_end().chars().last() != Some(':');
+
+    let mut result = vec![];
+    for (i, &(left_start, left_end, ty)) in items.iter().enumerate() {
+        let Some(&(right_start, right_end, _)) =
+            items[i + 1..].iter().find(|(.., other_ty)| *other_ty == ty)
+        else {
+            continue;
+        };
+
+        let left = &source[left_start..left_end];
+        let right = &source[right_start..right_end];
+        // The shorthand item is the field name, which is also the name of the value.
+        let new_left = if is_shorthand(left_start) {
+            format!("{left}: {right}")
+        } else {
+            right.to_owned()
+        };
+        let new_right = if is_shorthand(right_start) {
+            format!("{right}: {left}")
+        } else {
+            left.to_owned()
+        };
+
+        let cur_op = &source[left_start..right_end];
+        let op = format!("{new_left}{}{new_right}", &source[left_end..right_start]);
+
+        let mut mutated_source = source.to_string();
+        mutated_source.replace_range(left_start..right_end, &op);
+        result.push(MutantInfo::new(
+            mutated_source,
+            Mutation::new(
+                Range::new(left_start, right_end),
+                operator_name.to_string(),
+                cur_op.to_string(),
+                op,
+            ),
+        ));
+    }
+
+    result
+}
+
+/// Finds the delimiter opening the list of items starting at the `pos` index, skipping the field
+/// name for the `field: value` syntax.
+fn find_opening_delimiter(source: &str, pos: usize) -> Option<char> {
+    let before = source[..pos].trim_end();
+    let before = match before.strip_suffix(':') {
+        Some(field) => field
+            .trim_end()
+            .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+            .trim_end(),
+        None => before,
+    };
+    before.chars().last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+    use move_model::ty::PrimitiveType;
+
+    fn item(fid: FileId, start: u32, end: u32, ty: PrimitiveType) -> SwapItem {
+        SwapItem {
+            loc: Loc::new(fid, codespan::Span::new(start, end)),
+            ty: Type::Primitive(ty),
+        }
+    }
+
+    #[test]
+    fn test_apply_struct_pack_swap() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 21));
+        let source = "R { a: x, b: y, c: z }";
+        let fields = vec![
+            item(fid, 7, 8, PrimitiveType::U64),
+            item(fid, 13, 14, PrimitiveType::Bool),
+            item(fid, 19, 20, PrimitiveType::U64),
+        ];
+
+        let operator = StructPackSwap::new(loc, fields);
+        let expected = ["R { a: z, b: y, c: x }"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_apply_struct_pack_swap_shorthand() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 10));
+        let source = "R { x, y }";
+        let fields = vec![
+            item(fid, 4, 5, PrimitiveType::U64),
+            item(fid, 7, 8, PrimitiveType::U64),
+        ];
+
+        let operator = StructPackSwap::new(loc, fields);
+        let expected = ["R { x: y, y: x }"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_apply_struct_pack_swap_mixed_shorthand() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 16));
+        let fields = |left: (u32, u32), right: (u32, u32)| {
+            vec![
+                item(fid, left.0, left.1, PrimitiveType::U64),
+                item(fid, right.0, right.1, PrimitiveType::U64),
+            ]
+        };
+
+        let operator = StructPackSwap::new(loc.clone(), fields((4, 5), (10, 13)));
+        let result = operator.apply("R { x, y: foo }");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].mutated_source, "R { x: foo, y: x }");
+
+        let operator = StructPackSwap::new(loc, fields((7, 10), (12, 13)));
+        let result = operator.apply("R { x: foo, y }");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].mutated_source, "R { x: y, y: foo }");
+    }
+
+    #[test]
+    fn test_apply_positional_struct_pack_swap() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 7));
+        let source = "R(x, y)";
+        let fields = vec![
+            item(fid, 2, 3, PrimitiveType::U8),
+            item(fid, 5, 6, PrimitiveType::U8),
+        ];
+
+        let operator = StructPackSwap::new(loc, fields);
+        let expected = ["R(y, x)"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_get_file_id() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 0));
+        let operator = StructPackSwap::new(loc, vec![]);
+        assert_eq!(operator.get_file_id(), fid);
+    }
+}
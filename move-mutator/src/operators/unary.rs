@@ -3,9 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
     operators::ExpLoc,
     report::{Mutation, Range},
+    signature::Applicability,
 };
 use codespan::FileId;
 use move_model::{ast::Operation, model::Loc};
@@ -13,6 +14,17 @@ use std::fmt;
 
 pub const OPERATOR_NAME: &str = "unary_operator_replacement";
 
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Expression,
+    applies_to: Applicability::AnyFunction,
+    description: "Removes the unary negation operator `!`.",
+    before: "if (!is_frozen) transfer();",
+    after: "if ( is_frozen) transfer();",
+    rationale: "A surviving mutant means the negated condition is never tested with both values.",
+};
+
 /// Represents a unary operator mutation.
 #[derive(Debug, Clone)]
 pub struct Unary {
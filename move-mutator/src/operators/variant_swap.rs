@@ -0,0 +1,156 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::model::Loc;
+use std::{fmt, fmt::Debug};
+
+pub const OPERATOR_NAME: &str = "enum_variant_swap";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::Data,
+    applies_to: Applicability::AnyFunction,
+    description: "Replaces the variant in an enum pack expression with another variant of the same enum with the same payload.",
+    before: "Order::Buy { amount }",
+    after: "Order::Sell { amount }",
+    rationale: "A surviving mutant means the tests don't check which variant is created.",
+};
+
+/// Enum variant constructor swap operator.
+/// Replaces the variant in the enum pack expression with another variant of the same enum which
+/// has the same payload (field names and types), e.g. `Order::Buy { amount }` is mutated to
+/// `Order::Sell { amount }`.
+///
+/// Only variants with the same payload are used, so the mutant always compiles.
+#[derive(Debug, Clone)]
+pub struct VariantSwap {
+    loc: Loc,
+    variant: String,
+    alternatives: Vec<String>,
+}
+
+impl VariantSwap {
+    /// Creates a new instance of the enum variant swap operator.
+    #[must_use]
+    pub fn new(loc: Loc, variant: String, alternatives: Vec<String>) -> Self {
+        Self {
+            loc,
+            variant,
+            alternatives,
+        }
+    }
+}
+
+impl MutationOperator for VariantSwap {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let (start, end) = (
+            self.loc.span().start().to_usize(),
+            self.loc.span().end().to_usize(),
+        );
+        let pack = &source[start..end];
+
+        // The variant name is the last path segment before the payload.
+        let head = pack.find(['{', '(']).map_or(pack, |i| &pack[..i]);
+        let Some(pos) = head.rfind(self.variant.as_str()) else {
+            warn!("VariantSwap: variant {} not found in {pack}", self.variant);
+            return vec![];
+        };
+        let (start, end) = (start + pos, start + pos + self.variant.len());
+
+        self.alternatives
+            .iter()
+            .map(|op| {
+                let mut mutated_source = source.to_string();
+                mutated_source.replace_range(start..end, op);
+                MutantInfo::new(
+                    mutated_source,
+                    Mutation::new(
+                        Range::new(start, end),
+                        OPERATOR_NAME.to_string(),
+                        self.variant.clone(),
+                        op.clone(),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for VariantSwap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "VariantSwapOperator({}, location: file id: {:?}, index start: {}, index stop: {})",
+            self.variant,
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+
+    #[test]
+    fn test_apply_variant_swap() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let source = "let o = Order::Buy { amount: Buy };";
+        let loc = Loc::new(fid, codespan::Span::new(8, 34));
+
+        let operator = VariantSwap::new(loc, "Buy".to_owned(), vec![
+            "Sell".to_owned(),
+            "Hold".to_owned(),
+        ]);
+        let expected = [
+            "let o = Order::Sell { amount: Buy };",
+            "let o = Order::Hold { amount: Buy };",
+        ];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_apply_variant_swap_unit_variant() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let source = "Color::Red";
+        let loc = Loc::new(fid, codespan::Span::new(0, 10));
+
+        let operator = VariantSwap::new(loc, "Red".to_owned(), vec!["Blue".to_owned()]);
+        let result = operator.apply(source);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].mutated_source, "Color::Blue");
+    }
+
+    #[test]
+    fn test_get_file_id() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 0));
+        let operator = VariantSwap::new(loc, "Red".to_owned(), vec![]);
+        assert_eq!(operator.get_file_id(), fid);
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    operator::{MutantInfo, MutationOperator, OperatorCategory, OperatorInfo},
+    report::{Mutation, Range},
+    signature::Applicability,
+};
+use codespan::FileId;
+use move_model::model::Loc;
+use std::{fmt, fmt::Debug};
+
+pub const OPERATOR_NAME: &str = "variant_test_negation";
+
+/// The documentation of the operator.
+pub(crate) const INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    category: OperatorCategory::ControlFlow,
+    applies_to: Applicability::AnyFunction,
+    description: "Negates an enum variant test expression.",
+    before: "x is Shape::Circle",
+    after: "!(x is Shape::Circle)",
+    rationale: "A surviving mutant means the tests don't cover both outcomes of the variant test.",
+};
+
+/// Enum variant test negation operator.
+/// Negates the variant test expression, e.g. `x is Shape::Circle` is mutated to
+/// `!(x is Shape::Circle)`.
+///
+/// Variant tests are not regular boolean operators, so no other operator mutates them.
+#[derive(Debug, Clone)]
+pub struct VariantTest {
+    loc: Loc,
+}
+
+impl VariantTest {
+    /// Creates a new instance of the variant test negation operator.
+    #[must_use]
+    pub fn new(loc: Loc) -> Self {
+        Self { loc }
+    }
+}
+
+impl MutationOperator for VariantTest {
+    fn apply(&self, source: &str) -> Vec<MutantInfo> {
+        let (start, end) = (
+            self.loc.span().start().to_usize(),
+            self.loc.span().end().to_usize(),
+        );
+        let cur_op = &source[start..end];
+        let op = format!("!({cur_op})");
+
+        let mut mutated_source = source.to_string();
+        mutated_source.replace_range(start..end, &op);
+        vec![MutantInfo::new(
+            mutated_source,
+            Mutation::new(
+                Range::new(start, end),
+                OPERATOR_NAME.to_string(),
+                cur_op.to_string(),
+                op,
+            ),
+        )]
+    }
+
+    fn get_file_id(&self) -> FileId {
+        self.loc.file_id()
+    }
+
+    fn name(&self) -> String {
+        OPERATOR_NAME.to_string()
+    }
+}
+
+impl fmt::Display for VariantTest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "VariantTestOperator(location: file id: {:?}, index start: {}, index stop: {})",
+            self.loc.file_id(),
+            self.loc.span().start(),
+            self.loc.span().end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan::Files;
+
+    #[test]
+    fn test_apply_variant_test_negation() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let source = "if (s is Shape::Circle) 1 else 2";
+        let loc = Loc::new(fid, codespan::Span::new(4, 22));
+
+        let operator = VariantTest::new(loc);
+        let result = operator.apply(source);
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0].mutated_source,
+            "if (!(s is Shape::Circle)) 1 else 2"
+        );
+    }
+
+    #[test]
+    fn test_get_file_id() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 0));
+        let operator = VariantTest::new(loc);
+        assert_eq!(operator.get_file_id(), fid);
+    }
+}
@@ -105,6 +105,8 @@ pub(crate) fn setup_output_dir(mutator_configuration: &Configuration) -> anyhow:
         .unwrap_or(PathBuf::from(cli::DEFAULT_OUTPUT_DIR));
     trace!("Trying to set up output directory to: {output_dir:?}");
 
+    mutator_configuration.check_output_dir(&output_dir)?;
+
     // Check if output directory exists and if it should be overwritten.
     if output_dir.exists() && mutator_configuration.project.no_overwrite {
         return Err(anyhow::anyhow!(
@@ -253,4 +255,47 @@ mod tests {
         let config = Configuration::new(options, None);
         assert!(setup_output_dir(&config).is_err());
     }
+
+    #[test]
+    fn setup_output_dir_errors_if_directory_is_inside_package_sources() {
+        let temp_dir = tempdir().unwrap();
+        let package_dir = temp_dir.path().join("package");
+        fs::create_dir_all(package_dir.join("sources")).unwrap();
+        let options = cli::CLIOptions {
+            out_mutant_dir: Some(package_dir.join("sources/../sources/mutants")),
+            ..Default::default()
+        };
+        let config = Configuration::new(options.clone(), Some(package_dir.clone()));
+        assert!(setup_output_dir(&config).is_err());
+
+        // The package root is fine, as only the source directories are compiled.
+        let options = cli::CLIOptions {
+            out_mutant_dir: Some(package_dir.join("mutants")),
+            ..Default::default()
+        };
+        let config = Configuration::new(options, Some(package_dir.clone()));
+        assert!(setup_output_dir(&config).is_ok());
+    }
+
+    #[test]
+    fn setup_output_dir_errors_if_directory_contains_package() {
+        let temp_dir = tempdir().unwrap();
+        let package_dir = temp_dir.path().join("package");
+        fs::create_dir_all(package_dir.join("sources")).unwrap();
+        let options = cli::CLIOptions {
+            out_mutant_dir: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let config = Configuration::new(options, Some(package_dir.clone()));
+        assert!(setup_output_dir(&config).is_err());
+        assert!(package_dir.exists());
+
+        let options = cli::CLIOptions {
+            out_mutant_dir: Some(package_dir.join("sources/mutants")),
+            allow_output_in_package: true,
+            ..Default::default()
+        };
+        let config = Configuration::new(options, Some(package_dir));
+        assert!(setup_output_dir(&config).is_ok());
+    }
 }
@@ -4,17 +4,40 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json;
+use sha2::{Digest, Sha256};
 use std::{
+    collections::BTreeSet,
+    fmt,
     io::{Error, ErrorKind, Result, Write},
     path::{Path, PathBuf},
 };
 
+/// The version of the mutator report schema.
+pub const MUTATOR_REPORT_SCHEMA_VERSION: u32 = 1;
+
 /// The `Report` struct represents a report of mutations.
 /// It contains a vector of `MutationReport` instances.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Report {
+    /// The version of the report schema. Reports saved before the version was recorded have the
+    /// same schema as the version 1.
+    #[serde(default = "legacy_schema_version")]
+    schema_version: u32,
     /// The vector of `ReportEntry` instances.
     mutants: Vec<MutationReport>,
+    /// The number of generated mutants which failed the verification (didn't compile).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    invalid_mutants: usize,
+    /// The number of generated mutants in code not covered by unit tests.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    uncovered_mutants: usize,
+    /// Mutants which failed the verification, with the compiler diagnostics. Recorded only with
+    /// the `--keep-stillborn` option.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    stillborn: Vec<MutationReport>,
+    /// Non-trivial functions without any mutation site, which the mutation score doesn't assess.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    unassessed_functions: Vec<UnassessedFunction>,
 }
 
 impl Report {
@@ -22,16 +45,81 @@ impl Report {
     #[must_use]
     pub fn new() -> Self {
         Self {
+            schema_version: MUTATOR_REPORT_SCHEMA_VERSION,
             mutants: Vec::new(),
+            invalid_mutants: 0,
+            uncovered_mutants: 0,
+            stillborn: Vec::new(),
+            unassessed_functions: Vec::new(),
         }
     }
 
+    /// Records the number of mutants which were generated but are not present in the report,
+    /// as they didn't compile (`invalid`) or their code isn't covered by unit tests (`uncovered`).
+    pub fn set_discarded_mutants(&mut self, invalid: usize, uncovered: usize) {
+        self.invalid_mutants = invalid;
+        self.uncovered_mutants = uncovered;
+    }
+
+    /// Returns the number of generated mutants which failed the verification.
+    #[must_use]
+    pub fn invalid_mutants(&self) -> usize {
+        self.invalid_mutants
+    }
+
+    /// Returns the number of generated mutants in code not covered by unit tests.
+    #[must_use]
+    pub fn uncovered_mutants(&self) -> usize {
+        self.uncovered_mutants
+    }
+
     /// Adds a new `MutationReport` to the report.
     pub fn add_entry(&mut self, entry: MutationReport) {
         trace!("Adding a mutant to the report: {entry:?}");
         self.mutants.push(entry);
     }
 
+    /// Adds a mutant which failed the verification to the report.
+    pub fn add_stillborn(&mut self, entry: MutationReport) {
+        trace!("Adding a stillborn mutant to the report: {entry:?}");
+        self.stillborn.push(entry);
+    }
+
+    /// Returns the mutants which failed the verification (if recorded).
+    #[must_use]
+    pub fn get_stillborn(&self) -> &Vec<MutationReport> {
+        &self.stillborn
+    }
+
+    /// Keeps only the mutants (including the stillborn ones) whose IDs are listed, e.g. when
+    /// evaluating a subset of previously generated mutants.
+    ///
+    /// Returns the number of the listed IDs which don't belong to any mutant.
+    pub fn retain_listed_mutants(&mut self, listed: &BTreeSet<String>) -> usize {
+        let is_listed =
+            |entry: &MutationReport| entry.mutant_id().is_some_and(|id| listed.contains(id));
+        self.mutants.retain(is_listed);
+        self.stillborn.retain(is_listed);
+        listed.len() - self.mutants.len() - self.stillborn.len()
+    }
+
+    /// Returns the mutants for modification, e.g. re-mapping them to the reformatted sources.
+    #[must_use]
+    pub fn get_mutants_mut(&mut self) -> &mut [MutationReport] {
+        &mut self.mutants
+    }
+
+    /// Records the functions without any mutation site.
+    pub fn set_unassessed_functions(&mut self, functions: Vec<UnassessedFunction>) {
+        self.unassessed_functions = functions;
+    }
+
+    /// Returns the non-trivial functions without any mutation site.
+    #[must_use]
+    pub fn get_unassessed_functions(&self) -> &[UnassessedFunction] {
+        &self.unassessed_functions
+    }
+
     /// Saves the `Report` as a JSON file.
     ///
     /// # Errors
@@ -55,7 +143,19 @@ impl Report {
 
         let file = std::fs::File::open(path)?;
 
-        serde_json::from_reader(file).map_err(|e| Error::new(ErrorKind::Other, e))
+        let report: Self =
+            serde_json::from_reader(file).map_err(|e| Error::new(ErrorKind::Other, e))?;
+        if report.schema_version > MUTATOR_REPORT_SCHEMA_VERSION {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "the report has schema version {}, but only versions up to \
+                     {MUTATOR_REPORT_SCHEMA_VERSION} are supported",
+                    report.schema_version
+                ),
+            ));
+        }
+        Ok(report)
     }
 
     /// Saves the `Report` as a text file.
@@ -107,6 +207,16 @@ impl Report {
     }
 }
 
+/// The schema version of reports saved before the version was recorded.
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// Used to skip serialization of the zero counters, to keep the report backwards compatible.
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
 impl Default for Report {
     fn default() -> Self {
         Self::new()
@@ -115,7 +225,7 @@ impl Default for Report {
 
 /// The `Range` struct represents a range with a start and end.
 /// It is used to represent the location of a mutation inside the source file.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Range {
     /// The start of the range.
     start: usize,
@@ -133,6 +243,18 @@ impl Range {
         assert!(start <= end);
         Self { start, end }
     }
+
+    /// Returns the start of the range.
+    #[must_use]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end of the range.
+    #[must_use]
+    pub fn end(&self) -> usize {
+        self.end
+    }
 }
 
 /// The `Mutation` struct represents a modification that was applied to a file.
@@ -168,6 +290,12 @@ impl Mutation {
         }
     }
 
+    /// Returns the location of the modification.
+    #[must_use]
+    pub fn get_range(&self) -> Range {
+        self.changed_place
+    }
+
     /// Returns the original value.
     #[must_use]
     pub fn get_original_value(&self) -> &str {
@@ -204,6 +332,29 @@ pub struct MutationReport {
     mutations: Vec<Mutation>,
     /// The diff between the original and mutated file.
     diff: String,
+    /// Labels attached to the mutant by the label rules from the configuration file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    labels: Vec<String>,
+    /// The compiler diagnostics of the mutant which failed the verification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    diagnostics: Option<String>,
+    /// The targets of the compiler matrix under which the mutant doesn't compile, while it
+    /// compiles under the other ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    stillborn_under: Vec<String>,
+    /// The content hash of the original file the mutant was generated from, so the changes of the
+    /// sources made since then are detected. Missing in the reports of the older versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_hash: Option<String>,
+}
+
+/// Returns the content hash (the hex-encoded SHA-256 digest) of the source file.
+#[must_use]
+pub fn content_hash(source: &str) -> String {
+    Sha256::digest(source.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
 }
 
 impl MutationReport {
@@ -226,6 +377,10 @@ impl MutationReport {
             function_name: function_name.to_owned(),
             mutations: vec![],
             diff: patch.to_string(),
+            labels: vec![],
+            diagnostics: None,
+            stillborn_under: vec![],
+            original_hash: Some(content_hash(original_source)),
         }
     }
 
@@ -241,6 +396,16 @@ impl MutationReport {
         &self.mutant_path
     }
 
+    /// Return the unique mutant identifier, which is a part of the mutant file name.
+    #[must_use]
+    pub fn mutant_id(&self) -> Option<&str> {
+        self.mutant_path
+            .file_stem()?
+            .to_str()?
+            .rsplit_once("_mutant_")
+            .map(|(_, id)| id)
+    }
+
     /// Return the original file path.
     #[must_use]
     pub fn original_file_path(&self) -> &PathBuf {
@@ -270,6 +435,169 @@ impl MutationReport {
     pub fn get_mutations(&self) -> &Vec<Mutation> {
         &self.mutations
     }
+
+    /// Sets the labels of the mutant.
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        self.labels = labels;
+    }
+
+    /// Return the labels of the mutant.
+    #[must_use]
+    pub fn get_labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Sets the compiler diagnostics of the mutant which failed the verification.
+    pub fn set_diagnostics(&mut self, diagnostics: Option<String>) {
+        self.diagnostics = diagnostics;
+    }
+
+    /// Return the compiler diagnostics of the mutant which failed the verification.
+    #[must_use]
+    pub fn get_diagnostics(&self) -> Option<&str> {
+        self.diagnostics.as_deref()
+    }
+
+    /// Sets the targets of the compiler matrix under which the mutant doesn't compile.
+    pub fn set_stillborn_under(&mut self, targets: Vec<String>) {
+        self.stillborn_under = targets;
+    }
+
+    /// Return the targets of the compiler matrix under which the mutant doesn't compile, if it
+    /// compiles under the other targets.
+    #[must_use]
+    pub fn get_stillborn_under(&self) -> &[String] {
+        &self.stillborn_under
+    }
+
+    /// Return the content hash of the original file the mutant was generated from, if recorded.
+    #[must_use]
+    pub fn get_original_hash(&self) -> Option<&str> {
+        self.original_hash.as_deref()
+    }
+
+    /// Recovers the original source from the mutated one by reverting the modifications.
+    ///
+    /// Returns `None` if the modifications don't fit the mutated source.
+    #[must_use]
+    pub fn original_source(&self, mutated_source: &str) -> Option<String> {
+        let mut mutations = self.mutations.iter().collect::<Vec<_>>();
+        mutations.sort_by_key(|mutation| mutation.get_range());
+
+        let mut original = String::new();
+        // The positions after the previous modification in the mutated and the original source.
+        let (mut mutated_pos, mut original_pos) = (0, 0);
+        for mutation in mutations {
+            let range = mutation.get_range();
+            let start = mutated_pos + range.start().checked_sub(original_pos)?;
+            original.push_str(mutated_source.get(mutated_pos..start)?);
+            original.push_str(mutation.get_original_value());
+            mutated_pos = start + mutation.get_new_value().len();
+            original_pos = range.end();
+        }
+        original.push_str(mutated_source.get(mutated_pos..)?);
+        Some(original)
+    }
+
+    /// Moves the mutant to the new mutant file generated from the new original source, with the
+    /// modifications at the new positions.
+    pub fn relocate(
+        &mut self,
+        mutant_path: &Path,
+        original_source: &str,
+        mutated_source: &str,
+        mutations: Vec<Mutation>,
+    ) {
+        self.mutant_path = mutant_path.to_path_buf();
+        self.mutations = mutations;
+        self.diff = diffy::create_patch(original_source, mutated_source).to_string();
+        self.original_hash = Some(content_hash(original_source));
+    }
+}
+
+/// A non-trivial function to which no mutation operator applies.
+///
+/// Such functions contribute nothing to the mutation score, so they are reported to make clear
+/// that the score doesn't cover them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnassessedFunction {
+    /// The path to the original file.
+    pub original_file: PathBuf,
+    /// The name of the module.
+    pub module_name: String,
+    /// The name of the function.
+    pub function_name: String,
+}
+
+impl UnassessedFunction {
+    /// Returns the qualified name of the function (`module::function`).
+    #[must_use]
+    pub fn qname(&self) -> String {
+        format!("{}::{}", self.module_name, self.function_name)
+    }
+}
+
+/// Statistics of the mutant generation, printed before the mutants are tested.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct GenerationSummary {
+    /// The number of places where the mutation operators can be applied.
+    pub sites: usize,
+    /// The number of non-trivial functions without any mutation site.
+    pub unassessed_functions: usize,
+    /// The number of mutants created by applying the operators.
+    pub generated: usize,
+    /// The number of mutants in code not covered by unit tests.
+    pub uncovered: usize,
+    /// The number of mutants dropped by the `--max-mutants-per-file` option.
+    pub capped: usize,
+    /// The number of mutants dropped by the downsampling.
+    pub downsampled: usize,
+    /// The number of mutants not listed in the `--only-mutants` file. Set only when the file is
+    /// used.
+    pub unlisted: Option<usize>,
+    /// The number of mutants which failed the verification (didn't compile).
+    pub stillborn: usize,
+    /// The number of mutants compiling under some targets of the compiler matrix but not the
+    /// others. Set only when the matrix is used.
+    pub version_dependent: Option<usize>,
+    /// The number of mutants saved to the output directory.
+    pub kept: usize,
+}
+
+impl fmt::Display for GenerationSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut filtered = format!(
+            "{} (uncovered code: {}, per-file cap: {}, downsampling: {}",
+            self.uncovered + self.capped + self.downsampled + self.unlisted.unwrap_or_default(),
+            self.uncovered,
+            self.capped,
+            self.downsampled
+        );
+        if let Some(unlisted) = self.unlisted {
+            filtered += &format!(", not listed: {unlisted}");
+        }
+        filtered.push(')');
+        let mut rows = vec![
+            ("mutation sites found:", self.sites.to_string()),
+            (
+                "functions without sites:",
+                self.unassessed_functions.to_string(),
+            ),
+            ("mutants generated:", self.generated.to_string()),
+            ("filtered out:", filtered),
+            ("stillborn (not compiling):", self.stillborn.to_string()),
+        ];
+        if let Some(version_dependent) = self.version_dependent {
+            rows.push(("version-dependent:", version_dependent.to_string()));
+        }
+        rows.push(("mutants to test:", self.kept.to_string()));
+
+        writeln!(f, "Mutant generation summary:")?;
+        for (label, value) in rows {
+            writeln!(f, "  {label:<27}{value}")?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -280,7 +608,10 @@ mod tests {
     #[test]
     fn test_report() {
         let mut report = Report::new();
-        assert_eq!(report.to_json().unwrap(), "{\n  \"mutants\": []\n}");
+        assert_eq!(
+            report.to_json().unwrap(),
+            "{\n  \"schema_version\": 1,\n  \"mutants\": []\n}"
+        );
 
         let range = Range::new(0, 10);
         let modification = Mutation::new(
@@ -302,8 +633,109 @@ mod tests {
         report.add_entry(report_entry.clone());
         assert_eq!(
             report.to_json().unwrap(),
-            "{\n  \"mutants\": [\n    {\n      \"mutant_path\": \"file\",\n      \"original_file\": \"original_file\",\n      \"module_name\": \"module\",\n      \"function_name\": \"function\",\n      \"mutations\": [\n        {\n          \"changed_place\": {\n            \"start\": 0,\n            \"end\": 10\n          },\n          \"operator_name\": \"operator\",\n          \"old_value\": \"old\",\n          \"new_value\": \"new\"\n        }\n      ],\n      \"diff\": \"--- original\\n+++ modified\\n@@ -1 +1 @@\\n-diff\\n+\\n\"\n    }\n  ]\n}"
+            "{\n  \"schema_version\": 1,\n  \"mutants\": [\n    {\n      \"mutant_path\": \"file\",\n      \"original_file\": \"original_file\",\n      \"module_name\": \"module\",\n      \"function_name\": \"function\",\n      \"mutations\": [\n        {\n          \"changed_place\": {\n            \"start\": 0,\n            \"end\": 10\n          },\n          \"operator_name\": \"operator\",\n          \"old_value\": \"old\",\n          \"new_value\": \"new\"\n        }\n      ],\n      \"diff\": \"--- original\\n+++ modified\\n@@ -1 +1 @@\\n-diff\\n+\\n\"\n    }\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn unassessed_functions_are_recorded() {
+        let mut report = Report::new();
+        report.set_unassessed_functions(vec![UnassessedFunction {
+            original_file: PathBuf::from("sources/Vault.move"),
+            module_name: "Vault".to_owned(),
+            function_name: "balance_of".to_owned(),
+        }]);
+        let json = report.to_json().unwrap();
+        assert!(json.contains("\"unassessed_functions\": ["));
+
+        let loaded: Report = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.get_unassessed_functions()[0].qname(),
+            "Vault::balance_of"
+        );
+    }
+
+    #[test]
+    fn listed_mutants_are_retained() {
+        let entry = |path: &str| {
+            MutationReport::new(
+                Path::new(path),
+                Path::new("sources/Sum.move"),
+                "Sum",
+                "sum",
+                "\n",
+                "diff\n",
+            )
+        };
+        let mut report = Report::new();
+        report.add_entry(entry("mutants_output/sources/Sum_mutant_1f2e.move"));
+        report.add_entry(entry("mutants_output/sources/Sum_mutant_a81c.move"));
+        report.add_entry(entry("file"));
+        report.add_stillborn(entry("mutants_output/sources/Sum_mutant_3f2a.move"));
+
+        let listed = BTreeSet::from(["a81c".to_owned(), "3f2a".to_owned(), "ffff".to_owned()]);
+        assert_eq!(report.retain_listed_mutants(&listed), 1);
+        assert_eq!(report.get_mutants().len(), 1);
+        assert_eq!(report.get_mutants()[0].mutant_id(), Some("a81c"));
+        assert_eq!(report.get_stillborn().len(), 1);
+    }
+
+    #[test]
+    fn test_mutant_id() {
+        let report_entry = MutationReport::new(
+            Path::new("mutants_output/sources/Sum_mutant_1f2e.move"),
+            Path::new("sources/Sum.move"),
+            "module",
+            "function",
+            "\n",
+            "diff\n",
+        );
+        assert_eq!(report_entry.mutant_id(), Some("1f2e"));
+
+        let report_entry = MutationReport::new(
+            Path::new("file"),
+            Path::new("original_file"),
+            "module",
+            "function",
+            "\n",
+            "diff\n",
         );
+        assert_eq!(report_entry.mutant_id(), None);
+    }
+
+    #[test]
+    fn original_source_is_recovered() {
+        let original = "fun f(a: u64): u64 { a + 1 }";
+        let mutated = "fun f(a: u64): u64 { a - 1000 }";
+        let mut report_entry = MutationReport::new(
+            Path::new("file"),
+            Path::new("original_file"),
+            "module",
+            "function",
+            mutated,
+            original,
+        );
+        report_entry.add_modification(Mutation::new(
+            Range::new(25, 26),
+            "literal_replacement".to_owned(),
+            "1".to_owned(),
+            "1000".to_owned(),
+        ));
+        report_entry.add_modification(Mutation::new(
+            Range::new(23, 24),
+            "binary_operator_replacement".to_owned(),
+            "+".to_owned(),
+            "-".to_owned(),
+        ));
+        assert_eq!(
+            report_entry.original_source(mutated).as_deref(),
+            Some(original)
+        );
+        assert_eq!(
+            report_entry.get_original_hash(),
+            Some(content_hash(original).as_str())
+        );
+        assert!(report_entry.original_source("fun").is_none());
     }
 
     #[test]
@@ -374,4 +806,41 @@ mod tests {
         let path = Path::new("non_existent_directory/test_report.txt");
         report.save_to_text_file(path).unwrap();
     }
+
+    #[test]
+    fn generation_summary_lists_filtered_mutants() {
+        let mut summary = GenerationSummary {
+            sites: 12,
+            unassessed_functions: 1,
+            generated: 40,
+            uncovered: 4,
+            capped: 6,
+            downsampled: 0,
+            unlisted: None,
+            stillborn: 3,
+            version_dependent: None,
+            kept: 27,
+        };
+        let printed = summary.to_string();
+        assert!(printed.contains("  mutation sites found:      12\n"));
+        assert!(printed.contains("  functions without sites:   1\n"));
+        assert!(printed.contains(
+            "  filtered out:              10 (uncovered code: 4, per-file cap: 6, downsampling: 0)\n"
+        ));
+        assert!(printed.contains("  stillborn (not compiling): 3\n"));
+        assert!(printed.contains("  mutants to test:           27\n"));
+
+        assert!(!summary.to_string().contains("version-dependent"));
+
+        summary.version_dependent = Some(1);
+        assert!(summary
+            .to_string()
+            .contains("  version-dependent:         1\n"));
+
+        summary.unlisted = Some(5);
+        assert!(summary.to_string().contains(
+            "  filtered out:              15 (uncovered code: 4, per-file cap: 6, downsampling: 0, \
+             not listed: 5)\n"
+        ));
+    }
 }
@@ -0,0 +1,213 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::{BTreeSet, VecDeque},
+    path::PathBuf,
+};
+
+/// The mutation site used to spread the selected mutants.
+pub(crate) struct Site {
+    pub file: PathBuf,
+    pub function: String,
+    pub operator: String,
+}
+
+/// Mutants of a single function, grouped by the operator.
+struct FunctionMutants {
+    function: String,
+    operators: Vec<(String, VecDeque<usize>)>,
+    next: usize,
+}
+
+impl FunctionMutants {
+    /// Takes the mutant of the next operator which still has mutants left.
+    fn take(&mut self) -> Option<usize> {
+        let len = self.operators.len();
+        let idx = (0..len)
+            .map(|i| (self.next + i) % len)
+            .find(|&i| !self.operators[i].1.is_empty())?;
+        self.next = idx + 1;
+        self.operators[idx].1.pop_front()
+    }
+}
+
+/// Keeps at most `max` mutants of every file.
+///
+/// Instead of keeping the first `max` mutants, the kept mutants are spread across the functions
+/// and operators of the file: functions take turns, and every function takes turns among its
+/// operators. The selection is deterministic and the order of the mutants is preserved.
+pub(crate) fn cap_per_file<T>(mutants: Vec<T>, max: usize, site: impl Fn(&T) -> Site) -> Vec<T> {
+    let mut files: Vec<(PathBuf, Vec<FunctionMutants>)> = vec![];
+    for (idx, mutant) in mutants.iter().enumerate() {
+        let Site {
+            file,
+            function,
+            operator,
+        } = site(mutant);
+
+        let functions = match files.iter().position(|(f, _)| *f == file) {
+            Some(pos) => &mut files[pos].1,
+            None => {
+                files.push((file, vec![]));
+                &mut files.last_mut().expect("file was just added").1
+            },
+        };
+        let function = match functions.iter().position(|f| f.function == function) {
+            Some(pos) => &mut functions[pos],
+            None => {
+                functions.push(FunctionMutants {
+                    function,
+                    operators: vec![],
+                    next: 0,
+                });
+                functions.last_mut().expect("function was just added")
+            },
+        };
+        match function
+            .operators
+            .iter_mut()
+            .find(|(op, _)| *op == operator)
+        {
+            Some((_, indices)) => indices.push_back(idx),
+            None => function.operators.push((operator, VecDeque::from([idx]))),
+        }
+    }
+
+    let mut keep = vec![false; mutants.len()];
+    for (file, mut functions) in files {
+        let total: usize = functions
+            .iter()
+            .flat_map(|f| &f.operators)
+            .map(|(_, indices)| indices.len())
+            .sum();
+        if total > max {
+            info!(
+                "Keeping {max} of {total} mutants in {} (--max-mutants-per-file)",
+                file.display()
+            );
+        }
+
+        let mut kept = 0;
+        while kept < total.min(max) {
+            for function in &mut functions {
+                if kept == max {
+                    break;
+                }
+                if let Some(idx) = function.take() {
+                    keep[idx] = true;
+                    kept += 1;
+                }
+            }
+        }
+    }
+
+    mutants
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(mutant, keep)| keep.then_some(mutant))
+        .collect()
+}
+
+/// Keeps `keep` randomly chosen mutants, preferring the mutants with the IDs in `preferred`.
+///
+/// The preferred mutants (e.g. the ones tested in the previous run) are chosen first and only
+/// the remaining slots are filled with other mutants, so the sample stays the same as long as
+/// the mutants exist. The order of the mutants is preserved.
+pub(crate) fn downsample<T>(
+    mutants: Vec<T>,
+    keep: usize,
+    preferred: &BTreeSet<String>,
+    id: impl Fn(&T) -> String,
+    rng: &mut impl Rng,
+) -> Vec<T> {
+    let (preferred, others): (Vec<usize>, Vec<usize>) =
+        (0..mutants.len()).partition(|&idx| preferred.contains(&id(&mutants[idx])));
+
+    let mut selected = vec![false; mutants.len()];
+    let reselected = preferred.len().min(keep);
+    for &idx in preferred
+        .choose_multiple(rng, reselected)
+        .chain(others.choose_multiple(rng, keep - reselected))
+    {
+        selected[idx] = true;
+    }
+    if !preferred.is_empty() {
+        info!(
+            "Re-selected {reselected} of {keep} kept mutants from the previous run (--stable-sampling)"
+        );
+    }
+
+    mutants
+        .into_iter()
+        .zip(selected)
+        .filter_map(|(mutant, selected)| selected.then_some(mutant))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn downsampling_prefers_previously_selected_mutants() {
+        let mutants = ["a1", "b2", "c3", "d4", "e5", "f6"];
+        let previous = BTreeSet::from(["b2".to_owned(), "e5".to_owned(), "gone".to_owned()]);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let kept = downsample(
+                mutants.to_vec(),
+                3,
+                &previous,
+                |id| id.to_string(),
+                &mut rng,
+            );
+            assert_eq!(kept.len(), 3);
+            assert!(kept.contains(&"b2") && kept.contains(&"e5"), "{kept:?}");
+            // The order is preserved.
+            assert!(kept.windows(2).all(|w| w[0] < w[1]), "{kept:?}");
+        }
+
+        let kept = downsample(
+            mutants.to_vec(),
+            1,
+            &previous,
+            |id| id.to_string(),
+            &mut rng,
+        );
+        assert!(kept == ["b2"] || kept == ["e5"], "{kept:?}");
+    }
+
+    #[test]
+    fn kept_mutants_are_spread_across_functions_and_operators() {
+        let mutants = [
+            ("a.move", "f", "binary"),
+            ("a.move", "f", "binary"),
+            ("a.move", "f", "binary"),
+            ("a.move", "f", "binary"),
+            ("a.move", "g", "literal"),
+            ("a.move", "g", "literal"),
+            ("a.move", "g", "unary"),
+            ("a.move", "g", "unary"),
+            ("b.move", "h", "binary"),
+            ("b.move", "h", "literal"),
+        ]
+        .into_iter()
+        .enumerate()
+        .collect::<Vec<_>>();
+
+        let kept = cap_per_file(mutants, 4, |(_, (file, function, operator))| Site {
+            file: PathBuf::from(file),
+            function: function.to_string(),
+            operator: operator.to_string(),
+        });
+        let kept = kept.into_iter().map(|(idx, _)| idx).collect::<Vec<_>>();
+        // Two mutants of `f`, one of each operator in `g`, and the whole small file.
+        assert_eq!(kept, vec![0, 1, 4, 6, 8, 9]);
+    }
+}
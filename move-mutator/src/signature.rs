@@ -0,0 +1,328 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mutate::contains_attribute;
+use anyhow::Context;
+use globset::{Glob, GlobMatcher};
+use move_model::{
+    model::FunctionEnv,
+    ty::{PrimitiveType, Type},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The signature of the mutated function, which decides which operators apply to the function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FunctionSignature {
+    /// The visibility: `public`, `friend`, `package` or `private`.
+    pub(crate) visibility: String,
+    /// Whether the function is an `entry` function.
+    pub(crate) is_entry: bool,
+    /// Whether the function is attributed with `#[view]`.
+    pub(crate) is_view: bool,
+    /// The parameter types, separated by commas (e.g. `&signer, u64`).
+    pub(crate) params: String,
+    /// The return type (`()` if the function returns nothing).
+    pub(crate) returns: String,
+    /// Whether the function returns a value.
+    pub(crate) returns_value: bool,
+    /// Whether the function returns `bool`.
+    pub(crate) returns_bool: bool,
+}
+
+impl FunctionSignature {
+    /// Reads the signature of the function.
+    pub(crate) fn of(function: &FunctionEnv<'_>) -> Self {
+        let visibility = if function.has_package_visibility() {
+            "package".to_owned()
+        } else {
+            format!("{:?}", function.visibility()).to_lowercase()
+        };
+        let display_ctx = function.get_type_display_ctx();
+        let params = function
+            .get_parameter_types()
+            .iter()
+            .map(|ty| ty.display(&display_ctx).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let result = function.get_result_type();
+        let sym_pool = function.module_env.symbol_pool();
+
+        Self {
+            visibility,
+            is_entry: function.is_entry(),
+            is_view: contains_attribute(function.get_attributes(), sym_pool, &["view"]).is_some(),
+            params,
+            returns: result.display(&display_ctx).to_string(),
+            returns_value: !matches!(&result, Type::Tuple(tys) if tys.is_empty()),
+            returns_bool: result == Type::Primitive(PrimitiveType::Bool),
+        }
+    }
+}
+
+/// The functions an operator applies to, declared by the operator next to its documentation.
+///
+/// Operators which make sense only for some kinds of functions declare it here, so they don't
+/// produce noise elsewhere. The projects can narrow it further with the operator gates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Applicability {
+    /// All functions.
+    AnyFunction,
+    /// Functions returning a value.
+    ReturningFunctions,
+    /// Functions returning `bool`.
+    BoolFunctions,
+    /// The `entry` functions.
+    EntryFunctions,
+}
+
+impl Applicability {
+    /// Checks if the function with the signature is applicable.
+    pub(crate) fn matches(&self, signature: &FunctionSignature) -> bool {
+        match self {
+            Applicability::AnyFunction => true,
+            Applicability::ReturningFunctions => signature.returns_value,
+            Applicability::BoolFunctions => signature.returns_bool,
+            Applicability::EntryFunctions => signature.is_entry,
+        }
+    }
+}
+
+impl fmt::Display for Applicability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Applicability::AnyFunction => "any function",
+            Applicability::ReturningFunctions => "functions returning a value",
+            Applicability::BoolFunctions => "functions returning `bool`",
+            Applicability::EntryFunctions => "entry functions",
+        })
+    }
+}
+
+/// A gate limiting the operators to the functions with the matching signature.
+///
+/// Gates are set in the configuration file, e.g.:
+/// ```toml
+/// [[operator-gates]]
+/// operator = "unary_operator_replacement"
+/// returns = "bool"
+/// ```
+/// The operators matching the `operator` pattern of some gate apply only to the functions matching
+/// all the given patterns of at least one such gate. Other operators are not affected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct OperatorGate {
+    /// Glob pattern of the gated operator names.
+    pub operator: String,
+    /// Glob pattern of the function name (`function` or `module::function`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// Glob pattern of the visibility: `public`, `friend`, `package` or `private`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    /// Whether the function must (or must not) be an `entry` function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<bool>,
+    /// Whether the function must (or must not) be a `#[view]` function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub view: Option<bool>,
+    /// Glob pattern of the parameter types, separated by commas (e.g. `&signer, *`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<String>,
+    /// Glob pattern of the return type (`()` if the function returns nothing).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub returns: Option<String>,
+}
+
+/// Keys of a single operator gate with the boolean values. All other keys have string values.
+pub(crate) const OPERATOR_GATE_BOOL_KEYS: &[&str] = &["entry", "view"];
+
+/// Keys of a single operator gate.
+pub(crate) const OPERATOR_GATE_KEYS: &[&str] = &[
+    "operator",
+    "function",
+    "visibility",
+    "entry",
+    "view",
+    "params",
+    "returns",
+];
+
+/// The compiled operator gate.
+#[derive(Debug)]
+struct Matcher {
+    operator: GlobMatcher,
+    function: Option<GlobMatcher>,
+    visibility: Option<GlobMatcher>,
+    entry: Option<bool>,
+    view: Option<bool>,
+    params: Option<GlobMatcher>,
+    returns: Option<GlobMatcher>,
+}
+
+/// Decides which operators apply to a function according to the operator gates.
+#[derive(Debug, Default)]
+pub(crate) struct OperatorGates {
+    matchers: Vec<Matcher>,
+}
+
+impl OperatorGates {
+    /// Compiles the operator gates.
+    ///
+    /// # Errors
+    /// Returns an error if any of the patterns is invalid.
+    pub(crate) fn new(gates: &[OperatorGate]) -> anyhow::Result<Self> {
+        let matchers = gates
+            .iter()
+            .map(|gate| {
+                let compile = |pattern: &str| {
+                    Glob::new(pattern)
+                        .map(|glob| glob.compile_matcher())
+                        .with_context(|| {
+                            format!(
+                                "invalid pattern `{pattern}` of the gate of operator `{}`",
+                                gate.operator
+                            )
+                        })
+                };
+                let compile_optional =
+                    |pattern: &Option<String>| pattern.as_deref().map(compile).transpose();
+                Ok(Matcher {
+                    operator: compile(&gate.operator)?,
+                    function: compile_optional(&gate.function)?,
+                    visibility: compile_optional(&gate.visibility)?,
+                    entry: gate.entry,
+                    view: gate.view,
+                    params: compile_optional(&gate.params)?,
+                    returns: compile_optional(&gate.returns)?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { matchers })
+    }
+
+    /// Checks if the operator applies to the function with the signature.
+    pub(crate) fn allows(
+        &self,
+        operator: &str,
+        signature: &FunctionSignature,
+        module: &str,
+        function: &str,
+    ) -> bool {
+        let qname = format!("{module}::{function}");
+        let mut gates = self
+            .matchers
+            .iter()
+            .filter(|matcher| matcher.operator.is_match(operator))
+            .peekable();
+        if gates.peek().is_none() {
+            return true;
+        }
+
+        gates.any(|matcher| {
+            matcher
+                .function
+                .as_ref()
+                .map_or(true, |m| m.is_match(function) || m.is_match(&qname))
+                && matcher
+                    .visibility
+                    .as_ref()
+                    .map_or(true, |m| m.is_match(&signature.visibility))
+                && matcher
+                    .entry
+                    .map_or(true, |entry| entry == signature.is_entry)
+                && matcher.view.map_or(true, |view| view == signature.is_view)
+                && matcher
+                    .params
+                    .as_ref()
+                    .map_or(true, |m| m.is_match(&signature.params))
+                && matcher
+                    .returns
+                    .as_ref()
+                    .map_or(true, |m| m.is_match(&signature.returns))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature(is_entry: bool, params: &str, returns: &str) -> FunctionSignature {
+        FunctionSignature {
+            visibility: "public".to_owned(),
+            is_entry,
+            is_view: false,
+            params: params.to_owned(),
+            returns: returns.to_owned(),
+            returns_value: returns != "()",
+            returns_bool: returns == "bool",
+        }
+    }
+
+    #[test]
+    fn operators_are_gated_by_signature() {
+        let gates = OperatorGates::new(&[
+            OperatorGate {
+                operator: "unary_*".to_owned(),
+                returns: Some("bool".to_owned()),
+                ..Default::default()
+            },
+            OperatorGate {
+                operator: "unary_*".to_owned(),
+                function: Some("Vault::is_*".to_owned()),
+                ..Default::default()
+            },
+            OperatorGate {
+                operator: "literal_replacement".to_owned(),
+                entry: Some(true),
+                params: Some("&signer, *".to_owned()),
+                ..Default::default()
+            },
+        ])
+        .unwrap();
+
+        let check = signature(false, "address", "bool");
+        let deposit = signature(true, "&signer, u64", "()");
+        let op = "unary_operator_replacement";
+        assert!(gates.allows(op, &check, "Vault", "has_balance"));
+        assert!(!gates.allows(op, &deposit, "Vault", "deposit"));
+        // Any of the gates of the operator is enough.
+        assert!(gates.allows(op, &deposit, "Vault", "is_frozen"));
+
+        assert!(gates.allows("literal_replacement", &deposit, "Vault", "deposit"));
+        assert!(!gates.allows("literal_replacement", &check, "Vault", "has_balance"));
+
+        // Operators without gates apply everywhere.
+        assert!(gates.allows(
+            "binary_operator_replacement",
+            &check,
+            "Vault",
+            "has_balance"
+        ));
+    }
+
+    #[test]
+    fn operator_applicability_is_checked() {
+        let check = signature(false, "address", "bool");
+        let deposit = signature(true, "&signer, u64", "()");
+        assert!(Applicability::AnyFunction.matches(&deposit));
+        assert!(Applicability::BoolFunctions.matches(&check));
+        assert!(!Applicability::ReturningFunctions.matches(&deposit));
+        assert!(Applicability::EntryFunctions.matches(&deposit));
+        assert!(!Applicability::EntryFunctions.matches(&check));
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        let err = OperatorGates::new(&[OperatorGate {
+            operator: "binary_*".to_owned(),
+            returns: Some("[bool".to_owned()),
+            ..Default::default()
+        }])
+        .unwrap_err();
+        assert!(err.to_string().contains("gate of operator `binary_*`"));
+    }
+}
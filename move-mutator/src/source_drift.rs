@@ -0,0 +1,284 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Detection of the source changes made since the mutants were generated.
+//!
+//! The previously generated mutants (`--use-generated-mutants`) are whole copies of the mutated
+//! source files, and their positions and diffs refer to the sources they were generated from. If
+//! the package has been reformatted in between (e.g. by `movefmt`), the mutants are re-mapped to
+//! the current sources. Any other change, including the whitespace inside the string literals, is
+//! refused, as the reported lines would be wrong.
+
+use crate::report::{content_hash, Mutation, Range, Report};
+use anyhow::{anyhow, bail, Context};
+use mutator_common::tmp_package_dir::strip_path_prefix;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The subdirectory of the output directory with the re-mapped mutants.
+const REMAPPED_DIR: &str = "remapped_mutants";
+
+/// Checks the previously generated mutants against the current sources of the package and
+/// re-maps the mutants of the files which have only been reformatted since.
+///
+/// The re-mapped mutant files are written to the output directory. Mutants from the reports
+/// without the content hashes of the original files are left as they are.
+///
+/// Returns the number of re-mapped mutants.
+///
+/// # Errors
+///
+/// Returns an error if a source file has changed beyond whitespace, or the mutant files don't
+/// match the sources recorded in the report.
+pub fn remap_reformatted_mutants(
+    report: &mut Report,
+    package_path: &Path,
+    outdir: &Path,
+) -> anyhow::Result<usize> {
+    let mut current_sources = BTreeMap::<PathBuf, String>::new();
+    let mut remapped = 0;
+    for mutant in report.get_mutants_mut() {
+        let Some(hash) = mutant.get_original_hash() else {
+            continue;
+        };
+        let original_file = strip_path_prefix(mutant.original_file_path())?;
+        if !current_sources.contains_key(&original_file) {
+            let path = package_path.join(&original_file);
+            let source = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            current_sources.insert(original_file.clone(), source);
+        }
+        let current = &current_sources[&original_file];
+        if content_hash(current) == hash {
+            continue;
+        }
+
+        let mutant_path = mutant.mutant_path().clone();
+        let mutated = fs::read_to_string(&mutant_path)
+            .with_context(|| format!("failed to read {}", mutant_path.display()))?;
+        let original = mutant
+            .original_source(&mutated)
+            .filter(|original| content_hash(original) == hash)
+            .ok_or_else(|| {
+                anyhow!(
+                    "the mutant {} doesn't match the source it was generated from",
+                    mutant_path.display()
+                )
+            })?;
+        if let Some(line) = first_difference_line(&original, current) {
+            bail!(
+                "{} has changed since the mutants were generated (first difference at line \
+                 {line}). Only reformatting can be re-mapped, generate the mutants again",
+                original_file.display()
+            );
+        }
+
+        let (mutated, mutations) = remap_mutations(&original, current, mutant.get_mutations());
+        let remapped_dir = outdir.join(REMAPPED_DIR);
+        fs::create_dir_all(&remapped_dir)?;
+        let remapped_path = remapped_dir.join(
+            mutant_path
+                .file_name()
+                .ok_or_else(|| anyhow!("invalid mutant path {}", mutant_path.display()))?,
+        );
+        fs::write(&remapped_path, &mutated)?;
+        mutant.relocate(&remapped_path, current, &mutated, mutations);
+        remapped += 1;
+    }
+    Ok(remapped)
+}
+
+/// Returns the byte offsets of the significant characters of the source: the non-whitespace
+/// characters and all the characters of the string literals, as reformatting never changes the
+/// whitespace inside them.
+///
+/// The comments are skipped over, so the quotes inside them don't start string literals.
+fn significant_offsets(source: &str) -> Vec<usize> {
+    let mut offsets = vec![];
+    let mut chars = source.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        offsets.push(idx);
+        match (c, chars.peek().map(|(_, next)| *next)) {
+            // A string literal, including the byte (`b"..."`) and hex (`x"..."`) strings.
+            ('"', _) => {
+                while let Some((idx, c)) = chars.next() {
+                    offsets.push(idx);
+                    match c {
+                        '\\' => offsets.extend(chars.next().map(|(idx, _)| idx)),
+                        '"' => break,
+                        _ => {},
+                    }
+                }
+            },
+            // The comments, to their end.
+            ('/', Some('/')) => {
+                while let Some((idx, c)) = chars.next_if(|(_, c)| *c != '\n') {
+                    if !c.is_whitespace() {
+                        offsets.push(idx);
+                    }
+                }
+            },
+            ('/', Some('*')) => {
+                offsets.extend(chars.next().map(|(idx, _)| idx));
+                let mut previous = None;
+                for (idx, c) in chars.by_ref() {
+                    if !c.is_whitespace() {
+                        offsets.push(idx);
+                    }
+                    if previous == Some('*') && c == '/' {
+                        break;
+                    }
+                    previous = Some(c);
+                }
+            },
+            _ => {},
+        }
+    }
+    offsets
+}
+
+/// Returns the line of the current source (starting from one) with the first difference from the
+/// original source, ignoring the whitespace outside the string literals. Returns `None` if the
+/// sources differ only in such whitespace.
+fn first_difference_line(original: &str, current: &str) -> Option<usize> {
+    let significant = |source: &str| {
+        significant_offsets(source)
+            .into_iter()
+            .map(|idx| (idx, source[idx..].chars().next().expect("char at offset")))
+            .collect::<Vec<_>>()
+    };
+    let original = significant(original);
+    let current_chars = significant(current);
+    let offset = match current_chars
+        .iter()
+        .zip(&original)
+        .find(|((_, current), (_, original))| current != original)
+    {
+        Some(((idx, _), _)) => *idx,
+        None if current_chars.len() == original.len() => return None,
+        // One source continues after the other ends.
+        None => current_chars
+            .get(original.len())
+            .map_or(current.len(), |(idx, _)| *idx),
+    };
+    Some(current[..offset].matches('\n').count() + 1)
+}
+
+/// Moves the modifications from the original source to the current source, which differs only in
+/// the whitespace outside the string literals, and applies them.
+///
+/// Returns the mutated current source and the modifications at the new positions.
+fn remap_mutations(
+    original: &str,
+    current: &str,
+    mutations: &[Mutation],
+) -> (String, Vec<Mutation>) {
+    let original_offsets = significant_offsets(original);
+    let current_offsets = significant_offsets(current);
+    // The offset in the current source before the same significant character as the original
+    // offset.
+    let map_start = |offset: usize| {
+        let idx = original_offsets.partition_point(|&o| o < offset);
+        current_offsets.get(idx).copied().unwrap_or(current.len())
+    };
+    // The offset in the current source after the same significant character as the original
+    // offset.
+    let map_end = |offset: usize, start: usize| {
+        let idx = original_offsets.partition_point(|&o| o < offset);
+        match idx.checked_sub(1).and_then(|idx| current_offsets.get(idx)) {
+            Some(&last) if last >= start => {
+                last + current[last..].chars().next().map_or(0, char::len_utf8)
+            },
+            _ => start,
+        }
+    };
+
+    let mut remapped = mutations
+        .iter()
+        .map(|mutation| {
+            let range = mutation.get_range();
+            let start = map_start(range.start());
+            let end = map_end(range.end(), start);
+            Mutation::new(
+                Range::new(start, end),
+                mutation.get_operator_name().to_owned(),
+                current[start..end].to_owned(),
+                mutation.get_new_value().to_owned(),
+            )
+        })
+        .collect::<Vec<_>>();
+    remapped.sort_by_key(Mutation::get_range);
+
+    let mut mutated = current.to_owned();
+    for mutation in remapped.iter().rev() {
+        let range = mutation.get_range();
+        mutated.replace_range(range.start()..range.end(), mutation.get_new_value());
+    }
+    (mutated, remapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGINAL: &str = "module 0x42::m {\n    fun f(a: u64): u64 { a + 1 }\n}\n";
+    const FORMATTED: &str = "module 0x42::m {\n    fun f(a: u64): u64 {\n        a + 1\n    }\n}\n";
+
+    #[test]
+    fn only_whitespace_changes_are_accepted() {
+        assert_eq!(first_difference_line(ORIGINAL, FORMATTED), None);
+        let edited = FORMATTED.replace("a + 1", "a + 2");
+        assert_eq!(first_difference_line(ORIGINAL, &edited), Some(3));
+        let extended = format!("{FORMATTED}module 0x42::n {{}}\n");
+        assert_eq!(first_difference_line(ORIGINAL, &extended), Some(6));
+    }
+
+    #[test]
+    fn whitespace_changes_in_string_literals_are_refused() {
+        let original = "fun f(): vector<u8> {\n    // \"quoted\n    b\"a b\"\n}\n";
+        let formatted = "fun f(): vector<u8> {\n    //  \"quoted\n    b\"a b\" }\n";
+        assert_eq!(first_difference_line(original, formatted), None);
+        let edited = original.replace("a b", "a  b");
+        assert_eq!(first_difference_line(original, &edited), Some(3));
+        let escaped = "/* \" */ b\"\\\" a\"";
+        assert_eq!(
+            first_difference_line(escaped, &escaped.replace(" a", "a")),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn mutations_are_moved_to_the_formatted_source() {
+        let start = ORIGINAL.find("a + 1").unwrap();
+        let mutations = [
+            Mutation::new(
+                Range::new(start, start + 5),
+                "binary_operator_swap".to_owned(),
+                "a + 1".to_owned(),
+                "1 + a".to_owned(),
+            ),
+            Mutation::new(
+                Range::new(start + 4, start + 5),
+                "literal_replacement".to_owned(),
+                "1".to_owned(),
+                "0".to_owned(),
+            ),
+        ];
+
+        let (mutated, remapped) = remap_mutations(ORIGINAL, FORMATTED, &mutations[..1]);
+        assert_eq!(mutated, FORMATTED.replace("a + 1", "1 + a"));
+        assert_eq!(remapped[0].get_original_value(), "a + 1");
+
+        let (mutated, remapped) = remap_mutations(ORIGINAL, FORMATTED, &mutations[1..]);
+        assert_eq!(mutated, FORMATTED.replace("a + 1", "a + 0"));
+        let range = remapped[0].get_range();
+        assert_eq!(&FORMATTED[range.start()..range.end()], "1");
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Snapshot testing of the mutation operators.
+//!
+//! An operator is applied to a small Move snippet and the produced mutants are rendered as a
+//! plain text snapshot, which is compared with the expected one stored next to the snippet.
+//! The snippet lists the applied operators in the annotation line, e.g.:
+//! ```move
+//! // operators: binary_operator_replacement, literal_replacement
+//! module 0x42::M {
+//!     fun inc(x: u64): u64 {
+//!         x + 1
+//!     }
+//! }
+//! ```
+//!
+//! The snapshots are updated instead of compared if the `UPBL` environment variable is set.
+
+use crate::{cli::CLIOptions, operators, report::Report, run_move_mutator};
+use anyhow::{bail, Context};
+use move_model::metadata::{CompilerVersion, LanguageVersion};
+use move_package::{BuildConfig, CompilerConfig};
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The prefix of the snippet line listing the applied operators.
+pub const OPERATORS_ANNOTATION: &str = "// operators:";
+
+/// The extension of the expected snapshot file.
+pub const SNAPSHOT_EXTENSION: &str = "exp";
+
+/// The environment variable which makes [`check_snapshot`] update the snapshot files.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "UPBL";
+
+/// The manifest of the package created for the snippet.
+const SNIPPET_MANIFEST: &str = "[package]\nname = \"snippet\"\nversion = \"0.0.0\"\n";
+
+/// Returns the operators listed in the annotation line of the snippet.
+///
+/// # Errors
+/// Returns an error if the annotation is missing or it lists an unknown operator.
+pub fn snippet_operators(source: &str) -> anyhow::Result<Vec<String>> {
+    let Some(list) = source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(OPERATORS_ANNOTATION))
+    else {
+        bail!("the snippet has no `{OPERATORS_ANNOTATION}` annotation");
+    };
+
+    let operators = list
+        .split(',')
+        .map(str::trim)
+        .filter(|op| !op.is_empty())
+        .map(|op| {
+            if operators::OPERATORS.contains(&op) {
+                Ok(op.to_owned())
+            } else {
+                Err(anyhow::anyhow!(
+                    "unknown operator '{op}', available operators: {}",
+                    operators::OPERATORS.join(", ")
+                ))
+            }
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if operators.is_empty() {
+        bail!("the `{OPERATORS_ANNOTATION}` annotation lists no operators");
+    }
+    Ok(operators)
+}
+
+/// Applies the annotated operators to the snippet and renders the produced mutants.
+///
+/// The snippet is compiled as the only source file of a package without dependencies, using the
+/// latest stable language version. Every line of the snapshot describes a single modification:
+/// its position in the snippet, the mutated function, the operator and the replaced code.
+///
+/// # Errors
+/// Returns an error if the annotation is invalid or the snippet can't be mutated.
+pub fn snapshot(source: &str) -> anyhow::Result<String> {
+    let operators = snippet_operators(source)?;
+
+    let dir = tempfile::tempdir()?;
+    let package_path = create_snippet_package(dir.path(), source)?;
+
+    let outdir = dir.path().join("mutants");
+    let options = CLIOptions {
+        out_mutant_dir: Some(outdir.clone()),
+        extra_operators: operators
+            .iter()
+            .filter(|op| operators::OPTIONAL_OPERATORS.contains(&op.as_str()))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+    let config = snippet_build_config();
+    run_move_mutator(options, &config, &package_path)?;
+
+    let report = Report::load_from_json_file(&outdir.join("report.json"))?;
+    let mut entries = report
+        .get_mutants()
+        .iter()
+        .flat_map(|mutant| {
+            mutant
+                .get_mutations()
+                .iter()
+                .map(move |mutation| (mutant.get_function_name(), mutation))
+        })
+        .filter(|(_, mutation)| {
+            operators
+                .iter()
+                .any(|op| op == mutation.get_operator_name())
+        })
+        .map(|(function, mutation)| {
+            let (line, column) = position(source, mutation.get_range().start());
+            (
+                (line, column),
+                format!(
+                    "{line}:{column} {function}: {} {:?} -> {:?}",
+                    mutation.get_operator_name(),
+                    mutation.get_original_value(),
+                    mutation.get_new_value()
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+    // The mutants of a single site keep the order in which the operator produced them.
+    entries.sort_by_key(|(position, _)| *position);
+
+    let mut snapshot = String::new();
+    for (_, entry) in entries {
+        let _ = writeln!(snapshot, "{entry}");
+    }
+    Ok(snapshot)
+}
+
+/// Creates the package with the snippet as its only source file in the given directory and
+/// returns the package path.
+///
+/// # Errors
+/// Returns an error if the package files can't be written.
+pub fn create_snippet_package(dir: &Path, source: &str) -> anyhow::Result<PathBuf> {
+    let package_path = dir.join("snippet");
+    fs::create_dir_all(package_path.join("sources"))?;
+    fs::write(package_path.join("Move.toml"), SNIPPET_MANIFEST)?;
+    fs::write(package_path.join("sources").join("snippet.move"), source)?;
+    Ok(package_path)
+}
+
+/// Returns the build configuration of the snippet package, which uses the latest stable language
+/// version.
+#[must_use]
+pub fn snippet_build_config() -> BuildConfig {
+    BuildConfig {
+        compiler_config: CompilerConfig {
+            language_version: Some(LanguageVersion::latest_stable()),
+            compiler_version: Some(CompilerVersion::latest_stable()),
+            ..Default::default()
+        },
+        skip_fetch_latest_git_deps: true,
+        ..Default::default()
+    }
+}
+
+/// Compares the snapshot of the snippet with the expected one, stored in the file with the
+/// [`SNAPSHOT_EXTENSION`] extension next to the snippet.
+///
+/// If the [`UPDATE_SNAPSHOTS_ENV`] environment variable is set, the expected snapshot is
+/// overwritten instead.
+///
+/// # Errors
+/// Returns an error with the diff of the snapshots if they differ.
+pub fn check_snapshot(snippet_path: &Path) -> anyhow::Result<()> {
+    let source = fs::read_to_string(snippet_path)
+        .with_context(|| format!("failed to read {}", snippet_path.display()))?;
+    let generated = snapshot(&source)
+        .with_context(|| format!("failed to mutate {}", snippet_path.display()))?;
+
+    let snapshot_path = snapshot_path(snippet_path);
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        fs::write(&snapshot_path, generated)?;
+        return Ok(());
+    }
+
+    let expected = fs::read_to_string(&snapshot_path).with_context(|| {
+        format!(
+            "failed to read {}, run with {UPDATE_SNAPSHOTS_ENV}=1 to create it",
+            snapshot_path.display()
+        )
+    })?;
+    if expected != generated {
+        bail!(
+            "the snapshot of {} has changed, run with {UPDATE_SNAPSHOTS_ENV}=1 to update it:\n{}",
+            snippet_path.display(),
+            diffy::create_patch(&expected, &generated)
+        );
+    }
+    Ok(())
+}
+
+/// Returns the path of the expected snapshot of the snippet.
+fn snapshot_path(snippet_path: &Path) -> PathBuf {
+    snippet_path.with_extension(SNAPSHOT_EXTENSION)
+}
+
+/// Returns the 1-based line and column of the byte offset in the source.
+fn position(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_are_read_from_the_annotation() {
+        let source =
+            "// operators: literal_replacement, struct_pack_field_swap\nmodule 0x42::M {}\n";
+        assert_eq!(snippet_operators(source).unwrap(), vec![
+            "literal_replacement",
+            "struct_pack_field_swap"
+        ]);
+
+        let err = snippet_operators("module 0x42::M {}\n").unwrap_err();
+        assert!(err.to_string().contains("no `// operators:` annotation"));
+        let err = snippet_operators("// operators: literal\n").unwrap_err();
+        assert!(err.to_string().contains("unknown operator 'literal'"));
+    }
+
+    #[test]
+    fn positions_are_one_based() {
+        let source = "module 0x42::M {\n    fun f(): u64 { 1 }\n}\n";
+        assert_eq!(position(source, 0), (1, 1));
+        assert_eq!(position(source, source.find('1').unwrap()), (2, 20));
+    }
+}
@@ -36,6 +36,7 @@ const PACKAGE_PATHS: &[&str] = &[
     "tests/move-assets/simple",
     "tests/move-assets/skip_mutation_examples",
     "tests/move-assets/check_swap_operator",
+    "tests/move-assets/check_move_2_features",
 ];
 
 // Check if the mutator works correctly on the basic packages.
@@ -77,7 +78,7 @@ fn check_mutator_verify_mutants_correctly() {
 
     let options = CLIOptions {
         out_mutant_dir: Some(outdir.clone()),
-        verify_mutants: true,
+        verify_mutants: Some(true),
         ..Default::default()
     };
 
@@ -186,7 +187,7 @@ fn check_mutator_fails_verify_file_without_package() {
     let options = CLIOptions {
         move_sources: vec![package_path.join("Sub.move")],
         out_mutant_dir: Some(outdir.clone()),
-        verify_mutants: true,
+        verify_mutants: Some(true),
         ..Default::default()
     };
 
@@ -248,6 +249,61 @@ fn check_mutator_cli_filters_functions_properly() {
     fs::remove_dir_all(package_path).unwrap();
 }
 
+// Check that the mutator fails when the filters match no mutation sites, unless it's allowed.
+#[test]
+fn check_mutator_fails_when_filters_match_nothing() {
+    let package_path = clone_project("tests/move-assets/simple");
+    let outdir = package_path.join("outdir");
+
+    let options = CLIOptions {
+        mutate_functions: FunctionFilter::Selected(vec!["non_existing_function".into()]),
+        out_mutant_dir: Some(outdir.clone()),
+        ..Default::default()
+    };
+
+    let config = quick_build_config();
+
+    let err = move_mutator::run_move_mutator(options.clone(), &config, &package_path)
+        .expect_err("empty filters should fail");
+    let msg = err.to_string();
+    assert!(msg.contains("match no mutation sites"));
+    assert!(msg.contains("Sum::sum"));
+
+    let options = CLIOptions {
+        allow_empty: Some(true),
+        ..options
+    };
+    let result = move_mutator::run_move_mutator(options, &config, &package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+    assert!(report.get_mutants().is_empty());
+    fs::remove_dir_all(package_path).unwrap();
+}
+
+// Check that the mutator suggests similar names for misspelled filters.
+#[test]
+fn check_mutator_suggests_similar_names_for_filters() {
+    let package_path = clone_project("tests/move-assets/simple");
+    let outdir = package_path.join("outdir");
+
+    let options = CLIOptions {
+        mutate_modules: ModuleFilter::Selected(vec!["Summ".into()]),
+        out_mutant_dir: Some(outdir),
+        ..Default::default()
+    };
+
+    let config = quick_build_config();
+
+    let err = move_mutator::run_move_mutator(options, &config, &package_path)
+        .expect_err("misspelled filters should fail");
+    assert!(err
+        .to_string()
+        .contains("no module named `Summ` found, did you mean `Sum`?"));
+    fs::remove_dir_all(package_path).unwrap();
+}
+
 // This test runs a mutator multiple times and checks number of binary swap operator mutants for
 // each run on specific function.
 #[test]
@@ -394,3 +450,284 @@ fn check_mutator_uses_skip_mutation_attribute_properly() {
     }
     fs::remove_dir_all(package_path).unwrap();
 }
+
+// Check that the existence checks are removed by the mutator and that only the checks are removed.
+#[test]
+fn check_mutator_removes_exists_checks() {
+    let config = quick_build_config();
+
+    let package_path = clone_project("tests/move-assets/basic_coin");
+    let outdir = package_path.join("outdir");
+
+    let options = CLIOptions {
+        mutate_modules: ModuleFilter::Selected(vec!["BasicCoin".to_owned()]),
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: Some(true),
+        ..Default::default()
+    };
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, &package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+
+    let exists_check_mutants = report
+        .get_mutants()
+        .iter()
+        .filter(|mutant| {
+            mutant
+                .get_mutations()
+                .iter()
+                .any(|m| m.get_operator_name() == "exists_check_removal")
+        })
+        .collect::<Vec<_>>();
+
+    // There is only one existence check in the `BasicCoin` module.
+    assert_eq!(exists_check_mutants.len(), 1);
+    let mutant = exists_check_mutants[0];
+    assert_eq!(mutant.get_function_name(), "publish_balance");
+    assert!(mutant.get_mutations()[0]
+        .get_original_value()
+        .contains("exists<Balance>"));
+    fs::remove_dir_all(package_path).unwrap();
+}
+
+// Check that the optional struct pack and destructuring operators are applied only when enabled.
+#[test]
+fn check_mutator_pack_operators_are_optional() {
+    let config = quick_build_config();
+
+    let operators = ["struct_pack_field_swap", "destructuring_binding_swap"];
+    let expected = [
+        (vec![], vec![]),
+        (
+            operators
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                ("new", "struct_pack_field_swap"),
+                ("bounds", "destructuring_binding_swap"),
+                ("ordered", "destructuring_binding_swap"),
+            ],
+        ),
+    ];
+
+    for (extra_operators, expected_mutants) in expected {
+        let package_path = clone_project("tests/move-assets/check_pack_operators");
+        let outdir = package_path.join("outdir");
+
+        let options = CLIOptions {
+            out_mutant_dir: Some(outdir.clone()),
+            verify_mutants: Some(true),
+            extra_operators,
+            ..Default::default()
+        };
+
+        let result = move_mutator::run_move_mutator(options.clone(), &config, &package_path);
+        assert!(result.is_ok());
+
+        let report_path = outdir.join("report.json");
+        let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+
+        let mut mutants = report
+            .get_mutants()
+            .iter()
+            .flat_map(|mutant| {
+                mutant
+                    .get_mutations()
+                    .iter()
+                    .filter(|m| operators.contains(&m.get_operator_name()))
+                    .map(|m| (mutant.get_function_name(), m.get_operator_name()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        mutants.sort();
+
+        let mut expected_mutants = expected_mutants;
+        expected_mutants.sort();
+        assert_eq!(mutants, expected_mutants);
+        fs::remove_dir_all(package_path).unwrap();
+    }
+}
+
+// Check that constructs introduced in Move 2 (enums, match, receiver-style calls and lambdas)
+// are mutated.
+#[test]
+fn check_mutator_mutates_move_2_features() {
+    let config = quick_build_config();
+
+    let package_path = clone_project("tests/move-assets/check_move_2_features");
+    let outdir = package_path.join("outdir");
+
+    let options = CLIOptions {
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: Some(true),
+        ..Default::default()
+    };
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, &package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+
+    for function in ["is_circle", "area", "total_area", "double"] {
+        assert!(
+            report
+                .get_mutants()
+                .iter()
+                .any(|mutant| mutant.get_function_name() == function),
+            "no mutants generated for {function}"
+        );
+    }
+
+    let variant_test_mutants = report
+        .get_mutants()
+        .iter()
+        .filter(|mutant| {
+            mutant
+                .get_mutations()
+                .iter()
+                .any(|m| m.get_operator_name() == "variant_test_negation")
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(variant_test_mutants.len(), 1);
+    assert_eq!(variant_test_mutants[0].get_function_name(), "is_circle");
+    fs::remove_dir_all(package_path).unwrap();
+}
+
+// Check that enum variants are swapped only with the variants with the same payload and that
+// match arms are mutated.
+#[test]
+fn check_mutator_enum_operators_work_correctly() {
+    let config = quick_build_config();
+
+    let package_path = clone_project("tests/move-assets/check_move_2_features");
+    let outdir = package_path.join("outdir");
+
+    let options = CLIOptions {
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: Some(true),
+        ..Default::default()
+    };
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, &package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+
+    let mut mutants = report
+        .get_mutants()
+        .iter()
+        .flat_map(|mutant| {
+            mutant
+                .get_mutations()
+                .iter()
+                .filter(|m| {
+                    ["enum_variant_swap", "match_arm_replacement"].contains(&m.get_operator_name())
+                })
+                .map(|m| (mutant.get_function_name(), m.get_operator_name()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    mutants.sort();
+
+    // Variants of `Shape` have different payloads, so only `Direction` variants are swapped.
+    // Arms of the `match` in `area` use the bound variables, so they can't be swapped.
+    assert_eq!(mutants, vec![
+        ("flip", "enum_variant_swap"),
+        ("flip", "enum_variant_swap"),
+        ("flip", "match_arm_replacement"),
+    ]);
+    fs::remove_dir_all(package_path).unwrap();
+}
+
+// Check that expressions inside the receiver of the receiver-style call (`x.f()`) are mutated and
+// the mutants compile.
+#[test]
+fn check_mutator_mutates_receiver_calls() {
+    let config = quick_build_config();
+
+    let package_path = clone_project("tests/move-assets/check_move_2_features");
+    let outdir = package_path.join("outdir");
+
+    let options = CLIOptions {
+        mutate_functions: FunctionFilter::Selected(vec!["grown_area".to_owned()]),
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: Some(true),
+        ..Default::default()
+    };
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, &package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+
+    // Only mutants which compile are kept, so `+` in the receiver must be replaced properly.
+    let replaced = report
+        .get_mutants()
+        .iter()
+        .flat_map(|mutant| mutant.get_mutations())
+        .filter(|m| m.get_operator_name() == "binary_operator_replacement")
+        .map(|m| m.get_original_value())
+        .collect::<Vec<_>>();
+    assert!(!replaced.is_empty());
+    assert!(replaced.iter().all(|op| *op == "+"));
+    fs::remove_dir_all(package_path).unwrap();
+}
+
+// Check if identical sources and configuration always produce the same mutants in the same order,
+// no matter how many threads are used.
+#[test]
+fn check_mutator_generation_is_deterministic() {
+    let config = quick_build_config();
+
+    for project in [
+        "tests/move-assets/simple",
+        "tests/move-assets/check_move_2_features",
+    ] {
+        let package_path = clone_project(project);
+
+        let generate = |threads: usize| {
+            let outdir = tempdir().unwrap().into_path();
+            let options = CLIOptions {
+                out_mutant_dir: Some(outdir.clone()),
+                verify_mutants: Some(true),
+                ..Default::default()
+            };
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| move_mutator::run_move_mutator(options, &config, &package_path))
+                .unwrap();
+
+            let report =
+                move_mutator::report::Report::load_from_json_file(&outdir.join("report.json"))
+                    .unwrap();
+            let mutants = report
+                .get_mutants()
+                .iter()
+                .map(|m| {
+                    (
+                        m.mutant_path().file_name().unwrap().to_owned(),
+                        m.get_module_name().to_owned(),
+                        m.get_function_name().to_owned(),
+                        m.get_diff().to_owned(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            fs::remove_dir_all(outdir).unwrap();
+            mutants
+        };
+
+        let first = generate(1);
+        assert!(!first.is_empty());
+        assert_eq!(first, generate(4), "mutants differ for {project}");
+        fs::remove_dir_all(package_path).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_mutator::{
+    cli::CLIOptions,
+    compiler::verify_mutant,
+    report::{MutationReport, Report},
+    testing::{create_snippet_package, snippet_build_config},
+};
+use proptest::{prelude::*, sample::select};
+use std::{fs, path::Path};
+use tempfile::tempdir;
+
+// Every generated package is compiled once per mutant, so only a few cases are run by default.
+const CASES: u32 = 8;
+
+const OPTIONAL_OPERATORS: &[&str] = &[
+    "struct_pack_field_swap",
+    "destructuring_binding_swap",
+    "loop_invariant_weakening",
+    "constant_replacement",
+    "off_by_one_replacement",
+];
+
+/// Mutates the source with all the operators and checks that:
+/// - the mutator neither panics nor fails,
+/// - every mutant is the original source with exactly the reported modification,
+/// - the kept mutants compile and the stillborn ones come with the compiler diagnostics.
+fn mutate_and_check(source: &str) {
+    let dir = tempdir().unwrap();
+    let package_path = create_snippet_package(dir.path(), source).unwrap();
+    let outdir = dir.path().join("mutants");
+
+    let options = CLIOptions {
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: Some(true),
+        keep_stillborn: Some(true),
+        extra_operators: OPTIONAL_OPERATORS.iter().map(ToString::to_string).collect(),
+        ..Default::default()
+    };
+    let config = snippet_build_config();
+    if let Err(e) = move_mutator::run_move_mutator(options, &config, &package_path) {
+        panic!("failed to mutate the source: {e:#}\n{source}");
+    }
+
+    let report = Report::load_from_json_file(&outdir.join("report.json")).unwrap();
+    for entry in report.get_mutants() {
+        let mutated = check_modification(entry);
+        if let Err(e) = verify_mutant(&config, &mutated, entry.original_file_path()) {
+            panic!("the kept mutant doesn't compile: {e:#}\n{mutated}");
+        }
+    }
+    for entry in report.get_stillborn() {
+        let mutated = check_modification(entry);
+        assert!(
+            entry.get_diagnostics().is_some(),
+            "the stillborn mutant has no diagnostics:\n{mutated}"
+        );
+        assert!(entry
+            .mutant_path()
+            .components()
+            .any(|c| c.as_os_str() == "stillborn"));
+    }
+}
+
+/// Checks that the mutant is the original source with the reported modification applied and
+/// returns the mutant source.
+fn check_modification(entry: &MutationReport) -> String {
+    let original = fs::read_to_string(entry.original_file_path()).unwrap();
+    let mutated = fs::read_to_string(entry.mutant_path()).unwrap();
+
+    let [mutation] = entry.get_mutations().as_slice() else {
+        panic!("expected a single modification: {entry:?}");
+    };
+    let range = mutation.get_range();
+    assert_eq!(
+        &original[range.start()..range.end()],
+        mutation.get_original_value()
+    );
+
+    let mut expected = original;
+    expected.replace_range(range.start()..range.end(), mutation.get_new_value());
+    assert_eq!(mutated, expected);
+    mutated
+}
+
+/// A variable or a literal.
+fn atom() -> BoxedStrategy<String> {
+    prop_oneof![
+        Just("a".to_owned()),
+        Just("b".to_owned()),
+        (0u64..1000).prop_map(|n| n.to_string()),
+    ]
+    .boxed()
+}
+
+/// An integer expression. The leftmost operand of every operation is a variable, so the
+/// compiler never folds (and rejects) constant expressions like `1 / 0`.
+fn u64_exp() -> BoxedStrategy<String> {
+    let leaf = prop_oneof![Just("a".to_owned()), Just("b".to_owned())];
+    leaf.prop_recursive(3, 16, 3, |inner| {
+        prop_oneof![
+            (
+                inner.clone(),
+                select(vec!["+", "-", "*", "/", "%", "&", "|", "^"]),
+                atom()
+            )
+                .prop_map(|(l, op, r)| format!("({l} {op} {r})")),
+            (bool_exp(inner.clone()), inner.clone(), inner)
+                .prop_map(|(c, t, e)| format!("(if ({c}) {t} else {e})")),
+        ]
+    })
+    .boxed()
+}
+
+/// A boolean expression over the given integer expressions.
+fn bool_exp(num: BoxedStrategy<String>) -> BoxedStrategy<String> {
+    let cmp = (num, select(vec!["==", "!=", "<", ">", "<=", ">="]), atom())
+        .prop_map(|(l, op, r)| format!("{l} {op} {r}"))
+        .boxed();
+    prop_oneof![
+        cmp.clone(),
+        cmp.clone().prop_map(|c| format!("!({c})")),
+        (cmp.clone(), select(vec!["&&", "||"]), cmp)
+            .prop_map(|(l, op, r)| format!("({l}) {op} ({r})")),
+    ]
+    .boxed()
+}
+
+fn statement() -> BoxedStrategy<String> {
+    let cond = || bool_exp(u64_exp());
+    prop_oneof![
+        u64_exp().prop_map(|e| format!("a = {e};")),
+        (cond(), 1u64..100).prop_map(|(c, code)| format!("if ({c}) abort {code};")),
+        (cond(), 1u64..100).prop_map(|(c, code)| format!("assert!({c}, {code});")),
+        (cond(), cond()).prop_map(|(c, b)| {
+            format!("loop {{ a = a + 1; if ({c}) continue; if ({b}) break; }};")
+        }),
+    ]
+    .boxed()
+}
+
+fn module_source() -> impl Strategy<Value = String> {
+    let function = (prop::collection::vec(statement(), 0..4), u64_exp());
+    prop::collection::vec(function, 1..3).prop_map(|functions| {
+        let mut source = "module 0x42::fuzz {\n".to_owned();
+        for (idx, (statements, result)) in functions.into_iter().enumerate() {
+            source.push_str(&format!("    fun f{idx}(a: u64, b: u64): u64 {{\n"));
+            for statement in statements {
+                source.push_str(&format!("        {statement}\n"));
+            }
+            source.push_str(&format!("        {result}\n    }}\n"));
+        }
+        source.push_str("}\n");
+        source
+    })
+}
+
+/// Runs the default number of cases, unless it's set by the `PROPTEST_CASES` variable.
+fn config() -> ProptestConfig {
+    if std::env::var_os("PROPTEST_CASES").is_some() {
+        ProptestConfig::default()
+    } else {
+        ProptestConfig::with_cases(CASES)
+    }
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn generated_sources_are_mutated_consistently(source in module_source()) {
+        mutate_and_check(&source);
+    }
+}
+
+// The operator snapshot corpus covers the syntax which is not generated above (structs, enums,
+// matches, global storage).
+#[test]
+fn corpus_sources_are_mutated_consistently() {
+    for entry in fs::read_dir(Path::new("tests/operators")).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "move") {
+            mutate_and_check(&fs::read_to_string(path).unwrap());
+        }
+    }
+}
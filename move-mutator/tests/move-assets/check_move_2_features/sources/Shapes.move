@@ -0,0 +1,73 @@
+module TestAccount::Shapes {
+    use std::vector;
+
+    enum Shape has drop {
+        Circle { radius: u64 },
+        Rectangle { width: u64, height: u64 },
+    }
+
+    enum Direction has drop {
+        Up,
+        Down,
+    }
+
+    fun flip(self: &Direction): Direction {
+        match (self) {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+        }
+    }
+
+    fun circle(radius: u64): Shape {
+        Shape::Circle { radius }
+    }
+
+    fun rectangle(width: u64, height: u64): Shape {
+        Shape::Rectangle { width, height }
+    }
+
+    fun is_circle(self: &Shape): bool {
+        self is Shape::Circle
+    }
+
+    fun area(self: &Shape): u64 {
+        match (self) {
+            Shape::Circle { radius } => 3 * *radius * *radius,
+            Shape::Rectangle { width, height } => *width * *height,
+        }
+    }
+
+    fun grown_area(width: u64, height: u64): u64 {
+        rectangle(width + 1, height).area()
+    }
+
+    fun total_area(shapes: &vector<Shape>): u64 {
+        let total = 0;
+        let i = 0;
+        while (i < vector::length(shapes)) {
+            total += vector::borrow(shapes, i).area();
+            i += 1;
+        };
+        total
+    }
+
+    inline fun apply(x: u64, f: |u64| u64): u64 {
+        f(x)
+    }
+
+    fun double(x: u64): u64 {
+        apply(x, |v| v * 2)
+    }
+
+    #[test]
+    fun area_test() {
+        assert!(circle(1).area() == 3, 0);
+        assert!(rectangle(2, 3).area() == 6, 0);
+        assert!(circle(1).is_circle(), 0);
+        assert!(!rectangle(2, 3).is_circle(), 0);
+        assert!(total_area(&vector[circle(1), rectangle(2, 3)]) == 9, 0);
+        assert!(grown_area(1, 3) == 6, 0);
+        assert!(double(2) == 4, 0);
+        assert!(Direction::Up.flip() is Direction::Down, 0);
+    }
+}
@@ -0,0 +1,21 @@
+module TestAccount::Range {
+    struct Range has copy, drop {
+        start: u64,
+        end: u64,
+        inclusive: bool,
+    }
+
+    public fun new(start: u64, end: u64): Range {
+        Range { start, end, inclusive: false }
+    }
+
+    public fun bounds(r: &Range): (u64, u64) {
+        let Range { start, end, inclusive: _ } = *r;
+        (start, end)
+    }
+
+    public fun ordered(a: u64, b: u64): (u64, u64) {
+        let (low, high) = if (a < b) { (a, b) } else { (b, a) };
+        (low, high)
+    }
+}
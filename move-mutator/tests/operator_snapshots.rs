@@ -0,0 +1,15 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_mutator::testing::check_snapshot;
+use std::path::Path;
+
+// Every snippet in the corpus is mutated and the mutants are compared with the expected snapshot.
+// Run with `UPBL=1` to update the snapshots after an intended change of the operators.
+fn test_operator_snapshot(path: &Path) -> datatest_stable::Result<()> {
+    check_snapshot(path)?;
+    Ok(())
+}
+
+datatest_stable::harness!(test_operator_snapshot, "tests/operators", r".*\.move$");
@@ -0,0 +1,12 @@
+// operators: abort_code_replacement
+module 0x42::abort_code_replacement {
+    const E_ZERO_AMOUNT: u64 = 2;
+    const E_FROZEN: u64 = 3;
+
+    fun withdraw(balance: u64, amount: u64, frozen: bool): u64 {
+        assert!(amount <= balance);
+        assert!(amount > 0, E_ZERO_AMOUNT);
+        if (frozen) abort E_FROZEN;
+        balance - amount
+    }
+}
@@ -0,0 +1,11 @@
+// operators: assert_abort_deletion
+module 0x42::assert_abort_deletion {
+    fun check(x: u64) {
+        if (x > 10) abort 42;
+        assert!(x > 1, 1);
+    }
+
+    fun value(x: u64): u64 {
+        if (x > 0) x else abort 2
+    }
+}
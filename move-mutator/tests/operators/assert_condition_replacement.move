@@ -0,0 +1,7 @@
+// operators: assert_condition_replacement
+module 0x42::assert_condition_replacement {
+    fun withdraw(balance: u64, amount: u64): u64 {
+        assert!(amount <= balance, 1);
+        balance - amount
+    }
+}
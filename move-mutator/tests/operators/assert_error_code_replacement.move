@@ -0,0 +1,10 @@
+// operators: assert_error_code_replacement
+module 0x42::assert_error_code_replacement {
+    const E_ZERO_AMOUNT: u64 = 2;
+
+    fun withdraw(balance: u64, amount: u64): u64 {
+        assert!(amount <= balance, 1);
+        assert!(amount > 0, E_ZERO_AMOUNT);
+        balance - amount
+    }
+}
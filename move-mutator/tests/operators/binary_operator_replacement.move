@@ -0,0 +1,14 @@
+// operators: binary_operator_replacement
+module 0x42::binary_operator_replacement {
+    fun sub(x: u64, y: u64): u64 {
+        x - y
+    }
+
+    fun is_zero(x: u64): bool {
+        x == 0
+    }
+
+    fun both(a: bool, b: bool): bool {
+        a && b
+    }
+}
@@ -0,0 +1,15 @@
+// operators: binary_operator_swap
+module 0x42::binary_operator_swap {
+    fun sub(x: u64, y: u64): u64 {
+        x - y
+    }
+
+    // Swapping the operands of commutative operators creates equivalent mutants.
+    fun add(x: u64, y: u64): u64 {
+        x + y
+    }
+
+    fun lt(x: u64, y: u64): bool {
+        x < y
+    }
+}
@@ -0,0 +1,25 @@
+// operators: bitwise_operator_replacement
+module 0x42::bitwise_operator_replacement {
+    fun set_flag(flags: u8, flag: u8): u8 {
+        flags | flag
+    }
+
+    fun has_flag(flags: u8, flag: u8): bool {
+        flags & flag != 0
+    }
+
+    fun toggle_flag(flags: u8, flag: u8): u8 {
+        flags ^ flag
+    }
+
+    // The arithmetic replacements are equivalent for the zero operand.
+    fun clear_all(flags: u8): u8 {
+        flags & 0
+    }
+
+    fun mask(flags: u8, mask: u8): u8 {
+        let masked = flags;
+        masked &= mask;
+        masked
+    }
+}
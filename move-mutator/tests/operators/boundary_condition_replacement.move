@@ -0,0 +1,19 @@
+// operators: boundary_condition_replacement
+module 0x42::boundary_condition_replacement {
+    fun can_withdraw(balance: u64, amount: u64): bool {
+        amount <= balance
+    }
+
+    fun is_expired(now: u64, deadline: u64): bool {
+        now > deadline
+    }
+
+    fun check_limit(amount: u64, limit: u64) {
+        assert!(amount < limit, 1);
+    }
+
+    // Other comparisons are mutated by the binary operator replacement.
+    fun is_zero(x: u64): bool {
+        x == 0
+    }
+}
@@ -0,0 +1,14 @@
+// operators: break_continue_replacement
+module 0x42::break_continue_replacement {
+    fun sum_odd(n: u64): u64 {
+        let sum = 0;
+        let i = 0;
+        loop {
+            i = i + 1;
+            if (i > n) break;
+            if (i % 2 == 0) continue;
+            sum = sum + i;
+        };
+        sum
+    }
+}
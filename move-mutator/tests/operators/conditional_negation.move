@@ -0,0 +1,15 @@
+// operators: conditional_negation
+module 0x42::conditional_negation {
+    fun max(x: u64, y: u64): u64 {
+        if (x > y) x else y
+    }
+
+    fun count_down(n: u64): u64 {
+        let steps = 0;
+        while (n > 0) {
+            n = n - 1;
+            steps = steps + 1;
+        };
+        steps
+    }
+}
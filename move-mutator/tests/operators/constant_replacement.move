@@ -0,0 +1,6 @@
+// operators: constant_replacement
+module 0x42::constant_replacement {
+    fun fee(amount: u64): u64 {
+        amount * 30 / 10000
+    }
+}
@@ -0,0 +1,12 @@
+// operators: delete_statement
+module 0x42::delete_statement {
+    struct R has key { v: u64 }
+
+    fun check(x: u64) {
+        if (x > 10) abort 42;
+    }
+
+    fun publish(account: &signer, v: u64) {
+        move_to(account, R { v });
+    }
+}
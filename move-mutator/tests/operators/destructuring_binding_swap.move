@@ -0,0 +1,17 @@
+// operators: destructuring_binding_swap
+module 0x42::destructuring_binding_swap {
+    struct Range has copy, drop {
+        start: u64,
+        end: u64,
+    }
+
+    fun bounds(r: &Range): (u64, u64) {
+        let Range { start, end } = *r;
+        (start, end)
+    }
+
+    fun ordered(a: u64, b: u64): (u64, u64) {
+        let (low, high) = if (a < b) { (a, b) } else { (b, a) };
+        (low, high)
+    }
+}
@@ -0,0 +1,17 @@
+// operators: enum_variant_swap
+module 0x42::enum_variant_swap {
+    enum Order has drop {
+        Buy { amount: u64 },
+        Sell { amount: u64 },
+        Cancel,
+    }
+
+    fun buy(amount: u64): Order {
+        Order::Buy { amount }
+    }
+
+    // No other variant has the same (empty) payload.
+    fun cancel(): Order {
+        Order::Cancel
+    }
+}
@@ -0,0 +1,9 @@
+// operators: exists_check_removal
+module 0x42::exists_check_removal {
+    struct R has key { v: u64 }
+
+    fun get(addr: address): u64 acquires R {
+        assert!(exists<R>(addr), 1);
+        borrow_global<R>(addr).v
+    }
+}
@@ -0,0 +1,6 @@
+// operators: if_else_replacement
+module 0x42::if_else_replacement {
+    fun max(x: u64, y: u64): u64 {
+        if (x > y) x else y
+    }
+}
@@ -0,0 +1,10 @@
+// operators: literal_replacement
+module 0x42::literal_replacement {
+    fun add_five(x: u64): u64 {
+        x + 5
+    }
+
+    fun yes(): bool {
+        true
+    }
+}
@@ -0,0 +1,17 @@
+// operators: loop_invariant_weakening
+module 0x42::loop_invariant_weakening {
+    fun sum(n: u64): u64 {
+        let sum = 0;
+        let i = 0;
+        while ({
+            spec {
+                invariant i <= n && sum == i * (i + 1) / 2;
+            };
+            i < n
+        }) {
+            i = i + 1;
+            sum = sum + i;
+        };
+        sum
+    }
+}
@@ -0,0 +1,14 @@
+// operators: match_arm_replacement
+module 0x42::match_arm_replacement {
+    enum Side has drop {
+        Buy,
+        Sell,
+    }
+
+    fun sign(side: Side): u64 {
+        match (side) {
+            Side::Buy => 1,
+            Side::Sell => 2,
+        }
+    }
+}
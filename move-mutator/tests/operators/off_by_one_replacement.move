@@ -0,0 +1,10 @@
+// operators: off_by_one_replacement
+module 0x42::off_by_one_replacement {
+    fun is_full(count: u64): bool {
+        count >= 10
+    }
+
+    fun fee(amount: u64): u64 {
+        amount * 30
+    }
+}
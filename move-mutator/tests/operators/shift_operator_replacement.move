@@ -0,0 +1,21 @@
+// operators: shift_operator_replacement
+module 0x42::shift_operator_replacement {
+    fun high_byte(value: u16): u8 {
+        ((value >> 8) as u8)
+    }
+
+    fun scale(value: u64, decimals: u8): u64 {
+        value << decimals
+    }
+
+    // The amount isn't decreased below zero.
+    fun identity(value: u64): u64 {
+        value << 0
+    }
+
+    fun unpack(value: u64, bits: u8): u64 {
+        let unpacked = value;
+        unpacked >>= bits;
+        unpacked
+    }
+}
@@ -0,0 +1,12 @@
+// operators: struct_pack_field_swap
+module 0x42::struct_pack_field_swap {
+    struct Range has copy, drop {
+        start: u64,
+        end: u64,
+        inclusive: bool,
+    }
+
+    fun new(start: u64, end: u64): Range {
+        Range { start, end, inclusive: false }
+    }
+}
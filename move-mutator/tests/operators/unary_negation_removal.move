@@ -0,0 +1,10 @@
+// operators: unary_negation_removal
+module 0x42::unary_negation_removal {
+    fun can_transfer(is_admin: bool, is_frozen: bool): bool {
+        is_admin || !is_frozen
+    }
+
+    fun negate(x: bool): bool {
+        !x
+    }
+}
@@ -0,0 +1,6 @@
+// operators: unary_operator_replacement
+module 0x42::unary_operator_replacement {
+    fun negate(x: bool): bool {
+        !x
+    }
+}
@@ -0,0 +1,11 @@
+// operators: variant_test_negation
+module 0x42::variant_test_negation {
+    enum Side has drop {
+        Buy,
+        Sell,
+    }
+
+    fun is_buy(side: &Side): bool {
+        side is Side::Buy
+    }
+}
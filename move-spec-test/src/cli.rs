@@ -3,8 +3,28 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
-use move_mutator::cli::{FunctionFilter, ModuleFilter, PackagePathCheck};
-use std::path::PathBuf;
+use move_mutator::{
+    cli::{
+        parse_operator, parse_optional_operator, ConfigFormat, FunctionFilter, ModuleFilter,
+        PackagePathCheck,
+    },
+    compiler::CompilerTarget,
+};
+#[cfg(feature = "webhook")]
+use mutator_common::notify::NotifyOn;
+use mutator_common::{
+    checkpoint::CheckpointOptions,
+    crash_report::{self, CrashContext, REDACTED},
+    locale::Locale,
+    post_run::SummaryFormat,
+    report::ReportFormat,
+    results_store::StoreLocation,
+    score::ScoreOptions,
+};
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
 
 /// Command line options for specification test tool.
 #[derive(Parser, Default, Debug, Clone)]
@@ -31,18 +51,33 @@ pub struct CLIOptions {
     )]
     pub mutate_functions: FunctionFilter,
 
-    /// Optional configuration file for mutator tool.
+    /// Optional configuration file (TOML) for mutator tool. Run `config schema` to see the
+    /// available keys.
     #[clap(long, value_parser, conflicts_with = "use_generated_mutants")]
     pub mutator_conf: Option<PathBuf>,
 
+    /// Print the effective mutator options (defaults merged with the configuration file,
+    /// environment variables and the command line) in the given format and exit.
+    #[clap(long, value_enum, num_args = 0..=1, default_missing_value = "toml")]
+    pub print_config: Option<ConfigFormat>,
+
     /// Optional configuration file for prover tool.
     #[clap(long, value_parser)]
     pub prover_conf: Option<PathBuf>,
 
-    /// Save report to a JSON file.
+    /// Save report to a file (JSON by default, see `--report-format`).
     #[clap(long, value_parser)]
     pub output: Option<PathBuf>,
 
+    /// The format of the report saved to the output file.
+    #[clap(long, value_enum, default_value_t)]
+    pub report_format: ReportFormat,
+
+    /// The locale used to format numbers in the summary printed on the screen (e.g. `en`,
+    /// `de-DE`). The saved report is not affected.
+    #[clap(long, default_value_t)]
+    pub locale: Locale,
+
     /// Use previously generated mutants.
     #[clap(long, value_parser)]
     pub use_generated_mutants: Option<PathBuf>,
@@ -50,10 +85,12 @@ pub struct CLIOptions {
     /// Indicates if mutants should be verified and made sure mutants can compile.
     #[clap(
         long,
-        default_value = "false",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
         conflicts_with = "use_generated_mutants"
     )]
-    pub verify_mutants: bool,
+    pub verify_mutants: Option<bool>,
 
     /// Extra arguments to pass to the prover.
     #[clap(long, value_parser)]
@@ -62,6 +99,184 @@ pub struct CLIOptions {
     /// Remove averagely given percentage of mutants. See the doc for more details.
     #[clap(long, conflicts_with = "use_generated_mutants")]
     pub downsampling_ratio_percentage: Option<usize>,
+
+    /// Evaluate only the mutants whose IDs are listed in the file, one per line (`#` starts a
+    /// comment), e.g. to re-check the survivors of a previous run after adding tests. With
+    /// `--use-generated-mutants`, the listed mutants are picked from the generated ones, so
+    /// nothing is regenerated.
+    #[clap(long, value_parser, conflicts_with = "downsampling_ratio_percentage")]
+    pub only_mutants: Option<PathBuf>,
+
+    /// When downsampling, select the mutants tested by the latest run of the package recorded in
+    /// the `--results-store` first, so the score changes reflect the changes of the code and tests
+    /// rather than a different sample.
+    #[clap(
+        long,
+        requires = "results_store",
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub stable_sampling: bool,
+
+    /// Keep at most the given number of mutants per file. The kept mutants are spread across the
+    /// functions and operators of the file.
+    #[clap(long, conflicts_with = "use_generated_mutants")]
+    pub max_mutants_per_file: Option<usize>,
+
+    /// Optional mutation operators to enable (disabled by default), separated by commas.
+    #[clap(
+        long,
+        value_parser = parse_optional_operator,
+        value_delimiter = ',',
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub extra_operators: Vec<String>,
+
+    /// Mutation operators to disable, separated by commas, e.g. `assert_error_code_replacement`
+    /// to keep mutating the `assert!` conditions but not their error codes.
+    #[clap(
+        long,
+        value_parser = parse_operator,
+        value_delimiter = ',',
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub disable_operators: Vec<String>,
+
+    /// Generate skeletons of specification clauses for the surviving mutants in the
+    /// `suggested_specs` directory.
+    #[clap(long)]
+    pub suggest_specs: bool,
+
+    /// Only warn (instead of failing) when the module and function filters match no mutation sites.
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub allow_empty: Option<bool>,
+
+    /// Record the mutants which fail to compile in the report, together with the compiler
+    /// diagnostics (see `display-report explain`).
+    #[clap(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub keep_stillborn: Option<bool>,
+
+    /// Verify the mutants also under the given language versions (optionally with the compiler
+    /// version, e.g. `2.0,2.1@2.0`) and report the mutants which compile under some of them but
+    /// not the others.
+    #[clap(
+        long,
+        value_delimiter = ',',
+        requires = "verify_mutants",
+        conflicts_with = "use_generated_mutants"
+    )]
+    pub compiler_matrix: Vec<CompilerTarget>,
+
+    /// Options of the mutation score computation.
+    #[clap(flatten)]
+    pub score: ScoreOptions,
+
+    /// Attribute the surviving mutants to owners using the given `CODEOWNERS` file (or a mapping
+    /// file with the same syntax).
+    #[clap(long, value_parser)]
+    pub codeowners: Option<PathBuf>,
+
+    /// A command run after the tool finishes, e.g. to post the summary of the new surviving
+    /// mutants to an issue tracker. The command is run without a shell and gets the summary on the
+    /// standard input. See the README for the provided environment variables.
+    #[clap(long)]
+    pub post_run_cmd: Option<String>,
+
+    /// The report of an earlier run (e.g. on the main branch). The mutants which survived it are
+    /// left out of the post-run summary, so only the new survivors are reported.
+    #[clap(long, value_parser, requires = "post_run_cmd")]
+    pub post_run_baseline: Option<PathBuf>,
+
+    /// The format of the post-run summary.
+    #[clap(long, value_enum, default_value_t, requires = "post_run_cmd")]
+    pub post_run_format: SummaryFormat,
+
+    /// The URL to POST the JSON summary to when the run finishes (or fails).
+    #[cfg(feature = "webhook")]
+    #[clap(long)]
+    pub notify_webhook: Option<String>,
+
+    /// When to notify the webhook.
+    #[cfg(feature = "webhook")]
+    #[clap(long, value_enum, default_value_t, requires = "notify_webhook")]
+    pub notify_on: NotifyOn,
+
+    /// Record the summary of the run in the results store and print the change of the mutation
+    /// score since the previous run: a `.jsonl` file, an SQLite database (`.db`, `.sqlite`, with
+    /// the `sqlite` feature) or a `postgres://` URL (with the `postgres` feature).
+    #[clap(long)]
+    pub results_store: Option<StoreLocation>,
+
+    /// Print the time spent in each phase of the run (with the number of processed mutants)
+    /// at the end.
+    #[clap(long)]
+    pub timings: bool,
+
+    /// Sample the memory usage of the tool and its worker processes during the run and print the
+    /// peak memory with the timings at the end (Linux only).
+    #[clap(long)]
+    pub profile_memory: bool,
+
+    /// Print each surviving mutant as soon as it survives: the diff with the changed words
+    /// highlighted.
+    #[clap(short, long)]
+    pub verbose: bool,
+
+    /// Skip the baseline run (the verification of the original code), e.g. when it has already passed in a
+    /// prior CI step. If the baseline fails, all mutants are reported as killed.
+    #[clap(long)]
+    pub skip_baseline: bool,
+
+    /// The maximum number of prover instances run in parallel on the mutants. Each instance needs
+    /// a lot of memory, so it can be set lower than the number of CPUs used for the other work,
+    /// such as the mutant verification. Defaults to the number of CPUs.
+    #[clap(long)]
+    pub prover_jobs: Option<NonZeroUsize>,
+
+    /// Verify only the functions affected by each mutant - the mutated function, or its module if
+    /// it's called only there - instead of the whole package. The package is verified whole for
+    /// the mutants of functions called from other modules and in packages with global
+    /// invariants.
+    #[clap(long)]
+    pub partial_verification: bool,
+
+    /// Options of the checkpoints, which allow resuming an interrupted run.
+    #[clap(flatten)]
+    pub checkpoint: CheckpointOptions,
+}
+
+impl CLIOptions {
+    /// Returns the context of the crash report, with the webhook URL redacted.
+    #[must_use]
+    pub fn crash_context(&self, package_path: &Path) -> CrashContext {
+        let mut options = self.clone();
+        #[cfg(feature = "webhook")]
+        {
+            options.notify_webhook = options.notify_webhook.map(|_| REDACTED.to_owned());
+        }
+        if let Some(StoreLocation::Postgres(url)) = &mut options.results_store {
+            REDACTED.clone_into(url);
+        }
+
+        CrashContext {
+            tool: "move-spec-test".to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            output_dir: crash_report::report_dir(self.output.as_deref()),
+            package_dir: Some(package_path.to_path_buf()),
+            options: format!("{options:#?}"),
+        }
+    }
 }
 
 impl<'a> PackagePathCheck<'a> for CLIOptions {
@@ -71,14 +286,33 @@ impl<'a> PackagePathCheck<'a> for CLIOptions {
 }
 
 /// This function creates a mutator CLI options from the given spec-test options.
+///
+/// The operators mutating the specifications (e.g. the loop invariants) are always enabled, as
+/// only the prover can kill such mutants.
 #[must_use]
 pub fn create_mutator_options(options: &CLIOptions) -> move_mutator::cli::CLIOptions {
+    let mut extra_operators = options.extra_operators.clone();
+    for op in move_mutator::cli::SPEC_OPERATORS {
+        if !extra_operators.iter().any(|extra| extra == op) {
+            extra_operators.push(op.to_string());
+        }
+    }
+
     move_mutator::cli::CLIOptions {
         move_sources: options.move_sources.clone(),
         mutate_modules: options.mutate_modules.clone(),
         mutate_functions: options.mutate_functions.clone(),
         verify_mutants: options.verify_mutants,
         downsampling_ratio_percentage: options.downsampling_ratio_percentage,
+        only_mutants: options.only_mutants.clone(),
+        max_mutants_per_file: options.max_mutants_per_file,
+        extra_operators,
+        disable_operators: options.disable_operators.clone(),
+        allow_empty: options.allow_empty,
+        keep_stillborn: options.keep_stillborn,
+        compiler_matrix: options.compiler_matrix.clone(),
+        mutator_conf: options.mutator_conf.clone(),
+        use_generated_mutants: options.use_generated_mutants.clone(),
         ..Default::default()
     }
 }
@@ -131,6 +365,11 @@ mod tests {
         assert_eq!(mutator_options.move_sources, options.move_sources);
         assert_eq!(mutator_options.mutate_modules, options.mutate_modules);
         assert_eq!(mutator_options.mutate_functions, options.mutate_functions);
+        assert_eq!(mutator_options.mutator_conf, options.mutator_conf);
+        assert_eq!(
+            mutator_options.extra_operators,
+            move_mutator::cli::SPEC_OPERATORS
+        );
     }
 
     #[test]
@@ -4,23 +4,45 @@
 
 pub mod cli;
 mod prover;
+mod suggested_specs;
 
 extern crate pretty_env_logger;
 #[macro_use]
 extern crate log;
 
-use crate::prover::prove;
+use crate::{
+    prover::{prove, PartialScopes},
+    suggested_specs::{generate_suggested_specs, SUGGESTED_SPECS_DIR},
+};
 use anyhow::anyhow;
 use fs_extra::dir::CopyOptions;
+use move_model::model::VerificationScope;
+use move_mutator::{
+    configuration::Configuration, report::MutationReport, source_drift::remap_reformatted_mutants,
+};
 use move_package::BuildConfig;
+#[cfg(feature = "webhook")]
+use mutator_common::notify::notify_webhook;
 use mutator_common::{
     benchmark::{Benchmark, Benchmarks},
-    report::{MiniReport, MutantStatus, Report},
+    codeowners::CodeOwners,
+    color::{termcolor_choice, Stream},
+    crash_report,
+    diff_render::render_survivor,
+    memory::{self, MemoryProfiler},
+    mutant_ids::load_mutant_ids,
+    post_run::run_post_run_cmd,
+    report::{
+        Backend, MiniReport, MutantRecord, MutantStatus, Report, StillbornRecord,
+        UnassessedFunctionRecord,
+    },
+    results_store::{record_run, write_last_tested_mutants},
     tmp_package_dir::{setup_outdir_and_package_path, strip_path_prefix},
 };
 use rayon::prelude::*;
 use std::{
     fs,
+    num::NonZeroUsize,
     path::{Path, PathBuf},
 };
 
@@ -49,34 +71,86 @@ pub fn run_spec_test(
     config: &BuildConfig,
     original_package_path: &Path,
 ) -> anyhow::Result<()> {
+    if let Some(format) = options.print_config {
+        let configuration = Configuration::load(cli::create_mutator_options(options), None)?;
+        println!("{}", configuration.project.to_config_string(format)?);
+        return Ok(());
+    }
+
+    let result = run(options, config, original_package_path);
+    if let (Some(store), Ok(report)) = (&options.results_store, &result) {
+        record_run(store, "move-spec-test", report);
+    }
+    #[cfg(feature = "webhook")]
+    if let Some(url) = &options.notify_webhook {
+        notify_webhook(url, options.notify_on, "move-spec-test", &result);
+    }
+    result.map(|_| ())
+}
+
+/// Runs the tool and returns the final report.
+fn run(
+    options: &cli::CLIOptions,
+    config: &BuildConfig,
+    original_package_path: &Path,
+) -> anyhow::Result<Report> {
     // We need to initialize logger using try_init() as it might be already initialized in some other tool
     // (e.g. move-mutator). If we use init() instead, we will get an abort.
     let _ = pretty_env_logger::try_init();
 
-    // Setup output dir and clone package path there.
-    let (outdir, package_path) = setup_outdir_and_package_path(original_package_path)?;
-
-    info!("Running specification tester with the following options: {options:?}");
-
     // Always create and use benchmarks.
     // Benchmarks call only time getting functions, so it's safe to use them in any case and
     // they are not expensive to create (won't hit the performance).
     let mut benchmarks = Benchmarks::new();
     benchmarks.total_tool_duration.start();
+    let memory_profiler = options
+        .profile_memory
+        .then(|| MemoryProfiler::start(memory::SAMPLING_INTERVAL));
+    benchmarks.resolution.start();
+
+    // Reject invalid mutator options (including the ones from the configuration file) before
+    // running anything.
+    let configuration = Configuration::load(cli::create_mutator_options(options), None)?;
+    if options.stable_sampling
+        && configuration
+            .project
+            .downsampling_ratio_percentage
+            .is_none()
+    {
+        anyhow::bail!("'--stable-sampling' requires '--downsampling-ratio-percentage' to be set");
+    }
+    let suppressions = options.score.load_suppressions()?;
+
+    // Setup output dir and clone package path there.
+    let (outdir, package_path) = setup_outdir_and_package_path(original_package_path)?;
+    let checkpoint = options
+        .checkpoint
+        .open("move-spec-test", &original_package_path.canonicalize()?)?;
+
+    benchmarks.resolution.stop();
+
+    info!("Running specification tester with the following options: {options:?}");
 
     let prover_conf = cli::generate_prover_options(options)?;
     info!("Using prover configuration: {prover_conf:?}");
 
-    let mut error_writer = termcolor::StandardStream::stderr(termcolor::ColorChoice::Auto);
+    let mut error_writer = termcolor::StandardStream::stderr(termcolor_choice(Stream::Stderr));
 
-    benchmarks.executing_original_package.start();
-    let result = prove(config, &package_path, &prover_conf, &mut error_writer);
-    benchmarks.executing_original_package.stop();
-
-    if let Err(e) = result {
-        let msg = format!("Original code verification failed! Prover failed with error: {e}");
-        error!("{msg}");
-        return Err(anyhow!(msg));
+    if options.skip_baseline {
+        let msg = "the verification of the original code is skipped (--skip-baseline), if it \
+                   fails, all mutants will be reported as killed";
+        warn!("{msg}");
+        eprintln!("WARNING: {msg}");
+    } else {
+        benchmarks.executing_original_package.start();
+        let result = prove(config, &package_path, &prover_conf, &mut error_writer);
+        benchmarks.executing_original_package.stop();
+
+        if let Err(e) = result {
+            let msg = format!("Original code verification failed! Prover failed with error: {e}");
+            error!("{msg}");
+            return Err(anyhow!(msg));
+        }
     }
 
     // We can skip fetching the latest deps for generating mutants and proving those mutants
@@ -88,108 +162,316 @@ pub fn run_spec_test(
         mutant_path.clone()
     } else {
         benchmarks.mutator.start();
-        let outdir_mutant = run_mutator(options, &quick_config, &package_path, &outdir)?;
+        // Without the baseline run, the dependencies are fetched by the mutator.
+        let mutator_config = if options.skip_baseline {
+            config
+        } else {
+            &quick_config
+        };
+        let outdir_mutant = run_mutator(
+            options,
+            mutator_config,
+            &package_path,
+            &original_package_path.canonicalize()?,
+            &outdir,
+        )?;
         benchmarks.mutator.stop();
         outdir_mutant
     };
 
-    let report =
+    let mut report =
         move_mutator::report::Report::load_from_json_file(&outdir_mutant.join("report.json"))?;
-
-    benchmarks.executing_tests_on_mutants.start();
-    let cp_opts = CopyOptions::new().content_only(true);
-    let (proving_benchmarks, mini_reports): (Vec<Benchmark>, Vec<MiniReport>) = report
-        .get_mutants()
-        .into_par_iter()
-        .map(|elem| {
-            let mut benchmark = Benchmark::new();
-
-            let mutant_file = elem.mutant_path();
-            // In case the number of mutants is very low, a single thread might be used.
-            let rayon_tid = rayon::current_thread_index().unwrap_or(0);
-            info!(
-                "job_{rayon_tid}: Running prover for mutant {}",
-                mutant_file.display()
+    // The mutator generates only the listed mutants, the previously generated ones are filtered
+    // here.
+    if let (Some(only_mutants), Some(mutant_path)) =
+        (&options.only_mutants, &options.use_generated_mutants)
+    {
+        let missing = report.retain_listed_mutants(&load_mutant_ids(only_mutants)?);
+        if missing > 0 {
+            let msg = format!(
+                "{missing} of the mutants listed in {} aren't in {}",
+                only_mutants.display(),
+                mutant_path.display()
             );
+            warn!("{msg}");
+            eprintln!("WARNING: {msg}");
+        }
+    }
+    // The previously generated mutants are checked against the current sources, so the reported
+    // positions stay accurate after the package is reformatted.
+    if options.use_generated_mutants.is_some() {
+        let remapped = remap_reformatted_mutants(&mut report, &package_path, &outdir)?;
+        if remapped > 0 {
+            println!("Re-mapped {remapped} mutants to the reformatted sources");
+        }
+    }
 
-            // Strip prefix to get the path relative to the package directory.
-            let original_file =
-                strip_path_prefix(elem.original_file_path()).expect("invalid package path");
-            let job_outdir = outdir.join(format!("prover_{rayon_tid}"));
-
-            let _ = fs::remove_dir_all(&job_outdir);
-            fs_extra::dir::copy(&package_path, &job_outdir, &cp_opts)
-                .expect("copying directory failed");
+    // The scopes given in the prover options are kept.
+    let narrow_scopes = options.partial_verification
+        && matches!(
+            prover_conf.prover.verify_scope,
+            VerificationScope::All | VerificationScope::Public
+        );
+    let partial_scopes = if narrow_scopes {
+        PartialScopes::new(&quick_config, &package_path)?
+    } else {
+        PartialScopes::default()
+    };
 
-            trace!(
-                "Copying mutant file {} to the package directory {}",
-                mutant_file.display(),
-                outdir.join(&original_file).display()
-            );
-            // Should never fail, since files will always exists.
-            fs::copy(mutant_file, job_outdir.join(&original_file)).expect("copying file failed");
-
-            benchmark.start();
-            let mut error_writer = std::io::sink();
-            let result = prove(&quick_config, &job_outdir, &prover_conf, &mut error_writer);
-            benchmark.stop();
-
-            let mutant_status = if let Err(e) = result {
-                trace!("Mutant killed! Prover failed with error: {e}");
-                MutantStatus::Killed
-            } else {
-                trace!("Mutant {} hasn't been killed!", mutant_file.display());
-                MutantStatus::Alive
-            };
-
-            let diff = elem.get_diff().to_owned();
-
-            let mut qname = elem.get_module_name().to_owned();
-            qname.push_str("::");
-            qname.push_str(elem.get_function_name());
-
-            (
-                benchmark,
-                MiniReport::new(original_file.to_path_buf(), qname, mutant_status, diff),
-            )
+    benchmarks.executing_tests_on_mutants.start();
+    let cp_opts = CopyOptions::new().content_only(true);
+    // The provers run in a dedicated pool, so their number is limited independently of the
+    // threads used for the rest of the work.
+    let prover_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.prover_jobs.map_or(0, NonZeroUsize::get))
+        .thread_name(|idx| format!("prover_{idx}"))
+        .build()?;
+    let (proving_benchmarks, mini_reports): (Vec<Option<Benchmark>>, Vec<MiniReport>) = prover_pool
+        .install(|| {
+            report
+                .get_mutants()
+                .into_par_iter()
+                .map(|elem| {
+                    let mutant_file = elem.mutant_path();
+                    if let Some(status) = elem.mutant_id().and_then(|id| suppressions.status(id)) {
+                        info!(
+                            "Mutant {} is {status} in the suppressions file",
+                            mutant_file.display()
+                        );
+                        return (None, mini_report(elem, status));
+                    }
+                    if let Some(mini_report) = checkpoint
+                        .as_ref()
+                        .zip(elem.mutant_id())
+                        .and_then(|(checkpoint, id)| checkpoint.resumed(id))
+                    {
+                        info!(
+                            "Mutant {} has been evaluated before the interruption",
+                            mutant_file.display()
+                        );
+                        return (None, mini_report);
+                    }
+
+                    let mut benchmark = Benchmark::new();
+                    crash_report::set_current_mutant(Some(mutant_file));
+                    // In case the number of mutants is very low, a single thread might be used.
+                    let rayon_tid = rayon::current_thread_index().unwrap_or(0);
+                    info!(
+                        "job_{rayon_tid}: Running prover for mutant {}",
+                        mutant_file.display()
+                    );
+
+                    // Strip prefix to get the path relative to the package directory.
+                    let original_file =
+                        strip_path_prefix(elem.original_file_path()).expect("invalid package path");
+                    let job_outdir = outdir.join(format!("prover_{rayon_tid}"));
+
+                    let _ = fs::remove_dir_all(&job_outdir);
+                    fs_extra::dir::copy(&package_path, &job_outdir, &cp_opts)
+                        .expect("copying directory failed");
+
+                    trace!(
+                        "Copying mutant file {} to the package directory {}",
+                        mutant_file.display(),
+                        outdir.join(&original_file).display()
+                    );
+                    // Should never fail, since files will always exists.
+                    fs::copy(mutant_file, job_outdir.join(&original_file))
+                        .expect("copying file failed");
+
+                    let mut mutant_prover_conf = prover_conf.clone();
+                    if let Some(scope) =
+                        partial_scopes.scope(elem.get_module_name(), elem.get_function_name())
+                    {
+                        trace!(
+                            "Verifying only {scope:?} for mutant {}",
+                            mutant_file.display()
+                        );
+                        mutant_prover_conf.prover.verify_scope = scope.clone();
+                    }
+
+                    benchmark.start();
+                    let mut error_writer = std::io::sink();
+                    let result = prove(
+                        &quick_config,
+                        &job_outdir,
+                        &mutant_prover_conf,
+                        &mut error_writer,
+                    );
+                    benchmark.stop();
+
+                    let mutant_status = if let Err(e) = result {
+                        trace!("Mutant killed! Prover failed with error: {e}");
+                        MutantStatus::Killed
+                    } else {
+                        trace!("Mutant {} hasn't been killed!", mutant_file.display());
+                        MutantStatus::Alive
+                    };
+                    crash_report::set_current_mutant(None);
+
+                    let mini_report =
+                        mini_report(elem, mutant_status).with_duration(benchmark.elapsed);
+                    if let Some(checkpoint) = &checkpoint {
+                        checkpoint.record(&mini_report);
+                    }
+                    if options.verbose && mutant_status == MutantStatus::Alive {
+                        // A single print keeps the output of parallel jobs together.
+                        print!(
+                            "{}",
+                            render_survivor(&mini_report, elem.original_file_path())
+                        );
+                    }
+                    (Some(benchmark), mini_report)
+                })
+                .collect::<Vec<(_, _)>>()
         })
-        .collect::<Vec<(_, _)>>()
         .into_iter()
         .unzip();
 
     benchmarks.executing_tests_on_mutants.stop();
-    benchmarks.mutant_results = proving_benchmarks;
+    benchmarks.reporting.start();
+    benchmarks.mutant_results = proving_benchmarks.into_iter().flatten().collect();
 
     // Prepare a report.
     let mut test_report = Report::new(original_package_path.canonicalize()?);
-    for MiniReport {
-        original_file,
-        qname,
-        mutant_status,
-        diff,
-    } in mini_reports
-    {
-        test_report.increment_mutants_tested(&original_file, &qname);
-        if let MutantStatus::Alive = mutant_status {
-            test_report.add_mutants_alive_diff(&original_file, &qname, &diff);
-        } else {
-            test_report.increment_mutants_killed(&original_file, &qname);
-            test_report.add_mutants_killed_diff(&original_file, &qname, &diff);
+    test_report.set_backend(Backend::Prover);
+    for mini_report in &mini_reports {
+        let MiniReport {
+            original_file,
+            qname,
+            mutant_status,
+            diff,
+            ..
+        } = mini_report;
+
+        match mutant_status {
+            MutantStatus::Alive => {
+                test_report.increment_mutants_tested(original_file, qname);
+                test_report.add_mutants_alive_diff(original_file, qname, diff);
+            },
+            MutantStatus::Killed => {
+                test_report.increment_mutants_tested(original_file, qname);
+                test_report.increment_mutants_killed(original_file, qname);
+                test_report.add_mutants_killed_diff(original_file, qname, diff);
+            },
+            // Mutants which haven't been verified count according to the score policy.
+            MutantStatus::Excluded
+            | MutantStatus::Skipped
+            | MutantStatus::Suppressed
+            | MutantStatus::Equivalent => {},
         }
+        test_report.add_mutant_record(MutantRecord::from(mini_report));
+    }
+    for elem in report.get_stillborn() {
+        test_report.add_stillborn_record(stillborn_record(elem)?);
+    }
+    for function in report.get_unassessed_functions() {
+        test_report.add_unassessed_function_record(UnassessedFunctionRecord {
+            original_file: strip_path_prefix(&function.original_file)?,
+            qname: function.qname(),
+        });
+    }
+    test_report.set_discarded_mutants(
+        u32::try_from(report.invalid_mutants())?,
+        u32::try_from(report.uncovered_mutants())?,
+    );
+    test_report.set_score_policy(options.score.score_policy());
+
+    if let Some(codeowners) = &options.codeowners {
+        test_report.assign_owners(&CodeOwners::from_file(codeowners)?);
     }
 
-    test_report.print_table();
-
-    benchmarks.total_tool_duration.stop();
-    benchmarks.display();
+    test_report.print_table(&options.locale);
+
+    if options.suggest_specs {
+        let survivors = report
+            .get_mutants()
+            .iter()
+            .zip(&mini_reports)
+            .filter(|(_, r)| r.mutant_status == MutantStatus::Alive)
+            .map(|(mutant, _)| mutant)
+            .collect::<Vec<_>>();
+        let suggested_specs_dir = std::env::current_dir()?.join(SUGGESTED_SPECS_DIR);
+        let generated =
+            generate_suggested_specs(&survivors, original_package_path, &suggested_specs_dir)?;
+        if !generated.is_empty() {
+            println!(
+                "Suggested specifications for surviving mutants saved to: {}",
+                suggested_specs_dir.display()
+            );
+        }
+    }
 
     if let Some(outfile) = &options.output {
         let out = std::env::current_dir()?.join(outfile);
-        test_report.save_to_json_file(&out)?;
+        test_report.save_to_file(&out, options.report_format)?;
         println!("Report saved to: {}", out.display());
     }
+    // The run has finished, so there is nothing to resume.
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.finish();
+    }
+
+    benchmarks.reporting.stop();
+    benchmarks.total_tool_duration.stop();
+    benchmarks.memory = memory_profiler.map(MemoryProfiler::finish);
+    benchmarks.display();
+    if options.timings || options.profile_memory {
+        print!(
+            "{}",
+            benchmarks.timings_table(
+                report.get_mutants().len() + report.invalid_mutants(),
+                report.invalid_mutants()
+            )
+        );
+    }
+
+    if let Some(cmd) = &options.post_run_cmd {
+        run_post_run_cmd(
+            cmd,
+            &test_report,
+            options.post_run_baseline.as_deref(),
+            options.post_run_format,
+        )?;
+    }
 
-    Ok(())
+    Ok(test_report)
+}
+
+/// Creates the report of the mutant with the given status, without the benchmark.
+fn mini_report(elem: &MutationReport, mutant_status: MutantStatus) -> MiniReport {
+    let original_file = strip_path_prefix(elem.original_file_path()).expect("invalid package path");
+    let qname = format!("{}::{}", elem.get_module_name(), elem.get_function_name());
+    let operators = elem
+        .get_mutations()
+        .iter()
+        .map(|m| m.get_operator_name().to_owned())
+        .collect();
+
+    MiniReport::new(
+        original_file,
+        qname,
+        mutant_status,
+        elem.get_diff().to_owned(),
+    )
+    .with_mutant_info(elem.mutant_id().unwrap_or_default(), operators)
+    .with_labels(elem.get_labels().to_vec())
+}
+
+/// Creates the record of the mutant which failed to compile.
+fn stillborn_record(elem: &MutationReport) -> anyhow::Result<StillbornRecord> {
+    Ok(StillbornRecord {
+        id: elem.mutant_id().unwrap_or_default().to_owned(),
+        original_file: strip_path_prefix(elem.original_file_path())?,
+        qname: format!("{}::{}", elem.get_module_name(), elem.get_function_name()),
+        operators: elem
+            .get_mutations()
+            .iter()
+            .map(|m| m.get_operator_name().to_owned())
+            .collect(),
+        diagnostics: elem.get_diagnostics().unwrap_or_default().to_owned(),
+        diff: elem.get_diff().to_owned(),
+    })
 }
 
 /// This function runs the Move Mutator tool.
@@ -197,6 +479,7 @@ fn run_mutator(
     options: &cli::CLIOptions,
     config: &BuildConfig,
     package_path: &Path,
+    original_package_path: &Path,
     outdir: &Path,
 ) -> anyhow::Result<PathBuf> {
     debug!("Running the move mutator tool");
@@ -205,6 +488,14 @@ fn run_mutator(
 
     let mut mutator_conf = cli::create_mutator_options(options);
     mutator_conf.out_mutant_dir = Some(outdir_mutant.clone());
+    if let (true, Some(store)) = (options.stable_sampling, &options.results_store) {
+        mutator_conf.stable_sampling = write_last_tested_mutants(
+            store,
+            "move-spec-test",
+            original_package_path,
+            &outdir.join("stable_sampling.txt"),
+        )?;
+    }
 
     move_mutator::run_move_mutator(mutator_conf, config, package_path)?;
 
@@ -4,17 +4,31 @@
 
 #![forbid(unsafe_code)]
 
-use clap::{Parser, Subcommand};
-use move_mutator::cli::PackagePathCheck;
-use move_package::BuildConfig;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use move_mutator::cli::{ConfigCmd, DescribeOperatorOptions, PackagePathCheck};
 use move_spec_test::{cli::CLIOptions, run_spec_test};
-use mutator_common::display_report::DisplayReportOptions;
-use std::path::PathBuf;
+use mutator_common::{
+    cli_docs::{print_completions, render_manpage, Shell},
+    color::{set_color_choice, ColorChoice},
+    crash_report,
+    display_report::DisplayReportOptions,
+    env_vars::with_env_vars,
+    package::PackageArgs,
+    package_defaults::get_matches_with_package_defaults,
+};
+
+/// The prefix of environment variables which can be used to set the options of all commands.
+const ENV_VAR_PREFIX: &str = "MOVE_SPEC_TEST_";
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 #[command(propagate_version = true)]
 struct Opts {
+    /// When to use colors in the output. The `NO_COLOR` environment variable disables the colors
+    /// in the `auto` mode.
+    #[clap(global = true, long, value_enum, default_value_t)]
+    color: ColorChoice,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -23,35 +37,67 @@ struct Opts {
 enum Commands {
     /// Runs the specification test tool.
     Run {
-        /// The path to the target Move package.
-        #[clap(long, value_parser)]
-        package_dir: Option<PathBuf>,
+        /// The package and build options.
+        #[clap(flatten)]
+        package: PackageArgs,
 
         /// Command line options for specification tester.
         #[clap(flatten)]
         cli_options: CLIOptions,
-
-        /// The build configuration options.
-        #[clap(flatten)]
-        build_config: BuildConfig,
     },
 
     /// Display the report in a more readable format.
+    #[clap(visible_alias = "report")]
     DisplayReport(DisplayReportOptions),
+
+    /// Work with the mutator configuration file.
+    #[command(subcommand)]
+    Config(ConfigCmd),
+
+    /// Describe a mutation operator, with an example and the weakness of the tests it reveals.
+    DescribeOperator(DescribeOperatorOptions),
+
+    /// Generate the shell completion script.
+    Completions {
+        /// The shell to generate the script for.
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+
+    /// Generate the man page.
+    Manpage,
+}
+
+/// Creates the command with all options, including the ones set by environment variables.
+fn command() -> clap::Command {
+    with_env_vars(Opts::command(), ENV_VAR_PREFIX, Some("run"))
 }
 
 fn main() -> anyhow::Result<()> {
-    let opts = Opts::parse();
+    let matches = get_matches_with_package_defaults(command(), Some("run"), "move-spec-test")?;
+    let opts = Opts::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    set_color_choice(opts.color);
 
     match opts.command {
         Commands::Run {
-            package_dir,
+            package,
             cli_options,
-            build_config,
         } => {
-            let package_path = cli_options.resolve(package_dir)?;
-            run_spec_test(&cli_options, &build_config, &package_path)
+            let package_path = cli_options.resolve(package.package_dir.clone())?;
+            crash_report::init_logger();
+            crash_report::install(cli_options.crash_context(&package_path));
+            run_spec_test(&cli_options, &package.build_config(), &package_path)
         },
         Commands::DisplayReport(display_report) => display_report.execute(),
+        Commands::Config(config) => config.execute(),
+        Commands::DescribeOperator(describe) => describe.execute(),
+        Commands::Completions { shell } => {
+            print_completions(shell, &mut command());
+            Ok(())
+        },
+        Commands::Manpage => {
+            print!("{}", render_manpage(&command())?);
+            Ok(())
+        },
     }
 }
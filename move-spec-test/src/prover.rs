@@ -2,11 +2,129 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use move_model::metadata::{CompilerVersion, LanguageVersion};
+use move_model::{
+    metadata::{CompilerVersion, LanguageVersion},
+    model::{FunId, GlobalEnv, QualifiedId, VerificationScope},
+};
 use move_package::{BuildConfig, ModelConfig};
-use std::{path::Path, time::Instant};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+    time::Instant,
+};
 use termcolor::WriteColor;
 
+/// The narrowed verification scopes of the functions of the package, used to verify only the
+/// functions affected by a mutant (`--partial-verification`).
+#[derive(Debug, Default)]
+pub(crate) struct PartialScopes {
+    /// The scopes of the functions (`module::function`). The functions whose mutants need the
+    /// whole package verified are left out.
+    scopes: BTreeMap<String, VerificationScope>,
+}
+
+impl PartialScopes {
+    /// Finds the narrowed scopes of the functions of the package.
+    ///
+    /// A function is verified alone if no other function calls it, as the callers inline the
+    /// bodies of the non-opaque functions they call. If all of its (transitive) callers are in the
+    /// same module, that module is verified. Global invariants relate the specs of any functions
+    /// modifying the memory, so no scope is narrowed in packages declaring them.
+    pub(crate) fn new(config: &BuildConfig, package_path: &Path) -> anyhow::Result<Self> {
+        let env = build_model(config, package_path)?;
+        let has_invariants = env.get_modules().any(|module| {
+            !env.get_global_invariants_by_module(module.get_id())
+                .is_empty()
+        });
+        if has_invariants {
+            return Ok(Self::default());
+        }
+
+        let mut scopes = BTreeMap::new();
+        for module in env.get_modules().filter(|module| module.is_target()) {
+            let module_name = module.get_name().display(&env).to_string();
+            for function in module.get_functions() {
+                let caller_modules = transitive_callers(&env, function.get_qualified_id())
+                    .into_iter()
+                    .map(|caller| {
+                        env.get_module(caller.module_id)
+                            .get_name()
+                            .display(&env)
+                            .to_string()
+                    })
+                    .collect();
+                let function_name = function.get_name_str();
+                if let Some(scope) = narrowed_scope(&module_name, &function_name, &caller_modules) {
+                    scopes.insert(format!("{module_name}::{function_name}"), scope);
+                }
+            }
+        }
+        Ok(Self { scopes })
+    }
+
+    /// Returns the narrowed scope of the function, if any.
+    pub(crate) fn scope(&self, module: &str, function: &str) -> Option<&VerificationScope> {
+        self.scopes.get(&format!("{module}::{function}"))
+    }
+}
+
+/// Returns the scope verifying the mutants of the function, given the modules of its callers, or
+/// `None` if the whole package has to be verified.
+fn narrowed_scope(
+    module: &str,
+    function: &str,
+    caller_modules: &BTreeSet<String>,
+) -> Option<VerificationScope> {
+    if caller_modules.is_empty() {
+        Some(VerificationScope::Only(format!("{module}::{function}")))
+    } else if caller_modules.iter().all(|caller| caller == module) {
+        Some(VerificationScope::OnlyModule(module.to_owned()))
+    } else {
+        None
+    }
+}
+
+/// Returns the functions calling the function directly or indirectly.
+fn transitive_callers(
+    env: &GlobalEnv,
+    function: QualifiedId<FunId>,
+) -> BTreeSet<QualifiedId<FunId>> {
+    let mut callers = BTreeSet::new();
+    let mut pending = vec![function];
+    while let Some(callee) = pending.pop() {
+        for caller in env
+            .get_function(callee)
+            .get_calling_functions()
+            .into_iter()
+            .flatten()
+        {
+            if *caller != function && callers.insert(*caller) {
+                pending.push(*caller);
+            }
+        }
+    }
+    callers
+}
+
+/// Builds the model of the package, with only the package modules as the targets.
+fn build_model(config: &BuildConfig, package_path: &Path) -> anyhow::Result<GlobalEnv> {
+    config
+        .clone()
+        .move_model_for_package(package_path, ModelConfig {
+            // Do not run prover on dependencies, but focus on the local project only.
+            all_files_as_targets: false,
+            target_filter: None,
+            compiler_version: config
+                .compiler_config
+                .compiler_version
+                .unwrap_or(CompilerVersion::V2_0),
+            language_version: config
+                .compiler_config
+                .language_version
+                .unwrap_or(LanguageVersion::V1),
+        })
+}
+
 /// The `prove` function is responsible for proving the package.
 ///
 /// # Arguments
@@ -25,22 +143,7 @@ pub(crate) fn prove<W: WriteColor>(
     prover_conf: &move_prover::cli::Options,
     mut error_writer: &mut W,
 ) -> anyhow::Result<()> {
-    let mut model = config.clone().move_model_for_package(
-        package_path,
-        ModelConfig {
-            // Do not run prover on dependencies, but focus on the local project only.
-            all_files_as_targets: false,
-            target_filter: None,
-            compiler_version: config
-                .compiler_config
-                .compiler_version
-                .unwrap_or(CompilerVersion::V2_0),
-            language_version: config
-                .compiler_config
-                .language_version
-                .unwrap_or(LanguageVersion::V1),
-        },
-    )?;
+    let mut model = build_model(config, package_path)?;
 
     let mut prover_conf = prover_conf.clone();
     prover_conf.output_path = package_path
@@ -54,3 +157,23 @@ pub(crate) fn prove<W: WriteColor>(
 
     move_prover::run_move_prover_with_model(&mut model, &mut error_writer, prover_conf, Some(now))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_is_narrowed_to_the_callers() {
+        let modules = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+
+        assert!(matches!(
+            narrowed_scope("sum", "sum", &modules(&[])),
+            Some(VerificationScope::Only(name)) if name == "sum::sum"
+        ));
+        assert!(matches!(
+            narrowed_scope("sum", "sum", &modules(&["sum"])),
+            Some(VerificationScope::OnlyModule(name)) if name == "sum"
+        ));
+        assert!(narrowed_scope("sum", "sum", &modules(&["sum", "vault"])).is_none());
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_mutator::report::{Mutation, MutationReport};
+use mutator_common::{source::find_module_address, tmp_package_dir::strip_path_prefix};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The directory name where the suggested specifications are stored.
+pub const SUGGESTED_SPECS_DIR: &str = "suggested_specs";
+
+/// Generates skeletons of specification clauses for the mutants which survived the prover.
+///
+/// A single file with a module specification block (`spec <address>::<module> { ... }`) is
+/// generated for every module containing surviving mutants. Each mutated function gets a
+/// `spec <function> { ... }` block with clauses based on the mutated expression.
+///
+/// # Arguments
+///
+/// * `survivors` - mutants which survived the prover.
+/// * `package_path` - path to the tested package.
+/// * `outdir` - directory where the suggested specifications are stored.
+///
+/// # Returns
+///
+/// * `anyhow::Result<Vec<PathBuf>>` - paths to the generated files.
+pub(crate) fn generate_suggested_specs(
+    survivors: &[&MutationReport],
+    package_path: &Path,
+    outdir: &Path,
+) -> anyhow::Result<Vec<PathBuf>> {
+    // Group survivors by the module and then by the function.
+    let mut modules = BTreeMap::<(&str, PathBuf), BTreeMap<&str, Vec<&MutationReport>>>::new();
+    for mutant in survivors {
+        let original_file = strip_path_prefix(mutant.original_file_path())?;
+        modules
+            .entry((mutant.get_module_name(), original_file))
+            .or_default()
+            .entry(mutant.get_function_name())
+            .or_default()
+            .push(mutant);
+    }
+
+    if modules.is_empty() {
+        return Ok(vec![]);
+    }
+
+    fs::create_dir_all(outdir)?;
+
+    let mut generated = vec![];
+    for ((module, original_file), functions) in modules {
+        let source = fs::read_to_string(package_path.join(&original_file))?;
+        let address = find_module_address(&source, module).unwrap_or("TODO_ADDRESS");
+
+        let mut content = String::new();
+        writeln!(
+            content,
+            "// Suggested specifications for the mutants in the `{module}` module which survived the prover."
+        )?;
+        writeln!(
+            content,
+            "// Move the clauses to the module and replace the TODO placeholders."
+        )?;
+        writeln!(content, "spec {address}::{module} {{")?;
+
+        for (function, mutants) in functions {
+            writeln!(content, "    spec {function} {{")?;
+            for mutant in mutants {
+                write_spec_skeleton(&mut content, mutant)?;
+            }
+            writeln!(content, "    }}")?;
+        }
+        writeln!(content, "}}")?;
+
+        let path = outdir.join(format!("{module}_suggested_specs.move"));
+        fs::write(&path, content)?;
+        generated.push(path);
+    }
+
+    Ok(generated)
+}
+
+/// Writes suggested specification clauses for a single surviving mutant.
+fn write_spec_skeleton(content: &mut String, mutant: &MutationReport) -> std::fmt::Result {
+    writeln!(
+        content,
+        "        // Surviving mutant {}:",
+        mutant.mutant_id().unwrap_or_default()
+    )?;
+    for line in mutant
+        .get_diff()
+        .lines()
+        .filter(|l| l.starts_with(['-', '+']) && !l.starts_with("---") && !l.starts_with("+++"))
+    {
+        writeln!(content, "        // {line}")?;
+    }
+
+    // The original line is a better hint than e.g. a single replaced operator.
+    let original_line = mutant
+        .get_diff()
+        .lines()
+        .find(|l| l.starts_with('-') && !l.starts_with("---"))
+        .map_or("", |l| l[1..].trim());
+
+    for mutation in mutant.get_mutations() {
+        writeln!(
+            content,
+            "        {}",
+            suggest_clause(mutation, original_line)
+        )?;
+    }
+    Ok(())
+}
+
+/// Suggests a specification clause which could kill the mutant created by the mutation.
+fn suggest_clause(mutation: &Mutation, original_line: &str) -> String {
+    let original = mutation.get_original_value().trim();
+    match mutation.get_operator_name() {
+        "exists_check_removal" => match find_exists_call(original) {
+            Some(exists) => format!("aborts_if !{exists};"),
+            None => format!("aborts_if /* TODO: condition of `{original}` */;"),
+        },
+        "delete_statement" => {
+            format!("ensures /* TODO: the effect of `{original}`, e.g. exists<T>(addr) */;")
+        },
+        "assert_error_code_replacement" => {
+            format!("aborts_if /* TODO: the asserted condition is violated */ with {original};")
+        },
+        "abort_code_replacement" => {
+            format!("aborts_if /* TODO: the condition of the abort */ with {original};")
+        },
+        "assert_condition_replacement" => format!("aborts_if !({original});"),
+        "assert_abort_deletion" => format!("aborts_if /* TODO: condition of `{original}` */;"),
+        "if_else_replacement"
+        | "conditional_negation"
+        | "unary_operator_replacement"
+        | "unary_negation_removal"
+        | "variant_test_negation" => {
+            format!("ensures ({original}) ==> /* TODO: the outcome when the condition holds */;")
+        },
+        "loop_invariant_weakening" => {
+            format!("ensures /* TODO: the loop result implied by `{original}` */;")
+        },
+        _ => format!("ensures result == /* TODO: expression based on `{original_line}` */;"),
+    }
+}
+
+/// Finds the `exists<T>(addr)` call inside the expression.
+fn find_exists_call(exp: &str) -> Option<&str> {
+    let start = exp.find("exists<")?;
+    let mut depth = 0;
+    for (idx, c) in exp[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&exp[start..=start + idx]);
+                }
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::Range;
+
+    fn mutation(operator: &str, original: &str) -> Mutation {
+        Mutation::new(
+            Range::new(0, original.len()),
+            operator.to_owned(),
+            original.to_owned(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn suggests_aborts_if_for_removed_exists_check() {
+        let m = mutation(
+            "exists_check_removal",
+            "assert!(exists<Balance>(signer::address_of(account)), 1)",
+        );
+        assert_eq!(
+            suggest_clause(&m, ""),
+            "aborts_if !exists<Balance>(signer::address_of(account));"
+        );
+    }
+
+    #[test]
+    fn suggests_aborts_if_for_asserts() {
+        let m = mutation("assert_condition_replacement", "amount <= balance");
+        assert_eq!(suggest_clause(&m, ""), "aborts_if !(amount <= balance);");
+        let m = mutation("assert_error_code_replacement", "E_INSUFFICIENT_BALANCE");
+        assert_eq!(
+            suggest_clause(&m, ""),
+            "aborts_if /* TODO: the asserted condition is violated */ with E_INSUFFICIENT_BALANCE;"
+        );
+    }
+
+    #[test]
+    fn suggests_ensures_result_for_expressions() {
+        let m = mutation("binary_operator_replacement", "+");
+        assert_eq!(
+            suggest_clause(&m, "x + y"),
+            "ensures result == /* TODO: expression based on `x + y` */;"
+        );
+    }
+
+    #[test]
+    fn generates_specs_for_survivors() {
+        let package_path = tempfile::tempdir().unwrap().into_path();
+        fs::create_dir_all(package_path.join("sources")).unwrap();
+        let source = "module TestAccount::Sum {\n    fun sum(x: u128, y: u128): u128 {\n        x + y\n    }\n}\n";
+        fs::write(package_path.join("sources/Sum.move"), source).unwrap();
+        let outdir = package_path.join(SUGGESTED_SPECS_DIR);
+
+        let mut mutant = MutationReport::new(
+            Path::new("mutants/sources/Sum_mutant_abc.move"),
+            Path::new("/tmp/original_package/sources/Sum.move"),
+            "Sum",
+            "sum",
+            &source.replace("x + y", "x - y"),
+            source,
+        );
+        mutant.add_modification(mutation("binary_operator_replacement", "+"));
+
+        let generated = generate_suggested_specs(&[&mutant], &package_path, &outdir).unwrap();
+        assert_eq!(generated, vec![outdir.join("Sum_suggested_specs.move")]);
+
+        let content = fs::read_to_string(&generated[0]).unwrap();
+        assert!(content.contains("spec TestAccount::Sum {"));
+        assert!(content.contains("    spec sum {"));
+        assert!(content.contains("// Surviving mutant abc:"));
+        assert!(content.contains("ensures result == /* TODO: expression based on `x + y` */;"));
+
+        fs::remove_dir_all(package_path).unwrap();
+    }
+}
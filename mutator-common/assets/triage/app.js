@@ -0,0 +1,193 @@
+// The mutant triage page. The report data is loaded by `data.js` into `MUTATION_REPORT`, so the
+// page also works when opened straight from the disk.
+(function () {
+  "use strict";
+
+  const report = window.MUTATION_REPORT;
+  const mutants = report.mutants;
+  const selected = new Set();
+  let sortKey = "status";
+  let ascending = true;
+  let current = null;
+
+  const $ = (id) => document.getElementById(id);
+  const moduleOf = (mutant) => mutant.qname.split("::")[0];
+
+  function fillOptions(select, values) {
+    [...new Set(values)].sort().forEach((value) => {
+      const option = document.createElement("option");
+      option.value = option.textContent = value;
+      select.appendChild(option);
+    });
+  }
+
+  function matches(mutant) {
+    const text = $("search").value.toLowerCase();
+    const status = $("status").value;
+    const module = $("module").value;
+    const operator = $("operator").value;
+    return (
+      (!status || mutant.status === status) &&
+      (!module || moduleOf(mutant) === module) &&
+      (!operator || mutant.operators.includes(operator)) &&
+      (!text ||
+        [mutant.id, mutant.qname, mutant.original_file, mutant.diff].some((field) =>
+          field.toLowerCase().includes(text)
+        ))
+    );
+  }
+
+  function compare(a, b) {
+    const key = (mutant) => {
+      const value = mutant[sortKey];
+      return Array.isArray(value) ? value.join(",") : value === undefined ? 0 : value;
+    };
+    const [x, y] = [key(a), key(b)];
+    const order = typeof x === "number" ? x - y : String(x).localeCompare(String(y));
+    return ascending ? order : -order;
+  }
+
+  function listed() {
+    return mutants.filter(matches).sort(compare);
+  }
+
+  function cell(row, text, className) {
+    const td = document.createElement("td");
+    td.textContent = text;
+    if (className) {
+      td.className = className;
+    }
+    row.appendChild(td);
+    return td;
+  }
+
+  function render() {
+    const body = document.querySelector("#mutants tbody");
+    body.replaceChildren();
+    const rows = listed();
+    rows.forEach((mutant) => {
+      const row = document.createElement("tr");
+      if (mutant === current) {
+        row.className = "current";
+      }
+
+      const checkbox = document.createElement("input");
+      checkbox.type = "checkbox";
+      checkbox.checked = selected.has(mutant.id);
+      checkbox.addEventListener("click", (event) => {
+        event.stopPropagation();
+        checkbox.checked ? selected.add(mutant.id) : selected.delete(mutant.id);
+        updateSelection();
+      });
+      cell(row, "").appendChild(checkbox);
+
+      cell(row, mutant.id);
+      cell(row, mutant.status, "status-" + mutant.status);
+      cell(row, mutant.qname);
+      cell(row, mutant.operators.join(", "));
+      cell(row, mutant.original_file);
+      cell(row, mutant.duration_ms ? mutant.duration_ms + " ms" : "");
+      row.addEventListener("click", () => showDetails(mutant));
+      body.appendChild(row);
+    });
+    $("count").textContent = rows.length + " of " + mutants.length + " mutants";
+    document.querySelectorAll("th[data-key]").forEach((th) => {
+      th.className = th.dataset.key === sortKey ? (ascending ? "asc" : "desc") : "";
+    });
+  }
+
+  function showDetails(mutant) {
+    current = mutant;
+    $("details").hidden = false;
+    $("details-title").textContent = mutant.qname + " (" + mutant.id + ")";
+
+    const info = $("details-info");
+    info.replaceChildren();
+    const entries = [
+      ["Status", mutant.status],
+      ["Operators", mutant.operators.join(", ")],
+      ["File", mutant.original_file],
+      ["Killed by", (mutant.killed_by || []).join(", ")],
+      ["Kill reason", mutant.kill_reason],
+      ["Owners", (mutant.owners || []).join(", ")],
+      ["Labels", (mutant.labels || []).join(", ")],
+    ];
+    entries
+      .filter(([, value]) => value)
+      .forEach(([label, value]) => {
+        const dt = document.createElement("dt");
+        const dd = document.createElement("dd");
+        dt.textContent = label;
+        dd.textContent = value;
+        info.append(dt, dd);
+      });
+
+    const diff = $("diff");
+    diff.replaceChildren();
+    mutant.diff.split("\n").forEach((line) => {
+      const span = document.createElement("span");
+      span.textContent = line + "\n";
+      if (line.startsWith("@@")) {
+        span.className = "line-hunk";
+      } else if (line.startsWith("+") && !line.startsWith("+++")) {
+        span.className = "line-add";
+      } else if (line.startsWith("-") && !line.startsWith("---")) {
+        span.className = "line-del";
+      }
+      diff.appendChild(span);
+    });
+    render();
+  }
+
+  function updateSelection() {
+    $("selected").textContent = selected.size + " selected";
+    $("download").disabled = selected.size === 0;
+  }
+
+  // The suppressions file lists one mutant ID per line followed by its kind, see the
+  // `--suppressions` option of the tools.
+  function downloadSuppressions() {
+    const kind = $("kind").value;
+    const lines = mutants
+      .filter((mutant) => selected.has(mutant.id))
+      .map((mutant) => mutant.id + " " + kind + " # " + mutant.qname);
+    const content = "# Exported from the mutant triage page.\n" + lines.join("\n") + "\n";
+    const link = document.createElement("a");
+    link.href = URL.createObjectURL(new Blob([content], { type: "text/plain" }));
+    link.download = "suppressions.txt";
+    link.click();
+    URL.revokeObjectURL(link.href);
+  }
+
+  $("summary").textContent =
+    report.package_dir + ": " + mutants.length + " mutants, mutation score " +
+    report.mutation_score.toFixed(2) + "%";
+  fillOptions($("status"), mutants.map((mutant) => mutant.status));
+  fillOptions($("module"), mutants.map(moduleOf));
+  fillOptions($("operator"), mutants.flatMap((mutant) => mutant.operators));
+
+  ["search", "status", "module", "operator"].forEach((id) =>
+    $(id).addEventListener("input", render)
+  );
+  document.querySelectorAll("th[data-key]").forEach((th) =>
+    th.addEventListener("click", () => {
+      ascending = th.dataset.key === sortKey ? !ascending : true;
+      sortKey = th.dataset.key;
+      render();
+    })
+  );
+  $("select-all").addEventListener("change", (event) => {
+    listed().forEach((mutant) =>
+      event.target.checked ? selected.add(mutant.id) : selected.delete(mutant.id)
+    );
+    updateSelection();
+    render();
+  });
+  $("download").addEventListener("click", downloadSuppressions);
+
+  // Surviving mutants need the triage, so they are listed by default.
+  if (mutants.some((mutant) => mutant.status === "alive")) {
+    $("status").value = "alive";
+  }
+  render();
+})();
@@ -0,0 +1,453 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    graph::{export_graph, GraphFormat},
+    kill_matrix::KillMatrix,
+    report::{KillReason, Report},
+    source::changed_lines,
+};
+use anyhow::Result;
+use clap::Subcommand;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Subcommand)]
+pub enum AnalyzeCmd {
+    /// Compute a near-minimal subset of tests which kills the same mutants as the whole test
+    /// suite, and list the redundant tests.
+    Minimize,
+
+    /// Rank the mutated source lines by suspiciousness for the given failing tests, to help
+    /// finding the fault. The ranking is a heuristic based on the recorded kills only, without
+    /// the coverage of the tests.
+    Localize {
+        /// Names of the currently failing tests, separated by commas. The names can be given
+        /// without the module address prefix (e.g. `sum::test_sum`).
+        #[clap(long, value_delimiter = ',', required = true)]
+        failing_tests: Vec<String>,
+
+        /// The number of the most suspicious lines to show.
+        #[clap(long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// Export the graph linking the tests, the mutants and the mutated functions (edges: kills,
+    /// covers, mutates) for visualization in external graph tools.
+    Graph {
+        /// The format of the graph.
+        #[clap(long, value_enum, default_value_t)]
+        format: GraphFormat,
+
+        /// Path where the graph is saved.
+        #[clap(long)]
+        output: PathBuf,
+    },
+}
+
+impl AnalyzeCmd {
+    /// Execute the command on the given report.
+    ///
+    /// The tools embed the commands in their own `analyze` command, next to the commands
+    /// analyzing the package itself.
+    pub fn execute(&self, path_to_report: &Path) -> Result<()> {
+        match self {
+            AnalyzeCmd::Minimize => display_minimized_tests(path_to_report),
+            AnalyzeCmd::Localize { failing_tests, top } => {
+                display_suspicious_lines(path_to_report, failing_tests, *top)
+            },
+            AnalyzeCmd::Graph { format, output } => export_graph(path_to_report, *format, output),
+        }
+    }
+}
+
+/// The result of the test-suite minimization.
+#[derive(Debug, PartialEq)]
+pub struct MinimizedTests {
+    /// Indices of the tests in the minimized test suite.
+    pub selected: Vec<usize>,
+    /// Indices of the tests which can be removed without lowering the kill score.
+    pub redundant: Vec<usize>,
+    /// The number of mutants killed by the tests.
+    pub killed_mutants: usize,
+}
+
+/// Computes a near-minimal subset of tests which kills all mutants killed by the whole suite.
+///
+/// Finding the minimal subset is the NP-hard set cover problem, so the greedy approximation is
+/// used: the test killing the most not-yet-killed mutants is picked until all are killed. Tests
+/// made redundant by tests picked later are dropped afterwards.
+pub fn minimize_tests(matrix: &KillMatrix) -> MinimizedTests {
+    let mut kills = vec![BTreeSet::new(); matrix.tests.len()];
+    for &(test, mutant) in &matrix.kills {
+        kills[test].insert(mutant);
+    }
+    let killed = kills.iter().flatten().copied().collect::<BTreeSet<_>>();
+
+    let mut remaining = killed.clone();
+    let mut selected = vec![];
+    while !remaining.is_empty() {
+        // Ties are resolved by the test order, so the result is deterministic.
+        let Some(best) = (0..kills.len())
+            .filter(|test| !selected.contains(test))
+            .max_by_key(|&test| {
+                (
+                    kills[test].intersection(&remaining).count(),
+                    std::cmp::Reverse(test),
+                )
+            })
+        else {
+            break;
+        };
+        remaining.retain(|mutant| !kills[best].contains(mutant));
+        selected.push(best);
+    }
+
+    // A test picked early might kill only mutants which are killed by the tests picked later.
+    for idx in (0..selected.len()).rev() {
+        let test = selected[idx];
+        let killed_by_others = selected
+            .iter()
+            .filter(|&&other| other != test)
+            .flat_map(|&other| kills[other].iter().copied())
+            .collect::<BTreeSet<_>>();
+        if kills[test].iter().all(|m| killed_by_others.contains(m)) {
+            selected.remove(idx);
+        }
+    }
+
+    selected.sort_unstable();
+    let redundant = (0..matrix.tests.len())
+        .filter(|test| selected.binary_search(test).is_err())
+        .collect();
+
+    MinimizedTests {
+        selected,
+        redundant,
+        killed_mutants: killed.len(),
+    }
+}
+
+/// Prints the minimized test suite and the redundant tests.
+pub fn display_minimized_tests(path_to_report: impl AsRef<Path>) -> Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    let matrix = KillMatrix::from_report(&report)?;
+    let minimized = minimize_tests(&matrix);
+
+    println!(
+        "{} of {} tests kill all {} mutants killed by the whole test suite:",
+        minimized.selected.len(),
+        matrix.tests.len(),
+        minimized.killed_mutants
+    );
+    for &test in &minimized.selected {
+        println!("  - {}", matrix.tests[test]);
+    }
+
+    if minimized.redundant.is_empty() {
+        println!("No redundant tests found.");
+    } else {
+        println!("Redundant tests (they don't kill any additional mutant):");
+        for &test in &minimized.redundant {
+            println!("  - {}", matrix.tests[test]);
+        }
+    }
+    Ok(())
+}
+
+/// A source line with its suspiciousness score.
+#[derive(Debug, PartialEq)]
+pub struct SuspiciousLine {
+    /// The file path relative to the package directory.
+    pub file: PathBuf,
+    /// The line number (starting from 1).
+    pub line: usize,
+    /// The suspiciousness score, between 0 and 1.
+    pub score: f64,
+}
+
+/// Ranks the mutated source lines by suspiciousness for the given failing tests.
+///
+/// Each mutant is scored with the Ochiai formula over the kills, assuming that the mutants
+/// behaving like the fault (killed by the failing tests, but not by the others) are placed close
+/// to the fault:
+///
+/// `kill_score = killed_by_failing / sqrt(failing_tests * killed_by_any)`
+///
+/// If the report records the tests covering the mutants (the tests ran with `--coverage`), the
+/// mutated lines are also scored by the spectrum-based Ochiai formula over the coverage, and the
+/// mutant gets the average of both scores:
+///
+/// `coverage_score = covered_by_failing / sqrt(failing_tests * covered_by_any)`
+///
+/// Without the coverage, lines executed by the failing tests without any killed mutant aren't
+/// ranked at all. The line gets the score of its most suspicious mutant. Only lines with a
+/// positive score are returned, the most suspicious first.
+pub fn rank_suspicious_lines(
+    report: &Report,
+    failing_tests: &BTreeSet<String>,
+) -> Result<Vec<SuspiciousLine>> {
+    let has_coverage = report.mutants.iter().any(|m| !m.covered_by.is_empty());
+    #[allow(clippy::cast_precision_loss)]
+    let ochiai = |failing: usize, any: usize| {
+        if failing == 0 {
+            return 0.0;
+        }
+        failing as f64 / ((failing_tests.len() * any) as f64).sqrt()
+    };
+
+    let mut scores = BTreeMap::<(PathBuf, usize), f64>::new();
+    for mutant in &report.mutants {
+        // Tests didn't run on the mutant, so there is no information about it.
+        if mutant.kill_reason == Some(KillReason::CompileFailure) {
+            continue;
+        }
+
+        let killed_by_failing = mutant
+            .killed_by
+            .iter()
+            .filter(|test| failing_tests.contains(*test))
+            .count();
+        let kill_score = ochiai(killed_by_failing, mutant.killed_by.len());
+        let score = if has_coverage {
+            let covered_by_failing = mutant
+                .covered_by
+                .iter()
+                .filter(|test| failing_tests.contains(*test))
+                .count();
+            (kill_score + ochiai(covered_by_failing, mutant.covered_by.len())) / 2.0
+        } else {
+            kill_score
+        };
+        if score <= 0.0 {
+            continue;
+        }
+
+        for line in changed_lines(&mutant.diff)? {
+            let entry = scores
+                .entry((mutant.original_file.clone(), line))
+                .or_default();
+            *entry = entry.max(score);
+        }
+    }
+
+    let mut lines = scores
+        .into_iter()
+        .map(|((file, line), score)| SuspiciousLine { file, line, score })
+        .collect::<Vec<_>>();
+    // The sort is stable, so lines with the same score stay ordered by the location.
+    lines.sort_by(|a, b| b.score.total_cmp(&a.score));
+    Ok(lines)
+}
+
+/// Resolves the test names given by the user to the full test names in the report.
+///
+/// The name matches either the full test name or its suffix following the `::` separator.
+fn resolve_tests(report: &Report, names: &[String]) -> Result<BTreeSet<String>> {
+    let mut tests = BTreeSet::new();
+    for name in names {
+        let suffix = format!("::{name}");
+        let matching = report
+            .tests
+            .iter()
+            .filter(|test| *test == name || test.ends_with(&suffix))
+            .collect::<Vec<_>>();
+        match matching.as_slice() {
+            [] => anyhow::bail!("test {name} not found in the report"),
+            [test] => {
+                tests.insert(test.to_string());
+            },
+            _ => anyhow::bail!(
+                "test name {name} is ambiguous, matching tests: {}",
+                matching
+                    .iter()
+                    .map(|t| t.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+    Ok(tests)
+}
+
+/// Prints the most suspicious source lines for the given failing tests.
+pub fn display_suspicious_lines(
+    path_to_report: impl AsRef<Path>,
+    failing_tests: &[String],
+    top: usize,
+) -> Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    if report.tests.is_empty() {
+        anyhow::bail!("the report doesn't contain per-test results");
+    }
+    let failing_tests = resolve_tests(&report, failing_tests)?;
+    let lines = rank_suspicious_lines(&report, &failing_tests)?;
+
+    if lines.is_empty() {
+        println!(
+            "No mutant has been killed or covered by the failing tests, the fault can't be \
+             localized."
+        );
+        return Ok(());
+    }
+
+    println!("The most suspicious lines:");
+    let mut sources = BTreeMap::new();
+    for SuspiciousLine { file, line, score } in lines.iter().take(top) {
+        let source = sources
+            .entry(file.clone())
+            .or_insert_with(|| fs::read_to_string(report.package_dir.join(file)).ok());
+        let code = source
+            .as_deref()
+            .and_then(|s| s.lines().nth(line - 1))
+            .unwrap_or_default()
+            .trim();
+        println!("{score:.3}  {}:{line}  {code}", file.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MiniReport, MutantRecord, MutantStatus};
+
+    fn matrix(tests: usize, kills: &[(usize, usize)]) -> KillMatrix {
+        KillMatrix {
+            schema_version: crate::kill_matrix::KILL_MATRIX_SCHEMA_VERSION,
+            tests: (0..tests).map(|t| format!("test_{t}")).collect(),
+            mutants: vec![],
+            not_run: vec![],
+            kills: kills.to_vec(),
+        }
+    }
+
+    #[test]
+    fn minimization_preserves_kills() {
+        // test_0 kills everything that test_1 and test_2 kill, test_3 kills nothing.
+        let m = matrix(4, &[(0, 0), (0, 1), (0, 2), (1, 0), (2, 2), (2, 1)]);
+        assert_eq!(minimize_tests(&m), MinimizedTests {
+            selected: vec![0],
+            redundant: vec![1, 2, 3],
+            killed_mutants: 3,
+        });
+    }
+
+    #[test]
+    fn minimization_drops_tests_made_redundant_later() {
+        // Greedy picks test_0 first, but test_1 and test_2 together cover it.
+        let m = matrix(3, &[
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (2, 3),
+            (2, 4),
+            (2, 5),
+        ]);
+        let minimized = minimize_tests(&m);
+        assert_eq!(minimized.selected, vec![1, 2]);
+        assert_eq!(minimized.redundant, vec![0]);
+        assert_eq!(minimized.killed_mutants, 6);
+    }
+
+    fn record(file: &str, diff: &str, killed_by: &[&str]) -> MutantRecord {
+        let status = if killed_by.is_empty() {
+            MutantStatus::Alive
+        } else {
+            MutantStatus::Killed
+        };
+        let mini_report = MiniReport::new(
+            PathBuf::from(file),
+            "Sum::sum".to_owned(),
+            status,
+            diff.to_owned(),
+        )
+        .with_killed_by(killed_by.iter().map(|t| t.to_string()).collect());
+        MutantRecord::from(&mini_report)
+    }
+
+    #[test]
+    fn lines_killed_only_by_failing_tests_are_most_suspicious() {
+        let original = "fun sum(x: u64, y: u64): u64 {\n    let a = x + y;\n    a * 1\n}\n";
+        let diff_line2 = diffy::create_patch(original, &original.replace("x + y", "x - y"));
+        let diff_line3 = diffy::create_patch(original, &original.replace("a * 1", "a / 1"));
+
+        let mut report = Report::new("package_dir".into());
+        report.add_mutant_record(record("sources/Sum.move", &diff_line2.to_string(), &[
+            "0x42::sum::test_sum",
+        ]));
+        report.add_mutant_record(record("sources/Sum.move", &diff_line3.to_string(), &[
+            "0x42::sum::test_sum",
+            "0x42::sum::test_other",
+        ]));
+        report.add_mutant_record(record("sources/Sum.move", &diff_line3.to_string(), &[]));
+        report.set_tests([
+            "0x42::sum::test_sum".to_owned(),
+            "0x42::sum::test_other".to_owned(),
+        ]);
+
+        let failing = BTreeSet::from(["0x42::sum::test_sum".to_owned()]);
+        let lines = rank_suspicious_lines(&report, &failing).unwrap();
+        assert_eq!(lines, vec![
+            SuspiciousLine {
+                file: PathBuf::from("sources/Sum.move"),
+                line: 2,
+                score: 1.0,
+            },
+            SuspiciousLine {
+                file: PathBuf::from("sources/Sum.move"),
+                line: 3,
+                score: 1.0 / 2.0_f64.sqrt(),
+            },
+        ]);
+
+        let resolved = resolve_tests(&report, &["sum::test_sum".to_owned()]).unwrap();
+        assert_eq!(resolved, BTreeSet::from(["0x42::sum::test_sum".to_owned()]));
+        assert!(resolve_tests(&report, &["missing".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn coverage_ranks_lines_without_killed_mutants() {
+        let original = "fun sum(x: u64, y: u64): u64 {\n    let a = x + y;\n    a * 1\n}\n";
+        let diff_line2 = diffy::create_patch(original, &original.replace("x + y", "x - y"));
+        let diff_line3 = diffy::create_patch(original, &original.replace("a * 1", "a / 1"));
+
+        let mut report = Report::new("package_dir".into());
+        let mut killed = record("sources/Sum.move", &diff_line2.to_string(), &[
+            "0x42::sum::test_sum",
+            "0x42::sum::test_other",
+        ]);
+        killed.covered_by = vec![
+            "0x42::sum::test_sum".to_owned(),
+            "0x42::sum::test_other".to_owned(),
+        ];
+        report.add_mutant_record(killed);
+        // The surviving mutant is executed only by the failing test.
+        let mut alive = record("sources/Sum.move", &diff_line3.to_string(), &[]);
+        alive.covered_by = vec!["0x42::sum::test_sum".to_owned()];
+        report.add_mutant_record(alive);
+
+        let failing = BTreeSet::from(["0x42::sum::test_sum".to_owned()]);
+        let lines = rank_suspicious_lines(&report, &failing).unwrap();
+        let kill_and_coverage = 1.0 / 2.0_f64.sqrt();
+        assert_eq!(lines, vec![
+            SuspiciousLine {
+                file: PathBuf::from("sources/Sum.move"),
+                line: 2,
+                score: kill_and_coverage,
+            },
+            SuspiciousLine {
+                file: PathBuf::from("sources/Sum.move"),
+                line: 3,
+                score: 0.5,
+            },
+        ]);
+    }
+}
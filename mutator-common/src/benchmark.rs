@@ -2,6 +2,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::memory::MemoryPeaks;
 use log::info;
 use std::time::{Duration, Instant};
 
@@ -45,6 +46,8 @@ impl Benchmark {
 pub struct Benchmarks {
     /// Total time for the whole tool to complete.
     pub total_tool_duration: Benchmark,
+    /// Benchmark for resolving the options and setting up the package.
+    pub resolution: Benchmark,
     /// Benchmark for the test execution on all mutants.
     pub executing_original_package: Benchmark,
     /// Benchmark for the mutator.
@@ -53,6 +56,10 @@ pub struct Benchmarks {
     pub executing_tests_on_mutants: Benchmark,
     /// Benchmark for the each mutant.
     pub mutant_results: Vec<Benchmark>,
+    /// Benchmark for preparing the report.
+    pub reporting: Benchmark,
+    /// The peak memory usage, if profiled (`--profile-memory`).
+    pub memory: Option<MemoryPeaks>,
 }
 
 impl Benchmarks {
@@ -60,13 +67,54 @@ impl Benchmarks {
     pub fn new() -> Self {
         Self {
             total_tool_duration: Benchmark::new(),
+            resolution: Benchmark::new(),
             executing_original_package: Benchmark::new(),
             mutator: Benchmark::new(),
             executing_tests_on_mutants: Benchmark::new(),
             mutant_results: Vec::new(),
+            reporting: Benchmark::new(),
+            memory: None,
         }
     }
 
+    /// Returns the per-phase breakdown of the run with the number of processed mutants.
+    ///
+    /// Mutants are verified while being generated, so both are measured as a single phase.
+    pub fn timings_table(&self, mutants_generated: usize, mutants_invalid: usize) -> String {
+        let executed = self.mutant_results.len();
+        let execution = if executed == 0 {
+            String::new()
+        } else {
+            let average = self.executing_tests_on_mutants.elapsed / executed as u32;
+            format!("{executed} mutants (average {average:.1?})")
+        };
+
+        let rows = [
+            ("resolution", &self.resolution, String::new()),
+            ("baseline", &self.executing_original_package, String::new()),
+            (
+                "generation and verification",
+                &self.mutator,
+                format!("{mutants_generated} mutants ({mutants_invalid} invalid)"),
+            ),
+            ("execution", &self.executing_tests_on_mutants, execution),
+            ("reporting", &self.reporting, String::new()),
+            ("total", &self.total_tool_duration, String::new()),
+        ];
+
+        let mut table = String::from("Timings:\n");
+        for (phase, benchmark, count) in rows {
+            let elapsed = format!("{:.1?}", benchmark.elapsed);
+            let row = format!("  {phase:<28} {elapsed:>10}  {count}");
+            table.push_str(row.trim_end());
+            table.push('\n');
+        }
+        if let Some(memory) = &self.memory {
+            table.push_str(&memory.to_string());
+        }
+        table
+    }
+
     /// Displays the benchmarks with the `RUST_LOG` info level.
     pub fn display(&self) {
         info!(
@@ -116,6 +164,38 @@ mod tests {
     use super::*;
     use std::{thread, time::Duration};
 
+    #[test]
+    fn timings_table_lists_all_phases() {
+        let mut benchmarks = Benchmarks::new();
+        benchmarks.executing_tests_on_mutants.elapsed = Duration::from_secs(3);
+        benchmarks.mutant_results = vec![Benchmark::new(); 2];
+
+        let table = benchmarks.timings_table(3, 1);
+        for phase in [
+            "resolution",
+            "baseline",
+            "generation and verification",
+            "execution",
+            "reporting",
+            "total",
+        ] {
+            assert!(table.contains(phase), "{phase} missing in {table}");
+        }
+        assert!(table.contains("3 mutants (1 invalid)"));
+        assert!(table.contains("2 mutants (average 1.5s)"));
+        assert!(!table.contains("Peak memory"));
+
+        benchmarks.memory = Some(MemoryPeaks {
+            orchestrator: 1024,
+            workers: 2048,
+            total: 3072,
+            samples: 4,
+        });
+        let table = benchmarks.timings_table(3, 1);
+        assert!(table.contains("Peak memory (4 samples):"));
+        assert!(table.contains("3.0 KiB"));
+    }
+
     #[test]
     fn benchmark_records_correct_elapsed_time() {
         let mut benchmark = Benchmark::new();
@@ -155,6 +235,7 @@ mod tests {
             mutator: Benchmark::new(),
             executing_tests_on_mutants: Benchmark::new(),
             mutant_results: Vec::new(),
+            ..Benchmarks::new()
         };
 
         benchmarks.total_tool_duration.start();
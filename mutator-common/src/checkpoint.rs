@@ -0,0 +1,376 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::MiniReport;
+use anyhow::{anyhow, Context};
+use clap::Args;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// How often the results are saved to the checkpoint file (`--checkpoint-every`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointInterval {
+    /// After the given number of evaluated mutants.
+    Mutants(NonZeroUsize),
+    /// After the given time since the last checkpoint.
+    Time(Duration),
+}
+
+impl FromStr for CheckpointInterval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            anyhow!(
+                "invalid checkpoint interval '{s}', expected a number of mutants (e.g. `50`) or a \
+                 duration in seconds, minutes or hours (e.g. `90s`, `5m`, `1h`)"
+            )
+        };
+        if let Ok(mutants) = s.parse::<NonZeroUsize>() {
+            return Ok(Self::Mutants(mutants));
+        }
+
+        let unit = s.chars().last().ok_or_else(invalid)?;
+        let multiplier = match unit {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            _ => return Err(invalid()),
+        };
+        let value = s[..s.len() - 1]
+            .parse::<u64>()
+            .ok()
+            .filter(|value| *value > 0)
+            .ok_or_else(invalid)?;
+        Ok(Self::Time(Duration::from_secs(value * multiplier)))
+    }
+}
+
+impl fmt::Display for CheckpointInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mutants(mutants) => write!(f, "{mutants} mutants"),
+            Self::Time(duration) => write!(f, "{}s", duration.as_secs()),
+        }
+    }
+}
+
+/// Options of the checkpoints, which allow resuming an interrupted run.
+#[derive(Args, Debug, Clone, Default, PartialEq)]
+pub struct CheckpointOptions {
+    /// Save the results of the evaluated mutants to the checkpoint file after the given number
+    /// of mutants (e.g. `50`) or time (e.g. `90s`, `5m`, `1h`), so a run interrupted e.g. by the
+    /// preemption of a spot CI runner can be continued with `--resume`.
+    #[clap(long)]
+    pub checkpoint_every: Option<CheckpointInterval>,
+
+    /// Continue the interrupted run from the checkpoint file, evaluating only the mutants which
+    /// haven't been evaluated before the interruption.
+    #[clap(long)]
+    pub resume: bool,
+
+    /// The checkpoint file used by `--checkpoint-every` and `--resume`. Defaults to
+    /// `<tool>.checkpoint.json` in the current directory.
+    #[clap(long, value_parser)]
+    pub checkpoint_file: Option<PathBuf>,
+}
+
+impl CheckpointOptions {
+    /// Creates the checkpoint of the run of the package, if enabled.
+    ///
+    /// # Errors
+    /// Returns an error if the checkpoint to resume from can't be loaded.
+    pub fn open(&self, tool: &str, package_dir: &Path) -> anyhow::Result<Option<Checkpoint>> {
+        if self.checkpoint_every.is_none() && !self.resume {
+            return Ok(None);
+        }
+        let path = self
+            .checkpoint_file
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("{tool}.checkpoint.json")));
+        let checkpoint = Checkpoint::new(path, self.checkpoint_every, package_dir);
+        if self.resume {
+            checkpoint.resume()?;
+        }
+        if let Some(interval) = self.checkpoint_every {
+            println!(
+                "Saving a checkpoint every {interval} to {}",
+                checkpoint.path.display()
+            );
+        }
+        Ok(Some(checkpoint))
+    }
+}
+
+/// The version of the checkpoint file schema.
+const CHECKPOINT_SCHEMA_VERSION: u32 = 1;
+
+/// The content of the checkpoint file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CheckpointData {
+    /// The version of the checkpoint file schema. The checkpoints saved before the version was
+    /// recorded have the version 1.
+    #[serde(default = "legacy_schema_version")]
+    schema_version: u32,
+    /// The package the results belong to.
+    package_dir: PathBuf,
+    /// The results of the mutants evaluated so far.
+    results: Vec<MiniReport>,
+}
+
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// The state of the checkpoint shared by the workers.
+#[derive(Debug)]
+struct State {
+    data: CheckpointData,
+    /// The number of results recorded since the last checkpoint.
+    unsaved: usize,
+    /// The time of the last checkpoint.
+    saved_at: Instant,
+    /// The results loaded from the checkpoint of the interrupted run, by the mutant ID.
+    resumed: BTreeMap<String, MiniReport>,
+}
+
+/// Periodically saved results of the run, so a run interrupted e.g. by the preemption of a spot
+/// CI runner can be resumed with `--resume`, losing at most one checkpoint interval of work.
+///
+/// The results are matched to the mutants of the resumed run by the mutant IDs, which stay the
+/// same as long as the mutated code doesn't change. The file is removed when the run finishes.
+#[derive(Debug)]
+pub struct Checkpoint {
+    path: PathBuf,
+    interval: Option<CheckpointInterval>,
+    state: Mutex<State>,
+}
+
+impl Checkpoint {
+    /// Creates the checkpoint of the run of the package, saved at the given interval (if any).
+    pub fn new(path: PathBuf, interval: Option<CheckpointInterval>, package_dir: &Path) -> Self {
+        Self {
+            path,
+            interval,
+            state: Mutex::new(State {
+                data: CheckpointData {
+                    schema_version: CHECKPOINT_SCHEMA_VERSION,
+                    package_dir: package_dir.to_path_buf(),
+                    results: vec![],
+                },
+                unsaved: 0,
+                saved_at: Instant::now(),
+                resumed: BTreeMap::new(),
+            }),
+        }
+    }
+
+    /// Loads the results saved by the interrupted run and keeps them in the checkpoint.
+    ///
+    /// A missing checkpoint file means there is nothing to resume.
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read, it has been saved by a newer version of the tool
+    /// or it belongs to a different package.
+    pub fn resume(&self) -> anyhow::Result<()> {
+        if !self.path.exists() {
+            println!(
+                "No checkpoint found at {}, starting from the beginning",
+                self.path.display()
+            );
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("cannot read checkpoint {}", self.path.display()))?;
+        let data: CheckpointData = serde_json::from_str(&content)
+            .with_context(|| format!("invalid checkpoint {}", self.path.display()))?;
+
+        if data.schema_version > CHECKPOINT_SCHEMA_VERSION {
+            anyhow::bail!(
+                "checkpoint {} has schema version {}, but only versions up to \
+                 {CHECKPOINT_SCHEMA_VERSION} are supported, remove it to start from the beginning",
+                self.path.display(),
+                data.schema_version
+            );
+        }
+
+        let mut state = self.lock();
+        if data.package_dir != state.data.package_dir {
+            anyhow::bail!(
+                "checkpoint {} belongs to package {}, remove it to start from the beginning",
+                self.path.display(),
+                data.package_dir.display()
+            );
+        }
+        println!(
+            "Resuming from checkpoint {} with {} evaluated mutants",
+            self.path.display(),
+            data.results.len()
+        );
+
+        // Mutants without an ID can't be matched, so they are evaluated again.
+        state.resumed = data
+            .results
+            .iter()
+            .filter(|report| !report.mutant_id.is_empty())
+            .map(|report| (report.mutant_id.clone(), report.clone()))
+            .collect();
+        state.data.results = data.results;
+        Ok(())
+    }
+
+    /// Returns the result of the mutant evaluated before the interruption, if any.
+    pub fn resumed(&self, mutant_id: &str) -> Option<MiniReport> {
+        self.lock().resumed.get(mutant_id).cloned()
+    }
+
+    /// Records the result of an evaluated mutant and saves the checkpoint when the interval has
+    /// elapsed.
+    ///
+    /// Failing to save the checkpoint doesn't fail the run, only a warning is printed.
+    pub fn record(&self, report: &MiniReport) {
+        let mut state = self.lock();
+        state.data.results.push(report.clone());
+        state.unsaved += 1;
+
+        let due = match self.interval {
+            Some(CheckpointInterval::Mutants(mutants)) => state.unsaved >= mutants.get(),
+            Some(CheckpointInterval::Time(duration)) => state.saved_at.elapsed() >= duration,
+            None => false,
+        };
+        if due {
+            if let Err(e) = self.save(&state.data) {
+                let msg = format!("cannot save checkpoint {}: {e:#}", self.path.display());
+                warn!("{msg}");
+                eprintln!("WARNING: {msg}");
+            }
+            state.unsaved = 0;
+            state.saved_at = Instant::now();
+        }
+    }
+
+    /// Removes the checkpoint file once the run has finished, so the next run starts from the
+    /// beginning.
+    pub fn finish(self) {
+        if self.path.exists() {
+            if let Err(e) = fs::remove_file(&self.path) {
+                warn!("cannot remove checkpoint {}: {e}", self.path.display());
+            }
+        }
+    }
+
+    /// Saves the data to a temporary file first, so an interruption while saving doesn't
+    /// corrupt the previous checkpoint.
+    fn save(&self, data: &CheckpointData) -> anyhow::Result<()> {
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(data)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        info!(
+            "Checkpoint with {} evaluated mutants saved to {}",
+            data.results.len(),
+            self.path.display()
+        );
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // A panicking worker doesn't leave the results in an inconsistent state.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::MutantStatus;
+
+    fn report(id: &str) -> MiniReport {
+        MiniReport::new(
+            "sources/vault.move".into(),
+            "vault::withdraw".to_owned(),
+            MutantStatus::Killed,
+            String::new(),
+        )
+        .with_mutant_info(id, vec!["binary_operator_replacement".to_owned()])
+    }
+
+    #[test]
+    fn checkpoint_intervals_are_parsed() {
+        assert_eq!(
+            "50".parse::<CheckpointInterval>().unwrap(),
+            CheckpointInterval::Mutants(NonZeroUsize::new(50).unwrap())
+        );
+        assert_eq!(
+            "5m".parse::<CheckpointInterval>().unwrap(),
+            CheckpointInterval::Time(Duration::from_secs(300))
+        );
+        assert_eq!(
+            "90s".parse::<CheckpointInterval>().unwrap(),
+            CheckpointInterval::Time(Duration::from_secs(90))
+        );
+        for invalid in ["0", "0s", "5d", "m", ""] {
+            assert!(invalid.parse::<CheckpointInterval>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn interrupted_run_is_resumed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let package = Path::new("/packages/vault");
+        let interval = Some(CheckpointInterval::Mutants(NonZeroUsize::new(2).unwrap()));
+
+        let checkpoint = Checkpoint::new(path.clone(), interval, package);
+        checkpoint.resume().unwrap();
+        assert!(checkpoint.resumed("a1").is_none());
+        checkpoint.record(&report("a1"));
+        assert!(!path.exists());
+        checkpoint.record(&report("b2"));
+        // The third result is lost by the interruption.
+        checkpoint.record(&report("c3"));
+        drop(checkpoint);
+
+        let checkpoint = Checkpoint::new(path.clone(), interval, package);
+        checkpoint.resume().unwrap();
+        assert_eq!(
+            checkpoint.resumed("a1").unwrap().mutant_status,
+            MutantStatus::Killed
+        );
+        assert!(checkpoint.resumed("b2").is_some());
+        assert!(checkpoint.resumed("c3").is_none());
+        // The resumed results are kept in the next checkpoints.
+        checkpoint.record(&report("c3"));
+        checkpoint.record(&report("d4"));
+        let saved: CheckpointData =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.results.len(), 4);
+
+        checkpoint.finish();
+        assert!(!path.exists());
+
+        assert_eq!(saved.schema_version, CHECKPOINT_SCHEMA_VERSION);
+        let other = Checkpoint::new(path.clone(), interval, Path::new("/packages/other"));
+        fs::write(&path, serde_json::to_vec(&saved).unwrap()).unwrap();
+        let err = other.resume().unwrap_err();
+        assert!(err.to_string().contains("belongs to package"));
+
+        let newer = CheckpointData {
+            schema_version: CHECKPOINT_SCHEMA_VERSION + 1,
+            ..saved
+        };
+        fs::write(&path, serde_json::to_vec(&newer).unwrap()).unwrap();
+        let err = other.resume().unwrap_err();
+        assert!(err.to_string().contains("schema version"));
+    }
+}
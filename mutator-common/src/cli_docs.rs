@@ -0,0 +1,77 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Command;
+pub use clap_complete::Shell;
+use clap_mangen::Man;
+use std::io;
+
+/// Prints the shell completion script for the command to the standard output.
+pub fn print_completions(shell: Shell, cmd: &mut Command) {
+    let name = cmd.get_name().to_owned();
+    clap_complete::generate(shell, cmd, name, &mut io::stdout());
+}
+
+/// Renders the man pages (in the roff format) of the command and all its subcommands.
+///
+/// The pages are generated by `clap_mangen`, one per command, and concatenated in a single
+/// document: the page of the command is followed by the pages of the subcommands, named like
+/// `tool-run`, which the command's page refers to.
+///
+/// # Errors
+///
+/// Returns an error if a page can't be rendered.
+pub fn render_manpage(cmd: &Command) -> io::Result<String> {
+    let mut cmd = cmd.clone().disable_help_subcommand(true);
+    cmd.build();
+
+    let mut page = vec![];
+    Man::new(cmd.clone()).render(&mut page)?;
+    for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        Man::new(sub.clone()).render(&mut page)?;
+    }
+    String::from_utf8(page).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, ArgAction};
+
+    fn command() -> Command {
+        Command::new("tool")
+            .version("1.0.0")
+            .about("A testing tool")
+            .subcommand(
+                Command::new("run")
+                    .about("Runs the tool")
+                    .arg(
+                        Arg::new("output")
+                            .long("output")
+                            .short('o')
+                            .help("Save report to a file")
+                            .env("TOOL_OUTPUT"),
+                    )
+                    .arg(
+                        Arg::new("verify")
+                            .long("verify-mutants")
+                            .action(ArgAction::SetTrue)
+                            .help("Verify mutants"),
+                    ),
+            )
+    }
+
+    #[test]
+    fn manpage_contains_commands_and_options() {
+        let page = render_manpage(&command()).unwrap();
+        assert!(page.contains(".TH tool 1"));
+        assert!(page.contains("A testing tool"));
+        // The command's page refers to the page of the subcommand, which follows it.
+        assert!(page.contains("tool\\-run(1)"));
+        assert_eq!(page.matches(".TH ").count(), 2);
+        assert!(page.contains("Save report to a file"));
+        assert!(page.contains("TOOL_OUTPUT"));
+        assert!(page.contains("\\-\\-verify\\-mutants"));
+    }
+}
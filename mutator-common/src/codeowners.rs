@@ -0,0 +1,164 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Directories (relative to the repository root) where GitHub looks for the `CODEOWNERS` file.
+const CODEOWNERS_DIRS: &[&str] = &[".github", "docs"];
+
+/// A single `<pattern> <owner>...` rule.
+#[derive(Debug)]
+struct Rule {
+    /// Paths matched by the pattern.
+    matcher: GlobSet,
+    /// Owners of the matched paths.
+    owners: Vec<String>,
+}
+
+/// Ownership rules parsed from a `CODEOWNERS` file (or a mapping file using the same syntax).
+///
+/// Each non-empty line which is not a comment contains a gitignore-style pattern followed by the
+/// owners (e.g. `@org/team`). As in GitHub, the last matching rule takes precedence.
+#[derive(Debug)]
+pub struct CodeOwners {
+    /// The directory the patterns are relative to.
+    root: PathBuf,
+    /// The ownership rules in the order of the file.
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Loads the ownership rules from the file.
+    ///
+    /// Patterns are relative to the directory containing the file, or to the repository root if
+    /// the file is placed in the `.github` or `docs` directory.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read owners file {}", path.display()))?;
+
+        let path = path.canonicalize()?;
+        let mut root = path.parent().unwrap_or(Path::new("/")).to_path_buf();
+        if root
+            .file_name()
+            .is_some_and(|dir| CODEOWNERS_DIRS.iter().any(|d| dir == *d))
+        {
+            root.pop();
+        }
+
+        Self::parse(&content, root)
+    }
+
+    /// Parses the ownership rules, with patterns relative to the `root` directory.
+    fn parse(content: &str, root: PathBuf) -> Result<Self> {
+        let mut rules = vec![];
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let pattern = fields.next().unwrap_or_default();
+            let owners = fields
+                .take_while(|owner| !owner.starts_with('#'))
+                .map(str::to_owned)
+                .collect();
+
+            let mut matcher = GlobSetBuilder::new();
+            for glob in pattern_to_globs(pattern) {
+                matcher.add(
+                    GlobBuilder::new(&glob)
+                        .literal_separator(true)
+                        .build()
+                        .with_context(|| format!("invalid pattern on line {}", idx + 1))?,
+                );
+            }
+            rules.push(Rule {
+                matcher: matcher.build()?,
+                owners,
+            });
+        }
+
+        Ok(Self { root, rules })
+    }
+
+    /// Returns the owners of the file. The path is either absolute or relative to the root.
+    ///
+    /// An empty slice is returned for files without owners.
+    pub fn owners_of(&self, path: &Path) -> &[String] {
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matcher.is_match(path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Translates the gitignore-style pattern into globs matching the paths relative to the root.
+fn pattern_to_globs(pattern: &str) -> Vec<String> {
+    // Patterns containing a separator (other than the trailing one) are anchored to the root.
+    let anchored = pattern.trim_end_matches('/').contains('/');
+    let mut glob = pattern.trim_start_matches('/').to_owned();
+    if !anchored {
+        glob.insert_str(0, "**/");
+    }
+
+    if glob.ends_with('/') {
+        // Only directories are matched.
+        glob.push_str("**");
+        vec![glob]
+    } else {
+        // The pattern matches either the file or all files in the directory.
+        vec![format!("{glob}/**"), glob]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let content = "\
+            # Default owners\n\
+            *       @org/core\n\
+            \n\
+            /sources/coin/  @org/tokens # comment\n\
+            *.spec.move     @org/verification @alice\n\
+            docs            @org/docs\n";
+        let owners = CodeOwners::parse(content, PathBuf::from("/repo")).unwrap();
+
+        assert_eq!(owners.owners_of(Path::new("/repo/sources/Sum.move")), [
+            "@org/core"
+        ]);
+        assert_eq!(owners.owners_of(Path::new("sources/coin/Coin.move")), [
+            "@org/tokens"
+        ]);
+        assert_eq!(
+            owners.owners_of(Path::new("/repo/sources/coin/Coin.spec.move")),
+            ["@org/verification", "@alice"]
+        );
+        assert_eq!(owners.owners_of(Path::new("pkg/docs/README.md")), [
+            "@org/docs"
+        ]);
+    }
+
+    #[test]
+    fn files_without_owners() {
+        let owners = CodeOwners::parse("/sources/ @org/core\n", PathBuf::from("/repo")).unwrap();
+        assert!(owners
+            .owners_of(Path::new("/repo/tests/Sum.move"))
+            .is_empty());
+        assert!(owners
+            .owners_of(Path::new("/repo/other/sources/Sum.move"))
+            .is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::ValueEnum;
+use prettytable::Table;
+use std::{
+    io::{self, IsTerminal},
+    sync::OnceLock,
+};
+
+/// The environment variable which disables colors, see <https://no-color.org>.
+pub const NO_COLOR_ENV: &str = "NO_COLOR";
+
+/// The color choice of the whole run, set by [`set_color_choice`].
+static COLOR_CHOICE: OnceLock<ColorChoice> = OnceLock::new();
+
+/// When to use colors in the output.
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Use colors if the output is a terminal and the `NO_COLOR` variable isn't set.
+    #[default]
+    Auto,
+    /// Always use colors, even if the `NO_COLOR` variable is set.
+    Always,
+    /// Never use colors.
+    Never,
+}
+
+/// The output stream checked for the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Sets the color choice used by all the output of the run: the logs, the compiler diagnostics,
+/// the tables and the diffs.
+///
+/// Only the first choice is used if called more than once. It should be called before the
+/// logger is initialized.
+pub fn set_color_choice(choice: ColorChoice) {
+    let _ = COLOR_CHOICE.set(choice);
+}
+
+/// Returns the color choice of the run, [`ColorChoice::Auto`] if it hasn't been set.
+pub fn color_choice() -> ColorChoice {
+    COLOR_CHOICE.get().copied().unwrap_or_default()
+}
+
+/// Checks if the output written to the stream should be colored.
+pub fn use_colors(stream: Stream) -> bool {
+    match color_choice() {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            let no_color = std::env::var_os(NO_COLOR_ENV).is_some_and(|v| !v.is_empty());
+            let is_terminal = match stream {
+                Stream::Stdout => io::stdout().is_terminal(),
+                Stream::Stderr => io::stderr().is_terminal(),
+            };
+            !no_color && is_terminal
+        },
+    }
+}
+
+/// Returns the color choice for the `termcolor` writers, e.g. the one passed to
+/// `GlobalEnv::report_diag`.
+pub fn termcolor_choice(stream: Stream) -> termcolor::ColorChoice {
+    if use_colors(stream) {
+        termcolor::ColorChoice::Always
+    } else {
+        termcolor::ColorChoice::Never
+    }
+}
+
+/// Returns the write style of the logger, accepted by `env_logger::Builder::parse_write_style`.
+pub fn log_write_style() -> &'static str {
+    if use_colors(Stream::Stderr) {
+        "always"
+    } else {
+        "never"
+    }
+}
+
+/// Prints the table to the standard output, colored only if the colors are enabled.
+pub fn print_table(table: &Table) {
+    if use_colors(Stream::Stdout) {
+        let _ = table.print_tty(true);
+    } else {
+        let _ = table.print(&mut io::stdout());
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    color::{use_colors, Stream},
+    report::MiniReport,
+};
+use clap::Args;
+use diffy::{Line, Patch, PatchFormatter};
+use std::{fmt::Write, fs, ops::Range, path::Path};
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_CYAN: &str = "\x1b[36m";
+const ANSI_HIGHLIGHT: &str = "\x1b[1;7m";
+
+/// Options of rendering the mutant diffs.
+#[derive(Args, Debug, Clone, PartialEq)]
+pub struct DiffOptions {
+    /// The number of context lines around the mutated lines.
+    ///
+    /// The diff is recreated from the original source file, so it must not be changed since the
+    /// report has been generated. Otherwise, the diff stored in the report is shown as is.
+    #[clap(global = true, long, default_value_t = 3)]
+    pub diff_context: usize,
+
+    /// Highlight the changed words within the mutated lines.
+    #[clap(global = true, long)]
+    pub word_diff: bool,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            diff_context: 3,
+            word_diff: false,
+        }
+    }
+}
+
+/// Renders the diff of the mutant for the terminal.
+///
+/// The `source_path` points to the original source file, which is needed to change the number of
+/// context lines. The diff is colored if the colors are enabled for the standard output.
+pub fn render_diff(
+    diff: &str,
+    source_path: &Path,
+    options: &DiffOptions,
+) -> anyhow::Result<String> {
+    render_diff_with_colors(diff, source_path, options, use_colors(Stream::Stdout))
+}
+
+/// Renders the diff, with or without colors. Without colors, the changed words are marked like
+/// `[-old-]` and `{+new+}`.
+fn render_diff_with_colors(
+    diff: &str,
+    source_path: &Path,
+    options: &DiffOptions,
+    colored: bool,
+) -> anyhow::Result<String> {
+    let patch = Patch::from_str(diff)?;
+    let recreated = recreate_diff(&patch, source_path, options.diff_context);
+    let recreated = match &recreated {
+        Some(diff) => Patch::from_str(diff)?,
+        None => patch,
+    };
+
+    if options.word_diff {
+        Ok(format_word_diff(&recreated, colored))
+    } else if colored {
+        Ok(PatchFormatter::new()
+            .with_color()
+            .fmt_patch(&recreated)
+            .to_string())
+    } else {
+        Ok(recreated.to_string())
+    }
+}
+
+/// Renders the surviving mutant for the verbose output printed right when the mutant survives:
+/// the header, the inline diff with the changed words highlighted and the tests which passed on
+/// the mutant (the tests covering the mutated code which didn't notice the change).
+///
+/// The `source_path` points to the original source file, see [`render_diff`].
+pub fn render_survivor(mini_report: &MiniReport, source_path: &Path) -> String {
+    render_survivor_with_colors(mini_report, source_path, use_colors(Stream::Stdout))
+}
+
+/// Renders the surviving mutant, with or without colors.
+fn render_survivor_with_colors(
+    mini_report: &MiniReport,
+    source_path: &Path,
+    colored: bool,
+) -> String {
+    let (red, reset) = if colored {
+        (ANSI_RED, ANSI_RESET)
+    } else {
+        ("", "")
+    };
+    let options = DiffOptions {
+        word_diff: true,
+        ..DiffOptions::default()
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Mutant {} {red}survived{reset} in {} ({}):",
+        mini_report.mutant_id,
+        mini_report.qname,
+        mini_report.operators.join(", ")
+    );
+    match render_diff_with_colors(&mini_report.diff, source_path, &options, colored) {
+        Ok(diff) => out.push_str(&diff),
+        Err(_) => out.push_str(&mini_report.diff),
+    }
+    if !mini_report.passed_tests.is_empty() {
+        let _ = writeln!(
+            out,
+            "Tests passing on the mutant: {}",
+            mini_report.passed_tests.join(", ")
+        );
+    }
+    out
+}
+
+/// Recreates the diff with the given number of context lines, if the original source file is
+/// available and unchanged.
+fn recreate_diff(patch: &Patch<'_, str>, source_path: &Path, context: usize) -> Option<String> {
+    let original = fs::read_to_string(source_path).ok()?;
+    let mutated = diffy::apply(&original, patch).ok()?;
+    Some(
+        diffy::DiffOptions::new()
+            .set_context_len(context)
+            .create_patch(&original, &mutated)
+            .to_string(),
+    )
+}
+
+/// Formats the patch, highlighting changed words in the pairs of deleted and inserted lines.
+fn format_word_diff(patch: &Patch<'_, str>, colored: bool) -> String {
+    let (cyan, reset) = if colored {
+        (ANSI_CYAN, ANSI_RESET)
+    } else {
+        ("", "")
+    };
+
+    let mut out = String::new();
+    for hunk in patch.hunks() {
+        let (old, new) = (hunk.old_range(), hunk.new_range());
+        let _ = writeln!(
+            out,
+            "{cyan}@@ -{},{} +{},{} @@{reset}",
+            old.start(),
+            old.len(),
+            new.start(),
+            new.len()
+        );
+
+        let lines = hunk.lines();
+        let mut i = 0;
+        while i < lines.len() {
+            let Line::Context(line) = &lines[i] else {
+                // Collect the block of deleted lines followed by the inserted lines.
+                let deleted = take_while(&lines[i..], |l| matches!(l, Line::Delete(_)));
+                let inserted = take_while(&lines[i + deleted.len()..], |l| {
+                    matches!(l, Line::Insert(_))
+                });
+                i += deleted.len() + inserted.len();
+
+                for (idx, old_line) in deleted.iter().enumerate() {
+                    let changed = inserted
+                        .get(idx)
+                        .map(|new_line| changed_words(old_line, new_line).0);
+                    push_line(&mut out, '-', colored, old_line, changed);
+                }
+                for (idx, new_line) in inserted.iter().enumerate() {
+                    let changed = deleted
+                        .get(idx)
+                        .map(|old_line| changed_words(old_line, new_line).1);
+                    push_line(&mut out, '+', colored, new_line, changed);
+                }
+                continue;
+            };
+            let _ = write!(out, " {line}");
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Returns the contents of the leading lines matching the predicate.
+fn take_while<'a>(lines: &[Line<'a, str>], pred: impl Fn(&Line<'a, str>) -> bool) -> Vec<&'a str> {
+    lines
+        .iter()
+        .take_while(|&l| pred(l))
+        .map(|l| match l {
+            Line::Context(s) | Line::Delete(s) | Line::Insert(s) => *s,
+        })
+        .collect()
+}
+
+/// Writes the deleted (`-`) or inserted (`+`) line, highlighting the changed part, if any.
+fn push_line(
+    out: &mut String,
+    sign: char,
+    colored: bool,
+    line: &str,
+    changed: Option<Range<usize>>,
+) {
+    let line = line.trim_end_matches('\n');
+    let (color, reset) = match (colored, sign) {
+        (false, _) => ("", ""),
+        (true, '-') => (ANSI_RED, ANSI_RESET),
+        (true, _) => (ANSI_GREEN, ANSI_RESET),
+    };
+    let (open, close) = match (colored, sign) {
+        (true, _) => (ANSI_HIGHLIGHT, ANSI_RESET),
+        (false, '-') => ("[-", "-]"),
+        (false, _) => ("{+", "+}"),
+    };
+    match changed {
+        Some(range) if !range.is_empty() => {
+            let _ = writeln!(
+                out,
+                "{color}{sign}{}{open}{}{close}{color}{}{reset}",
+                &line[..range.start],
+                &line[range.clone()],
+                &line[range.end..]
+            );
+        },
+        _ => {
+            let _ = writeln!(out, "{color}{sign}{line}{reset}");
+        },
+    }
+}
+
+/// Finds the changed words in the pair of lines - the part between the longest common prefix and
+/// suffix consisting of whole words. Returns the byte ranges of the changed part in both lines.
+pub fn changed_words(old: &str, new: &str) -> (Range<usize>, Range<usize>) {
+    let (old, new) = (old.trim_end_matches('\n'), new.trim_end_matches('\n'));
+    let old_words = split_words(old);
+    let new_words = split_words(new);
+
+    let prefix = old_words
+        .iter()
+        .zip(&new_words)
+        .take_while(|(a, b)| a.1 == b.1)
+        .count();
+    let suffix = old_words[prefix..]
+        .iter()
+        .rev()
+        .zip(new_words[prefix..].iter().rev())
+        .take_while(|(a, b)| a.1 == b.1)
+        .count();
+
+    let range = |words: &[(usize, &str)], line: &str| {
+        let start = words.get(prefix).map_or(line.len(), |w| w.0);
+        let end = words
+            .len()
+            .checked_sub(suffix)
+            .and_then(|idx| words.get(idx))
+            .map_or(line.len(), |w| w.0);
+        start..end.max(start)
+    };
+    (range(&old_words, old), range(&new_words, new))
+}
+
+/// Splits the line into words (runs of alphanumeric characters or whitespaces) and single other
+/// characters, together with their byte offsets.
+fn split_words(line: &str) -> Vec<(usize, &str)> {
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+
+    let mut words = vec![];
+    let mut start = 0;
+    let mut chars = line.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        let next_class = chars.peek().map(|&(_, next)| class(next));
+        if class(c) == 2 || next_class != Some(class(c)) {
+            let end = idx + c.len_utf8();
+            words.push((start, &line[start..end]));
+            start = end;
+        }
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_words_are_whole_words() {
+        let old = "        let sum_r = x * y;\n";
+        let new = "        let sum_r = x / y;\n";
+        let (old_range, new_range) = changed_words(old, new);
+        assert_eq!(&old[old_range], "*");
+        assert_eq!(&new[new_range], "/");
+
+        let old = "if (amount > 1000) {";
+        let new = "if (amount > 1001) {";
+        let (old_range, new_range) = changed_words(old, new);
+        assert_eq!(&old[old_range], "1000");
+        assert_eq!(&new[new_range], "1001");
+
+        // Insertion only.
+        let old = "x + y";
+        let new = "x + y + 1";
+        let (old_range, new_range) = changed_words(old, new);
+        assert!(old_range.is_empty());
+        assert_eq!(&new[new_range], " + 1");
+    }
+
+    #[test]
+    fn diff_is_recreated_with_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let source_path = dir.path().join("Sum.move");
+        let original = "a\nb\nc\nd\ne\nf\ng\n";
+        let mutated = "a\nb\nc\nX\ne\nf\ng\n";
+        fs::write(&source_path, original).unwrap();
+        let diff = diffy::create_patch(original, mutated).to_string();
+
+        let options = DiffOptions {
+            diff_context: 1,
+            word_diff: false,
+        };
+        let rendered = render_diff_with_colors(&diff, &source_path, &options, true).unwrap();
+        assert!(rendered.contains("@@ -3,3 +3,3 @@"));
+
+        // The stored diff is used if the source has changed.
+        fs::write(&source_path, "changed\n").unwrap();
+        let rendered = render_diff_with_colors(&diff, &source_path, &options, true).unwrap();
+        assert!(rendered.contains("@@ -1,7 +1,7 @@"));
+
+        let options = DiffOptions {
+            diff_context: 3,
+            word_diff: true,
+        };
+        let rendered = render_diff_with_colors(&diff, &source_path, &options, true).unwrap();
+        assert!(rendered.contains(&format!("{ANSI_HIGHLIGHT}X{ANSI_RESET}")));
+
+        let rendered = render_diff_with_colors(&diff, &source_path, &options, false).unwrap();
+        assert!(!rendered.contains('\x1b'));
+        assert!(rendered.contains("-[-d-]\n+{+X+}\n"));
+    }
+
+    #[test]
+    fn survivor_is_rendered_with_tests() {
+        let dir = tempfile::tempdir().unwrap();
+        let source_path = dir.path().join("Sum.move");
+        let original = "fun sum(a: u64, b: u64): u64 {\n    a + b\n}\n";
+        let mutated = "fun sum(a: u64, b: u64): u64 {\n    a - b\n}\n";
+        fs::write(&source_path, original).unwrap();
+
+        let mini_report = MiniReport::new(
+            "sources/Sum.move".into(),
+            "Sum::sum".to_owned(),
+            crate::report::MutantStatus::Alive,
+            diffy::create_patch(original, mutated).to_string(),
+        )
+        .with_mutant_info("5f1c2a9e", vec!["binary_operator_replacement".to_owned()])
+        .with_passed_tests(vec!["0x42::Sum::test_zero".to_owned()]);
+
+        let rendered = render_survivor_with_colors(&mini_report, &source_path, false);
+        assert!(rendered
+            .starts_with("Mutant 5f1c2a9e survived in Sum::sum (binary_operator_replacement):\n"));
+        assert!(rendered.contains("-    a [-+-] b"));
+        assert!(rendered.contains("+    a {+-+} b"));
+        assert!(rendered.ends_with("Tests passing on the mutant: 0x42::Sum::test_zero\n"));
+    }
+}
@@ -3,10 +3,21 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::report::{MutantStats, Report};
+use super::{
+    color::print_table,
+    diff_render::{render_diff, DiffOptions},
+    explain::{operator_rationale, suggested_actions},
+    kill_matrix::KillMatrix,
+    locale::Locale,
+    report::{
+        MutantRecord, MutantStats, MutantStatus, Report, ReportFormat, StillbornRecord,
+        REPORT_SCHEMA_VERSION,
+    },
+    triage::write_triage_page,
+};
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
-use diffy::{Line, Patch, PatchFormatter};
+use clap::{Args, Parser, Subcommand};
+use diffy::Line;
 use prettytable::{
     color,
     format::{self, Alignment, LinePosition, LineSeparator},
@@ -49,6 +60,55 @@ pub enum DisplayReportCmd {
         #[clap(long, default_value = "alive")]
         mutants: MutantFilter,
     },
+
+    /// Display the mutants matching the filters.
+    Show {
+        #[clap(flatten)]
+        query: MutantQuery,
+
+        /// Print the diffs of the matching mutants.
+        #[clap(long)]
+        diffs: bool,
+    },
+
+    /// Explain the mutant with the given ID.
+    Explain {
+        /// The mutant ID (a hexadecimal number from the mutant file name).
+        mutant_id: String,
+    },
+
+    /// Convert the report to a different format.
+    Convert {
+        /// Path where the converted report is saved.
+        #[clap(long)]
+        output: PathBuf,
+
+        /// The format of the converted report.
+        #[clap(long, value_enum)]
+        format: ReportFormat,
+    },
+
+    /// Upgrade the report (e.g. a stored baseline) to the current schema version.
+    Migrate {
+        /// Path where the upgraded report is saved. The report is upgraded in place by default.
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Export the (test × mutant) kill matrix in a sparse JSON format.
+    KillMatrix {
+        /// Path where the kill matrix is saved.
+        #[clap(long)]
+        output: PathBuf,
+    },
+
+    /// Write the interactive mutant triage page (an HTML app with filtering, sorting, diffs and
+    /// the export of a suppressions file) to a directory.
+    Triage {
+        /// The directory where the page is written.
+        #[clap(long)]
+        output: PathBuf,
+    },
 }
 
 /// Display the report in a more readable format.
@@ -58,6 +118,14 @@ pub struct DisplayReportOptions {
     #[clap(global = true, long, default_value = "report.txt")]
     pub path_to_report: PathBuf,
 
+    /// The locale used to format numbers in the summary (e.g. `en`, `de-DE`).
+    #[clap(global = true, long, default_value_t)]
+    pub locale: Locale,
+
+    /// Options of rendering the mutant diffs.
+    #[clap(flatten)]
+    pub diff: DiffOptions,
+
     /// Display report subcommands.
     #[clap(subcommand)]
     pub cmds: DisplayReportCmd,
@@ -69,7 +137,7 @@ impl DisplayReportOptions {
         let path_to_report = &self.path_to_report;
 
         match &self.cmds {
-            DisplayReportCmd::Summary => display_summary(path_to_report),
+            DisplayReportCmd::Summary => display_summary(path_to_report, &self.locale),
             DisplayReportCmd::Coverage { modules } => {
                 display_coverage_on_screen(path_to_report, modules)
             },
@@ -77,7 +145,21 @@ impl DisplayReportOptions {
                 modules,
                 functions,
                 mutants,
-            } => display_mutants_on_screen(path_to_report, modules, functions, mutants),
+            } => display_mutants_on_screen(path_to_report, modules, functions, mutants, &self.diff),
+            DisplayReportCmd::Show { query, diffs } => {
+                display_matching_mutants(path_to_report, query, *diffs, &self.diff)
+            },
+            DisplayReportCmd::Explain { mutant_id } => {
+                display_explanation(path_to_report, mutant_id, &self.diff)
+            },
+            DisplayReportCmd::Convert { output, format } => {
+                convert_report(path_to_report, output, *format)
+            },
+            DisplayReportCmd::Migrate { output } => {
+                migrate_report_file(path_to_report, output.as_deref())
+            },
+            DisplayReportCmd::KillMatrix { output } => export_kill_matrix(path_to_report, output),
+            DisplayReportCmd::Triage { output } => export_triage_page(path_to_report, output),
         }
     }
 }
@@ -179,6 +261,55 @@ impl ModuleFilter {
     }
 }
 
+/// Filters selecting the mutants from the report. Empty filters match all mutants.
+#[derive(Args, Debug, Clone, Default, PartialEq)]
+pub struct MutantQuery {
+    /// Include only the mutants with the given status.
+    #[clap(long, value_enum)]
+    pub status: Option<MutantStatus>,
+
+    /// Include only the mutants in the given modules (comma-separated).
+    #[clap(long, value_delimiter = ',')]
+    pub module: Vec<String>,
+
+    /// Include only the mutants in the given functions (comma-separated).
+    #[clap(long, value_delimiter = ',')]
+    pub function: Vec<String>,
+
+    /// Include only the mutants created by the given operators (comma-separated). Parts of the
+    /// operator names are accepted, e.g. `binary-op` matches `binary_operator_replacement`.
+    #[clap(long, value_delimiter = ',')]
+    pub operator: Vec<String>,
+
+    /// Include only the mutants with one of the given labels (comma-separated).
+    #[clap(long, value_delimiter = ',')]
+    pub label: Vec<String>,
+}
+
+impl MutantQuery {
+    /// Checks whether the mutant matches all the filters.
+    pub fn matches(&self, mutant: &MutantRecord) -> bool {
+        let (module, function) = mutant
+            .qname
+            .split_once("::")
+            .unwrap_or(("", mutant.qname.as_str()));
+        let normalize = |name: &str| name.to_lowercase().replace('-', "_");
+
+        self.status.map_or(true, |status| mutant.status == status)
+            && (self.module.is_empty() || self.module.iter().any(|m| m == module))
+            && (self.function.is_empty() || self.function.iter().any(|f| f == function))
+            && (self.operator.is_empty()
+                || self.operator.iter().any(|op| {
+                    let op = normalize(op);
+                    mutant
+                        .operators
+                        .iter()
+                        .any(|name| normalize(name).contains(&op))
+                }))
+            && (self.label.is_empty() || self.label.iter().any(|l| mutant.labels.contains(l)))
+    }
+}
+
 /// Line stats for mutations.
 #[derive(Default, Debug)]
 struct MutatedLine {
@@ -226,7 +357,7 @@ pub fn display_coverage_on_screen(
     path_to_report: impl AsRef<Path>,
     modules: &ModuleFilter,
 ) -> Result<()> {
-    let report = Report::load_from_json_file(path_to_report.as_ref())?;
+    let report = Report::load_from_file(path_to_report.as_ref())?;
     let files_to_print = modules.get_all_files_containing_the_modules(&report);
 
     if files_to_print.is_empty() {
@@ -290,7 +421,7 @@ fn display_nice_legend_info() {
     add_row("0/4", "No mutants killed on this line", COLOR_CRITICAL);
     add_row("", "One final line without mutants", COLOR_NONE);
 
-    table.printstd();
+    print_table(&table);
 }
 
 fn display_nice_file_report(file: &Path, source_code: String, stats: FileStats) -> Result<()> {
@@ -325,7 +456,7 @@ fn display_nice_file_report(file: &Path, source_code: String, stats: FileStats)
         table.add_row(Row::new(vec![stat_cell, line_cell]));
     }
 
-    table.printstd();
+    print_table(&table);
     Ok(())
 }
 
@@ -350,7 +481,8 @@ fn calculate_file_stats(file: &Path, report: &Report) -> Result<FileStats> {
     Ok(file_stats)
 }
 
-fn find_mutated_line_number(file_diff: &str) -> Result<usize> {
+/// Returns the number of the first line changed by the mutant diff in the original file.
+pub(crate) fn find_mutated_line_number(file_diff: &str) -> Result<usize> {
     let patch = diffy::Patch::from_str(file_diff)?;
     let hunk = patch
         .hunks()
@@ -374,17 +506,22 @@ pub fn display_mutants_on_screen(
     modules: &ModuleFilter,
     functions: &FunctionFilter,
     mutant_filter: &MutantFilter,
+    diff_options: &DiffOptions,
 ) -> Result<()> {
-    let report = Report::load_from_json_file(path_to_report.as_ref())?;
+    let report = Report::load_from_file(path_to_report.as_ref())?;
     let files_to_print = modules.get_all_files_containing_the_modules(&report);
-    let Report { mut files, .. } = report;
+    let Report {
+        mut files,
+        package_dir,
+        ..
+    } = report;
 
     if files_to_print.is_empty() {
         println!("No matching files found.");
         return Ok(());
     };
 
-    let mut all_mutant_stats = Vec::<MutantStats>::new();
+    let mut all_mutant_stats = Vec::<(PathBuf, MutantStats)>::new();
     for file in files_to_print {
         if let Some(mut file_mutant_stats) = files.remove(&file) {
             if let FunctionFilter::Selected(filtered_funcs) = functions {
@@ -396,7 +533,11 @@ pub fn display_mutants_on_screen(
                     filtered_funcs.contains(&func.to_owned())
                 });
             }
-            all_mutant_stats.extend(file_mutant_stats);
+            all_mutant_stats.extend(
+                file_mutant_stats
+                    .into_iter()
+                    .map(|stats| (package_dir.join(&file), stats)),
+            );
         }
     }
 
@@ -405,14 +546,12 @@ pub fn display_mutants_on_screen(
         return Ok(());
     };
 
-    let f = PatchFormatter::new().with_color();
-    for mutant in all_mutant_stats {
+    for (source_path, mutant) in all_mutant_stats {
         if mutant_filter.contains_alive() {
             for diff in mutant.mutants_alive_diffs {
                 println!("----------------------------------------------------------------------------------------------------");
                 println!("{}: Alive mutant", mutant.module_func);
-                let patch = Patch::from_str(&diff).expect("invalid patch");
-                println!("{}", f.fmt_patch(&patch));
+                println!("{}", render_diff(&diff, &source_path, diff_options)?);
             }
         }
 
@@ -420,8 +559,7 @@ pub fn display_mutants_on_screen(
             for diff in mutant.mutants_killed_diff {
                 println!("----------------------------------------------------------------------------------------------------");
                 println!("{}: Killed mutant", mutant.module_func);
-                let patch = Patch::from_str(&diff).expect("invalid patch");
-                println!("{}", f.fmt_patch(&patch));
+                println!("{}", render_diff(&diff, &source_path, diff_options)?);
             }
         }
 
@@ -431,10 +569,219 @@ pub fn display_mutants_on_screen(
     Ok(())
 }
 
+/// Explains the mutant: the operator rationale, the diff, the tests that killed it, the outcome
+/// and suggested next actions.
+pub fn display_explanation(
+    path_to_report: impl AsRef<Path>,
+    mutant_id: &str,
+    diff_options: &DiffOptions,
+) -> Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    let Some(mutant) = report.find_mutant(mutant_id) else {
+        if let Some(stillborn) = report.find_stillborn(mutant_id) {
+            return display_stillborn_explanation(&report, stillborn, diff_options);
+        }
+        anyhow::bail!("mutant {mutant_id} not found in the report");
+    };
+
+    println!("Mutant {}", mutant.id);
+    println!(
+        "Location: {} ({})",
+        mutant.original_file.display(),
+        mutant.qname
+    );
+    match mutant.kill_reason {
+        Some(reason) => println!("Outcome: {} ({reason})", mutant.status),
+        None => println!("Outcome: {}", mutant.status),
+    }
+    if let Some(seed) = mutant.seed {
+        println!("Test seed: {seed}");
+    }
+    if !mutant.owners.is_empty() {
+        println!("Owners: {}", mutant.owners.join(", "));
+    }
+    if mutant.new_code {
+        println!("The mutant touches recently changed lines (new code).");
+    }
+    println!();
+
+    for operator in &mutant.operators {
+        println!("Operator: {operator}");
+        println!("{}", operator_rationale(operator));
+        println!();
+    }
+
+    let source_path = report.get_package_dir().join(&mutant.original_file);
+    let diff = render_diff(&mutant.diff, &source_path, diff_options)
+        .context("invalid diff in the report")?;
+    println!("{diff}");
+
+    if !mutant.killed_by.is_empty() {
+        println!("Killed by:");
+        for test in &mutant.killed_by {
+            println!("  - {test}");
+        }
+        println!();
+    }
+
+    if !mutant.covered_by.is_empty() {
+        println!("Covered by:");
+        for test in &mutant.covered_by {
+            println!("  - {test}");
+        }
+    } else if report.coverage_map.is_some() {
+        println!("Covered by: no test executes the mutated code.");
+    } else {
+        println!("Covered by: unknown, run the mutation tests with `--coverage` to record it.");
+    }
+    println!();
+
+    println!("Suggested next actions:");
+    for action in suggested_actions(mutant) {
+        println!("  - {action}");
+    }
+
+    Ok(())
+}
+
+/// Explains the mutant which didn't compile: the operators, the diff and the compiler
+/// diagnostics.
+fn display_stillborn_explanation(
+    report: &Report,
+    mutant: &StillbornRecord,
+    diff_options: &DiffOptions,
+) -> Result<()> {
+    println!("Mutant {}", mutant.id);
+    println!(
+        "Location: {} ({})",
+        mutant.original_file.display(),
+        mutant.qname
+    );
+    println!("Outcome: stillborn (the mutant doesn't compile and has not been tested)");
+    println!("Operators: {}", mutant.operators.join(", "));
+    println!();
+
+    let source_path = report.get_package_dir().join(&mutant.original_file);
+    let diff = render_diff(&mutant.diff, &source_path, diff_options)
+        .context("invalid diff in the report")?;
+    println!("{diff}");
+
+    println!("Compiler diagnostics:");
+    println!("{}", mutant.diagnostics);
+    println!();
+    println!("Suggested next actions:");
+    println!(
+        "  - The mutation operator produced invalid code - report it together with the diff and \
+         the diagnostics above."
+    );
+
+    Ok(())
+}
+
+/// Displays the mutants matching the query, optionally with their diffs.
+pub fn display_matching_mutants(
+    path_to_report: impl AsRef<Path>,
+    query: &MutantQuery,
+    diffs: bool,
+    diff_options: &DiffOptions,
+) -> Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    let matching = report
+        .mutants
+        .iter()
+        .filter(|mutant| query.matches(mutant))
+        .collect::<Vec<_>>();
+
+    for mutant in &matching {
+        println!(
+            "{} {:<8} {} ({}) [{}]",
+            mutant.id,
+            mutant.status.to_string(),
+            mutant.original_file.display(),
+            mutant.qname,
+            mutant.operators.join(", ")
+        );
+        if diffs {
+            let source_path = report.get_package_dir().join(&mutant.original_file);
+            println!("{}", render_diff(&mutant.diff, &source_path, diff_options)?);
+        }
+    }
+    println!(
+        "{} of {} mutants match",
+        matching.len(),
+        report.mutants.len()
+    );
+
+    Ok(())
+}
+
+/// Convert the report to the given format.
+pub fn convert_report(
+    path_to_report: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    format: ReportFormat,
+) -> Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    report.save_to_file(output.as_ref(), format)?;
+    println!("Report saved to: {}", output.as_ref().display());
+    Ok(())
+}
+
+/// Upgrade the report to the current schema version, keeping its format.
+pub fn migrate_report_file(path_to_report: &Path, output: Option<&Path>) -> Result<()> {
+    let (report, format, version) = Report::load_and_migrate(path_to_report)?;
+    let output = output.unwrap_or(path_to_report);
+    report.save_to_file(output, format)?;
+    if version == REPORT_SCHEMA_VERSION {
+        println!(
+            "Report is already at schema version {version}, saved to: {}",
+            output.display()
+        );
+    } else {
+        println!(
+            "Report upgraded from schema version {version} to {REPORT_SCHEMA_VERSION}, saved to: {}",
+            output.display()
+        );
+    }
+    Ok(())
+}
+
+/// Export the kill matrix of the report.
+pub fn export_kill_matrix(
+    path_to_report: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    let matrix = KillMatrix::from_report(&report)?;
+    matrix.save_to_json_file(output.as_ref())?;
+    println!(
+        "Kill matrix ({} tests, {} mutants, {} kills) saved to: {}",
+        matrix.tests.len(),
+        matrix.mutants.len(),
+        matrix.kills.len(),
+        output.as_ref().display()
+    );
+    Ok(())
+}
+
+/// Write the mutant triage page for the report.
+pub fn export_triage_page(
+    path_to_report: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    write_triage_page(&report, output.as_ref())?;
+    println!(
+        "Triage page saved to: {}",
+        output.as_ref().join("index.html").display()
+    );
+    Ok(())
+}
+
 /// Summarize the report.
-pub fn display_summary(path_to_report: impl AsRef<Path>) -> Result<()> {
-    let report = Report::load_from_json_file(path_to_report.as_ref())?;
-    report.print_table();
+pub fn display_summary(path_to_report: impl AsRef<Path>, locale: &Locale) -> Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    report.print_table(locale);
     Ok(())
 }
 
@@ -453,6 +800,31 @@ mod tests {
         let module_name = "module";
         report.increment_mutants_tested(&path1, module_name);
         report.increment_mutants_tested(&path2, module_name);
+        report.add_mutant_record(MutantRecord {
+            id: "abc".to_owned(),
+            original_file: path1.clone(),
+            qname: "module::function".to_owned(),
+            operators: vec!["literal_replacement".to_owned()],
+            status: MutantStatus::Alive,
+            killed_by: vec![],
+            covered_by: vec![],
+            kill_reason: None,
+            new_code: false,
+            owners: vec![],
+            labels: vec![],
+            duration_ms: 0,
+            runs: None,
+            seed: None,
+            diff: diffy::create_patch("let a = 1;\n", "let a = 2;\n").to_string(),
+        });
+        report.add_stillborn_record(StillbornRecord {
+            id: "def".to_owned(),
+            original_file: path1.clone(),
+            qname: "module::function".to_owned(),
+            operators: vec!["delete_statement".to_owned()],
+            diagnostics: "error[E04007]: incompatible types".to_owned(),
+            diff: diffy::create_patch("let a = 1;\n", "").to_string(),
+        });
 
         let report_path = package_dir.join("report.txt");
         report
@@ -469,13 +841,84 @@ mod tests {
 
         let functions = FunctionFilter::All;
         let mutant_filter = MutantFilter::All;
-        let ret = display_mutants_on_screen(&report_path, &modules, &functions, &mutant_filter);
+        let ret = display_mutants_on_screen(
+            &report_path,
+            &modules,
+            &functions,
+            &mutant_filter,
+            &DiffOptions::default(),
+        );
+        assert!(ret.is_ok());
+
+        let ret = display_explanation(&report_path, "abc", &DiffOptions::default());
+        assert!(ret.is_ok());
+        let ret = display_explanation(&report_path, "def", &DiffOptions::default());
+        assert!(ret.is_ok());
+        let ret = display_explanation(&report_path, "xyz", &DiffOptions::default());
+        assert!(ret.is_err());
+
+        let cbor_path = package_dir.join("report.cbor");
+        let ret = convert_report(&report_path, &cbor_path, ReportFormat::Cbor);
+        assert!(ret.is_ok());
+        let ret = display_summary(&cbor_path, &Locale::default());
         assert!(ret.is_ok());
 
-        let ret = display_summary(report_path);
+        let ret = display_summary(report_path, &Locale::default());
         assert!(ret.is_ok());
     }
 
+    #[test]
+    fn mutant_query_matches_filters() {
+        let mutant = MutantRecord {
+            id: "abc".to_owned(),
+            original_file: PathBuf::from("sources/coin.move"),
+            qname: "coin::transfer".to_owned(),
+            operators: vec!["binary_operator_replacement".to_owned()],
+            status: MutantStatus::Alive,
+            killed_by: vec![],
+            covered_by: vec![],
+            kill_reason: None,
+            new_code: false,
+            owners: vec![],
+            labels: vec!["critical-path".to_owned()],
+            duration_ms: 0,
+            runs: None,
+            seed: None,
+            diff: String::new(),
+        };
+
+        assert!(MutantQuery::default().matches(&mutant));
+        let query = MutantQuery {
+            status: Some(MutantStatus::Alive),
+            module: vec!["coin".to_owned()],
+            function: vec![],
+            operator: vec!["binary-op".to_owned()],
+            label: vec!["critical-path".to_owned()],
+        };
+        assert!(query.matches(&mutant));
+        let other_label = MutantQuery {
+            label: vec!["arithmetic".to_owned()],
+            ..Default::default()
+        };
+        assert!(!other_label.matches(&mutant));
+
+        let killed = MutantQuery {
+            status: Some(MutantStatus::Killed),
+            ..Default::default()
+        };
+        assert!(!killed.matches(&mutant));
+        let other_module = MutantQuery {
+            module: vec!["account".to_owned()],
+            ..Default::default()
+        };
+        assert!(!other_module.matches(&mutant));
+        let other_operator = MutantQuery {
+            operator: vec!["literal".to_owned()],
+            ..Default::default()
+        };
+        assert!(!other_operator.matches(&mutant));
+    }
+
     #[test]
     fn report_file_not_found() {
         let path = PathBuf::from("/path/to/non/existing/file");
@@ -485,10 +928,19 @@ mod tests {
 
         let functions = FunctionFilter::All;
         let mutant_filter = MutantFilter::Alive;
-        let ret = display_mutants_on_screen(&path, &modules, &functions, &mutant_filter);
+        let ret = display_mutants_on_screen(
+            &path,
+            &modules,
+            &functions,
+            &mutant_filter,
+            &DiffOptions::default(),
+        );
+        assert!(ret.is_err());
+
+        let ret = display_summary(&path, &Locale::default());
         assert!(ret.is_err());
 
-        let ret = display_summary(path);
+        let ret = display_explanation(path, "abc", &DiffOptions::default());
         assert!(ret.is_err());
     }
 }
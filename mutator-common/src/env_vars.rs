@@ -0,0 +1,129 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::{ArgAction, Command};
+
+/// Allows setting all long options of the command and of all its subcommands with environment
+/// variables.
+///
+/// The variable name is the prefix followed by the option name in uppercase with dashes replaced
+/// by underscores, e.g. `--mutate-modules` can be set with `<PREFIX>MUTATE_MODULES`. The options of
+/// the subcommands have the names of the subcommands in the variable name too, e.g. `--output` of
+/// `display-report convert` can be set with `<PREFIX>DISPLAY_REPORT_CONVERT_OUTPUT`, except the
+/// main subcommand (e.g. `run`), whose options are named like the options of the command.
+///
+/// Options given on the command line take precedence over the environment variables, which take
+/// precedence over the default values.
+pub fn with_env_vars(cmd: Command, prefix: &str, main_subcommand: Option<&str>) -> Command {
+    let cmd = cmd.mut_args(|arg| {
+        let is_value_or_flag = !matches!(arg.get_action(), ArgAction::Help | ArgAction::Version);
+        match arg.get_long() {
+            Some(long) if is_value_or_flag => {
+                let name = format!("{prefix}{}", env_var_name(long));
+                arg.env(name)
+            },
+            _ => arg,
+        }
+    });
+
+    let subcommands = cmd
+        .get_subcommands()
+        .map(|subcmd| subcmd.get_name().to_owned())
+        .collect::<Vec<_>>();
+    subcommands.into_iter().fold(cmd, |cmd, name| {
+        let prefix = if main_subcommand == Some(name.as_str()) {
+            prefix.to_owned()
+        } else {
+            format!("{prefix}{}_", env_var_name(&name))
+        };
+        cmd.mut_subcommand(&name, |subcmd| with_env_vars(subcmd, &prefix, None))
+    })
+}
+
+/// Converts the name of an option or a subcommand to the form used in the environment variables.
+fn env_var_name(name: &str) -> String {
+    name.to_uppercase().replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+
+    fn command() -> Command {
+        let run = Command::new("run")
+            .arg(Arg::new("output").long("output"))
+            .arg(
+                Arg::new("verify")
+                    .long("verify-mutants")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("keep")
+                    .long("keep-stillborn")
+                    .value_parser(clap::value_parser!(bool))
+                    .num_args(0..=1)
+                    .require_equals(true)
+                    .default_missing_value("true"),
+            );
+        let convert = Command::new("convert").arg(Arg::new("output").long("output"));
+        let report = Command::new("display-report").subcommand(convert);
+        with_env_vars(
+            Command::new("tool")
+                .arg(Arg::new("color").long("color"))
+                .subcommand(run)
+                .subcommand(report),
+            "ENV_VARS_TEST_",
+            Some("run"),
+        )
+    }
+
+    #[test]
+    fn options_are_read_from_env_vars_with_cli_precedence() {
+        std::env::set_var("ENV_VARS_TEST_OUTPUT", "env.txt");
+        std::env::set_var("ENV_VARS_TEST_VERIFY_MUTANTS", "true");
+
+        let matches = command().try_get_matches_from(["tool", "run"]).unwrap();
+        let run = matches.subcommand_matches("run").unwrap();
+        assert_eq!(run.get_one::<String>("output").unwrap(), "env.txt");
+        assert!(run.get_flag("verify"));
+
+        let matches = command()
+            .try_get_matches_from(["tool", "run", "--output", "cli.txt"])
+            .unwrap();
+        let run = matches.subcommand_matches("run").unwrap();
+        assert_eq!(run.get_one::<String>("output").unwrap(), "cli.txt");
+    }
+
+    #[test]
+    fn boolean_options_from_env_vars_can_be_turned_off_on_the_cli() {
+        std::env::set_var("ENV_VARS_TEST_KEEP_STILLBORN", "true");
+
+        let matches = command().try_get_matches_from(["tool", "run"]).unwrap();
+        let run = matches.subcommand_matches("run").unwrap();
+        assert_eq!(run.get_one::<bool>("keep"), Some(&true));
+
+        let matches = command()
+            .try_get_matches_from(["tool", "run", "--keep-stillborn=false"])
+            .unwrap();
+        let run = matches.subcommand_matches("run").unwrap();
+        assert_eq!(run.get_one::<bool>("keep"), Some(&false));
+    }
+
+    #[test]
+    fn options_of_all_subcommands_are_read_from_env_vars() {
+        std::env::set_var("ENV_VARS_TEST_COLOR", "never");
+        std::env::set_var("ENV_VARS_TEST_DISPLAY_REPORT_CONVERT_OUTPUT", "report.json");
+
+        let matches = command()
+            .try_get_matches_from(["tool", "display-report", "convert"])
+            .unwrap();
+        assert_eq!(matches.get_one::<String>("color").unwrap(), "never");
+        let convert = matches
+            .subcommand_matches("display-report")
+            .and_then(|report| report.subcommand_matches("convert"))
+            .unwrap();
+        assert_eq!(convert.get_one::<String>("output").unwrap(), "report.json");
+    }
+}
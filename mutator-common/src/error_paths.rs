@@ -0,0 +1,194 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::{MutantStatus, Report};
+use std::{collections::BTreeMap, path::PathBuf};
+use tabled::{builder::Builder, settings::Style};
+
+/// An error path of the code - an `abort` or `assert!` site - disabled by some mutants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorPath {
+    /// The source file of the site.
+    pub file: PathBuf,
+    /// The function of the site (`module::function`).
+    pub qname: String,
+    /// The line of the site (starting from 1).
+    pub line: usize,
+    /// The abort code as written in the source (e.g. `E_NOT_OWNER`), if found.
+    pub code: Option<String>,
+    /// Whether any test triggers the error, i.e. some mutant disabling the site is killed.
+    pub exercised: bool,
+}
+
+/// Finds the error paths disabled by the tested mutants.
+///
+/// A mutant disables an error path if it removes an `abort` or `assert!` site (e.g. the
+/// `delete_statement`, `assert_abort_deletion` and `exists_check_removal` operators) or replaces
+/// the condition guarding an `abort` with `false`. The path is exercised by the tests if any of
+/// the mutants disabling it is killed: a test relies on the error being raised.
+pub fn error_paths(report: &Report) -> Vec<ErrorPath> {
+    let mut paths = BTreeMap::<(PathBuf, usize), ErrorPath>::new();
+    for mutant in &report.mutants {
+        let killed = match mutant.status {
+            MutantStatus::Killed => true,
+            MutantStatus::Alive => false,
+            _ => continue,
+        };
+        let Some((line, code)) = disabled_site(&mutant.diff) else {
+            continue;
+        };
+        paths
+            .entry((mutant.original_file.clone(), line))
+            .or_insert_with(|| ErrorPath {
+                file: mutant.original_file.clone(),
+                qname: mutant.qname.clone(),
+                line,
+                code,
+                exercised: false,
+            })
+            .exercised |= killed;
+    }
+    paths.into_values().collect()
+}
+
+/// Prints the error paths which no test triggers.
+pub(crate) fn print_unexercised_error_paths(paths: &[ErrorPath]) {
+    let mut builder = Builder::new();
+    builder.push_record(["Error path never exercised", "Location", "Abort code"]);
+    for path in paths.iter().filter(|path| !path.exercised) {
+        builder.push_record([
+            format!("{}:{}", path.file.display(), path.line),
+            path.qname.clone(),
+            path.code.clone().unwrap_or_else(|| "-".to_owned()),
+        ]);
+    }
+
+    let table = builder.build().with(Style::modern_rounded()).to_string();
+    println!("{table}");
+    println!(); // Empty line before the end
+}
+
+/// Returns the line and the abort code of the error site disabled by the mutant diff, if any.
+fn disabled_site(diff: &str) -> Option<(usize, Option<String>)> {
+    let mut removed = vec![];
+    let mut added = vec![];
+    let mut line = 0;
+    for diff_line in diff.lines() {
+        if let Some(header) = diff_line.strip_prefix("@@ -") {
+            // `@@ -12,7 +12,7 @@` - the first line of the hunk in the original file.
+            line = header
+                .split(|c: char| !c.is_ascii_digit())
+                .next()
+                .and_then(|start| start.parse().ok())
+                .unwrap_or(0);
+        } else if diff_line.starts_with("---") || diff_line.starts_with("+++") {
+            continue;
+        } else if let Some(text) = diff_line.strip_prefix('-') {
+            removed.push((line, text));
+            line += 1;
+        } else if let Some(text) = diff_line.strip_prefix('+') {
+            added.push(text);
+        } else {
+            line += 1;
+        }
+    }
+
+    let (line, text) = removed.into_iter().find(|(_, text)| is_error_site(text))?;
+    let site_removed = !added.iter().any(|text| is_error_site(text));
+    let guard_disabled = added
+        .iter()
+        .any(|text| text.replace(' ', "").contains("if(false)"));
+    (site_removed || guard_disabled).then(|| (line, abort_code(text)))
+}
+
+/// Checks whether the source line raises an error.
+fn is_error_site(text: &str) -> bool {
+    text.contains("assert!")
+        || text
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| word == "abort")
+}
+
+/// Extracts the abort code from the `assert!(cond, code)` or `abort code` source line.
+fn abort_code(text: &str) -> Option<String> {
+    let code = if let Some(idx) = text.find("assert!(") {
+        let args = &text[idx + "assert!(".len()..];
+        // The code is the last argument at the top nesting level.
+        let mut depth = 0;
+        let mut start = None;
+        let mut end = args.len();
+        for (idx, c) in args.char_indices() {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' if depth == 0 => {
+                    end = idx;
+                    break;
+                },
+                ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => start = Some(idx + 1),
+                _ => {},
+            }
+        }
+        &args[start?..end]
+    } else {
+        let idx = text.find("abort")?;
+        let rest = &text[idx + "abort".len()..];
+        &rest[..rest.find([';', '}']).unwrap_or(rest.len())]
+    };
+    let code = code.trim();
+    (!code.is_empty()).then(|| code.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MiniReport, MutantRecord};
+
+    const REMOVED_ASSERT: &str = "--- original\n+++ modified\n@@ -9,3 +9,3 @@\n     fun withdraw(amount: u64) {\n-        assert!(amount <= LIMIT, E_LIMIT);\n+        {};\n     }\n";
+    const DISABLED_ABORT: &str = "--- original\n+++ modified\n@@ -20,2 +20,2 @@\n-        if (balance < amount) abort 7;\n+        if (false) abort 7;\n     }\n";
+    const NEGATED_ABORT: &str = "--- original\n+++ modified\n@@ -20,2 +20,2 @@\n-        if (balance < amount) abort 7;\n+        if (!(balance < amount)) abort 7;\n     }\n";
+
+    #[test]
+    fn disabled_error_sites_are_found() {
+        assert_eq!(
+            disabled_site(REMOVED_ASSERT),
+            Some((10, Some("E_LIMIT".to_owned())))
+        );
+        assert_eq!(
+            disabled_site(DISABLED_ABORT),
+            Some((20, Some("7".to_owned())))
+        );
+        assert_eq!(disabled_site(NEGATED_ABORT), None);
+
+        assert_eq!(
+            abort_code("assert!(vector::length(&v) > 0, error::invalid_argument(E_EMPTY));"),
+            Some("error::invalid_argument(E_EMPTY)".to_owned())
+        );
+        assert_eq!(abort_code("assert!(ok)"), None);
+        assert!(!is_error_site("let aborted = true;"));
+    }
+
+    #[test]
+    fn error_paths_are_exercised_by_killed_mutants() {
+        let mut report = Report::new("package".into());
+        let mut add = |diff: &str, status| {
+            let mini_report = MiniReport::new(
+                "sources/vault.move".into(),
+                "vault::withdraw".to_owned(),
+                status,
+                diff.to_owned(),
+            );
+            report.add_mutant_record(MutantRecord::from(&mini_report));
+        };
+        add(REMOVED_ASSERT, MutantStatus::Alive);
+        add(DISABLED_ABORT, MutantStatus::Alive);
+        add(DISABLED_ABORT, MutantStatus::Killed);
+
+        let paths = error_paths(&report);
+        assert_eq!(paths.len(), 2);
+        assert!(!paths[0].exercised);
+        assert_eq!(paths[0].line, 10);
+        assert!(paths[1].exercised);
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::report::{MutantRecord, MutantStatus};
+
+/// Returns a short rationale for the mutation operator - what kind of weakness in the test suite
+/// or specification a surviving mutant created by the operator reveals.
+pub fn operator_rationale(operator: &str) -> &'static str {
+    match operator {
+        "binary_operator_replacement" => {
+            "Replaces a binary operator with another one from the same category. A survivor means \
+             no test distinguishes the results of both operators for the used operand values."
+        },
+        "binary_operator_swap" => {
+            "Swaps the operands of a non-commutative binary operator. A survivor means the order \
+             of the operands is not checked."
+        },
+        "bitwise_operator_replacement" => {
+            "Replaces a bitwise operator with another bitwise or arithmetic operator. A survivor \
+             means the tests only use operands for which both operators give the same result, \
+             e.g. flags without common bits."
+        },
+        "boundary_condition_replacement" => {
+            "Replaces a strict comparison with the non-strict one or vice versa. A survivor means \
+             the boundary value itself is never tested, which is where off-by-one errors hide."
+        },
+        "shift_operator_replacement" => {
+            "Replaces a shift operator with the opposite one or changes the shift amount by one. \
+             A survivor means the shifted values or amounts in the tests don't tell the shifts \
+             apart, e.g. zero is shifted."
+        },
+        "unary_operator_replacement" => {
+            "Removes a negation. A survivor means the negated condition is not checked for both \
+             of its values."
+        },
+        "unary_negation_removal" => {
+            "Removes the negation of a sub-condition of `&&` or `||`. A survivor means the \
+             sub-condition is never tested with both values while the other sub-conditions are \
+             fixed."
+        },
+        "literal_replacement" => {
+            "Replaces a literal with a different value. A survivor means the exact value is not \
+             checked by any test or specification."
+        },
+        "constant_replacement" => {
+            "Replaces an integer literal with `0`, `1` or the maximum value of its type. A \
+             survivor means the tests miss the overflow, underflow or edge case behind the value."
+        },
+        "off_by_one_replacement" => {
+            "Moves a literal in a comparison or a vector index by one. A survivor means no test \
+             hits the exact limit or index, which is where fence-post errors hide."
+        },
+        "if_else_replacement" => {
+            "Replaces a condition with a constant. A survivor means one of the branches is not \
+             exercised or its effects are not checked."
+        },
+        "conditional_negation" => {
+            "Negates the condition of an `if` or a `while` loop. A survivor means the tests take \
+             only one of the branches or can't tell the branches apart."
+        },
+        "assert_condition_replacement" => {
+            "Replaces the condition of an `assert!` with a constant or its negation. A survivor \
+             means the check never fails in the tests, or the tests pass even when it always \
+             fails."
+        },
+        "assert_error_code_replacement" => {
+            "Changes the error code of an `assert!`. A survivor means the tests expect just any \
+             abort, not the exact error code."
+        },
+        "assert_abort_deletion" => {
+            "Deletes a whole `assert!` or an `abort`. A survivor means no test expects the \
+             error, so the checked invariant is never exercised."
+        },
+        "abort_code_replacement" => {
+            "Replaces the error code of an `abort` or an `assert!` with zero or another code of \
+             the module. A survivor means the tests don't tell the errors of the module apart."
+        },
+        "break_continue_replacement" => {
+            "Replaces or removes a loop control statement. A survivor means the loop is not \
+             exercised with enough iterations."
+        },
+        "delete_statement" => {
+            "Deletes a statement. A survivor means the side effects of the statement are not \
+             checked."
+        },
+        "exists_check_removal" => {
+            "Removes a resource existence check. A survivor means the path with a missing (or \
+             already existing) resource is not exercised."
+        },
+        "variant_test_negation" => {
+            "Negates an enum variant test. A survivor means no test distinguishes values of the \
+             tested variant from the other variants."
+        },
+        "enum_variant_swap" => {
+            "Replaces an enum variant with another variant with the same payload. A survivor \
+             means the variant of the created value is not checked."
+        },
+        "match_arm_replacement" => {
+            "Deletes or swaps bodies of match arms. A survivor means some of the arms are not \
+             exercised or their results are not distinguished."
+        },
+        "struct_pack_field_swap" => {
+            "Swaps values of two struct fields with the same type. A survivor means the fields \
+             of the created struct are not checked separately."
+        },
+        "destructuring_binding_swap" => {
+            "Swaps two bindings with the same type in a destructuring pattern. A survivor means \
+             the destructured values are not checked separately."
+        },
+        "loop_invariant_weakening" => {
+            "Weakens a loop invariant. A survivor means the function specification doesn't rely \
+             on the invariant, so it is either redundant or the postconditions are too weak."
+        },
+        _ => "No rationale available for this operator.",
+    }
+}
+
+/// Returns suggested next actions for the mutant.
+pub fn suggested_actions(record: &MutantRecord) -> Vec<String> {
+    match record.status {
+        MutantStatus::Killed => {
+            return vec!["No action needed - the mutant has been killed.".to_owned()];
+        },
+        MutantStatus::Excluded => {
+            return vec![
+                "No action needed - the mutant is excluded from the score by the kill policy."
+                    .to_owned(),
+            ];
+        },
+        MutantStatus::Skipped => {
+            return vec![
+                "The mutant hasn't been tested, as the first mutants of the function have all \
+                 survived. Add tests exercising the function."
+                    .to_owned(),
+            ];
+        },
+        MutantStatus::Suppressed | MutantStatus::Equivalent => {
+            return vec![
+                "No action needed - the mutant is listed in the suppressions file. Remove it from \
+                 the file to evaluate it again."
+                    .to_owned(),
+            ];
+        },
+        MutantStatus::Alive => {},
+    }
+
+    let function = record
+        .qname
+        .split_once("::")
+        .map_or(record.qname.as_str(), |(_, func)| func);
+
+    let mut actions = record
+        .operators
+        .iter()
+        .filter_map(|op| match op.as_str() {
+            "binary_operator_replacement" | "binary_operator_swap" => Some(format!(
+                "Add a test calling `{function}` with operand values for which the original and \
+                 the mutated expression differ (e.g. boundary values) and assert the result."
+            )),
+            "bitwise_operator_replacement" => Some(format!(
+                "Add a test calling `{function}` with operands sharing some bits (e.g. setting \
+                 a flag which is already set) and assert the result."
+            )),
+            "boundary_condition_replacement" => Some(format!(
+                "Add a test calling `{function}` with the operands equal (e.g. withdrawing exactly \
+                 the whole balance) and assert the outcome."
+            )),
+            "shift_operator_replacement" => Some(format!(
+                "Add a test calling `{function}` with non-zero values whose low and high bits \
+                 differ and assert the exact result."
+            )),
+            "unary_operator_replacement"
+            | "if_else_replacement"
+            | "conditional_negation"
+            | "variant_test_negation" => Some(format!(
+                "Add tests covering both outcomes of the condition in `{function}`."
+            )),
+            "unary_negation_removal" => Some(format!(
+                "Add tests of `{function}` changing only the negated sub-condition, keeping the \
+                 other sub-conditions of the `&&` or `||` fixed."
+            )),
+            "assert_condition_replacement" => Some(format!(
+                "Add an `#[expected_failure]` test calling `{function}` with values violating \
+                 the asserted condition, and a test with values just satisfying it."
+            )),
+            "assert_abort_deletion" => Some(format!(
+                "Add an `#[expected_failure(abort_code = ...)]` test calling `{function}` with \
+                 values violating the deleted check."
+            )),
+            "assert_error_code_replacement" | "abort_code_replacement" => Some(format!(
+                "Pin the error code in the failing tests of `{function}` with \
+                 `#[expected_failure(abort_code = ...)]`."
+            )),
+            "literal_replacement" => Some(format!(
+                "Add a test asserting the exact value returned (or stored) by `{function}`."
+            )),
+            "constant_replacement" => Some(format!(
+                "Add a test of `{function}` with extreme inputs (zero, one, values close to the \
+                 maximum) and assert the result or the expected abort."
+            )),
+            "off_by_one_replacement" => Some(format!(
+                "Add tests calling `{function}` exactly at the limit and one past it (or with the \
+                 first and the last index) and assert the outcome."
+            )),
+            "break_continue_replacement" => Some(format!(
+                "Add a test executing the loop in `{function}` more than once and assert the result."
+            )),
+            "delete_statement" => Some(format!(
+                "Add a test asserting the side effects of `{function}` (e.g. the resource exists \
+                 after the call)."
+            )),
+            "exists_check_removal" => Some(format!(
+                "Add an `#[expected_failure]` test calling `{function}` when the resource does \
+                 not exist (or already exists)."
+            )),
+            "enum_variant_swap" | "match_arm_replacement" => Some(format!(
+                "Add tests calling `{function}` with values of every enum variant and assert \
+                 the result for each of them."
+            )),
+            "struct_pack_field_swap" | "destructuring_binding_swap" => Some(format!(
+                "Add a test calling `{function}` with distinct values and assert each field \
+                 separately."
+            )),
+            "loop_invariant_weakening" => Some(format!(
+                "Add a postcondition to `{function}` describing the result of the loop, which \
+                 can't be proven without the invariant."
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if actions.is_empty() {
+        actions.push(format!(
+            "Add a test asserting the return value or side effects of `{function}`."
+        ));
+    }
+    actions.push(format!(
+        "Alternatively, add a specification for `{function}` (e.g. `ensures` clause) which \
+         rejects the mutated behaviour."
+    ));
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn record(status: MutantStatus, operator: &str) -> MutantRecord {
+        MutantRecord {
+            id: "abc".to_owned(),
+            original_file: PathBuf::from("sources/Sum.move"),
+            qname: "Sum::sum".to_owned(),
+            operators: vec![operator.to_owned()],
+            status,
+            killed_by: vec![],
+            covered_by: vec![],
+            kill_reason: None,
+            new_code: false,
+            owners: vec![],
+            labels: vec![],
+            duration_ms: 0,
+            runs: None,
+            seed: None,
+            diff: String::new(),
+        }
+    }
+
+    #[test]
+    fn suggested_actions_for_killed_mutant() {
+        let actions = suggested_actions(&record(MutantStatus::Killed, "literal_replacement"));
+        assert_eq!(actions.len(), 1);
+    }
+
+    #[test]
+    fn suggested_actions_for_alive_mutant_mention_function() {
+        let actions = suggested_actions(&record(MutantStatus::Alive, "literal_replacement"));
+        assert_eq!(actions.len(), 2);
+        assert!(actions.iter().all(|a| a.contains("`sum`")));
+    }
+
+    #[test]
+    fn unknown_operator_has_generic_suggestion() {
+        assert_eq!(
+            operator_rationale("unknown"),
+            "No rationale available for this operator."
+        );
+        let actions = suggested_actions(&record(MutantStatus::Alive, "unknown"));
+        assert_eq!(actions.len(), 2);
+    }
+}
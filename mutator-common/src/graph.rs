@@ -0,0 +1,360 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{kill_matrix::KillMatrix, report::Report};
+use clap::ValueEnum;
+use std::{collections::BTreeSet, fmt::Write, fs, path::Path};
+
+/// The format of the exported graph.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT.
+    #[default]
+    Dot,
+    /// GraphML, readable by e.g. Gephi, yEd or Cytoscape.
+    Graphml,
+}
+
+/// The kind of the graph node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum NodeKind {
+    Test,
+    Mutant,
+    Function,
+}
+
+impl NodeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            NodeKind::Test => "test",
+            NodeKind::Mutant => "mutant",
+            NodeKind::Function => "function",
+        }
+    }
+}
+
+/// A node of the graph.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Node {
+    kind: NodeKind,
+    label: String,
+    /// The status of the mutant, empty for the other nodes.
+    status: String,
+}
+
+impl Node {
+    /// The identifier unique across the node kinds.
+    fn id(&self) -> String {
+        format!("{}:{}", self.kind.as_str(), self.label)
+    }
+}
+
+/// The relation represented by the graph edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Relation {
+    /// The test failed on the mutant.
+    Kills,
+    /// The test exercises the function - it killed at least one of the function's mutants.
+    Covers,
+    /// The mutant changes the function.
+    Mutates,
+}
+
+impl Relation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Relation::Kills => "kills",
+            Relation::Covers => "covers",
+            Relation::Mutates => "mutates",
+        }
+    }
+}
+
+/// The graph linking the tests, the mutants and the mutated functions.
+///
+/// The tests kill the mutants, the mutants mutate the functions and the tests cover the functions
+/// whose mutants they kill. Tests without any edge kill nothing, and functions covered by no test
+/// have only surviving mutants, which makes the weak spots of the test suite visible in graph
+/// tools.
+#[derive(Debug, PartialEq)]
+pub struct KillGraph {
+    nodes: Vec<Node>,
+    edges: BTreeSet<(usize, Relation, usize)>,
+}
+
+impl KillGraph {
+    /// Builds the graph from the report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report doesn't contain per-test results.
+    pub fn from_report(report: &Report) -> anyhow::Result<Self> {
+        let matrix = KillMatrix::from_report(report)?;
+        let mut nodes = matrix
+            .tests
+            .iter()
+            .map(|test| Node {
+                kind: NodeKind::Test,
+                label: test.clone(),
+                status: String::new(),
+            })
+            .collect::<Vec<_>>();
+
+        let mutants_start = nodes.len();
+        nodes.extend(report.mutants.iter().map(|mutant| {
+            Node {
+                kind: NodeKind::Mutant,
+                label: mutant.id.clone(),
+                status: mutant
+                    .status
+                    .to_possible_value()
+                    .map(|value| value.get_name().to_owned())
+                    .unwrap_or_default(),
+            }
+        }));
+
+        let functions = report
+            .mutants
+            .iter()
+            .map(|mutant| mutant.qname.as_str())
+            .collect::<BTreeSet<_>>();
+        let functions_start = nodes.len();
+        nodes.extend(functions.iter().map(|function| Node {
+            kind: NodeKind::Function,
+            label: (*function).to_owned(),
+            status: String::new(),
+        }));
+        let function_node = |qname: &str| {
+            functions_start
+                + functions
+                    .iter()
+                    .position(|function| *function == qname)
+                    .expect("function node exists")
+        };
+
+        let mut edges = BTreeSet::new();
+        for (mutant_idx, mutant) in report.mutants.iter().enumerate() {
+            edges.insert((
+                mutants_start + mutant_idx,
+                Relation::Mutates,
+                function_node(&mutant.qname),
+            ));
+        }
+        for &(test, mutant_idx) in &matrix.kills {
+            edges.insert((test, Relation::Kills, mutants_start + mutant_idx));
+            edges.insert((
+                test,
+                Relation::Covers,
+                function_node(&report.mutants[mutant_idx].qname),
+            ));
+        }
+
+        Ok(Self { nodes, edges })
+    }
+
+    /// Returns the numbers of the tests, mutants and functions in the graph.
+    pub fn node_counts(&self) -> (usize, usize, usize) {
+        let count = |kind| self.nodes.iter().filter(|node| node.kind == kind).count();
+        (
+            count(NodeKind::Test),
+            count(NodeKind::Mutant),
+            count(NodeKind::Function),
+        )
+    }
+
+    /// Returns the number of the edges.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Renders the graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph kills {\n  rankdir=LR;\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Test => "box",
+                NodeKind::Mutant => "ellipse",
+                NodeKind::Function => "component",
+            };
+            let _ = write!(
+                dot,
+                "  \"{}\" [label=\"{}\", shape={shape}, kind=\"{}\"",
+                dot_escape(&node.id()),
+                dot_escape(&node.label),
+                node.kind.as_str()
+            );
+            if node.kind == NodeKind::Mutant {
+                let color = if node.status == "alive" {
+                    "red"
+                } else {
+                    "black"
+                };
+                let _ = write!(
+                    dot,
+                    ", status=\"{}\", color={color}",
+                    dot_escape(&node.status)
+                );
+            }
+            dot.push_str("];\n");
+        }
+        for &(from, relation, to) in &self.edges {
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                dot_escape(&self.nodes[from].id()),
+                dot_escape(&self.nodes[to].id()),
+                relation.as_str()
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph in the GraphML format.
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n  \
+             <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n  \
+             <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n  \
+             <key id=\"status\" for=\"node\" attr.name=\"status\" attr.type=\"string\"/>\n  \
+             <key id=\"relation\" for=\"edge\" attr.name=\"relation\" attr.type=\"string\"/>\n  \
+             <graph id=\"kills\" edgedefault=\"directed\">\n",
+        );
+        for node in &self.nodes {
+            let _ = writeln!(
+                xml,
+                "    <node id=\"{}\">\n      <data key=\"kind\">{}</data>\n      \
+                 <data key=\"label\">{}</data>",
+                xml_escape(&node.id()),
+                node.kind.as_str(),
+                xml_escape(&node.label)
+            );
+            if !node.status.is_empty() {
+                let _ = writeln!(
+                    xml,
+                    "      <data key=\"status\">{}</data>",
+                    xml_escape(&node.status)
+                );
+            }
+            xml.push_str("    </node>\n");
+        }
+        for &(from, relation, to) in &self.edges {
+            let _ = writeln!(
+                xml,
+                "    <edge source=\"{}\" target=\"{}\">\n      \
+                 <data key=\"relation\">{}</data>\n    </edge>",
+                xml_escape(&self.nodes[from].id()),
+                xml_escape(&self.nodes[to].id()),
+                relation.as_str()
+            );
+        }
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+}
+
+/// Escapes the string for a quoted DOT identifier.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escapes the string for XML text and attribute values.
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Export the graph of the kill relationships of the report.
+pub fn export_graph(
+    path_to_report: impl AsRef<Path>,
+    format: GraphFormat,
+    output: impl AsRef<Path>,
+) -> anyhow::Result<()> {
+    let report = Report::load_from_file(path_to_report.as_ref())?;
+    let graph = KillGraph::from_report(&report)?;
+    let content = match format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Graphml => graph.to_graphml(),
+    };
+    fs::write(output.as_ref(), content)?;
+
+    let (tests, mutants, functions) = graph.node_counts();
+    println!(
+        "Graph ({tests} tests, {mutants} mutants, {functions} functions, {} edges) saved to: {}",
+        graph.edge_count(),
+        output.as_ref().display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MiniReport, MutantRecord, MutantStatus};
+    use std::path::PathBuf;
+
+    fn add_mutant(report: &mut Report, id: &str, qname: &str, killed_by: &[&str]) {
+        let status = if killed_by.is_empty() {
+            MutantStatus::Alive
+        } else {
+            MutantStatus::Killed
+        };
+        let mini_report = MiniReport::new(
+            PathBuf::from("sources/Sum.move"),
+            qname.to_owned(),
+            status,
+            String::new(),
+        )
+        .with_mutant_info(id, vec![])
+        .with_killed_by(killed_by.iter().map(|t| t.to_string()).collect());
+        report.add_mutant_record(MutantRecord::from(&mini_report));
+    }
+
+    fn report() -> Report {
+        let mut report = Report::new("package_dir".into());
+        add_mutant(&mut report, "a1", "Sum::sum", &["sum::test_sum"]);
+        add_mutant(&mut report, "b2", "Sum::sum", &["sum::test_sum"]);
+        add_mutant(&mut report, "c3", "Sum::<weird>", &[]);
+        report.set_tests(["sum::test_sum".to_owned(), "sum::test_noop".to_owned()]);
+        report
+    }
+
+    #[test]
+    fn graph_links_tests_mutants_and_functions() {
+        let graph = KillGraph::from_report(&report()).unwrap();
+        assert_eq!(graph.node_counts(), (2, 3, 2));
+        // 3 mutates, 2 kills and 1 covers edge - the test covers the function once.
+        assert_eq!(graph.edge_count(), 6);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph kills {\n"));
+        assert!(dot.contains("\"test:sum::test_sum\" -> \"mutant:a1\" [label=\"kills\"];"));
+        assert!(dot.contains("\"test:sum::test_sum\" -> \"function:Sum::sum\" [label=\"covers\"];"));
+        assert!(dot.contains("\"mutant:c3\" -> \"function:Sum::<weird>\" [label=\"mutates\"];"));
+        assert!(dot.contains("status=\"alive\", color=red"));
+        // The test killing nothing has no edges.
+        assert!(!dot.contains("\"test:sum::test_noop\" ->"));
+    }
+
+    #[test]
+    fn graphml_is_escaped() {
+        let graphml = KillGraph::from_report(&report()).unwrap().to_graphml();
+        assert!(graphml.contains("<node id=\"function:Sum::&lt;weird&gt;\">"));
+        assert!(graphml.contains(
+            "<edge source=\"mutant:c3\" target=\"function:Sum::&lt;weird&gt;\">\n      \
+             <data key=\"relation\">mutates</data>"
+        ));
+        assert!(graphml.ends_with("</graphml>\n"));
+    }
+
+    #[test]
+    fn graph_requires_test_results() {
+        let report = Report::new("package_dir".into());
+        assert!(KillGraph::from_report(&report).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::{KillReason, Report};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// The version of the kill matrix schema.
+pub const KILL_MATRIX_SCHEMA_VERSION: u32 = 1;
+
+/// The (test × mutant) matrix of outcomes in a sparse (coordinate list) format.
+///
+/// Tests are the rows and mutants are the columns. Only the kills are stored - every test passed
+/// on every mutant it has been run on, unless there is a kill entry for the pair. Only the scored
+/// (killed or alive) mutants are included, so the matrix agrees with the mutation score.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct KillMatrix {
+    /// The version of the kill matrix schema.
+    pub schema_version: u32,
+    /// Names of the tests (rows).
+    pub tests: Vec<String>,
+    /// Identifiers of the mutants (columns).
+    pub mutants: Vec<String>,
+    /// Indices of the mutants on which no test could run, because the tests didn't compile.
+    pub not_run: Vec<usize>,
+    /// `[test, mutant]` index pairs - the test failed on the mutant.
+    pub kills: Vec<(usize, usize)>,
+}
+
+impl KillMatrix {
+    /// Builds the kill matrix from the report.
+    ///
+    /// Mutants which haven't been tested (skipped or suppressed) or don't count in the score
+    /// (excluded by the kill policy or equivalent) are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report doesn't contain per-test results.
+    pub fn from_report(report: &Report) -> anyhow::Result<Self> {
+        if report.tests.is_empty() {
+            anyhow::bail!("the report doesn't contain per-test results");
+        }
+
+        let mutants = report
+            .mutants
+            .iter()
+            .filter(|m| m.status.is_scored())
+            .collect::<Vec<_>>();
+
+        let mut not_run = vec![];
+        let mut kills = vec![];
+        for (mutant_idx, mutant) in mutants.iter().enumerate() {
+            if mutant.kill_reason == Some(KillReason::CompileFailure) {
+                not_run.push(mutant_idx);
+            }
+            for test in &mutant.killed_by {
+                // Tests are sorted.
+                if let Ok(test_idx) = report.tests.binary_search(test) {
+                    kills.push((test_idx, mutant_idx));
+                }
+            }
+        }
+        kills.sort_unstable();
+
+        Ok(Self {
+            schema_version: KILL_MATRIX_SCHEMA_VERSION,
+            tests: report.tests.clone(),
+            mutants: mutants.iter().map(|m| m.id.clone()).collect(),
+            not_run,
+            kills,
+        })
+    }
+
+    /// Save the kill matrix to a JSON file.
+    pub fn save_to_json_file(&self, path: &Path) -> anyhow::Result<()> {
+        let file = fs::File::create(path)?;
+        Ok(serde_json::to_writer_pretty(file, self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MiniReport, MutantRecord, MutantStatus};
+    use std::path::PathBuf;
+
+    fn add_mutant(report: &mut Report, id: &str, killed_by: &[&str], reason: Option<KillReason>) {
+        let status = if reason.is_some() {
+            MutantStatus::Killed
+        } else {
+            MutantStatus::Alive
+        };
+        add_mutant_with_status(report, id, killed_by, reason, status);
+    }
+
+    fn add_mutant_with_status(
+        report: &mut Report,
+        id: &str,
+        killed_by: &[&str],
+        reason: Option<KillReason>,
+        status: MutantStatus,
+    ) {
+        let mut mini_report = MiniReport::new(
+            PathBuf::from("sources/Sum.move"),
+            "Sum::sum".to_owned(),
+            status,
+            String::new(),
+        )
+        .with_mutant_info(id, vec![])
+        .with_killed_by(killed_by.iter().map(|t| t.to_string()).collect());
+        if let Some(reason) = reason {
+            mini_report = mini_report.with_kill_reason(reason);
+        }
+        report.add_mutant_record(MutantRecord::from(&mini_report));
+    }
+
+    #[test]
+    fn kill_matrix_from_report() {
+        let mut report = Report::new("package_dir".into());
+        add_mutant(
+            &mut report,
+            "a",
+            &["test_b", "test_a"],
+            Some(KillReason::TestFailure),
+        );
+        add_mutant(&mut report, "b", &[], None);
+        add_mutant(&mut report, "c", &[], Some(KillReason::CompileFailure));
+        report.set_tests([
+            "test_b".to_owned(),
+            "test_a".to_owned(),
+            "test_c".to_owned(),
+        ]);
+
+        let matrix = KillMatrix::from_report(&report).unwrap();
+        assert_eq!(matrix, KillMatrix {
+            schema_version: KILL_MATRIX_SCHEMA_VERSION,
+            tests: vec![
+                "test_a".to_owned(),
+                "test_b".to_owned(),
+                "test_c".to_owned()
+            ],
+            mutants: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+            not_run: vec![2],
+            kills: vec![(0, 0), (1, 0)],
+        });
+    }
+
+    #[test]
+    fn kill_matrix_leaves_out_mutants_which_are_not_scored() {
+        let mut report = Report::new("package_dir".into());
+        add_mutant(&mut report, "a", &["test_a"], Some(KillReason::TestFailure));
+        add_mutant_with_status(
+            &mut report,
+            "b",
+            &["test_b"],
+            Some(KillReason::SoftKill),
+            MutantStatus::Excluded,
+        );
+        add_mutant_with_status(
+            &mut report,
+            "c",
+            &[],
+            Some(KillReason::CompileFailure),
+            MutantStatus::Excluded,
+        );
+        for (id, status) in [
+            ("d", MutantStatus::Skipped),
+            ("e", MutantStatus::Suppressed),
+            ("f", MutantStatus::Equivalent),
+        ] {
+            add_mutant_with_status(&mut report, id, &[], None, status);
+        }
+        add_mutant(&mut report, "g", &[], None);
+        report.set_tests(["test_a".to_owned(), "test_b".to_owned()]);
+
+        let matrix = KillMatrix::from_report(&report).unwrap();
+        assert_eq!(matrix, KillMatrix {
+            schema_version: KILL_MATRIX_SCHEMA_VERSION,
+            tests: vec!["test_a".to_owned(), "test_b".to_owned()],
+            mutants: vec!["a".to_owned(), "g".to_owned()],
+            not_run: vec![],
+            kills: vec![(0, 0)],
+        });
+
+        // The test killing only the excluded mutant doesn't kill anything that counts.
+        let minimized = crate::analyze::minimize_tests(&matrix);
+        assert_eq!(minimized.selected, vec![0]);
+        assert_eq!(minimized.redundant, vec![1]);
+    }
+
+    #[test]
+    fn kill_matrix_requires_test_results() {
+        let report = Report::new("package_dir".into());
+        assert!(KillMatrix::from_report(&report).is_err());
+    }
+}
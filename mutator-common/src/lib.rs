@@ -2,14 +2,92 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+/// Analysis of the test suite based on the mutation testing results.
+pub mod analyze;
+
 /// Benchmarking.
 pub mod benchmark;
 
+/// Checkpoints of the results, so interrupted runs can be resumed.
+pub mod checkpoint;
+
+/// Generation of the shell completion scripts and man pages.
+pub mod cli_docs;
+
+/// Attribution of mutants to code owners.
+pub mod codeowners;
+
+/// Color handling of the output.
+pub mod color;
+
+/// Crash reports written on internal errors.
+pub mod crash_report;
+
+/// Rendering of the mutant diffs.
+pub mod diff_render;
+
 /// A module for displaying reports in a nice fashion.
 pub mod display_report;
 
+/// Error paths (`abort` and `assert!` sites) never exercised by the tests.
+pub mod error_paths;
+
+/// Export of the graph of the kill relationships.
+pub mod graph;
+
+/// Configuration of the CLI options with environment variables.
+pub mod env_vars;
+
+/// A module for explaining mutants and suggesting next actions.
+pub mod explain;
+
+/// The (test × mutant) kill matrix.
+pub mod kill_matrix;
+
+/// Locale-aware formatting of numbers in the human-readable output.
+pub mod locale;
+
+/// The lists of the mutants to evaluate.
+pub mod mutant_ids;
+
+/// Sampling of the memory usage of the tool and its workers.
+pub mod memory;
+
+/// Webhook notifications about finished runs.
+#[cfg(feature = "webhook")]
+pub mod notify;
+
+/// Package and build options shared by the tools.
+pub mod package;
+
+/// Default options of the tools stored with the package.
+pub mod package_defaults;
+
+/// Hooks run after the tool finishes.
+pub mod post_run;
+
 /// A module for generating concise, valuable reports.
 pub mod report;
 
+/// Pluggable store of the run results, keeping the trend of the mutation score.
+pub mod results_store;
+
+/// Retrying of the requests to external services.
+#[cfg(any(feature = "upload", feature = "webhook"))]
+pub mod retry;
+
+/// Mutation score policy and suppressed mutants.
+pub mod score;
+
+/// Helpers for inspecting the Move source code.
+pub mod source;
+
 /// A path setup container for packages under test.
 pub mod tmp_package_dir;
+
+/// The interactive mutant triage page.
+pub mod triage;
+
+/// Uploading of the run artifacts to object storage.
+#[cfg(feature = "upload")]
+pub mod upload;
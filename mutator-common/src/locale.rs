@@ -0,0 +1,142 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{fmt, str::FromStr};
+
+/// Number formatting conventions used in the human-readable output.
+///
+/// The locale affects only the output printed on the screen. Machine-readable reports (e.g. JSON)
+/// are always locale-independent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    /// The language code, e.g. `en`.
+    language: &'static str,
+    /// The character separating the integer and the fractional part.
+    decimal_separator: char,
+    /// The character separating groups of thousands.
+    group_separator: char,
+}
+
+/// Supported languages with their decimal and group separators.
+const LOCALES: &[(&str, char, char)] = &[
+    ("en", '.', ','),
+    ("ja", '.', ','),
+    ("ko", '.', ','),
+    ("zh", '.', ','),
+    ("da", ',', '.'),
+    ("de", ',', '.'),
+    ("el", ',', '.'),
+    ("es", ',', '.'),
+    ("id", ',', '.'),
+    ("it", ',', '.'),
+    ("nl", ',', '.'),
+    ("pt", ',', '.'),
+    ("tr", ',', '.'),
+    ("cs", ',', '\u{a0}'),
+    ("fi", ',', '\u{a0}'),
+    ("fr", ',', '\u{a0}'),
+    ("hu", ',', '\u{a0}'),
+    ("nb", ',', '\u{a0}'),
+    ("pl", ',', '\u{a0}'),
+    ("ru", ',', '\u{a0}'),
+    ("sk", ',', '\u{a0}'),
+    ("sv", ',', '\u{a0}'),
+    ("uk", ',', '\u{a0}'),
+];
+
+impl Locale {
+    /// Formats the integer, separating groups of thousands.
+    pub fn format_count(&self, count: u32) -> String {
+        let digits = count.to_string();
+        let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+        for (idx, digit) in digits.chars().enumerate() {
+            if idx > 0 && (digits.len() - idx) % 3 == 0 {
+                result.push(self.group_separator);
+            }
+            result.push(digit);
+        }
+        result
+    }
+
+    /// Formats the percentage with two decimal places.
+    pub fn format_percentage(&self, percentage: f64) -> String {
+        let formatted = format!("{percentage:.2}");
+        let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, "00"));
+        let integer = integer
+            .parse::<u32>()
+            .map_or_else(|_| integer.to_owned(), |i| self.format_count(i));
+        format!("{integer}{}{fraction}%", self.decimal_separator)
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        "en".parse()
+            .expect("the default locale is always supported")
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Parses the locale from a language tag like `de`, `de-DE` or `de_DE.UTF-8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let language = match language.as_str() {
+            "c" | "posix" => "en",
+            lang => lang,
+        };
+
+        LOCALES
+            .iter()
+            .find(|(lang, ..)| *lang == language)
+            .map(|&(language, decimal_separator, group_separator)| Locale {
+                language,
+                decimal_separator,
+                group_separator,
+            })
+            .ok_or_else(|| {
+                let supported = LOCALES.iter().map(|(lang, ..)| *lang).collect::<Vec<_>>();
+                format!(
+                    "unsupported locale '{s}', supported languages: {}",
+                    supported.join(", ")
+                )
+            })
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.language)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numbers_according_to_locale() {
+        let en = Locale::default();
+        assert_eq!(en.format_count(1234567), "1,234,567");
+        assert_eq!(en.format_percentage(1234.5), "1,234.50%");
+
+        let de: Locale = "de_DE.UTF-8".parse().unwrap();
+        assert_eq!(de.to_string(), "de");
+        assert_eq!(de.format_count(123), "123");
+        assert_eq!(de.format_percentage(66.666), "66,67%");
+
+        let fr: Locale = "fr-FR".parse().unwrap();
+        assert_eq!(fr.format_count(1000), "1\u{a0}000");
+    }
+
+    #[test]
+    fn unsupported_locale_fails() {
+        assert!("xx".parse::<Locale>().is_err());
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fmt, fs,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// How often the memory usage is sampled.
+pub const SAMPLING_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The peak memory usage (resident set size) observed during the run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryPeaks {
+    /// The peak memory of the tool process itself, in bytes.
+    pub orchestrator: u64,
+    /// The peak memory of all the worker processes (e.g. the provers) running at once, in bytes.
+    pub workers: u64,
+    /// The peak memory of the tool process and its workers together, in bytes.
+    pub total: u64,
+    /// The number of samples taken.
+    pub samples: usize,
+}
+
+impl MemoryPeaks {
+    /// Updates the peaks with a sample of the orchestrator and the workers memory.
+    fn record(&mut self, orchestrator: u64, workers: u64) {
+        self.orchestrator = self.orchestrator.max(orchestrator);
+        self.workers = self.workers.max(workers);
+        self.total = self.total.max(orchestrator + workers);
+        self.samples += 1;
+    }
+}
+
+impl fmt::Display for MemoryPeaks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.samples == 0 {
+            return writeln!(f, "Peak memory: not available on this platform");
+        }
+        writeln!(f, "Peak memory ({} samples):", self.samples)?;
+        for (label, bytes) in [
+            ("orchestrator", self.orchestrator),
+            ("workers", self.workers),
+            ("total", self.total),
+        ] {
+            writeln!(f, "  {label:<28} {:>10}", format_bytes(bytes))?;
+        }
+        Ok(())
+    }
+}
+
+/// Samples the memory usage of the tool process and its child processes in the background
+/// (`--profile-memory`), to help size the CI runners and find leaks across the compilations.
+///
+/// The memory is read from `/proc`, so the samples are only taken on Linux.
+pub struct MemoryProfiler {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<MemoryPeaks>,
+}
+
+impl MemoryProfiler {
+    /// Starts sampling the memory usage with the given interval.
+    pub fn start(interval: Duration) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            let mut peaks = MemoryPeaks::default();
+            let pid = std::process::id();
+            loop {
+                if let Some(orchestrator) = rss(pid) {
+                    let workers = descendants(pid).into_iter().filter_map(rss).sum();
+                    peaks.record(orchestrator, workers);
+                }
+                if stop_flag.load(Ordering::Relaxed) {
+                    return peaks;
+                }
+                thread::sleep(interval);
+            }
+        });
+        Self { stop, handle }
+    }
+
+    /// Stops sampling and returns the observed peaks.
+    pub fn finish(self) -> MemoryPeaks {
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle.join().unwrap_or_default()
+    }
+}
+
+/// Reads the resident set size of the process in bytes.
+fn rss(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    parse_rss(&status)
+}
+
+/// Parses the `VmRSS` line of the `/proc/<pid>/status` file.
+fn parse_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib = line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|kib| kib.parse::<u64>().ok())?;
+    Some(kib * 1024)
+}
+
+/// Finds all the descendant processes of the process (children of all its threads, recursively).
+fn descendants(pid: u32) -> Vec<u32> {
+    let mut result = vec![];
+    let mut queue = vec![pid];
+    while let Some(pid) = queue.pop() {
+        let Ok(tasks) = fs::read_dir(format!("/proc/{pid}/task")) else {
+            continue;
+        };
+        for task in tasks.flatten() {
+            let Ok(children) = fs::read_to_string(task.path().join("children")) else {
+                continue;
+            };
+            for child in children.split_whitespace().filter_map(|c| c.parse().ok()) {
+                result.push(child);
+                queue.push(child);
+            }
+        }
+    }
+    result
+}
+
+/// Formats the number of bytes in the binary units (e.g. `1.5 GiB`).
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rss_is_parsed_and_formatted() {
+        let status = "Name:\tmove-spec-test\nVmPeak:\t  300000 kB\nVmRSS:\t  204800 kB\n";
+        assert_eq!(parse_rss(status), Some(200 * 1024 * 1024));
+        assert_eq!(parse_rss("Name:\tzombie\n"), None);
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(200 * 1024 * 1024), "200.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024 / 2), "1.5 GiB");
+    }
+
+    #[test]
+    fn peaks_are_tracked_separately() {
+        let mut peaks = MemoryPeaks::default();
+        peaks.record(100, 500);
+        peaks.record(300, 0);
+        assert_eq!(peaks, MemoryPeaks {
+            orchestrator: 300,
+            workers: 500,
+            total: 600,
+            samples: 2,
+        });
+        assert!(peaks.to_string().contains("total"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn profiler_samples_own_process() {
+        let peaks = MemoryProfiler::start(Duration::from_millis(1)).finish();
+        assert!(peaks.samples > 0);
+        assert!(peaks.orchestrator > 0);
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context;
+use std::{collections::BTreeSet, fs, path::Path};
+
+/// Loads the list of the mutant IDs from the file (`--only-mutants`).
+///
+/// # Errors
+/// Returns an error if the file can't be read or it lists no mutants.
+pub fn load_mutant_ids(path: &Path) -> anyhow::Result<BTreeSet<String>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let ids = parse_mutant_ids(&content);
+    if ids.is_empty() {
+        anyhow::bail!("no mutant IDs found in {}", path.display());
+    }
+    Ok(ids)
+}
+
+/// Parses the mutant IDs, one per line. `#` starts a comment and only the first word of the line
+/// is read, so the lines of the suppressions file or of a table copied from the output can be
+/// used as well.
+pub fn parse_mutant_ids(content: &str) -> BTreeSet<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split_once('#').map_or(line, |(line, _)| line);
+            line.split_whitespace().next()
+        })
+        .map(str::to_owned)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutant_ids_are_parsed() {
+        let ids = parse_mutant_ids(
+            "# Survivors of yesterday's run.\n\
+             3f2a1b\n\
+             \n\
+             a81c equivalent # x * 1 == x / 1\n\
+             3f2a1b\n",
+        );
+        assert_eq!(
+            ids,
+            BTreeSet::from(["3f2a1b".to_owned(), "a81c".to_owned()])
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ids.txt");
+        fs::write(&path, "# Nothing yet.\n").unwrap();
+        let err = load_mutant_ids(&path).unwrap_err();
+        assert!(err.to_string().contains("no mutant IDs found"));
+    }
+}
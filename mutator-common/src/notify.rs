@@ -0,0 +1,141 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{report::Report, retry::with_retries};
+use clap::ValueEnum;
+use log::info;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// The number of attempts to deliver the notification.
+const NOTIFY_ATTEMPTS: u32 = 3;
+
+/// The version of the notification payload schema.
+const PAYLOAD_SCHEMA_VERSION: u32 = 1;
+
+/// The timeout for a single delivery attempt.
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Defines when the webhook is notified.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum NotifyOn {
+    /// After every run.
+    #[default]
+    Always,
+    /// When there are surviving mutants or the run fails.
+    Survivors,
+    /// Only when the run fails.
+    Failure,
+}
+
+/// Posts the summary of the run to the webhook, if the policy allows it.
+///
+/// The summary is a JSON object with the `text` field (understood by Slack and Teams incoming
+/// webhooks) and the run statistics. Delivery is retried a few times. Failing to deliver the
+/// notification doesn't fail the run, only a warning is printed.
+pub fn notify_webhook(url: &str, notify_on: NotifyOn, tool: &str, result: &anyhow::Result<Report>) {
+    if !should_notify(notify_on, result) {
+        return;
+    }
+
+    let payload = payload(tool, result);
+    let delivery = with_retries(
+        NOTIFY_ATTEMPTS,
+        || {
+            ureq::post(url)
+                .timeout(NOTIFY_TIMEOUT)
+                .send_json(payload.clone())
+        },
+        |attempt, error| info!("Webhook notification attempt {attempt} failed: {error}, retrying"),
+    );
+    match delivery {
+        Ok(_) => info!("Webhook notified"),
+        Err(error) => eprintln!("Warning: failed to notify the webhook: {error}"),
+    }
+}
+
+/// Checks whether the webhook should be notified about the result.
+fn should_notify(notify_on: NotifyOn, result: &anyhow::Result<Report>) -> bool {
+    match (notify_on, result) {
+        (NotifyOn::Always, _) | (_, Err(_)) => true,
+        (NotifyOn::Survivors, Ok(report)) => report.mutants_tested() > report.mutants_killed(),
+        (NotifyOn::Failure, Ok(_)) => false,
+    }
+}
+
+/// Builds the JSON summary of the run.
+fn payload(tool: &str, result: &anyhow::Result<Report>) -> Value {
+    match result {
+        Ok(report) => {
+            let tested = report.mutants_tested();
+            let killed = report.mutants_killed();
+            let score = report.score_breakdown().score();
+            json!({
+                "text": format!(
+                    "{tool} finished: {tested} mutants tested, {} alive, mutation score {score:.2}%",
+                    tested - killed
+                ),
+                "schema_version": PAYLOAD_SCHEMA_VERSION,
+                "tool": tool,
+                "status": "success",
+                "package_dir": report.package_dir,
+                "mutants_tested": tested,
+                "mutants_killed": killed,
+                "mutants_alive": tested - killed,
+                "mutation_score": score,
+            })
+        },
+        Err(e) => json!({
+            "text": format!("{tool} failed: {e:#}"),
+            "schema_version": PAYLOAD_SCHEMA_VERSION,
+            "tool": tool,
+            "status": "failure",
+            "error": format!("{e:#}"),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn report(tested: u32, killed: u32) -> Report {
+        let mut report = Report::new("package_dir".into());
+        let path = PathBuf::from("sources/Sum.move");
+        for _ in 0..tested {
+            report.increment_mutants_tested(&path, "Sum::sum");
+        }
+        for _ in 0..killed {
+            report.increment_mutants_killed(&path, "Sum::sum");
+        }
+        report
+    }
+
+    #[test]
+    fn notification_policy() {
+        let all_killed = Ok(report(2, 2));
+        let survivors = Ok(report(2, 1));
+        let failure = Err(anyhow::anyhow!("tests failed"));
+
+        assert!(should_notify(NotifyOn::Always, &all_killed));
+        assert!(!should_notify(NotifyOn::Survivors, &all_killed));
+        assert!(should_notify(NotifyOn::Survivors, &survivors));
+        assert!(should_notify(NotifyOn::Survivors, &failure));
+        assert!(!should_notify(NotifyOn::Failure, &survivors));
+        assert!(should_notify(NotifyOn::Failure, &failure));
+    }
+
+    #[test]
+    fn payload_contains_summary() {
+        let success = payload("move-mutation-test", &Ok(report(4, 3)));
+        assert_eq!(success["status"], "success");
+        assert_eq!(success["mutants_alive"], 1);
+        assert_eq!(success["mutation_score"], 75.0);
+
+        let failure = payload("move-mutation-test", &Err(anyhow::anyhow!("tests failed")));
+        assert_eq!(failure["status"], "failure");
+        assert_eq!(failure["text"], "move-mutation-test failed: tests failed");
+    }
+}
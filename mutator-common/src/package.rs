@@ -0,0 +1,106 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Args;
+use move_core_types::account_address::AccountAddress;
+use move_model::metadata::{CompilerVersion, LanguageVersion};
+use move_package::BuildConfig;
+use std::path::PathBuf;
+
+/// The package and build options shared by the tools, so the flags have the same names, defaults
+/// and conflicts everywhere.
+///
+/// The names follow the package options of the `aptos move` commands, which `move-mutation-test`
+/// takes to run the unit tests.
+#[derive(Args, Debug, Clone, Default)]
+pub struct PackageArgs {
+    /// The path to the target Move package.
+    #[clap(long, value_parser)]
+    pub package_dir: Option<PathBuf>,
+
+    /// Named addresses of the package, e.g. `--named-addresses alice=0x1234,bob=0x5678`.
+    #[clap(long, value_parser = parse_named_address, value_delimiter = ',')]
+    pub named_addresses: Vec<(String, AccountAddress)>,
+
+    /// Enable the Move 2 language and compiler (a shorthand for `--language-version 2.0
+    /// --compiler-version 2.0`). The explicitly given versions take precedence.
+    #[clap(long)]
+    pub move_2: bool,
+
+    /// The build configuration for the Move package.
+    #[clap(flatten)]
+    pub build_config: BuildConfig,
+}
+
+impl PackageArgs {
+    /// Returns the build configuration with the named addresses and the Move 2 versions applied.
+    #[must_use]
+    pub fn build_config(&self) -> BuildConfig {
+        let mut config = self.build_config.clone();
+        config
+            .additional_named_addresses
+            .extend(self.named_addresses.iter().cloned());
+        if self.move_2 {
+            let compiler_config = &mut config.compiler_config;
+            compiler_config
+                .language_version
+                .get_or_insert(LanguageVersion::V2_0);
+            compiler_config
+                .compiler_version
+                .get_or_insert(CompilerVersion::V2_0);
+        }
+        config
+    }
+}
+
+/// Parses the named address in the `name=address` form.
+fn parse_named_address(s: &str) -> Result<(String, AccountAddress), String> {
+    let (name, address) = s
+        .split_once('=')
+        .filter(|(name, _)| !name.is_empty())
+        .ok_or_else(|| format!("invalid named address '{s}', expected NAME=ADDRESS"))?;
+    let address = AccountAddress::from_hex_literal(address)
+        .map_err(|e| format!("invalid address of '{name}': {e}"))?;
+    Ok((name.to_owned(), address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[clap(flatten)]
+        package: PackageArgs,
+    }
+
+    #[test]
+    fn package_args_are_applied_to_build_config() {
+        let cli = Cli::parse_from([
+            "tool",
+            "--package-dir",
+            "pkg",
+            "--named-addresses",
+            "alice=0x1234,bob=0x5678",
+            "--move-2",
+            "--dev",
+        ]);
+        let config = cli.package.build_config();
+        assert_eq!(cli.package.package_dir, Some(PathBuf::from("pkg")));
+        assert!(config.dev_mode);
+        assert_eq!(
+            config.additional_named_addresses.get("bob"),
+            Some(&AccountAddress::from_hex_literal("0x5678").unwrap())
+        );
+        assert_eq!(
+            config.compiler_config.language_version,
+            Some(LanguageVersion::V2_0)
+        );
+
+        assert!(parse_named_address("alice").is_err());
+        assert!(parse_named_address("=0x1").is_err());
+        assert!(parse_named_address("alice=xyz").is_err());
+    }
+}
@@ -0,0 +1,426 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Context};
+use clap::{ArgAction, ArgMatches, Command, ValueHint};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The section of `Move.toml` (under `[package.metadata]`) with the default options of the tools.
+pub const MANIFEST_SECTION: &str = "mutation-test";
+
+/// The file next to `Move.toml` with the default options, for packages which keep the manifest
+/// free of the tool settings.
+pub const DEFAULTS_FILE: &str = "mutation-test.toml";
+
+/// The tools which can have their own table of options.
+const TOOLS: &[&str] = &["move-mutator", "move-mutation-test", "move-spec-test"];
+
+/// The options which can be set by the package. They only select and evaluate the mutants, so a
+/// package can't run commands, set the environment, reach the network or write outside of the
+/// output directory through them.
+const ALLOWED_OPTIONS: &[&str] = &[
+    "allow-empty",
+    "codeowners",
+    "compiler-matrix",
+    "coverage",
+    "coverage-fallback",
+    "coverage-summary",
+    "disable-operators",
+    "downsampling-ratio-percentage",
+    "extra-operators",
+    "filter",
+    "gas-limit",
+    "ignore-compile-warnings",
+    "keep-stillborn",
+    "kill-on",
+    "max-mutants-per-file",
+    "mutate-functions",
+    "mutate-manifest",
+    "mutate-modules",
+    "new-code-commits",
+    "new-code-days",
+    "only-mutants",
+    "partial-verification",
+    "pin-abort-codes",
+    "prover-jobs",
+    "runs-per-mutant",
+    "score-include",
+    "shuffle",
+    "shuffle-seed",
+    "skip-uncovered-after",
+    "suppressions",
+    "test-seed",
+    "verify-mutants",
+    "view-snapshots",
+];
+
+/// Default options of the tools stored with the package.
+///
+/// The keys are named after the long CLI options, and only the options selecting and evaluating
+/// the mutants can be set (see [`ALLOWED_OPTIONS`]). The top-level keys are shared by all tools and
+/// are skipped by the tools which don't have such option, while the keys in the table named after
+/// the tool (e.g. `[package.metadata.mutation-test.move-spec-test]`) apply only to that tool, take
+/// precedence over the shared ones and must be known to the tool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageDefaults {
+    /// The file the defaults were loaded from.
+    source: PathBuf,
+    /// The package directory, relative paths are resolved against it.
+    package_dir: PathBuf,
+    /// The options shared by all tools.
+    shared: toml::Table,
+    /// The options of the current tool.
+    tool: toml::Table,
+}
+
+impl PackageDefaults {
+    /// Loads the defaults of the tool from the `Move.toml` section or the sibling file.
+    ///
+    /// Returns `None` if the package has no defaults.
+    ///
+    /// # Errors
+    /// Returns an error if the files can't be parsed, or if both of them contain the defaults.
+    pub fn load(package_dir: &Path, tool: &str) -> anyhow::Result<Option<Self>> {
+        let read_table = |path: &Path| -> anyhow::Result<Option<toml::Table>> {
+            if !path.exists() {
+                return Ok(None);
+            }
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("cannot read {}", path.display()))?;
+            let table = content
+                .parse::<toml::Table>()
+                .with_context(|| format!("invalid TOML file {}", path.display()))?;
+            Ok(Some(table))
+        };
+
+        let manifest_path = package_dir.join("Move.toml");
+        let from_manifest = read_table(&manifest_path)?.and_then(|mut manifest| {
+            let package = manifest.remove("package")?;
+            let mut metadata = package.get("metadata")?.as_table()?.clone();
+            metadata.remove(MANIFEST_SECTION)
+        });
+        let file_path = package_dir.join(DEFAULTS_FILE);
+        let from_file = read_table(&file_path)?;
+
+        let (source, table) = match (from_manifest, from_file) {
+            (Some(_), Some(_)) => anyhow::bail!(
+                "the default options are set both in the [package.metadata.{MANIFEST_SECTION}] \
+                 section of {} and in {}, keep only one of them",
+                manifest_path.display(),
+                file_path.display()
+            ),
+            (Some(section), None) => (manifest_path, section),
+            (None, Some(table)) => (file_path, toml::Value::Table(table)),
+            (None, None) => return Ok(None),
+        };
+        let toml::Value::Table(table) = table else {
+            anyhow::bail!(
+                "[package.metadata.{MANIFEST_SECTION}] in {} must be a table",
+                source.display()
+            );
+        };
+
+        Self::from_table(table, package_dir, source, tool).map(Some)
+    }
+
+    /// Splits the table into the shared options and the options of the tool.
+    fn from_table(
+        mut shared: toml::Table,
+        package_dir: &Path,
+        source: PathBuf,
+        tool: &str,
+    ) -> anyhow::Result<Self> {
+        let mut tool_table = toml::Table::new();
+        for name in TOOLS {
+            match shared.remove(*name) {
+                Some(toml::Value::Table(table)) if *name == tool => tool_table = table,
+                Some(toml::Value::Table(_)) => {},
+                Some(_) => anyhow::bail!(
+                    "the options of `{name}` in {} must be a table",
+                    source.display()
+                ),
+                None => {},
+            }
+        }
+
+        Ok(Self {
+            source,
+            package_dir: package_dir.to_path_buf(),
+            shared,
+            tool: tool_table,
+        })
+    }
+
+    /// Sets the defaults as the default values of the options of the command (or its subcommand).
+    ///
+    /// The options given on the command line or with environment variables take precedence.
+    ///
+    /// # Errors
+    /// Returns an error listing all options which can't be set by the package, all options of the
+    /// tool table which the tool doesn't know and all values of a wrong type.
+    pub fn apply(&self, cmd: Command, subcommand: Option<&str>) -> anyhow::Result<Command> {
+        let target = match subcommand {
+            Some(name) => cmd
+                .find_subcommand(name)
+                .ok_or_else(|| anyhow!("unknown subcommand `{name}`"))?,
+            None => &cmd,
+        };
+
+        let mut defaults = vec![];
+        let mut errors = vec![];
+        let shared = self
+            .shared
+            .iter()
+            .filter(|(key, _)| !self.tool.contains_key(*key));
+        let options = shared
+            .map(|option| (option, false))
+            .chain(self.tool.iter().map(|option| (option, true)));
+        for ((key, value), is_tool_option) in options {
+            if !ALLOWED_OPTIONS.contains(&key.as_str()) {
+                errors.push(format!(
+                    "option `{key}` can't be set by the package, only on the command line"
+                ));
+                continue;
+            }
+            let Some(arg) = target
+                .get_arguments()
+                .find(|arg| arg.get_long() == Some(key.as_str()))
+            else {
+                if is_tool_option {
+                    errors.push(format!("unknown option `{key}`"));
+                }
+                continue;
+            };
+
+            let is_path = matches!(
+                arg.get_value_hint(),
+                ValueHint::AnyPath | ValueHint::FilePath | ValueHint::DirPath
+            );
+            let to_string = |value: &toml::Value| match value {
+                toml::Value::String(s) if is_path => {
+                    Some(self.package_dir.join(s).to_string_lossy().into_owned())
+                },
+                toml::Value::String(s) => Some(s.clone()),
+                toml::Value::Integer(i) => Some(i.to_string()),
+                toml::Value::Float(f) => Some(f.to_string()),
+                toml::Value::Boolean(b) => Some(b.to_string()),
+                _ => None,
+            };
+
+            let values = match (arg.get_action(), value) {
+                (ArgAction::SetTrue | ArgAction::SetFalse, toml::Value::Boolean(b)) => {
+                    vec![b.to_string()]
+                },
+                (ArgAction::SetTrue | ArgAction::SetFalse, _) => {
+                    errors.push(format!("option `{key}` must be a boolean"));
+                    continue;
+                },
+                (ArgAction::Append, toml::Value::Array(items)) => {
+                    match items.iter().map(to_string).collect::<Option<Vec<_>>>() {
+                        Some(values) => values,
+                        None => {
+                            errors.push(format!("option `{key}` must be an array of values"));
+                            continue;
+                        },
+                    }
+                },
+                (_, value) => match to_string(value) {
+                    Some(value) => vec![value],
+                    None => {
+                        errors.push(format!("option `{key}` takes a single value"));
+                        continue;
+                    },
+                },
+            };
+            defaults.push((arg.get_id().as_str().to_owned(), values));
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "invalid default options in {}:\n{}",
+                self.source.display(),
+                errors.join("\n")
+            );
+        }
+
+        let set_defaults = |mut cmd: Command| {
+            for (id, values) in &defaults {
+                cmd = cmd.mut_arg(id, |arg| arg.default_values(values.clone()));
+            }
+            cmd
+        };
+        Ok(match subcommand {
+            Some(name) => cmd.mut_subcommand(name, set_defaults),
+            None => set_defaults(cmd),
+        })
+    }
+}
+
+/// Parses the command line with the default options of the package applied.
+///
+/// The package directory is taken from the `--package-dir` option of the command (or of the
+/// subcommand, if given), the current directory is used otherwise. Other subcommands are parsed
+/// as they are.
+///
+/// # Errors
+/// Returns an error if the defaults of the package can't be loaded or applied.
+pub fn get_matches_with_package_defaults(
+    cmd: Command,
+    subcommand: Option<&str>,
+    tool: &str,
+) -> anyhow::Result<ArgMatches> {
+    let matches = cmd.clone().get_matches();
+    let target = match subcommand {
+        Some(name) => match matches.subcommand_matches(name) {
+            Some(target) => target,
+            None => return Ok(matches),
+        },
+        None => &matches,
+    };
+    let package_dir = target
+        .try_get_one::<PathBuf>("package_dir")
+        .ok()
+        .flatten()
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    match PackageDefaults::load(&package_dir, tool)? {
+        Some(defaults) => Ok(defaults.apply(cmd, subcommand)?.get_matches()),
+        None => Ok(matches),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+
+    fn command() -> Command {
+        let run = Command::new("run")
+            .arg(Arg::new("package_dir").long("package-dir"))
+            .arg(
+                Arg::new("suppressions")
+                    .long("suppressions")
+                    .value_parser(clap::value_parser!(PathBuf)),
+            )
+            .arg(
+                Arg::new("extra_operators")
+                    .long("extra-operators")
+                    .action(ArgAction::Append),
+            )
+            .arg(
+                Arg::new("verify_mutants")
+                    .long("verify-mutants")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(Arg::new("prover_jobs").long("prover-jobs"))
+            .arg(Arg::new("post_run_cmd").long("post-run-cmd"));
+        Command::new("tool").subcommand(run)
+    }
+
+    #[test]
+    fn defaults_are_loaded_from_the_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Move.toml"),
+            r#"
+            [package]
+            name = "vault"
+
+            [package.metadata.mutation-test]
+            extra-operators = ["loop_invariant_weakening"]
+            suppressions = "suppressions.txt"
+            verify-mutants = false
+            coverage = true
+
+            [package.metadata.mutation-test.move-spec-test]
+            verify-mutants = true
+            prover-jobs = 4
+
+            [package.metadata.mutation-test.move-mutation-test]
+            unknown = 1
+            "#,
+        )
+        .unwrap();
+
+        let defaults = PackageDefaults::load(dir.path(), "move-spec-test")
+            .unwrap()
+            .unwrap();
+        let matches = defaults
+            .apply(command(), Some("run"))
+            .unwrap()
+            .try_get_matches_from(["tool", "run", "--prover-jobs", "2"])
+            .unwrap();
+        let run = matches.subcommand_matches("run").unwrap();
+
+        assert_eq!(
+            run.get_many::<String>("extra_operators")
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec!["loop_invariant_weakening"]
+        );
+        // Paths are relative to the package.
+        assert_eq!(
+            run.get_one::<PathBuf>("suppressions").unwrap(),
+            &dir.path().join("suppressions.txt")
+        );
+        // The tool table takes precedence over the shared options.
+        assert!(run.get_flag("verify_mutants"));
+        // The command line takes precedence.
+        assert_eq!(run.get_one::<String>("prover_jobs").unwrap(), "2");
+
+        // The table of the other tool is checked only by that tool.
+        let defaults = PackageDefaults::load(dir.path(), "move-mutation-test")
+            .unwrap()
+            .unwrap();
+        let err = defaults.apply(command(), Some("run")).unwrap_err();
+        assert!(err.to_string().contains("option `unknown` can't be set"));
+    }
+
+    #[test]
+    fn commands_cant_be_set_by_the_package() {
+        let dir = tempfile::tempdir().unwrap();
+        for table in [
+            "post-run-cmd = \"curl https://example.com\"\n",
+            "[move-spec-test]\npost-run-cmd = \"curl https://example.com\"\n",
+        ] {
+            fs::write(dir.path().join(DEFAULTS_FILE), table).unwrap();
+            let defaults = PackageDefaults::load(dir.path(), "move-spec-test")
+                .unwrap()
+                .unwrap();
+            let err = defaults.apply(command(), Some("run")).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("option `post-run-cmd` can't be set by the package"));
+        }
+    }
+
+    #[test]
+    fn defaults_are_loaded_from_a_single_place() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(PackageDefaults::load(dir.path(), "move-mutator")
+            .unwrap()
+            .is_none());
+
+        fs::write(dir.path().join(DEFAULTS_FILE), "verify-mutants = 1\n").unwrap();
+        let defaults = PackageDefaults::load(dir.path(), "move-mutator")
+            .unwrap()
+            .unwrap();
+        let err = defaults.apply(command(), Some("run")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("option `verify-mutants` must be a boolean"));
+
+        fs::write(
+            dir.path().join("Move.toml"),
+            "[package.metadata.mutation-test]\ncoverage = true\n",
+        )
+        .unwrap();
+        let err = PackageDefaults::load(dir.path(), "move-mutator").unwrap_err();
+        assert!(err.to_string().contains("keep only one of them"));
+    }
+}
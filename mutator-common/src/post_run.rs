@@ -0,0 +1,326 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    display_report::find_mutated_line_number,
+    locale::Locale,
+    report::{MutantRecord, MutantStatus, Report},
+};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
+use log::info;
+use std::{
+    collections::BTreeSet,
+    env,
+    fmt::Write as _,
+    fs,
+    io::Write as _,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+/// The maximum number of surviving mutants listed in the summary. Issue trackers limit the size of
+/// comments, and the full list is available in the report anyway.
+const MAX_LISTED_SURVIVORS: usize = 100;
+
+/// The format of the summary passed to the post-run command.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SummaryFormat {
+    /// A Markdown document, suitable for issues and pull request comments.
+    #[default]
+    Markdown,
+    /// GitHub Actions workflow commands, which annotate the mutated lines when printed in a
+    /// workflow step.
+    Github,
+}
+
+/// Runs the post-run hook command.
+///
+/// The command is split into the program and its arguments like a shell does, but it's run
+/// directly, so the shell syntax (variables, pipes, `||`) is not interpreted. The summary of the
+/// new surviving mutants is written to the standard input of the command. The survivors are new if
+/// they are not alive in the baseline report (all survivors are new without the baseline).
+///
+/// The command gets the following environment variables:
+/// - `MUTATION_REPORT` - path to the report in the JSON format,
+/// - `MUTATION_SUMMARY` - path to the summary of the new surviving mutants in the given format,
+/// - `MUTANTS_TESTED`, `MUTANTS_ALIVE` and `MUTANTS_NEW_ALIVE` - the number of tested, surviving
+///   and new surviving mutants.
+///
+/// # Errors
+///
+/// Returns an error if the baseline can't be loaded, the command cannot be started or it exits with
+/// a non-zero status.
+pub fn run_post_run_cmd(
+    cmd: &str,
+    report: &Report,
+    baseline: Option<&Path>,
+    format: SummaryFormat,
+) -> Result<()> {
+    let args = shlex::split(cmd)
+        .ok_or_else(|| anyhow!("invalid quoting in the post-run command: {cmd}"))?;
+    let Some((program, args)) = args.split_first() else {
+        bail!("the post-run command is empty");
+    };
+
+    let baseline = baseline
+        .map(|path| {
+            Report::load_from_file(path)
+                .with_context(|| format!("cannot load the baseline report {}", path.display()))
+        })
+        .transpose()?;
+    let survivors = new_survivors(report, baseline.as_ref());
+    let summary = match format {
+        SummaryFormat::Markdown => markdown_summary(report, &survivors),
+        SummaryFormat::Github => github_annotations(report, &survivors),
+    };
+
+    let dir = tempfile::tempdir()?;
+    let report_path = dir.path().join("report.json");
+    let summary_path = dir.path().join("summary");
+    report.save_to_json_file(&report_path)?;
+    fs::write(&summary_path, &summary)?;
+
+    let tested = report.mutants_tested();
+    let alive = tested - report.mutants_killed();
+
+    info!("Running the post-run command: {cmd}");
+    let mut child = Command::new(program)
+        .args(args)
+        .env("MUTATION_REPORT", &report_path)
+        .env("MUTATION_SUMMARY", &summary_path)
+        .env("MUTANTS_TESTED", tested.to_string())
+        .env("MUTANTS_ALIVE", alive.to_string())
+        .env("MUTANTS_NEW_ALIVE", survivors.len().to_string())
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("failed to run the post-run command")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The command may exit without reading the summary, which is not an error.
+        let _ = stdin.write_all(summary.as_bytes());
+    }
+    let status = child.wait().context("failed to run the post-run command")?;
+
+    if !status.success() {
+        bail!("the post-run command failed with {status}");
+    }
+    Ok(())
+}
+
+/// Returns the surviving mutants of the report which are not alive in the baseline report.
+///
+/// The mutants are matched by their IDs, which don't change when unrelated code is edited.
+pub fn new_survivors<'a>(report: &'a Report, baseline: Option<&Report>) -> Vec<&'a MutantRecord> {
+    let known = baseline
+        .map(|baseline| {
+            baseline
+                .mutants
+                .iter()
+                .filter(|m| m.status == MutantStatus::Alive)
+                .map(|m| m.id.as_str())
+                .collect::<BTreeSet<_>>()
+        })
+        .unwrap_or_default();
+    report
+        .mutants
+        .iter()
+        .filter(|m| m.status == MutantStatus::Alive && !known.contains(m.id.as_str()))
+        .collect()
+}
+
+/// Renders the summary of the run with the list of the given surviving mutants in the Markdown
+/// format, suitable for issues and pull request comments.
+pub fn markdown_summary(report: &Report, survivors: &[&MutantRecord]) -> String {
+    let tested = report.mutants_tested();
+    let killed = report.mutants_killed();
+    let breakdown = report.score_breakdown();
+
+    let mut summary = "## Mutation testing results\n\n".to_owned();
+    let _ = writeln!(
+        summary,
+        "Mutants tested: {tested}, killed: {killed}, alive: {} (new: {})",
+        tested - killed,
+        survivors.len()
+    );
+    let _ = writeln!(
+        summary,
+        "Mutation score: {:.2}% (not counted: {})\n",
+        breakdown.score(),
+        breakdown.describe(false, &Locale::default())
+    );
+
+    if survivors.is_empty() {
+        summary.push_str("No new surviving mutants.\n");
+        return summary;
+    }
+
+    summary.push_str("### New surviving mutants\n\n");
+    summary.push_str("| Mutant | Location | Operators | Owners |\n");
+    summary.push_str("|---|---|---|---|\n");
+    for mutant in survivors.iter().take(MAX_LISTED_SURVIVORS) {
+        let new_code = if mutant.new_code { " (new code)" } else { "" };
+        let _ = writeln!(
+            summary,
+            "| `{}` | `{}` (`{}`){new_code} | {} | {} |",
+            mutant.id,
+            mutant.original_file.display(),
+            mutant.qname,
+            mutant.operators.join(", "),
+            mutant.owners.join(", ")
+        );
+    }
+    if survivors.len() > MAX_LISTED_SURVIVORS {
+        let _ = writeln!(
+            summary,
+            "\n...and {} more, see the full report.",
+            survivors.len() - MAX_LISTED_SURVIVORS
+        );
+    }
+
+    summary
+}
+
+/// Renders the given surviving mutants as GitHub Actions `::warning` workflow commands, one per
+/// mutant, pointing at the mutated line.
+///
+/// The file paths are relative to the current directory (the workspace in GitHub Actions) when the
+/// package is inside it.
+pub fn github_annotations(report: &Report, survivors: &[&MutantRecord]) -> String {
+    let cwd = env::current_dir().unwrap_or_default();
+    let mut annotations = String::new();
+    for mutant in survivors {
+        let path = report.get_package_dir().join(&mutant.original_file);
+        let path = path.strip_prefix(&cwd).unwrap_or(&path);
+        let line = find_mutated_line_number(&mutant.diff)
+            .map_or(String::new(), |line| format!(",line={line}"));
+        let message = format!(
+            "The mutant {} ({}) in `{}` survived the tests.\n{}",
+            mutant.id,
+            mutant.operators.join(", "),
+            mutant.qname,
+            mutant.diff
+        );
+        let _ = writeln!(
+            annotations,
+            "::warning file={}{line},title=Surviving mutant::{}",
+            escape_property(&path.display().to_string()),
+            escape_data(&message)
+        );
+    }
+    annotations
+}
+
+/// Escapes the message of a workflow command.
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MiniReport, MutantRecord};
+    use std::path::PathBuf;
+
+    const DIFF: &str = "--- original\n+++ modified\n@@ -3,3 +3,3 @@\n     fun sum(a: u64, b: u64): u64 {\n-        a + b\n+        a - b\n     }\n";
+
+    fn report(mutants: &[(&str, MutantStatus)]) -> Report {
+        let mut report = Report::new("package_dir".into());
+        let path = PathBuf::from("sources/Sum.move");
+        for (id, status) in mutants {
+            report.increment_mutants_tested(&path, "Sum::sum");
+            if *status == MutantStatus::Killed {
+                report.increment_mutants_killed(&path, "Sum::sum");
+            }
+            let mini_report = MiniReport::new(
+                path.clone(),
+                "Sum::sum".to_owned(),
+                *status,
+                DIFF.to_owned(),
+            )
+            .with_mutant_info(id, vec!["binary_operator_replacement".to_owned()])
+            .with_new_code(true);
+            report.add_mutant_record(MutantRecord::from(&mini_report));
+        }
+        report
+    }
+
+    #[test]
+    fn summary_lists_survivors() {
+        let report = report(&[("abc", MutantStatus::Alive), ("def", MutantStatus::Killed)]);
+
+        let summary = markdown_summary(&report, &new_survivors(&report, None));
+        assert!(summary.contains("Mutants tested: 2, killed: 1, alive: 1 (new: 1)"));
+        assert!(summary.contains(
+            "| `abc` | `sources/Sum.move` (`Sum::sum`) (new code) | binary_operator_replacement |  |"
+        ));
+        assert!(!summary.contains("`def`"));
+    }
+
+    #[test]
+    fn survivors_alive_in_baseline_are_not_new() {
+        let baseline = report(&[("abc", MutantStatus::Alive), ("def", MutantStatus::Alive)]);
+        let report = report(&[
+            ("abc", MutantStatus::Alive),
+            ("def", MutantStatus::Killed),
+            ("ghi", MutantStatus::Alive),
+        ]);
+
+        let survivors = new_survivors(&report, Some(&baseline));
+        let ids = survivors.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, ["ghi"]);
+        assert!(markdown_summary(&report, &survivors).contains("alive: 2 (new: 1)"));
+    }
+
+    #[test]
+    fn survivors_are_rendered_as_github_annotations() {
+        let report = report(&[("abc", MutantStatus::Alive)]);
+
+        let annotations = github_annotations(&report, &new_survivors(&report, None));
+        assert!(annotations.starts_with(
+            "::warning file=package_dir/sources/Sum.move,line=4,title=Surviving mutant::The mutant \
+             abc (binary_operator_replacement) in `Sum::sum` survived the tests.%0A--- original%0A"
+        ));
+        assert_eq!(annotations.lines().count(), 1);
+        assert_eq!(escape_property("a,b:c%"), "a%2Cb%3Ac%25");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn post_run_cmd_gets_report_paths_and_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.md");
+        let copy = dir.path().join("copy.md");
+        let report = report(&[]);
+
+        run_post_run_cmd(
+            &format!("cp /dev/stdin {}", out.display()),
+            &report,
+            None,
+            SummaryFormat::Markdown,
+        )
+        .unwrap();
+        assert!(fs::read_to_string(&out)
+            .unwrap()
+            .contains("No new surviving mutants."));
+
+        run_post_run_cmd(
+            &format!("sh -c 'cp \"$MUTATION_SUMMARY\" {}'", copy.display()),
+            &report,
+            None,
+            SummaryFormat::Markdown,
+        )
+        .unwrap();
+        assert_eq!(fs::read(&copy).unwrap(), fs::read(&out).unwrap());
+
+        assert!(run_post_run_cmd("false", &report, None, SummaryFormat::Markdown).is_err());
+        assert!(run_post_run_cmd("", &report, None, SummaryFormat::Markdown).is_err());
+    }
+}
@@ -3,25 +3,143 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{
+    codeowners::CodeOwners,
+    error_paths::{error_paths, print_unexercised_error_paths},
+    locale::Locale,
+    score::ScoreCategory,
+};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::{
-    collections::BTreeMap,
-    fs,
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
     path::{Path, PathBuf},
+    time::Duration,
 };
 use tabled::{builder::Builder, settings::Style};
 
+/// The owner shown for the mutants of files without owners.
+const UNOWNED: &str = "(unowned)";
+
+/// The number of the slowest mutants shown in the summary.
+const SLOWEST_MUTANTS: usize = 10;
+
+/// The version of the report schema. It's increased on every incompatible change of the report.
+/// Older reports are upgraded when they are loaded, see [`migrate_report`].
+pub const REPORT_SCHEMA_VERSION: u32 = 2;
+
+/// The version of reports saved before the version was recorded.
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// Migrations upgrading the report from the version `index + 1` to the next version.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[migrate_v1_to_v2];
+
+/// The backend which checked the mutants.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Backend {
+    /// Mutants are checked by running the unit tests (`move-mutation-test`).
+    UnitTests,
+    /// Mutants are checked by the Move Prover (`move-spec-test`).
+    Prover,
+}
+
 /// The final status of the mutant after running the tests on it.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
 pub enum MutantStatus {
     /// Killed mutant.
     Killed,
     /// Alive mutant.
+    #[value(alias = "survived")]
     Alive,
+    /// Mutant killed for a reason which doesn't count according to the kill policy.
+    /// Such mutants are not included in the mutation score.
+    Excluded,
+    /// Mutant not tested, as the first tested mutants of its function have all survived, so the
+    /// function has no effective coverage (`--skip-uncovered-after`).
+    Skipped,
+    /// Mutant not evaluated, as it's suppressed in the suppressions file.
+    Suppressed,
+    /// Mutant not evaluated, as it's marked as equivalent to the original code in the
+    /// suppressions file.
+    Equivalent,
+}
+
+impl MutantStatus {
+    /// Checks whether the mutant has been evaluated and counts as killed or alive.
+    pub fn is_scored(self) -> bool {
+        matches!(self, MutantStatus::Killed | MutantStatus::Alive)
+    }
+}
+
+impl std::fmt::Display for MutantStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MutantStatus::Killed => write!(f, "killed"),
+            MutantStatus::Alive => write!(f, "alive"),
+            MutantStatus::Excluded => write!(f, "excluded"),
+            MutantStatus::Skipped => write!(f, "skipped"),
+            MutantStatus::Suppressed => write!(f, "suppressed"),
+            MutantStatus::Equivalent => write!(f, "equivalent"),
+        }
+    }
+}
+
+/// The reason why the mutant has been killed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum KillReason {
+    /// At least one test failed.
+    TestFailure,
+    /// The package (including the tests) didn't compile.
+    CompileFailure,
+    /// At least one test timed out.
+    Timeout,
+    /// At least one test ran out of gas.
+    GasExhaustion,
+    /// Only tests with a loose `#[expected_failure]` annotation failed, as the mutant changed their
+    /// abort code (with `--pin-abort-codes`).
+    AbortCode,
+    /// Only the view snapshot tests failed, as the mutant changed the outputs of the view
+    /// functions (with `--view-snapshots`).
+    ViewOutput,
+    /// Only tests expecting a failure failed, as the mutant changed their abort code - the tests
+    /// still abort, only with a different error (a soft kill).
+    SoftKill,
+}
+
+impl KillReason {
+    /// The default kill policy - all kill reasons except the compile failures, so the mutants
+    /// rejected by the compiler rather than by the tests don't count.
+    pub const DEFAULT: [KillReason; 6] = [
+        KillReason::TestFailure,
+        KillReason::Timeout,
+        KillReason::GasExhaustion,
+        KillReason::AbortCode,
+        KillReason::ViewOutput,
+        KillReason::SoftKill,
+    ];
+}
+
+impl std::fmt::Display for KillReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KillReason::TestFailure => write!(f, "test-failure"),
+            KillReason::CompileFailure => write!(f, "compile-failure"),
+            KillReason::Timeout => write!(f, "timeout"),
+            KillReason::GasExhaustion => write!(f, "gas-exhaustion"),
+            KillReason::AbortCode => write!(f, "abort-code"),
+            KillReason::ViewOutput => write!(f, "view-output"),
+            KillReason::SoftKill => write!(f, "soft-kill"),
+        }
+    }
 }
 
 /// This struct represents a report single mutation test.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MiniReport {
     /// The original file name.
     pub original_file: PathBuf,
@@ -31,6 +149,26 @@ pub struct MiniReport {
     pub mutant_status: MutantStatus,
     /// A file difference that identifies mutants.
     pub diff: String,
+    /// Unique mutant identifier.
+    pub mutant_id: String,
+    /// Names of the mutation operators used to create the mutant.
+    pub operators: Vec<String>,
+    /// Names of the tests which killed the mutant (if known).
+    pub killed_by: Vec<String>,
+    /// The reason why the mutant has been killed (if known).
+    pub kill_reason: Option<KillReason>,
+    /// Whether the mutant touches recently changed lines.
+    pub new_code: bool,
+    /// Names of the tests which passed on the mutant (if known).
+    pub passed_tests: Vec<String>,
+    /// Time spent on evaluating the mutant.
+    pub duration: Duration,
+    /// Labels attached to the mutant by the configuration.
+    pub labels: Vec<String>,
+    /// The outcome of the repeated test runs (if the mutant is evaluated several times).
+    pub runs: Option<MutantRuns>,
+    /// The seed passed to the tests in the run which decided the outcome (if set).
+    pub seed: Option<u64>,
 }
 
 impl MiniReport {
@@ -46,29 +184,543 @@ impl MiniReport {
             qname,
             mutant_status,
             diff,
+            mutant_id: String::new(),
+            operators: vec![],
+            killed_by: vec![],
+            kill_reason: None,
+            new_code: false,
+            passed_tests: vec![],
+            duration: Duration::ZERO,
+            labels: vec![],
+            runs: None,
+            seed: None,
+        }
+    }
+
+    /// Set the mutant identifier and the operators used to create the mutant.
+    pub fn with_mutant_info(mut self, mutant_id: &str, operators: Vec<String>) -> Self {
+        self.mutant_id = mutant_id.to_owned();
+        self.operators = operators;
+        self
+    }
+
+    /// Set the names of the tests which killed the mutant.
+    pub fn with_killed_by(mut self, killed_by: Vec<String>) -> Self {
+        self.killed_by = killed_by;
+        self
+    }
+
+    /// Set the reason why the mutant has been killed.
+    pub fn with_kill_reason(mut self, kill_reason: KillReason) -> Self {
+        self.kill_reason = Some(kill_reason);
+        self
+    }
+
+    /// Set the names of the tests which passed on the mutant.
+    pub fn with_passed_tests(mut self, passed_tests: Vec<String>) -> Self {
+        self.passed_tests = passed_tests;
+        self
+    }
+
+    /// Set the time spent on evaluating the mutant.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set whether the mutant touches recently changed lines.
+    pub fn with_new_code(mut self, new_code: bool) -> Self {
+        self.new_code = new_code;
+        self
+    }
+
+    /// Set the labels attached to the mutant.
+    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Set the outcome of the repeated test runs.
+    pub fn with_runs(mut self, runs: MutantRuns) -> Self {
+        self.runs = Some(runs);
+        self
+    }
+
+    /// Set the seed passed to the tests.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+/// The outcome of a mutant evaluated several times (`--runs-per-mutant`), for tests whose
+/// outcome isn't deterministic.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MutantRuns {
+    /// The number of test runs.
+    pub runs: u32,
+    /// The number of runs in which the mutant has been killed.
+    pub killed: u32,
+}
+
+impl MutantRuns {
+    /// Returns the probability (in percent) that a single test run kills the mutant.
+    #[must_use]
+    pub fn kill_probability(&self) -> f64 {
+        percentage(self.killed, self.runs)
+    }
+
+    /// Checks whether the mutant is killed by the majority of the runs.
+    #[must_use]
+    pub fn is_killed(&self) -> bool {
+        self.killed * 2 > self.runs
+    }
+
+    /// Checks whether the outcome differs between the runs.
+    #[must_use]
+    pub fn is_uncertain(&self) -> bool {
+        self.killed > 0 && self.killed < self.runs
+    }
+}
+
+/// Detailed information about a single tested mutant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MutantRecord {
+    /// Unique mutant identifier.
+    pub id: String,
+    /// The original file name.
+    pub original_file: PathBuf,
+    /// Qualified name for the function using the 'module::function' syntax.
+    pub qname: String,
+    /// Names of the mutation operators used to create the mutant.
+    pub operators: Vec<String>,
+    /// Mutant status after testing it.
+    pub status: MutantStatus,
+    /// Names of the tests which killed the mutant (if known).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub killed_by: Vec<String>,
+    /// Names of the tests which execute the mutated code (if the coverage has been recorded).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub covered_by: Vec<String>,
+    /// The reason why the mutant has been killed (if known).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kill_reason: Option<KillReason>,
+    /// Whether the mutant touches recently changed lines.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub new_code: bool,
+    /// Owners of the mutated file (if known).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+    /// Labels attached to the mutant by the configuration.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Time spent on evaluating the mutant in milliseconds (if known).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub duration_ms: u64,
+    /// The outcome of the repeated test runs (if the mutant is evaluated several times).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runs: Option<MutantRuns>,
+    /// The seed passed to the tests in the run which decided the outcome (if set).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// A file difference that identifies the mutant.
+    pub diff: String,
+}
+
+impl From<&MiniReport> for MutantRecord {
+    fn from(mini_report: &MiniReport) -> Self {
+        Self {
+            id: mini_report.mutant_id.clone(),
+            original_file: mini_report.original_file.clone(),
+            qname: mini_report.qname.clone(),
+            operators: mini_report.operators.clone(),
+            status: mini_report.mutant_status,
+            killed_by: mini_report.killed_by.clone(),
+            covered_by: vec![],
+            kill_reason: mini_report.kill_reason,
+            new_code: mini_report.new_code,
+            owners: vec![],
+            labels: mini_report.labels.clone(),
+            duration_ms: u64::try_from(mini_report.duration.as_millis()).unwrap_or(u64::MAX),
+            runs: mini_report.runs,
+            seed: mini_report.seed,
+            diff: mini_report.diff.clone(),
         }
     }
 }
 
+/// A mutant which failed to compile, so it has not been tested.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StillbornRecord {
+    /// Unique mutant identifier.
+    pub id: String,
+    /// The original file name.
+    pub original_file: PathBuf,
+    /// Qualified name for the function using the 'module::function' syntax.
+    pub qname: String,
+    /// Names of the mutation operators used to create the mutant.
+    pub operators: Vec<String>,
+    /// The compiler diagnostics explaining why the mutant doesn't compile.
+    pub diagnostics: String,
+    /// A file difference that identifies the mutant.
+    pub diff: String,
+}
+
+/// A non-trivial function without any mutation site, so the mutation score doesn't assess it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UnassessedFunctionRecord {
+    /// The original file name.
+    pub original_file: PathBuf,
+    /// Qualified name for the function using the 'module::function' syntax.
+    pub qname: String,
+}
+
+/// The outcome of running the tests with a mutated package manifest.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ManifestMutantStatus {
+    /// The tests pass, so they don't depend on the mutated address value.
+    Independent,
+    /// Some tests fail, so they are coupled to the address literal.
+    Coupled,
+    /// The package doesn't build with the mutated manifest.
+    Invalid,
+}
+
+impl std::fmt::Display for ManifestMutantStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManifestMutantStatus::Independent => write!(f, "independent"),
+            ManifestMutantStatus::Coupled => write!(f, "coupled"),
+            ManifestMutantStatus::Invalid => write!(f, "invalid"),
+        }
+    }
+}
+
+/// A mutant of the package manifest (`Move.toml`), tested with the original code.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestMutantRecord {
+    /// The description of the mutated manifest entries.
+    pub description: String,
+    /// The outcome of the test run.
+    pub status: ManifestMutantStatus,
+    /// Names of the tests which failed with the mutated manifest.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_tests: Vec<String>,
+}
+
 /// This struct represents a report of the mutation and spec testing.
 ///
 /// It contains the list of entries, where each entry is a file and the number of mutants tested
 /// and killed in that file (in form of a `ReportEntry` structure).
-#[derive(Default, Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct Report {
+    /// The version of the report schema.
+    pub schema_version: u32,
     /// The list of entries in the report.
     pub files: BTreeMap<PathBuf, Vec<MutantStats>>,
     /// Package directory location.
     pub package_dir: PathBuf,
+    /// The backend which checked the mutants (if known). Reports from both tools share the same
+    /// schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<Backend>,
+    /// Detailed records of all tested mutants.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mutants: Vec<MutantRecord>,
+    /// Unit test coverage (percentage of covered instructions) per module, if computed.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub module_coverage: BTreeMap<String, f64>,
+    /// Path to the unit test coverage map collected during the baseline run, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_map: Option<PathBuf>,
+    /// Kill reasons which count towards the mutation score, if the tool supports the policy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kill_policy: Vec<KillReason>,
+    /// The number of generated mutants which didn't compile and were not tested.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub mutants_invalid: u32,
+    /// The number of generated mutants in code not covered by unit tests, which were not tested.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub mutants_uncovered: u32,
+    /// Names of all tests run on the mutants, if captured.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tests: Vec<String>,
+    /// Mutants which didn't compile, if recorded by the mutator (`keep-stillborn` option).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stillborn: Vec<StillbornRecord>,
+    /// Categories of mutants counted in the denominator of the mutation score, see
+    /// [`Report::score_breakdown`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub score_policy: Vec<ScoreCategory>,
+    /// Mutants of the package manifest, reported separately from the code mutants as they
+    /// don't count towards the mutation score.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub manifest_mutants: Vec<ManifestMutantRecord>,
+    /// Non-trivial functions without any mutation site, which the mutation score doesn't cover.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unassessed_functions: Vec<UnassessedFunctionRecord>,
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self::new(PathBuf::new())
+    }
 }
 
 impl Report {
     /// Creates a new report.
     pub fn new(package_dir: PathBuf) -> Self {
         Self {
+            schema_version: REPORT_SCHEMA_VERSION,
             files: BTreeMap::new(),
             package_dir,
+            backend: None,
+            mutants: vec![],
+            module_coverage: BTreeMap::new(),
+            coverage_map: None,
+            kill_policy: vec![],
+            mutants_invalid: 0,
+            mutants_uncovered: 0,
+            tests: vec![],
+            stillborn: vec![],
+            score_policy: vec![],
+            manifest_mutants: vec![],
+            unassessed_functions: vec![],
+        }
+    }
+
+    /// Records the backend which checked the mutants.
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = Some(backend);
+    }
+
+    /// Records the number of generated mutants which were not tested, as they didn't compile
+    /// (`invalid`) or their code isn't covered by unit tests (`uncovered`).
+    pub fn set_discarded_mutants(&mut self, invalid: u32, uncovered: u32) {
+        self.mutants_invalid = invalid;
+        self.mutants_uncovered = uncovered;
+    }
+
+    /// Returns the mutation score computed over different sets of mutants.
+    ///
+    /// Mutants in uncovered code are not compiled, so they are considered viable. Skipped mutants
+    /// count as uncovered.
+    pub fn mutation_scores(&self) -> MutationScores {
+        let killed = self.mutants_killed();
+        let tested = self.mutants_tested();
+        let uncovered = self.mutants_uncovered + self.mutants_skipped();
+        MutationScores {
+            all_generated: percentage(killed, tested + self.mutants_invalid + uncovered),
+            viable: percentage(killed, tested + uncovered),
+            covered: percentage(killed, tested),
+        }
+    }
+
+    /// Returns the number of mutants which were skipped, as their functions have no effective
+    /// coverage.
+    pub fn mutants_skipped(&self) -> u32 {
+        self.count_status(MutantStatus::Skipped)
+    }
+
+    /// Returns the number of mutants which only changed the abort codes of the tests expecting a
+    /// failure, whether they count as killed or are excluded by the kill policy.
+    pub fn mutants_soft_killed(&self) -> u32 {
+        let count = self
+            .mutants
+            .iter()
+            .filter(|m| m.kill_reason == Some(KillReason::SoftKill))
+            .count();
+        u32::try_from(count).unwrap_or(u32::MAX)
+    }
+
+    /// Returns the number of mutant records with the given status.
+    fn count_status(&self, status: MutantStatus) -> u32 {
+        let count = self.mutants.iter().filter(|m| m.status == status).count();
+        u32::try_from(count).unwrap_or(u32::MAX)
+    }
+
+    /// Records the categories of mutants counted in the denominator of the mutation score.
+    pub fn set_score_policy(&mut self, score_policy: Vec<ScoreCategory>) {
+        self.score_policy = score_policy;
+    }
+
+    /// Returns the mutation score according to the score policy, with the numbers of mutants in
+    /// every category.
+    ///
+    /// Only the evaluated mutants (killed or alive) and the categories included by the policy
+    /// count in the denominator. Mutants excluded by the kill policy never count.
+    pub fn score_breakdown(&self) -> ScoreBreakdown {
+        let killed = self.mutants_killed();
+        let categories = [
+            (
+                ScoreCategory::Suppressed,
+                self.count_status(MutantStatus::Suppressed),
+            ),
+            (
+                ScoreCategory::Equivalent,
+                self.count_status(MutantStatus::Equivalent),
+            ),
+            (
+                ScoreCategory::Unreachable,
+                self.mutants_uncovered + self.mutants_skipped(),
+            ),
+            (ScoreCategory::Stillborn, self.mutants_invalid),
+        ];
+        ScoreBreakdown {
+            killed,
+            alive: self.mutants_tested() - killed,
+            excluded: self.count_status(MutantStatus::Excluded),
+            categories: categories
+                .into_iter()
+                .map(|(category, count)| (category, count, self.score_policy.contains(&category)))
+                .collect(),
+        }
+    }
+
+    /// Records the kill policy used to compute the mutation score.
+    pub fn set_kill_policy(&mut self, kill_policy: Vec<KillReason>) {
+        self.kill_policy = kill_policy;
+    }
+
+    /// Records the names of the tests run on the mutants.
+    pub fn set_tests(&mut self, tests: impl IntoIterator<Item = String>) {
+        let tests = tests.into_iter().collect::<BTreeSet<_>>();
+        self.tests = tests.into_iter().collect();
+    }
+
+    /// Sets the unit test coverage per module, which is shown side by side with the mutation score.
+    pub fn set_module_coverage(&mut self, module_coverage: BTreeMap<String, f64>) {
+        self.module_coverage = module_coverage;
+    }
+
+    /// Records the path to the coverage map, so it can be reused by other tools (e.g.
+    /// `aptos move coverage`).
+    pub fn set_coverage_map(&mut self, coverage_map: PathBuf) {
+        self.coverage_map = Some(coverage_map);
+    }
+
+    /// Returns the mutation score (percentage of killed mutants) per module.
+    pub fn module_mutation_scores(&self) -> BTreeMap<String, f64> {
+        let mut counts = BTreeMap::<String, (u32, u32)>::new();
+        for stat in self.files.values().flatten() {
+            let (tested, killed) = counts.entry(stat.get_module_name()).or_default();
+            *tested += stat.tested;
+            *killed += stat.killed;
+        }
+
+        counts
+            .into_iter()
+            .map(|(module, (tested, killed))| (module, percentage(killed, tested)))
+            .collect()
+    }
+
+    /// Adds a detailed record of the tested mutant.
+    pub fn add_mutant_record(&mut self, record: MutantRecord) {
+        self.mutants.push(record);
+    }
+
+    /// Attributes the mutant records to the owners of the mutated files.
+    pub fn assign_owners(&mut self, codeowners: &CodeOwners) {
+        for mutant in &mut self.mutants {
+            mutant.owners = codeowners
+                .owners_of(&self.package_dir.join(&mutant.original_file))
+                .to_vec();
+        }
+    }
+
+    /// Returns the number of surviving mutants per owner.
+    /// Mutants of files without owners are counted under the `(unowned)` key.
+    pub fn survivors_by_owner(&self) -> BTreeMap<String, u32> {
+        let mut survivors = BTreeMap::new();
+        for mutant in &self.mutants {
+            if mutant.status != MutantStatus::Alive {
+                continue;
+            }
+            if mutant.owners.is_empty() {
+                *survivors.entry(UNOWNED.to_owned()).or_default() += 1;
+            }
+            for owner in &mutant.owners {
+                *survivors.entry(owner.clone()).or_default() += 1;
+            }
+        }
+        survivors
+    }
+
+    /// Returns the number of tested and killed mutants per label.
+    pub fn label_stats(&self) -> BTreeMap<String, (u32, u32)> {
+        let mut stats = BTreeMap::<String, (u32, u32)>::new();
+        for mutant in &self.mutants {
+            if !mutant.status.is_scored() {
+                continue;
+            }
+            for label in &mutant.labels {
+                let (tested, killed) = stats.entry(label.clone()).or_default();
+                *tested += 1;
+                *killed += u32::from(mutant.status == MutantStatus::Killed);
+            }
+        }
+        stats
+    }
+
+    /// Returns the 50th, 90th and 99th percentiles of the mutant evaluation durations (in
+    /// milliseconds), if the durations are known.
+    pub fn duration_percentiles(&self) -> Option<DurationPercentiles> {
+        let mut durations = self
+            .mutants
+            .iter()
+            .map(|m| m.duration_ms)
+            .filter(|d| *d > 0)
+            .collect::<Vec<_>>();
+        if durations.is_empty() {
+            return None;
         }
+        durations.sort_unstable();
+
+        // Nearest-rank method.
+        let percentile = |p: usize| durations[(durations.len() * p).div_ceil(100).max(1) - 1];
+        Some(DurationPercentiles {
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+        })
+    }
+
+    /// Returns the mutants which took the longest to evaluate, the slowest first.
+    pub fn slowest_mutants(&self, count: usize) -> Vec<&MutantRecord> {
+        let mut mutants = self
+            .mutants
+            .iter()
+            .filter(|m| m.duration_ms > 0)
+            .collect::<Vec<_>>();
+        mutants.sort_by(|a, b| b.duration_ms.cmp(&a.duration_ms));
+        mutants.truncate(count);
+        mutants
+    }
+
+    /// Finds the mutant record with the given identifier.
+    pub fn find_mutant(&self, id: &str) -> Option<&MutantRecord> {
+        self.mutants.iter().find(|m| m.id == id)
+    }
+
+    /// Adds the record of a tested manifest mutant.
+    pub fn add_manifest_mutant_record(&mut self, record: ManifestMutantRecord) {
+        self.manifest_mutants.push(record);
+    }
+
+    /// Adds the record of a mutant which didn't compile.
+    pub fn add_stillborn_record(&mut self, record: StillbornRecord) {
+        self.stillborn.push(record);
+    }
+
+    /// Adds the record of a function without any mutation site.
+    pub fn add_unassessed_function_record(&mut self, record: UnassessedFunctionRecord) {
+        self.unassessed_functions.push(record);
+    }
+
+    /// Finds the record of the mutant which didn't compile with the given identifier.
+    pub fn find_stillborn(&self, id: &str) -> Option<&StillbornRecord> {
+        self.stillborn.iter().find(|m| m.id == id)
     }
 
     /// Increments the number of mutants tested for the given path by 1.
@@ -140,10 +792,58 @@ impl Report {
         Self::load_from_str(report)
     }
 
+    /// Save the report to a compact binary (CBOR) file.
+    ///
+    /// The binary format is much smaller and faster to process than JSON for huge runs.
+    pub fn save_to_cbor_file(&self, path: &Path) -> anyhow::Result<()> {
+        let file = io::BufWriter::new(fs::File::create(path)?);
+        ciborium::into_writer(self, file)
+            .map_err(|e| anyhow::Error::msg(format!("failed to save the report: {e}")))
+    }
+
+    /// Save the report to a file in the given format.
+    pub fn save_to_file(&self, path: &Path, format: ReportFormat) -> anyhow::Result<()> {
+        match format {
+            ReportFormat::Json => self.save_to_json_file(path),
+            ReportFormat::Cbor => self.save_to_cbor_file(path),
+        }
+    }
+
+    /// Load the report from a file in any supported format.
+    ///
+    /// The format is detected from the content - JSON reports always start with an object.
+    pub fn load_from_file(path: &Path) -> anyhow::Result<Self> {
+        Ok(Self::load_and_migrate(path)?.0)
+    }
+
+    /// Load the report from a file in any supported format, upgrading it to the current schema
+    /// version. Returns the report, its format and the original schema version.
+    pub fn load_and_migrate(path: &Path) -> anyhow::Result<(Self, ReportFormat, u32)> {
+        let report = fs::read(path)?;
+        let format = ReportFormat::detect(&report);
+        let value = match format {
+            ReportFormat::Json => serde_json::from_slice::<Value>(&report)?,
+            ReportFormat::Cbor => ciborium::from_reader(report.as_slice())
+                .map_err(|e| anyhow::Error::msg(format!("failed to parse the report: {e}")))?,
+        };
+        let (report, version) = Self::from_value(value)?;
+        Ok((report, format, version))
+    }
+
     /// Load the report from a string.
     pub fn load_from_str<P: AsRef<str>>(report: P) -> anyhow::Result<Self> {
-        serde_json::from_str::<Report>(report.as_ref())
-            .map_err(|e| anyhow::Error::msg(format!("failed to parse the report: {e}")))
+        let value = serde_json::from_str::<Value>(report.as_ref())
+            .map_err(|e| anyhow::Error::msg(format!("failed to parse the report: {e}")))?;
+        Ok(Self::from_value(value)?.0)
+    }
+
+    /// Creates the report from its JSON form, upgrading it to the current schema version.
+    /// Returns the report and its original schema version.
+    fn from_value(mut value: Value) -> anyhow::Result<(Self, u32)> {
+        let version = migrate_report(&mut value)?;
+        let report = serde_json::from_value(value)
+            .map_err(|e| anyhow::Error::msg(format!("failed to parse the report: {e}")))?;
+        Ok((report, version))
     }
 
     /// Get package directory.
@@ -152,23 +852,18 @@ impl Report {
     }
 
     /// Prints the report to stdout in a table format.
-    pub fn print_table(&self) {
+    /// Numbers are formatted according to the given locale.
+    pub fn print_table(&self, locale: &Locale) {
         let mut builder = Builder::new();
         builder.push_record(["Module", "Mutants tested", "Mutants killed", "Percentage"]);
 
         for (path, stats) in &self.files {
             for stat in stats {
-                let percentage = if stat.tested == 0 {
-                    0.0
-                } else {
-                    f64::from(stat.killed) / f64::from(stat.tested) * 100.0
-                };
-
                 builder.push_record([
                     format!("{}::{}", path.display(), stat.module_func),
-                    stat.tested.to_string(),
-                    stat.killed.to_string(),
-                    format!("{percentage:.2}%"),
+                    locale.format_count(stat.tested),
+                    locale.format_count(stat.killed),
+                    locale.format_percentage(percentage(stat.killed, stat.tested)),
                 ]);
             }
         }
@@ -176,8 +871,259 @@ impl Report {
         let table = builder.build().with(Style::modern_rounded()).to_string();
 
         println!("{table}");
-        println!("Total mutants tested: {}", self.mutants_tested());
-        println!("Total mutants killed: {}", self.mutants_killed());
+        println!(
+            "Total mutants tested: {}",
+            locale.format_count(self.mutants_tested())
+        );
+        println!(
+            "Total mutants killed: {}",
+            locale.format_count(self.mutants_killed())
+        );
+        let soft_killed = self.mutants_soft_killed();
+        if soft_killed > 0 {
+            println!(
+                "Total mutants soft-killed (only the abort codes changed): {}",
+                locale.format_count(soft_killed)
+            );
+        }
+        if self.mutants_invalid > 0 {
+            println!(
+                "Total mutants invalid (not compiling): {}",
+                locale.format_count(self.mutants_invalid)
+            );
+        }
+        if self.mutants_uncovered > 0 {
+            println!(
+                "Total mutants uncovered by tests: {}",
+                locale.format_count(self.mutants_uncovered)
+            );
+        }
+        let skipped = self.mutants_skipped();
+        if skipped > 0 {
+            println!(
+                "Total mutants skipped (no effective coverage): {}",
+                locale.format_count(skipped)
+            );
+        }
+        self.score_breakdown().print(locale);
+        let scores = self.mutation_scores();
+        println!(
+            "Mutation score (all generated): {}",
+            locale.format_percentage(scores.all_generated)
+        );
+        println!(
+            "Mutation score (viable): {}",
+            locale.format_percentage(scores.viable)
+        );
+        println!(
+            "Mutation score (covered by tests): {}",
+            locale.format_percentage(scores.covered)
+        );
+        if !self.kill_policy.is_empty() {
+            let policy = self
+                .kill_policy
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            println!("Kill policy: {}", policy.join(", "));
+        }
+        let new_code = self
+            .mutants
+            .iter()
+            .filter(|m| m.new_code && m.status.is_scored())
+            .collect::<Vec<_>>();
+        if !new_code.is_empty() {
+            let alive = new_code
+                .iter()
+                .filter(|m| m.status == MutantStatus::Alive)
+                .count();
+            println!(
+                "Mutants in new code: {} tested, {} alive",
+                locale.format_count(u32::try_from(new_code.len()).unwrap_or(u32::MAX)),
+                locale.format_count(u32::try_from(alive).unwrap_or(u32::MAX))
+            );
+        }
+        println!(); // Empty line before the end
+
+        if !self.module_coverage.is_empty() {
+            self.print_coverage_table(locale);
+        }
+
+        if self.mutants.iter().any(|m| !m.labels.is_empty()) {
+            self.print_labels_table(locale);
+        }
+
+        if self.mutants.iter().any(|m| !m.owners.is_empty()) {
+            self.print_owners_table(locale);
+        }
+
+        if let Some(percentiles) = self.duration_percentiles() {
+            self.print_durations(&percentiles, locale);
+        }
+
+        if self
+            .mutants
+            .iter()
+            .any(|m| m.runs.is_some_and(|runs| runs.is_uncertain()))
+        {
+            self.print_uncertain_table(locale);
+        }
+
+        let error_paths = error_paths(self);
+        if error_paths.iter().any(|path| !path.exercised) {
+            print_unexercised_error_paths(&error_paths);
+        }
+
+        if !self.manifest_mutants.is_empty() {
+            self.print_manifest_table();
+        }
+
+        if !self.unassessed_functions.is_empty() {
+            self.print_unassessed_table();
+        }
+    }
+
+    /// Prints the mutants whose outcome differs between the repeated test runs, with the
+    /// probability that a single run kills them.
+    pub fn print_uncertain_table(&self, locale: &Locale) {
+        let mut builder = Builder::new();
+        builder.push_record(["Uncertain mutant", "Location", "Runs", "Kill probability"]);
+
+        for mutant in &self.mutants {
+            let Some(runs) = mutant.runs.filter(MutantRuns::is_uncertain) else {
+                continue;
+            };
+            builder.push_record([
+                mutant.id.clone(),
+                format!("{}::{}", mutant.original_file.display(), mutant.qname),
+                locale.format_count(runs.runs),
+                locale.format_percentage(runs.kill_probability()),
+            ]);
+        }
+
+        let table = builder.build().with(Style::modern_rounded()).to_string();
+        println!("{table}");
+        println!(); // Empty line before the end
+    }
+
+    /// Prints the percentiles of the mutant evaluation durations and the slowest mutants, which
+    /// helps to choose timeouts and reveals pathological tests.
+    pub fn print_durations(&self, percentiles: &DurationPercentiles, locale: &Locale) {
+        let format_ms = |ms: u64| {
+            format!(
+                "{} ms",
+                locale.format_count(u32::try_from(ms).unwrap_or(u32::MAX))
+            )
+        };
+        println!(
+            "Mutant evaluation time: p50 {}, p90 {}, p99 {}",
+            format_ms(percentiles.p50),
+            format_ms(percentiles.p90),
+            format_ms(percentiles.p99)
+        );
+
+        let mut builder = Builder::new();
+        builder.push_record(["Slowest mutants", "Location", "Time"]);
+        for mutant in self.slowest_mutants(SLOWEST_MUTANTS) {
+            builder.push_record([
+                mutant.id.clone(),
+                format!("{}::{}", mutant.original_file.display(), mutant.qname),
+                format_ms(mutant.duration_ms),
+            ]);
+        }
+
+        let table = builder.build().with(Style::modern_rounded()).to_string();
+        println!("{table}");
+        println!(); // Empty line before the end
+    }
+
+    /// Prints the mutation score per label attached by the configuration.
+    pub fn print_labels_table(&self, locale: &Locale) {
+        let mut builder = Builder::new();
+        builder.push_record(["Label", "Mutants tested", "Mutants killed", "Percentage"]);
+
+        for (label, (tested, killed)) in self.label_stats() {
+            builder.push_record([
+                label,
+                locale.format_count(tested),
+                locale.format_count(killed),
+                locale.format_percentage(percentage(killed, tested)),
+            ]);
+        }
+
+        let table = builder.build().with(Style::modern_rounded()).to_string();
+        println!("{table}");
+        println!(); // Empty line before the end
+    }
+
+    /// Prints the outcome of the manifest mutants. Coupled mutants show tests depending on the
+    /// specific address values.
+    pub fn print_manifest_table(&self) {
+        let mut builder = Builder::new();
+        builder.push_record(["Manifest mutant", "Status", "Failed tests"]);
+
+        for mutant in &self.manifest_mutants {
+            builder.push_record([
+                mutant.description.clone(),
+                mutant.status.to_string(),
+                mutant.failed_tests.join(", "),
+            ]);
+        }
+
+        let table = builder.build().with(Style::modern_rounded()).to_string();
+        println!("{table}");
+        println!(); // Empty line before the end
+    }
+
+    /// Prints the non-trivial functions without any mutation site. The mutation score says nothing
+    /// about how well the tests check them.
+    pub fn print_unassessed_table(&self) {
+        let mut builder = Builder::new();
+        builder.push_record(["Unassessed function", "File"]);
+
+        for function in &self.unassessed_functions {
+            builder.push_record([
+                function.qname.clone(),
+                function.original_file.display().to_string(),
+            ]);
+        }
+
+        let table = builder.build().with(Style::modern_rounded()).to_string();
+        println!("{table}");
+        println!(); // Empty line before the end
+    }
+
+    /// Prints the number of surviving mutants per owner, so follow-up work can be routed to the
+    /// owning teams.
+    pub fn print_owners_table(&self, locale: &Locale) {
+        let mut builder = Builder::new();
+        builder.push_record(["Owner", "Mutants alive"]);
+
+        for (owner, alive) in self.survivors_by_owner() {
+            builder.push_record([owner, locale.format_count(alive)]);
+        }
+
+        let table = builder.build().with(Style::modern_rounded()).to_string();
+        println!("{table}");
+        println!(); // Empty line before the end
+    }
+
+    /// Prints the unit test coverage side by side with the mutation score per module.
+    /// High coverage with low mutation score shows that tests execute the code without checking it.
+    pub fn print_coverage_table(&self, locale: &Locale) {
+        let mut builder = Builder::new();
+        builder.push_record(["Module", "Test coverage", "Mutation score"]);
+
+        for (module, score) in self.module_mutation_scores() {
+            let coverage = self
+                .module_coverage
+                .get(&module)
+                .map_or_else(|| "-".to_owned(), |c| locale.format_percentage(*c));
+            builder.push_record([module, coverage, locale.format_percentage(score)]);
+        }
+
+        let table = builder.build().with(Style::modern_rounded()).to_string();
+        println!("{table}");
         println!(); // Empty line before the end
     }
 
@@ -216,6 +1162,182 @@ impl Report {
     }
 }
 
+/// The file format of the report.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Human-readable JSON.
+    #[default]
+    Json,
+    /// Compact binary format (CBOR), suitable for runs with a huge number of mutants.
+    Cbor,
+}
+
+impl ReportFormat {
+    /// Detects the format of the serialized report.
+    fn detect(report: &[u8]) -> Self {
+        match report.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{') => ReportFormat::Json,
+            _ => ReportFormat::Cbor,
+        }
+    }
+}
+
+/// Upgrades the report in the JSON form to the current schema version.
+///
+/// Returns the original schema version of the report.
+///
+/// # Errors
+///
+/// Returns an error if the report isn't a JSON object or it was created by a newer version of the
+/// tool.
+pub fn migrate_report(report: &mut Value) -> anyhow::Result<u32> {
+    let Value::Object(report) = report else {
+        anyhow::bail!("failed to parse the report: the report is not an object");
+    };
+
+    let version = match report.get("schema_version") {
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| anyhow::anyhow!("invalid report schema version: {version}"))?,
+        None => LEGACY_SCHEMA_VERSION,
+    };
+    if version < LEGACY_SCHEMA_VERSION {
+        anyhow::bail!("invalid report schema version: {version}");
+    }
+    if version > REPORT_SCHEMA_VERSION {
+        anyhow::bail!(
+            "the report has schema version {version}, but only versions up to \
+             {REPORT_SCHEMA_VERSION} are supported, please update the tool"
+        );
+    }
+
+    for migration in MIGRATIONS
+        .iter()
+        .skip((version - LEGACY_SCHEMA_VERSION) as usize)
+    {
+        migration(report);
+    }
+    report.insert("schema_version".to_owned(), REPORT_SCHEMA_VERSION.into());
+    Ok(version)
+}
+
+/// The version 2 records the backend. Reports before the version 2 were produced by both tools,
+/// so the backend of the older reports is left unknown.
+fn migrate_v1_to_v2(_report: &mut Map<String, Value>) {}
+
+/// The mutation score computed over different denominators, since teams gate on different
+/// definitions of the score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MutationScores {
+    /// Killed mutants out of all generated mutants.
+    pub all_generated: f64,
+    /// Killed mutants out of all viable (compiling) mutants.
+    pub viable: f64,
+    /// Killed mutants out of mutants in code covered by tests.
+    pub covered: f64,
+}
+
+/// The mutation score according to the score policy, with the numbers of mutants in every
+/// category, so the score can be verified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreBreakdown {
+    /// The number of killed mutants.
+    pub killed: u32,
+    /// The number of surviving mutants.
+    pub alive: u32,
+    /// The number of mutants excluded by the kill policy, which never count.
+    pub excluded: u32,
+    /// The number of mutants in every category and whether the category counts in the score.
+    pub categories: Vec<(ScoreCategory, u32, bool)>,
+}
+
+impl ScoreBreakdown {
+    /// Returns the denominator of the score: the evaluated mutants and the counted categories.
+    pub fn counted(&self) -> u32 {
+        self.killed
+            + self.alive
+            + self
+                .categories
+                .iter()
+                .filter(|(_, _, counted)| *counted)
+                .map(|(_, count, _)| count)
+                .sum::<u32>()
+    }
+
+    /// Returns the mutation score (percentage of killed mutants out of the counted ones).
+    pub fn score(&self) -> f64 {
+        percentage(self.killed, self.counted())
+    }
+
+    /// Describes the counted (or not counted) mutants, e.g. `30 killed, 10 alive, 2 stillborn`.
+    pub fn describe(&self, counted: bool, locale: &Locale) -> String {
+        let mut parts = vec![];
+        if counted {
+            parts.push(format!("{} killed", locale.format_count(self.killed)));
+            parts.push(format!("{} alive", locale.format_count(self.alive)));
+        }
+        for (category, count, is_counted) in &self.categories {
+            if *is_counted == counted && *count > 0 {
+                parts.push(format!("{} {category}", locale.format_count(*count)));
+            }
+        }
+        if !counted && self.excluded > 0 {
+            parts.push(format!(
+                "{} excluded by the kill policy",
+                locale.format_count(self.excluded)
+            ));
+        }
+        if parts.is_empty() {
+            "none".to_owned()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// Prints the score with the breakdown of the counted and not counted mutants.
+    pub fn print(&self, locale: &Locale) {
+        println!(
+            "Mutation score: {} ({} killed out of {} counted mutants)",
+            locale.format_percentage(self.score()),
+            locale.format_count(self.killed),
+            locale.format_count(self.counted())
+        );
+        println!("  counted:     {}", self.describe(true, locale));
+        println!("  not counted: {}", self.describe(false, locale));
+    }
+}
+
+/// Percentiles of the mutant evaluation durations in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurationPercentiles {
+    /// The median.
+    pub p50: u64,
+    /// The 90th percentile.
+    pub p90: u64,
+    /// The 99th percentile.
+    pub p99: u64,
+}
+
+/// Used to skip serialization of the zero counters.
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Used to skip serialization of the unset flags.
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Returns the percentage of killed mutants.
+fn percentage(killed: u32, tested: u32) -> f64 {
+    if tested == 0 {
+        0.0
+    } else {
+        f64::from(killed) / f64::from(tested) * 100.0
+    }
+}
+
 /// This struct represents an entry in the report.
 /// It contains the number of mutants tested and killed.
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Clone)]
@@ -262,6 +1384,40 @@ mod tests {
         assert_eq!(report.entries().len(), 0);
     }
 
+    #[test]
+    fn backend_is_stored_in_the_report() {
+        let mut report = Report::new("package_dir".into());
+        report.set_backend(Backend::Prover);
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""backend":"prover""#));
+        assert_eq!(
+            Report::load_from_str(json).unwrap().backend,
+            Some(Backend::Prover)
+        );
+
+        // The backend is not made up for reports which don't record it.
+        let json = r#"{"files":{},"package_dir":"package_dir"}"#;
+        assert_eq!(Report::load_from_str(json).unwrap().backend, None);
+        let json = r#"{"schema_version":2,"files":{},"package_dir":"package_dir"}"#;
+        assert_eq!(Report::load_from_str(json).unwrap().backend, None);
+    }
+
+    #[test]
+    fn reports_are_migrated_to_the_current_schema() {
+        let mut legacy = serde_json::json!({ "files": {}, "package_dir": "package_dir" });
+        assert_eq!(migrate_report(&mut legacy).unwrap(), LEGACY_SCHEMA_VERSION);
+        assert_eq!(legacy["schema_version"], REPORT_SCHEMA_VERSION);
+        assert!(legacy.get("backend").is_none());
+
+        let mut current = serde_json::to_value(Report::new("package_dir".into())).unwrap();
+        assert_eq!(migrate_report(&mut current).unwrap(), REPORT_SCHEMA_VERSION);
+
+        let mut newer = serde_json::json!({ "schema_version": REPORT_SCHEMA_VERSION + 1 });
+        assert!(migrate_report(&mut newer).is_err());
+        let mut invalid = serde_json::json!({ "schema_version": 0 });
+        assert!(migrate_report(&mut invalid).is_err());
+    }
+
     #[test]
     fn increment_mutants_tested_adds_new_module_if_not_present() {
         let mut report = Report::new("package_dir".into());
@@ -359,4 +1515,251 @@ mod tests {
         let stat = entry.iter().find(|s| s.module_func == module_name).unwrap();
         assert_eq!(stat.mutants_alive_diffs, vec![diff1, diff2]);
     }
+
+    #[test]
+    fn module_mutation_scores_are_aggregated_per_module() {
+        let mut report = Report::new("package_dir".into());
+        let path = PathBuf::from("path/to/file");
+        report.increment_mutants_tested(&path, "module::f1");
+        report.increment_mutants_tested(&path, "module::f2");
+        report.increment_mutants_killed(&path, "module::f2");
+        report.increment_mutants_tested(&path, "other::f1");
+
+        let scores = report.module_mutation_scores();
+        assert_eq!(scores.get("module"), Some(&50.0));
+        assert_eq!(scores.get("other"), Some(&0.0));
+    }
+
+    #[test]
+    fn mutation_scores_use_different_denominators() {
+        let mut report = Report::new("package_dir".into());
+        let path = PathBuf::from("path/to/file");
+        for _ in 0..4 {
+            report.increment_mutants_tested(&path, "module::func");
+        }
+        report.increment_mutants_killed(&path, "module::func");
+        report.increment_mutants_killed(&path, "module::func");
+        report.set_discarded_mutants(2, 2);
+
+        assert_eq!(report.mutation_scores(), MutationScores {
+            all_generated: 25.0,
+            viable: 2.0 / 6.0 * 100.0,
+            covered: 50.0,
+        });
+    }
+
+    #[test]
+    fn score_breakdown_follows_the_score_policy() {
+        let mut report = Report::new("package_dir".into());
+        let path = PathBuf::from("path/to/file");
+        for _ in 0..4 {
+            report.increment_mutants_tested(&path, "module::func");
+        }
+        report.increment_mutants_killed(&path, "module::func");
+        report.increment_mutants_killed(&path, "module::func");
+        report.set_discarded_mutants(3, 1);
+        for status in [
+            MutantStatus::Suppressed,
+            MutantStatus::Equivalent,
+            MutantStatus::Skipped,
+            MutantStatus::Excluded,
+        ] {
+            let mini_report =
+                MiniReport::new(path.clone(), "module::func".into(), status, "".into());
+            report.add_mutant_record(MutantRecord::from(&mini_report));
+        }
+
+        // Nothing but the evaluated mutants counts by default.
+        let breakdown = report.score_breakdown();
+        assert_eq!(breakdown.counted(), 4);
+        assert_eq!(breakdown.score(), 50.0);
+        let locale = Locale::default();
+        assert_eq!(breakdown.describe(true, &locale), "2 killed, 2 alive");
+        assert_eq!(
+            breakdown.describe(false, &locale),
+            "1 suppressed, 1 equivalent, 2 unreachable, 3 stillborn, 1 excluded by the kill policy"
+        );
+
+        report.set_score_policy(vec![ScoreCategory::Unreachable, ScoreCategory::Stillborn]);
+        let breakdown = report.score_breakdown();
+        assert_eq!(breakdown.counted(), 9);
+        assert_eq!(
+            breakdown.describe(true, &locale),
+            "2 killed, 2 alive, 2 unreachable, 3 stillborn"
+        );
+    }
+
+    #[test]
+    fn cbor_report_round_trip() {
+        let mut report = Report::new("package_dir".into());
+        let path = PathBuf::from("path/to/file");
+        report.increment_mutants_tested(&path, "module::func");
+        report.increment_mutants_killed(&path, "module::func");
+        report.add_mutants_killed_diff(&path, "module::func", "diff");
+        report.set_module_coverage([("module".to_owned(), 75.0)].into());
+
+        let dir = tempfile::tempdir().unwrap();
+        let cbor_path = dir.path().join("report.cbor");
+        let json_path = dir.path().join("report.json");
+        report.save_to_file(&cbor_path, ReportFormat::Cbor).unwrap();
+        report.save_to_file(&json_path, ReportFormat::Json).unwrap();
+
+        let cbor_report = Report::load_from_file(&cbor_path).unwrap();
+        let json_report = Report::load_from_file(&json_path).unwrap();
+        assert_eq!(cbor_report, report);
+        assert_eq!(json_report, report);
+        assert!(fs::metadata(&cbor_path).unwrap().len() < fs::metadata(&json_path).unwrap().len());
+    }
+
+    #[test]
+    fn find_mutant_returns_added_record() {
+        let mut report = Report::new("package_dir".into());
+        let mini_report = MiniReport::new(
+            PathBuf::from("path/to/file"),
+            "module::function".to_owned(),
+            MutantStatus::Alive,
+            "diff".to_owned(),
+        )
+        .with_mutant_info("abc123", vec!["literal_replacement".to_owned()]);
+        report.add_mutant_record(MutantRecord::from(&mini_report));
+
+        let record = report.find_mutant("abc123").unwrap();
+        assert_eq!(record.qname, "module::function");
+        assert_eq!(record.status, MutantStatus::Alive);
+        assert!(report.find_mutant("def456").is_none());
+    }
+
+    #[test]
+    fn soft_kills_are_counted_regardless_of_the_kill_policy() {
+        let mut report = Report::new("package_dir".into());
+        for (status, reason) in [
+            (MutantStatus::Killed, KillReason::SoftKill),
+            (MutantStatus::Excluded, KillReason::SoftKill),
+            (MutantStatus::Killed, KillReason::TestFailure),
+        ] {
+            let mini_report = MiniReport::new(
+                PathBuf::from("path/to/file"),
+                "module::function".to_owned(),
+                status,
+                "diff".to_owned(),
+            )
+            .with_kill_reason(reason);
+            report.add_mutant_record(MutantRecord::from(&mini_report));
+        }
+        assert_eq!(report.mutants_soft_killed(), 2);
+        assert_eq!(KillReason::SoftKill.to_string(), "soft-kill");
+    }
+
+    #[test]
+    fn survivors_are_attributed_to_owners() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        fs::create_dir(root.join(".github")).unwrap();
+        let codeowners_path = root.join(".github/CODEOWNERS");
+        fs::write(
+            &codeowners_path,
+            "/pkg/sources/coin/ @org/tokens
+",
+        )
+        .unwrap();
+        let codeowners = CodeOwners::from_file(&codeowners_path).unwrap();
+
+        let mut report = Report::new(root.join("pkg"));
+        for (id, file, status) in [
+            ("a", "sources/coin/Coin.move", MutantStatus::Alive),
+            ("b", "sources/coin/Coin.move", MutantStatus::Killed),
+            ("c", "sources/Sum.move", MutantStatus::Alive),
+        ] {
+            let mini_report = MiniReport::new(
+                PathBuf::from(file),
+                "m::f".to_owned(),
+                status,
+                String::new(),
+            )
+            .with_mutant_info(id, vec![]);
+            report.add_mutant_record(MutantRecord::from(&mini_report));
+        }
+        report.assign_owners(&codeowners);
+
+        assert_eq!(report.find_mutant("b").unwrap().owners, ["@org/tokens"]);
+        assert_eq!(
+            report.survivors_by_owner(),
+            [("@org/tokens".to_owned(), 1), (UNOWNED.to_owned(), 1)].into()
+        );
+    }
+
+    #[test]
+    fn mutants_are_grouped_by_labels() {
+        let mut report = Report::new("package_dir".into());
+        for (status, labels) in [
+            (MutantStatus::Killed, vec!["critical-path", "arithmetic"]),
+            (MutantStatus::Alive, vec!["critical-path"]),
+            (MutantStatus::Excluded, vec!["critical-path"]),
+            (MutantStatus::Alive, vec![]),
+        ] {
+            let mini_report = MiniReport::new(
+                PathBuf::from("sources/Sum.move"),
+                "Sum::sum".to_owned(),
+                status,
+                String::new(),
+            )
+            .with_labels(labels.into_iter().map(str::to_owned).collect());
+            report.add_mutant_record(MutantRecord::from(&mini_report));
+        }
+
+        assert_eq!(
+            report.label_stats(),
+            [
+                ("arithmetic".to_owned(), (1, 1)),
+                ("critical-path".to_owned(), (2, 1))
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn repeated_runs_give_kill_probability() {
+        let runs = MutantRuns { runs: 4, killed: 3 };
+        assert!(runs.is_killed());
+        assert!(runs.is_uncertain());
+        assert_eq!(runs.kill_probability(), 75.0);
+
+        // A tie doesn't kill the mutant.
+        assert!(!MutantRuns { runs: 4, killed: 2 }.is_killed());
+        assert!(!MutantRuns { runs: 4, killed: 4 }.is_uncertain());
+        assert!(!MutantRuns { runs: 4, killed: 0 }.is_uncertain());
+    }
+
+    #[test]
+    fn duration_percentiles_and_slowest_mutants() {
+        let mut report = Report::new("package_dir".into());
+        assert_eq!(report.duration_percentiles(), None);
+
+        for ms in 1..=100 {
+            let mini_report = MiniReport::new(
+                PathBuf::from("sources/Sum.move"),
+                "Sum::sum".to_owned(),
+                MutantStatus::Killed,
+                String::new(),
+            )
+            .with_mutant_info(&ms.to_string(), vec![])
+            .with_duration(Duration::from_millis(ms));
+            report.add_mutant_record(MutantRecord::from(&mini_report));
+        }
+
+        assert_eq!(
+            report.duration_percentiles(),
+            Some(DurationPercentiles {
+                p50: 50,
+                p90: 90,
+                p99: 99
+            })
+        );
+        let slowest = report
+            .slowest_mutants(3)
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(slowest, ["100", "99", "98"]);
+    }
 }
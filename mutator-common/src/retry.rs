@@ -0,0 +1,66 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{thread, time::Duration};
+
+/// The longest wait between two attempts.
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Runs the operation until it succeeds or all attempts fail, waiting between the attempts with
+/// an exponential backoff (2, 4, 8... seconds, at most [`MAX_DELAY`]).
+///
+/// The `on_retry` callback gets the number of the failed attempt and its error, before waiting
+/// for the next attempt. The error of the last attempt is returned.
+pub fn with_retries<T, E>(
+    attempts: u32,
+    mut operation: impl FnMut() -> Result<T, E>,
+    mut on_retry: impl FnMut(u32, &E),
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Err(e) if attempt < attempts => {
+                on_retry(attempt, &e);
+                thread::sleep(backoff(attempt));
+                attempt += 1;
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Returns the wait after the given failed attempt.
+fn backoff(attempt: u32) -> Duration {
+    let secs = 1_u64.checked_shl(attempt).unwrap_or(u64::MAX);
+    Duration::from_secs(secs).min(MAX_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_is_capped() {
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+        assert_eq!(backoff(5), MAX_DELAY);
+        assert_eq!(backoff(100), MAX_DELAY);
+    }
+
+    #[test]
+    fn last_error_is_returned() {
+        let mut calls = 0;
+        let result = with_retries(
+            1,
+            || {
+                calls += 1;
+                Err::<(), _>(calls)
+            },
+            |_, _| panic!("no retry expected"),
+        );
+        assert_eq!(result, Err(1));
+
+        assert_eq!(with_retries(3, || Ok::<_, ()>(7), |_, _| {}), Ok(7));
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::MutantStatus;
+use anyhow::{bail, Context};
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Categories of mutants which can't be killed by improving the tests. They are excluded from
+/// the denominator of the mutation score, unless the score policy includes them.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScoreCategory {
+    /// Mutants suppressed in the suppressions file.
+    Suppressed,
+    /// Mutants marked as equivalent to the original code in the suppressions file.
+    Equivalent,
+    /// Mutants in code not reached by the tests (filtered out by the coverage or skipped by
+    /// `--skip-uncovered-after`).
+    Unreachable,
+    /// Mutants which don't compile.
+    Stillborn,
+}
+
+impl fmt::Display for ScoreCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoreCategory::Suppressed => write!(f, "suppressed"),
+            ScoreCategory::Equivalent => write!(f, "equivalent"),
+            ScoreCategory::Unreachable => write!(f, "unreachable"),
+            ScoreCategory::Stillborn => write!(f, "stillborn"),
+        }
+    }
+}
+
+/// Options of the mutation score computation.
+#[derive(Args, Debug, Clone, Default, PartialEq)]
+pub struct ScoreOptions {
+    /// A file listing the IDs of the mutants which are not evaluated, one per line, optionally
+    /// followed by the `equivalent` or `suppressed` (default) kind. `#` starts a comment.
+    #[clap(long, value_parser)]
+    pub suppressions: Option<PathBuf>,
+
+    /// Categories of mutants counted in the denominator of the mutation score (as not killed),
+    /// separated by commas. By default, none of them are counted.
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub score_include: Vec<ScoreCategory>,
+}
+
+impl ScoreOptions {
+    /// Returns the categories counted in the mutation score, sorted and without duplicates.
+    #[must_use]
+    pub fn score_policy(&self) -> Vec<ScoreCategory> {
+        let mut policy = self.score_include.clone();
+        policy.sort();
+        policy.dedup();
+        policy
+    }
+
+    /// Loads the suppressions file, if set.
+    pub fn load_suppressions(&self) -> anyhow::Result<Suppressions> {
+        match &self.suppressions {
+            Some(path) => Suppressions::from_file(path),
+            None => Ok(Suppressions::default()),
+        }
+    }
+}
+
+/// Mutants which are not evaluated, as they are suppressed or equivalent to the original code.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Suppressions {
+    /// The status of the listed mutants by their IDs.
+    mutants: BTreeMap<String, MutantStatus>,
+}
+
+impl Suppressions {
+    /// Loads the suppressions from the file.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        content
+            .parse()
+            .with_context(|| format!("invalid suppressions file {}", path.display()))
+    }
+
+    /// Returns the status of the mutant, if it's listed.
+    pub fn status(&self, mutant_id: &str) -> Option<MutantStatus> {
+        self.mutants.get(mutant_id).copied()
+    }
+}
+
+impl FromStr for Suppressions {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut mutants = BTreeMap::new();
+        for (line_no, line) in (1..).zip(s.lines()) {
+            let line = line.split_once('#').map_or(line, |(line, _)| line);
+            let mut words = line.split_whitespace();
+            let Some(id) = words.next() else {
+                continue;
+            };
+            let status = match words.next() {
+                None | Some("suppressed") => MutantStatus::Suppressed,
+                Some("equivalent") => MutantStatus::Equivalent,
+                Some(kind) => bail!(
+                    "line {line_no}: unknown kind '{kind}', expected 'suppressed' or 'equivalent'"
+                ),
+            };
+            if let Some(extra) = words.next() {
+                bail!("line {line_no}: unexpected '{extra}' after the kind");
+            }
+            mutants.insert(id.to_owned(), status);
+        }
+        Ok(Self { mutants })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppressions_are_parsed() {
+        let suppressions: Suppressions = "# Mutants which can't be killed.\n\
+                                          3f2a1b equivalent # x * 1 == x / 1\n\
+                                          \n\
+                                          a81c\n\
+                                          d02e suppressed\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            suppressions.status("3f2a1b"),
+            Some(MutantStatus::Equivalent)
+        );
+        assert_eq!(suppressions.status("a81c"), Some(MutantStatus::Suppressed));
+        assert_eq!(suppressions.status("d02e"), Some(MutantStatus::Suppressed));
+        assert_eq!(suppressions.status("ffff"), None);
+
+        let err = "3f2a1b unkillable\n".parse::<Suppressions>().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("line 1: unknown kind 'unkillable'"));
+    }
+}
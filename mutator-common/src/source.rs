@@ -0,0 +1,59 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use diffy::{Line, Patch};
+
+/// Finds the address of the module declared as `module <address>::<name>` in the Move source.
+pub fn find_module_address<'a>(source: &'a str, module: &str) -> Option<&'a str> {
+    source.match_indices("module ").find_map(|(idx, _)| {
+        let decl = source[idx + "module ".len()..]
+            .split(|c: char| c.is_whitespace() || c == '{')
+            .next()?;
+        let (address, name) = decl.rsplit_once("::")?;
+        (name == module).then_some(address)
+    })
+}
+
+/// Returns the lines of the original file (starting from 1) changed by the mutant's diff.
+pub fn changed_lines(diff: &str) -> Result<Vec<usize>> {
+    let patch = Patch::from_str(diff).context("invalid mutant diff")?;
+    let mut lines = vec![];
+    for hunk in patch.hunks() {
+        let mut line = hunk.old_range().start();
+        for hunk_line in hunk.lines() {
+            match hunk_line {
+                Line::Context(_) => line += 1,
+                Line::Delete(_) => {
+                    lines.push(line);
+                    line += 1;
+                },
+                Line::Insert(_) => {},
+            }
+        }
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_module_address() {
+        let source = "module TestAccount::Sum {\n}\n\nmodule 0x42::Other {\n}\n";
+        assert_eq!(find_module_address(source, "Sum"), Some("TestAccount"));
+        assert_eq!(find_module_address(source, "Other"), Some("0x42"));
+        assert_eq!(find_module_address(source, "Missing"), None);
+    }
+
+    #[test]
+    fn finds_changed_lines() {
+        let original =
+            "module 0x42::Sum {\n    fun sum(x: u64, y: u64): u64 {\n        x + y\n    }\n}\n";
+        let mutated = original.replace("x + y", "x - y");
+        let diff = diffy::create_patch(original, &mutated).to_string();
+        assert_eq!(changed_lines(&diff).unwrap(), vec![3]);
+    }
+}
@@ -58,6 +58,26 @@ pub fn setup_outdir_and_package_path<P: AsRef<Path>>(
     Ok((outdir, new_package_path))
 }
 
+/// Copies the prebuilt `build` directory into the recreated package path, so the compiled
+/// artifacts can be reused instead of building the package from scratch.
+pub fn use_prebuilt_package<P: AsRef<Path>>(build_dir: P, package_path: &Path) -> Result<()> {
+    let build_dir = build_dir.as_ref();
+    if !build_dir.is_dir() {
+        anyhow::bail!(
+            "prebuilt package directory {} does not exist",
+            build_dir.display()
+        );
+    }
+    info!("Using prebuilt package from: {build_dir:?}");
+
+    let new_build_dir = package_path.join("build");
+    fs::create_dir_all(&new_build_dir)?;
+
+    let options = CopyOptions::new().content_only(true);
+    fs_extra::dir::copy(build_dir, &new_build_dir, &options)?;
+    Ok(())
+}
+
 /// Helper method to strip the temp dir prefix and keep only the `sources/xxx.move` path.
 pub fn strip_path_prefix<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
     let original_file = path.as_ref().to_string_lossy();
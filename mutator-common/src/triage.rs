@@ -0,0 +1,79 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::Report;
+use serde_json::json;
+use std::{fs, path::Path};
+
+/// The static assets of the triage page.
+const ASSETS: &[(&str, &str)] = &[
+    ("index.html", include_str!("../assets/triage/index.html")),
+    ("app.js", include_str!("../assets/triage/app.js")),
+    ("style.css", include_str!("../assets/triage/style.css")),
+];
+
+/// The file with the report data loaded by the page.
+const DATA_FILE: &str = "data.js";
+
+/// Writes the mutant triage page for the report to the directory.
+///
+/// The page is a small single-page app listing the mutants with filtering, sorting and diff
+/// viewing, which can export a suppressions file from the mutants selected in the browser. The
+/// report data is written as a script rather than loaded with a request, so the page works when
+/// opened straight from the disk.
+pub fn write_triage_page(report: &Report, out_dir: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(out_dir)?;
+    for (name, content) in ASSETS {
+        fs::write(out_dir.join(name), content)?;
+    }
+    fs::write(out_dir.join(DATA_FILE), data_script(report)?)?;
+    Ok(())
+}
+
+/// Renders the report data as a script defining the `MUTATION_REPORT` variable.
+fn data_script(report: &Report) -> anyhow::Result<String> {
+    let data = json!({
+        "package_dir": report.package_dir,
+        "mutation_score": report.score_breakdown().score(),
+        "mutants": report.mutants,
+    });
+    // The diffs can contain anything, so the HTML special characters are escaped to keep the
+    // data from being interpreted by the browser in any context.
+    let data = serde_json::to_string(&data)?
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026");
+    Ok(format!("window.MUTATION_REPORT = {data};\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MiniReport, MutantRecord, MutantStatus};
+
+    #[test]
+    fn triage_page_contains_the_mutants() {
+        let mut report = Report::new("package".into());
+        let mini_report = MiniReport::new(
+            "sources/vault.move".into(),
+            "vault::withdraw".to_owned(),
+            MutantStatus::Alive,
+            "-    if (a < b) </script>\n+    if (a > b)\n".to_owned(),
+        )
+        .with_mutant_info("5f1c2a9e", vec!["binary_operator_replacement".to_owned()]);
+        report.add_mutant_record(MutantRecord::from(&mini_report));
+
+        let dir = tempfile::tempdir().unwrap();
+        write_triage_page(&report, dir.path()).unwrap();
+        for (name, _) in ASSETS {
+            assert!(dir.path().join(name).exists(), "{name} missing");
+        }
+
+        let data = fs::read_to_string(dir.path().join(DATA_FILE)).unwrap();
+        assert!(data.starts_with("window.MUTATION_REPORT = {"));
+        assert!(data.contains("\"id\":\"5f1c2a9e\""));
+        assert!(data.contains("vault::withdraw"));
+        assert!(!data.contains("</script>"));
+    }
+}